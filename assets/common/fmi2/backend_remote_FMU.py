@@ -19,6 +19,7 @@ from schemas.fmi2_messages_pb2 import (
     Fmi2GetIntegerReturn,
     Fmi2GetBooleanReturn,
     Fmi2GetStringReturn,
+    Fmi2GetRealOutputDerivativesReturn,
 )
 from schemas.unifmu_handshake_pb2 import (
     HandshakeStatus,
@@ -195,6 +196,14 @@ if __name__ == "__main__":
             result = Fmi2StatusReturn()
             fmu.setString(data.references,data.values)
             result.status = 0  
+        elif group == "Fmi2SetRealInputDerivatives":
+            result = Fmi2StatusReturn()
+            fmu.setRealInputDerivatives(data.references,data.orders,data.values)
+            result.status = 0
+        elif group == "Fmi2GetRealOutputDerivatives":
+            result = Fmi2GetRealOutputDerivativesReturn()
+            result.values[:] = fmu.getRealOutputDerivatives(data.references,data.orders)
+            result.status = 0
         else:
             logger.error(f"unrecognized command '{group}' received, shutting down")
             sys.exit(-1)
@@ -319,6 +319,10 @@ if __name__ == "__main__":
             result = Fmi3StatusReturn()
             fmu.setClock(data.value_references, data.values)
             result.status = 0
+        elif group == "Fmi3EvaluateDiscreteStates":
+            result = Fmi3StatusReturn()
+            fmu.evaluateDiscreteStates()
+            result.status = 0
         elif group == "Fmi3UpdateDiscreteStates":
             result = Fmi3UpdateDiscreteStatesReturn()
             result.status = 0
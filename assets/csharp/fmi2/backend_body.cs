@@ -2,6 +2,7 @@ using System;
 using Fmi2Messages;
 using UnifmuHandshake;
 using System.Collections.Generic;
+using System.Threading;
 using NetMQ.Sockets;
 using Google.Protobuf;
 using NetMQ;
@@ -12,6 +13,14 @@ namespace Launch
     {
         private static RequestSocket socket = new RequestSocket();
 
+        // Thread running an asynchronous Fmi2DoStep, if any. Log events
+        // emitted from it are buffered until the next command is received,
+        // as only the main thread may use the socket.
+        private static Thread stepThread = null;
+        private static volatile Fmi2Status stepStatus;
+        private static string pendingMessage = "";
+        private static List<(Fmi2Status, string, string)> bufferedLogs = new List<(Fmi2Status, string, string)>();
+
         private static void ConnectToEndpoint(string dispatcher_endpoint)
         {
             socket.Connect(dispatcher_endpoint);
@@ -36,6 +45,30 @@ namespace Launch
             );
         }
 
+        /// <summary>
+        /// Runs Fmi2DoStep of the model on a separate thread, so that the
+        /// importer can query the status of the step or cancel it while it is
+        /// running.
+        /// </summary>
+        private static void StartAsynchronousStep(Model model, Fmi2DoStep doStep)
+        {
+            stepStatus = Fmi2Status.Fmi2Pending;
+            // Cleared before the step thread starts, so that a cancellation
+            // arriving right after the step was started isn't lost.
+            model.step_canceled = false;
+            pendingMessage = $"Fmi2DoStep from time {doStep.CurrentTime} with step size {doStep.StepSize} is running";
+
+            stepThread = new Thread(() => {
+                stepStatus = model.Fmi2DoStep(
+                    doStep.CurrentTime,
+                    doStep.StepSize,
+                    doStep.NoSetFmuStatePriorToCurrentPoint
+                );
+            });
+            stepThread.IsBackground = true;
+            stepThread.Start();
+        }
+
         private static void Handshake()
         {
             SendReply(new HandshakeReply{Status = HandshakeStatus.Ok});
@@ -46,6 +79,15 @@ namespace Launch
             Model model = null;
 
             LogCallback logCallback = (status, category, message) => {
+                if (Thread.CurrentThread == stepThread)
+                {
+                    lock (bufferedLogs)
+                    {
+                        bufferedLogs.Add((status, category, message));
+                    }
+                    return;
+                }
+
                 SendReply(new Fmi2Return{Log = new Fmi2LogReturn{
                     Status = status,
                     Category = category,
@@ -64,10 +106,26 @@ namespace Launch
                 }
             };
 
+            Action flushBufferedLogs = () => {
+                List<(Fmi2Status, string, string)> logs;
+                lock (bufferedLogs)
+                {
+                    logs = new List<(Fmi2Status, string, string)>(bufferedLogs);
+                    bufferedLogs.Clear();
+                }
+
+                foreach (var (status, category, message) in logs)
+                {
+                    logCallback(status, category, message);
+                }
+            };
+
             while (true)
             {
                 Fmi2Command command = RecvCommand();
 
+                flushBufferedLogs();
+
                 switch (command.CommandCase)
                 {
                     case Fmi2Command.CommandOneofCase.Fmi2Instantiate:
@@ -92,11 +150,39 @@ namespace Launch
                         break;
 
                     case Fmi2Command.CommandOneofCase.Fmi2DoStep:
-                        SendStatusReply(model.Fmi2DoStep(
-                            command.Fmi2DoStep.CurrentTime,
-                            command.Fmi2DoStep.StepSize,
-                            command.Fmi2DoStep.NoSetFmuStatePriorToCurrentPoint
-                        ));
+                        if (command.Fmi2DoStep.RunAsynchronously)
+                        {
+                            StartAsynchronousStep(model, command.Fmi2DoStep);
+                            SendStatusReply(Fmi2Status.Fmi2Pending);
+                        }
+                        else
+                        {
+                            SendStatusReply(model.Fmi2DoStep(
+                                command.Fmi2DoStep.CurrentTime,
+                                command.Fmi2DoStep.StepSize,
+                                command.Fmi2DoStep.NoSetFmuStatePriorToCurrentPoint
+                            ));
+                        }
+                        break;
+
+                    case Fmi2Command.CommandOneofCase.Fmi2GetDoStepStatus:
+                        {
+                            // The status of a finished step is kept, as the
+                            // API may query it again after it has been
+                            // reported once.
+                            var status = Fmi2Status.Fmi2Pending;
+                            if (stepThread == null || !stepThread.IsAlive)
+                            {
+                                status = stepStatus;
+                                stepThread = null;
+                            }
+                            SendReply(new Fmi2Return{
+                                GetDoStepStatus = new Fmi2GetDoStepStatusReturn{
+                                    Status = status,
+                                    PendingMessage = pendingMessage
+                                }
+                            });
+                        }
                         break;
 
                     case Fmi2Command.CommandOneofCase.Fmi2SetReal:
@@ -168,8 +254,38 @@ namespace Launch
                         }
                         break;
 
+                    case Fmi2Command.CommandOneofCase.Fmi2SetRealInputDerivatives:
+                        SendStatusReply(model.Fmi2SetRealInputDerivatives(
+                            command.Fmi2SetRealInputDerivatives.References,
+                            command.Fmi2SetRealInputDerivatives.Orders,
+                            command.Fmi2SetRealInputDerivatives.Values
+                        ));
+                        break;
+
+                    case Fmi2Command.CommandOneofCase.Fmi2GetRealOutputDerivatives:
+                        {
+                            Fmi2Return result = new Fmi2Return{GetRealOutputDerivatives = new Fmi2GetRealOutputDerivativesReturn()};
+                            (Fmi2Status status, var values) = model.Fmi2GetRealOutputDerivatives(
+                                command.Fmi2GetRealOutputDerivatives.References,
+                                command.Fmi2GetRealOutputDerivatives.Orders
+                            );
+                            result.GetRealOutputDerivatives.Values.AddRange(values);
+                            result.GetRealOutputDerivatives.Status = status;
+                            SendReply(result);
+                        }
+                        break;
+
                     case Fmi2Command.CommandOneofCase.Fmi2CancelStep:
-                        SendStatusReply(model.Fmi2CancelStep());
+                        {
+                            var status = model.Fmi2CancelStep();
+                            if (stepThread != null)
+                            {
+                                stepThread.Join();
+                                stepThread = null;
+                            }
+                            flushBufferedLogs();
+                            SendStatusReply(status);
+                        }
                         break;
 
                     case Fmi2Command.CommandOneofCase.Fmi2Reset:
@@ -194,6 +310,100 @@ namespace Launch
                         SendStatusReply(model.Fmi2DeserializeFmuState(command.Fmi2DeserializeFmuState.State.ToByteArray()));
                         break;
 
+                    case Fmi2Command.CommandOneofCase.Fmi2EnterEventMode:
+                        SendStatusReply(model.Fmi2EnterEventMode());
+                        break;
+
+                    case Fmi2Command.CommandOneofCase.Fmi2NewDiscreteStates:
+                        {
+                            var (
+                                status,
+                                newDiscreteStatesNeeded,
+                                terminateSimulation,
+                                nominalsOfContinuousStatesChanged,
+                                valuesOfContinuousStatesChanged,
+                                nextEventTimeDefined,
+                                nextEventTime
+                            ) = model.Fmi2NewDiscreteStates();
+                            SendReply(new Fmi2Return{NewDiscreteStates = new Fmi2NewDiscreteStatesReturn{
+                                Status = status,
+                                NewDiscreteStatesNeeded = newDiscreteStatesNeeded,
+                                TerminateSimulation = terminateSimulation,
+                                NominalsOfContinuousStatesChanged = nominalsOfContinuousStatesChanged,
+                                ValuesOfContinuousStatesChanged = valuesOfContinuousStatesChanged,
+                                NextEventTimeDefined = nextEventTimeDefined,
+                                NextEventTime = nextEventTime
+                            }});
+                        }
+                        break;
+
+                    case Fmi2Command.CommandOneofCase.Fmi2EnterContinuousTimeMode:
+                        SendStatusReply(model.Fmi2EnterContinuousTimeMode());
+                        break;
+
+                    case Fmi2Command.CommandOneofCase.Fmi2CompletedIntegratorStep:
+                        {
+                            var (status, enterEventMode, terminateSimulation) = model.Fmi2CompletedIntegratorStep(
+                                command.Fmi2CompletedIntegratorStep.NoSetFmuStatePriorToCurrentPoint
+                            );
+                            SendReply(new Fmi2Return{CompletedIntegratorStep = new Fmi2CompletedIntegratorStepReturn{
+                                Status = status,
+                                EnterEventMode = enterEventMode,
+                                TerminateSimulation = terminateSimulation
+                            }});
+                        }
+                        break;
+
+                    case Fmi2Command.CommandOneofCase.Fmi2SetTime:
+                        SendStatusReply(model.Fmi2SetTime(command.Fmi2SetTime.Time));
+                        break;
+
+                    case Fmi2Command.CommandOneofCase.Fmi2SetContinuousStates:
+                        SendStatusReply(model.Fmi2SetContinuousStates(
+                            command.Fmi2SetContinuousStates.ContinuousStates
+                        ));
+                        break;
+
+                    case Fmi2Command.CommandOneofCase.Fmi2GetDerivatives:
+                        {
+                            Fmi2Return result = new Fmi2Return{GetDerivatives = new Fmi2GetDerivativesReturn()};
+                            (Fmi2Status status, var derivatives) = model.Fmi2GetDerivatives();
+                            result.GetDerivatives.Derivatives.AddRange(derivatives);
+                            result.GetDerivatives.Status = status;
+                            SendReply(result);
+                        }
+                        break;
+
+                    case Fmi2Command.CommandOneofCase.Fmi2GetEventIndicators:
+                        {
+                            Fmi2Return result = new Fmi2Return{GetEventIndicators = new Fmi2GetEventIndicatorsReturn()};
+                            (Fmi2Status status, var eventIndicators) = model.Fmi2GetEventIndicators();
+                            result.GetEventIndicators.EventIndicators.AddRange(eventIndicators);
+                            result.GetEventIndicators.Status = status;
+                            SendReply(result);
+                        }
+                        break;
+
+                    case Fmi2Command.CommandOneofCase.Fmi2GetContinuousStates:
+                        {
+                            Fmi2Return result = new Fmi2Return{GetContinuousStates = new Fmi2GetContinuousStatesReturn()};
+                            (Fmi2Status status, var continuousStates) = model.Fmi2GetContinuousStates();
+                            result.GetContinuousStates.ContinuousStates.AddRange(continuousStates);
+                            result.GetContinuousStates.Status = status;
+                            SendReply(result);
+                        }
+                        break;
+
+                    case Fmi2Command.CommandOneofCase.Fmi2GetNominalsOfContinuousStates:
+                        {
+                            Fmi2Return result = new Fmi2Return{GetNominalsOfContinuousStates = new Fmi2GetNominalsOfContinuousStatesReturn()};
+                            (Fmi2Status status, var nominals) = model.Fmi2GetNominalsOfContinuousStates();
+                            result.GetNominalsOfContinuousStates.Nominals.AddRange(nominals);
+                            result.GetNominalsOfContinuousStates.Status = status;
+                            SendReply(result);
+                        }
+                        break;
+
                     case Fmi2Command.CommandOneofCase.Fmi2FreeInstance:
                         SendReply(new Fmi2Return{FreeInstance = new Fmi2FreeInstanceReturn()});
                         Console.WriteLine("received fmi2FreeInstance, exiting with status code 0");
@@ -24,6 +24,15 @@ public class Model
     public string string_c { get; set; }
 
     private Dictionary<uint, PropertyInfo> reference_to_attributes = new Dictionary<uint, PropertyInfo>();
+    private List<uint> real_inputs = new List<uint> { 0, 1 };
+    private List<uint> real_outputs = new List<uint> { 2 };
+    // The time derivatives of the real inputs given by the importer through
+    // Fmi2SetRealInputDerivatives, indexed by value reference and derivative
+    // order. Used to interpolate the inputs during Fmi2DoStep.
+    private Dictionary<uint, Dictionary<int, double>> input_derivatives = new Dictionary<uint, Dictionary<int, double>>();
+    // Set by Fmi2CancelStep while an asynchronous Fmi2DoStep is running on
+    // another thread. A long running step should check it and return early.
+    public volatile bool step_canceled;
     private LogCallback log_callback { get; set; }
 
 
@@ -52,6 +61,7 @@ public class Model
     
     public Fmi2Status Fmi2DoStep(double currentTime, double stepSize, bool noStepPrior)
     {
+        InterpolateInputs(stepSize);
         UpdateOutputs();
         return Fmi2Status.Fmi2Ok;
     }
@@ -79,6 +89,11 @@ public class Model
 
     public Fmi2Status Fmi2SetReal(IEnumerable<uint> references, IEnumerable<double> values)
     {
+        // A new input value starts a new interpolation of that input
+        foreach (var r in references)
+        {
+            this.input_derivatives.Remove(r);
+        }
         return this.SetValueReflection(references, values);
     }
 
@@ -118,8 +133,62 @@ public class Model
         return this.GetValueReflection<String>(references);
     }
 
+    public Fmi2Status Fmi2SetRealInputDerivatives(IEnumerable<uint> references, IEnumerable<int> orders, IEnumerable<double> values)
+    {
+        foreach (var ((r, order), value) in references.Zip(orders).Zip(values))
+        {
+            if (!this.real_inputs.Contains(r))
+            {
+                Log($"Variable #{r}# isn't a real input.", Fmi2Status.Fmi2Error, "logStatusError");
+                return Fmi2Status.Fmi2Error;
+            }
+            if (order < 1)
+            {
+                Log($"Can't set derivative of order {order} for input #{r}#.", Fmi2Status.Fmi2Error, "logStatusError");
+                return Fmi2Status.Fmi2Error;
+            }
+
+            if (!this.input_derivatives.ContainsKey(r))
+            {
+                this.input_derivatives[r] = new Dictionary<int, double>();
+            }
+            this.input_derivatives[r][order] = value;
+        }
+
+        return Fmi2Status.Fmi2Ok;
+    }
+
+    public (Fmi2Status, IEnumerable<double>) Fmi2GetRealOutputDerivatives(IEnumerable<uint> references, IEnumerable<int> orders)
+    {
+        var values = new List<double>();
+
+        foreach (var (r, order) in references.Zip(orders))
+        {
+            if (!this.real_outputs.Contains(r))
+            {
+                Log($"Variable #{r}# isn't a real output.", Fmi2Status.Fmi2Error, "logStatusError");
+                return (Fmi2Status.Fmi2Error, new List<double>());
+            }
+
+            // As real_c = real_a + real_b, its derivatives are the sums of
+            // the derivatives of the inputs.
+            double value = 0.0;
+            foreach (var i in this.real_inputs)
+            {
+                if (this.input_derivatives.TryGetValue(i, out var derivatives) && derivatives.TryGetValue(order, out var derivative))
+                {
+                    value += derivative;
+                }
+            }
+            values.Add(value);
+        }
+
+        return (Fmi2Status.Fmi2Ok, values);
+    }
+
     public Fmi2Status Fmi2CancelStep()
     {
+        this.step_canceled = true;
         return Fmi2Status.Fmi2Ok;
     }
 
@@ -133,6 +202,8 @@ public class Model
         this.boolean_b = false;
         this.string_a = "";
         this.string_b = "";
+        this.input_derivatives.Clear();
+        this.step_canceled = false;
         this.UpdateOutputs();
 
         return Fmi2Status.Fmi2Ok;
@@ -161,6 +232,17 @@ public class Model
                 writer.Write(string_a);
                 writer.Write(string_b);
                 writer.Write(string_c);
+                writer.Write(input_derivatives.Count);
+                foreach (var (r, derivatives) in input_derivatives)
+                {
+                    writer.Write(r);
+                    writer.Write(derivatives.Count);
+                    foreach (var (order, derivative) in derivatives)
+                    {
+                        writer.Write(order);
+                        writer.Write(derivative);
+                    }
+                }
             }
             return (Fmi2Status.Fmi2Ok, m.ToArray());
         }
@@ -184,11 +266,133 @@ public class Model
                 this.string_a = reader.ReadString();
                 this.string_b = reader.ReadString();
                 this.string_c = reader.ReadString();
+                this.input_derivatives.Clear();
+                int n_inputs = reader.ReadInt32();
+                for (int i = 0; i < n_inputs; i++)
+                {
+                    uint r = reader.ReadUInt32();
+                    var derivatives = new Dictionary<int, double>();
+                    int n_derivatives = reader.ReadInt32();
+                    for (int j = 0; j < n_derivatives; j++)
+                    {
+                        int order = reader.ReadInt32();
+                        derivatives[order] = reader.ReadDouble();
+                    }
+                    this.input_derivatives[r] = derivatives;
+                }
             }
         }
         return Fmi2Status.Fmi2Ok;
     }
 
+    public Fmi2Status Fmi2EnterEventMode()
+    {
+        return Fmi2Status.Fmi2Ok;
+    }
+
+    public (Fmi2Status, bool, bool, bool, bool, bool, double) Fmi2NewDiscreteStates()
+    {
+        this.UpdateOutputs();
+
+        bool newDiscreteStatesNeeded = false;
+        bool terminateSimulation = false;
+        bool nominalsOfContinuousStatesChanged = false;
+        bool valuesOfContinuousStatesChanged = false;
+        bool nextEventTimeDefined = false;
+        double nextEventTime = 0.0;
+
+        return (
+            Fmi2Status.Fmi2Ok,
+            newDiscreteStatesNeeded,
+            terminateSimulation,
+            nominalsOfContinuousStatesChanged,
+            valuesOfContinuousStatesChanged,
+            nextEventTimeDefined,
+            nextEventTime
+        );
+    }
+
+    public Fmi2Status Fmi2EnterContinuousTimeMode()
+    {
+        return Fmi2Status.Fmi2Ok;
+    }
+
+    public (Fmi2Status, bool, bool) Fmi2CompletedIntegratorStep(bool noSetFmuStatePriorToCurrentPoint)
+    {
+        this.UpdateOutputs();
+
+        bool enterEventMode = false;
+        bool terminateSimulation = false;
+
+        return (Fmi2Status.Fmi2Ok, enterEventMode, terminateSimulation);
+    }
+
+    public Fmi2Status Fmi2SetTime(double time)
+    {
+        return Fmi2Status.Fmi2Ok;
+    }
+
+    // The placeholder model has no continuous states or event indicators,
+    // so the following functions only exchange empty lists.
+
+    public Fmi2Status Fmi2SetContinuousStates(IEnumerable<double> continuousStates)
+    {
+        return Fmi2Status.Fmi2Ok;
+    }
+
+    public (Fmi2Status, IEnumerable<double>) Fmi2GetDerivatives()
+    {
+        return (Fmi2Status.Fmi2Ok, new List<double>());
+    }
+
+    public (Fmi2Status, IEnumerable<double>) Fmi2GetEventIndicators()
+    {
+        return (Fmi2Status.Fmi2Ok, new List<double>());
+    }
+
+    public (Fmi2Status, IEnumerable<double>) Fmi2GetContinuousStates()
+    {
+        return (Fmi2Status.Fmi2Ok, new List<double>());
+    }
+
+    public (Fmi2Status, IEnumerable<double>) Fmi2GetNominalsOfContinuousStates()
+    {
+        return (Fmi2Status.Fmi2Ok, new List<double>());
+    }
+
+    // Moves each interpolated input and its derivatives to the end of the
+    // step along the Taylor polynomial given by the input derivatives.
+    private void InterpolateInputs(double stepSize)
+    {
+        foreach (var r in this.input_derivatives.Keys.ToList())
+        {
+            var derivatives = this.input_derivatives[r];
+            int max_order = derivatives.Keys.Max();
+            var coefficients = new double[max_order + 1];
+            coefficients[0] = (double)this.reference_to_attributes[r].GetValue(this);
+            foreach (var (order, derivative) in derivatives)
+            {
+                coefficients[order] = derivative;
+            }
+
+            var shifted = new double[max_order + 1];
+            for (int k = 0; k <= max_order; k++)
+            {
+                double term = 1.0;
+                for (int j = k; j <= max_order; j++)
+                {
+                    // term is stepSize^(j - k) / (j - k)!
+                    shifted[k] += coefficients[j] * term;
+                    term *= stepSize / (j - k + 1);
+                }
+            }
+
+            this.reference_to_attributes[r].SetValue(this, shifted[0]);
+            this.input_derivatives[r] = Enumerable.Range(1, max_order)
+                .ToDictionary(order => order, order => shifted[order]);
+        }
+    }
+
     private void UpdateOutputs()
     {
         this.real_c = real_a + real_b;
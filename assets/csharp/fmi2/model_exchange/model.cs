@@ -0,0 +1,319 @@
+using System;
+using System.Collections.Generic;
+using System.IO;
+using System.Reflection;
+
+using System.Linq;
+using Fmi2Messages;
+
+public delegate void LogCallback(Fmi2Status status, String category, String message);
+
+public class Model
+{
+    public double time { get; set; }
+    public double h { get; set; }
+    public double der_h { get { return this.v; } }
+    public double v { get; set; }
+    public double der_v { get { return this.g; } }
+    public double g { get; set; }
+    public double e { get; set; }
+
+    private Dictionary<uint, PropertyInfo> reference_to_attributes = new Dictionary<uint, PropertyInfo>();
+    // The continuous states of the model, in the order that they are
+    // exchanged with the importer.
+    private List<PropertyInfo> continuous_states = new List<PropertyInfo>();
+    // Set by Fmi2CancelStep. Model Exchange FMUs are never stepped, but the
+    // backend shares its step handling with Co-Simulation FMUs.
+    public volatile bool step_canceled;
+    private LogCallback log_callback { get; set; }
+
+
+    public Model(LogCallback log_callback)
+    {
+        this.log_callback = log_callback;
+
+        this.reference_to_attributes = new Dictionary<uint, PropertyInfo>
+            {
+              { 0, this.GetType().GetProperty("time") },
+              { 1, this.GetType().GetProperty("h") },
+              { 2, this.GetType().GetProperty("der_h") },
+              { 3, this.GetType().GetProperty("v") },
+              { 4, this.GetType().GetProperty("der_v") },
+              { 5, this.GetType().GetProperty("g") },
+              { 6, this.GetType().GetProperty("e") },
+            };
+
+        this.continuous_states = new List<PropertyInfo>
+            {
+              this.GetType().GetProperty("h"),
+              this.GetType().GetProperty("v"),
+            };
+
+    Fmi2Reset();
+    }
+
+    public Fmi2Status Fmi2DoStep(double currentTime, double stepSize, bool noStepPrior)
+    {
+        Log("Model Exchange FMUs can't be stepped.", Fmi2Status.Fmi2Error, "logStatusError");
+        return Fmi2Status.Fmi2Error;
+    }
+
+    public Fmi2Status Fmi2SetupExperiment(double startTime, double? stopTime, double? tolerance)
+    {
+        this.time = startTime;
+        return Fmi2Status.Fmi2Ok;
+    }
+
+    public Fmi2Status Fmi2SetDebugLogging(IEnumerable<String> categories, bool loggingOn)
+    {
+        return Fmi2Status.Fmi2Ok;
+    }
+
+    public Fmi2Status Fmi2EnterInitializationMode()
+    {
+        return Fmi2Status.Fmi2Ok;
+    }
+
+    public Fmi2Status Fmi2ExitInitializationMode()
+    {
+        return Fmi2Status.Fmi2Ok;
+    }
+
+    public Fmi2Status Fmi2SetReal(IEnumerable<uint> references, IEnumerable<double> values)
+    {
+        return this.SetValueReflection(references, values);
+    }
+
+    public Fmi2Status Fmi2SetInteger(IEnumerable<uint> references, IEnumerable<int> values)
+    {
+        return this.SetValueReflection(references, values);
+    }
+
+    public Fmi2Status Fmi2SetBoolean(IEnumerable<uint> references, IEnumerable<bool> values)
+    {
+        return this.SetValueReflection(references, values);
+    }
+
+    public Fmi2Status Fmi2SetString(IEnumerable<uint> references, IEnumerable<string> values)
+    {
+        return this.SetValueReflection(references, values);
+    }
+
+    public (Fmi2Status, IEnumerable<double>) Fmi2GetReal(IEnumerable<uint> references)
+    {
+        return this.GetValueReflection<double>(references);
+    }
+
+    public (Fmi2Status, IEnumerable<int>) Fmi2GetInteger(IEnumerable<uint> references)
+    {
+        return this.GetValueReflection<int>(references);
+    }
+
+    public (Fmi2Status, IEnumerable<bool>) Fmi2GetBoolean(IEnumerable<uint> references)
+    {
+        return this.GetValueReflection<bool>(references);
+    }
+
+    public (Fmi2Status, IEnumerable<String>) Fmi2GetString(IEnumerable<uint> references)
+    {
+        return this.GetValueReflection<String>(references);
+    }
+
+    public Fmi2Status Fmi2SetRealInputDerivatives(IEnumerable<uint> references, IEnumerable<int> orders, IEnumerable<double> values)
+    {
+        Log("The model doesn't interpolate its inputs.", Fmi2Status.Fmi2Error, "logStatusError");
+        return Fmi2Status.Fmi2Error;
+    }
+
+    public (Fmi2Status, IEnumerable<double>) Fmi2GetRealOutputDerivatives(IEnumerable<uint> references, IEnumerable<int> orders)
+    {
+        Log("The model doesn't provide output derivatives.", Fmi2Status.Fmi2Error, "logStatusError");
+        return (Fmi2Status.Fmi2Error, new List<double>());
+    }
+
+    public Fmi2Status Fmi2CancelStep()
+    {
+        this.step_canceled = true;
+        return Fmi2Status.Fmi2Ok;
+    }
+
+    public Fmi2Status Fmi2Reset()
+    {
+        this.time = 0.0;
+        this.h = 1.0;
+        this.v = 0.0;
+        this.g = -9.81;
+        this.e = 0.7;
+        this.step_canceled = false;
+
+        return Fmi2Status.Fmi2Ok;
+    }
+
+    public Fmi2Status Fmi2Terminate()
+    {
+        return Fmi2Status.Fmi2Ok;
+    }
+
+    public (Fmi2Status, byte[]) Fmi2SerializeFmuState()
+    {
+        using (MemoryStream m = new MemoryStream())
+        {
+            using (BinaryWriter writer = new BinaryWriter(m))
+            {
+                writer.Write(time);
+                writer.Write(h);
+                writer.Write(v);
+                writer.Write(g);
+                writer.Write(e);
+            }
+            return (Fmi2Status.Fmi2Ok, m.ToArray());
+        }
+    }
+
+    public Fmi2Status Fmi2DeserializeFmuState(byte[] state)
+    {
+        using (MemoryStream m = new MemoryStream(state))
+        {
+            using (BinaryReader reader = new BinaryReader(m))
+            {
+                this.time = reader.ReadDouble();
+                this.h = reader.ReadDouble();
+                this.v = reader.ReadDouble();
+                this.g = reader.ReadDouble();
+                this.e = reader.ReadDouble();
+            }
+        }
+        return Fmi2Status.Fmi2Ok;
+    }
+
+    public Fmi2Status Fmi2EnterEventMode()
+    {
+        return Fmi2Status.Fmi2Ok;
+    }
+
+    public (Fmi2Status, bool, bool, bool, bool, bool, double) Fmi2NewDiscreteStates()
+    {
+        bool newDiscreteStatesNeeded = false;
+        bool terminateSimulation = false;
+        bool nominalsOfContinuousStatesChanged = false;
+        bool valuesOfContinuousStatesChanged = false;
+        bool nextEventTimeDefined = false;
+        double nextEventTime = 0.0;
+
+        if (this.h <= 0.0 && this.v < 0.0)
+        {
+            this.h = 0.0;
+            this.v = -this.e * this.v;
+            valuesOfContinuousStatesChanged = true;
+        }
+
+        return (
+            Fmi2Status.Fmi2Ok,
+            newDiscreteStatesNeeded,
+            terminateSimulation,
+            nominalsOfContinuousStatesChanged,
+            valuesOfContinuousStatesChanged,
+            nextEventTimeDefined,
+            nextEventTime
+        );
+    }
+
+    public Fmi2Status Fmi2EnterContinuousTimeMode()
+    {
+        return Fmi2Status.Fmi2Ok;
+    }
+
+    public (Fmi2Status, bool, bool) Fmi2CompletedIntegratorStep(bool noSetFmuStatePriorToCurrentPoint)
+    {
+        bool enterEventMode = false;
+        bool terminateSimulation = false;
+
+        return (Fmi2Status.Fmi2Ok, enterEventMode, terminateSimulation);
+    }
+
+    public Fmi2Status Fmi2SetTime(double time)
+    {
+        this.time = time;
+        return Fmi2Status.Fmi2Ok;
+    }
+
+    public Fmi2Status Fmi2SetContinuousStates(IEnumerable<double> continuousStates)
+    {
+        foreach (var (state, value) in this.continuous_states.Zip(continuousStates))
+        {
+            state.SetValue(this, value);
+        }
+        return Fmi2Status.Fmi2Ok;
+    }
+
+    public (Fmi2Status, IEnumerable<double>) Fmi2GetDerivatives()
+    {
+        return (Fmi2Status.Fmi2Ok, new List<double> { this.der_h, this.der_v });
+    }
+
+    public (Fmi2Status, IEnumerable<double>) Fmi2GetEventIndicators()
+    {
+        // The ball hits the ground when the height crosses zero
+        return (Fmi2Status.Fmi2Ok, new List<double> { this.h });
+    }
+
+    public (Fmi2Status, IEnumerable<double>) Fmi2GetContinuousStates()
+    {
+        return (
+            Fmi2Status.Fmi2Ok,
+            this.continuous_states.Select(state => (double)state.GetValue(this)).ToList()
+        );
+    }
+
+    public (Fmi2Status, IEnumerable<double>) Fmi2GetNominalsOfContinuousStates()
+    {
+        return (Fmi2Status.Fmi2Ok, this.continuous_states.Select(state => 1.0).ToList());
+    }
+
+    /// <summary>UniFMU logging function
+    /// <para>
+    /// Call this function whenever something should be logged.
+    /// This will send a message thourgh the UniFMU layer to
+    /// the importer if the importer has enabled logging and
+    /// is interested in the given logging category.
+    /// </para>
+    /// </summary>
+    /// <param name="message">the message to be logged
+    /// </param>
+    /// <param name="status">the Fmi2Status that the FMU expects to next return (default: Fmi2Status.Fmi2Ok)</param>
+    /// <param name="category">the logging category that this log event falls under</param>
+    private void Log(String message, Fmi2Status status = Fmi2Status.Fmi2Ok, String category = "logAll") {
+        this.log_callback(status, category, message);
+    }
+
+    private Fmi2Status SetValueReflection<T>(IEnumerable<uint> references, IEnumerable<T> values)
+    {
+        foreach (var (r, v) in references.Zip(values))
+        {
+            if (!this.reference_to_attributes[r].CanWrite)
+            {
+                Log($"Variable #{r}# can't be set.", Fmi2Status.Fmi2Error, "logStatusError");
+                return Fmi2Status.Fmi2Error;
+            }
+
+            this.reference_to_attributes[r].SetValue(this, (object)v);
+        }
+
+        return Fmi2Status.Fmi2Ok;
+    }
+
+    private (Fmi2Status, IEnumerable<T>) GetValueReflection<T>(IEnumerable<uint> references)
+    {
+
+        var values = new List<T>(references.Count());
+
+        foreach (var r in references)
+        {
+            values.Add((T)this.reference_to_attributes[r].GetValue(this));
+        }
+
+        return (Fmi2Status.Fmi2Ok, values);
+    }
+
+
+}
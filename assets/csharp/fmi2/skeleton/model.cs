@@ -0,0 +1,263 @@
+using System;
+using System.Collections.Generic;
+using System.IO;
+using System.Reflection;
+
+using System.Linq;
+using Fmi2Messages;
+
+public delegate void LogCallback(Fmi2Status status, String category, String message);
+
+public class Model
+{
+    // {{declarations}}
+
+    private Dictionary<uint, PropertyInfo> reference_to_attributes = new Dictionary<uint, PropertyInfo>();
+    private LogCallback log_callback { get; set; }
+
+
+    public Model(LogCallback log_callback)
+    {
+        this.log_callback = log_callback;
+        var type = this.GetType();
+
+        this.reference_to_attributes = new Dictionary<uint, PropertyInfo>
+        {
+            // {{references}}
+        };
+
+        Fmi2Reset();
+    }
+
+    public Fmi2Status Fmi2DoStep(double currentTime, double stepSize, bool noStepPrior)
+    {
+        // Advance the model from currentTime to currentTime + stepSize here,
+        // updating the outputs from the inputs.
+        return Fmi2Status.Fmi2Ok;
+    }
+
+    public Fmi2Status Fmi2SetupExperiment(double startTime, double? stopTime, double? tolerance)
+    {
+        return Fmi2Status.Fmi2Ok;
+    }
+
+    public Fmi2Status Fmi2SetDebugLogging(IEnumerable<String> categories, bool loggingOn)
+    {
+        return Fmi2Status.Fmi2Ok;
+    }
+
+    public Fmi2Status Fmi2EnterInitializationMode()
+    {
+        return Fmi2Status.Fmi2Ok;
+    }
+
+    public Fmi2Status Fmi2ExitInitializationMode()
+    {
+        return Fmi2Status.Fmi2Ok;
+    }
+
+    public Fmi2Status Fmi2SetReal(IEnumerable<uint> references, IEnumerable<double> values)
+    {
+        return this.SetValueReflection(references, values);
+    }
+
+    public Fmi2Status Fmi2SetInteger(IEnumerable<uint> references, IEnumerable<int> values)
+    {
+        return this.SetValueReflection(references, values);
+    }
+
+    public Fmi2Status Fmi2SetBoolean(IEnumerable<uint> references, IEnumerable<bool> values)
+    {
+        return this.SetValueReflection(references, values);
+    }
+
+    public Fmi2Status Fmi2SetString(IEnumerable<uint> references, IEnumerable<string> values)
+    {
+        return this.SetValueReflection(references, values);
+    }
+
+    public (Fmi2Status, IEnumerable<double>) Fmi2GetReal(IEnumerable<uint> references)
+    {
+        return this.GetValueReflection<double>(references);
+    }
+
+    public (Fmi2Status, IEnumerable<int>) Fmi2GetInteger(IEnumerable<uint> references)
+    {
+        return this.GetValueReflection<int>(references);
+    }
+
+    public (Fmi2Status, IEnumerable<bool>) Fmi2GetBoolean(IEnumerable<uint> references)
+    {
+        return this.GetValueReflection<bool>(references);
+    }
+
+    public (Fmi2Status, IEnumerable<String>) Fmi2GetString(IEnumerable<uint> references)
+    {
+        return this.GetValueReflection<String>(references);
+    }
+
+    public Fmi2Status Fmi2SetRealInputDerivatives(IEnumerable<uint> references, IEnumerable<int> orders, IEnumerable<double> values)
+    {
+        Log("The model can't interpolate inputs.", Fmi2Status.Fmi2Error, "logStatusError");
+        return Fmi2Status.Fmi2Error;
+    }
+
+    public (Fmi2Status, IEnumerable<double>) Fmi2GetRealOutputDerivatives(IEnumerable<uint> references, IEnumerable<int> orders)
+    {
+        Log("The model can't compute output derivatives.", Fmi2Status.Fmi2Error, "logStatusError");
+        return (Fmi2Status.Fmi2Error, new List<double>());
+    }
+
+    public Fmi2Status Fmi2CancelStep()
+    {
+        return Fmi2Status.Fmi2Ok;
+    }
+
+    public Fmi2Status Fmi2Reset()
+    {
+        // {{start_values}}
+
+        return Fmi2Status.Fmi2Ok;
+    }
+
+    public Fmi2Status Fmi2Terminate()
+    {
+        return Fmi2Status.Fmi2Ok;
+    }
+
+    public (Fmi2Status, byte[]) Fmi2SerializeFmuState()
+    {
+        using (MemoryStream m = new MemoryStream())
+        {
+            using (BinaryWriter writer = new BinaryWriter(m))
+            {
+                foreach (var entry in this.reference_to_attributes.OrderBy(e => e.Key))
+                {
+                    switch (entry.Value.GetValue(this))
+                    {
+                        case double d: writer.Write(d); break;
+                        case int i: writer.Write(i); break;
+                        case bool b: writer.Write(b); break;
+                        case string s: writer.Write(s ?? ""); break;
+                        default:
+                            throw new InvalidOperationException($"Unsupported type for property {entry.Value.Name}");
+                    }
+                }
+            }
+            return (Fmi2Status.Fmi2Ok, m.ToArray());
+        }
+    }
+
+    public Fmi2Status Fmi2DeserializeFmuState(byte[] state)
+    {
+        using (MemoryStream m = new MemoryStream(state))
+        {
+            using (BinaryReader reader = new BinaryReader(m))
+            {
+                foreach (var entry in this.reference_to_attributes.OrderBy(e => e.Key))
+                {
+                    var prop = entry.Value;
+                    var type = prop.PropertyType;
+
+                    if (type == typeof(double)) prop.SetValue(this, reader.ReadDouble());
+                    else if (type == typeof(int)) prop.SetValue(this, reader.ReadInt32());
+                    else if (type == typeof(bool)) prop.SetValue(this, reader.ReadBoolean());
+                    else if (type == typeof(string)) prop.SetValue(this, reader.ReadString());
+                    else throw new InvalidOperationException($"Unsupported type for property {prop.Name}");
+                }
+            }
+        }
+        return Fmi2Status.Fmi2Ok;
+    }
+
+    public Fmi2Status Fmi2EnterEventMode()
+    {
+        return Fmi2Status.Fmi2Ok;
+    }
+
+    public (Fmi2Status, bool, bool, bool, bool, bool, double) Fmi2NewDiscreteStates()
+    {
+        return (Fmi2Status.Fmi2Ok, false, false, false, false, false, 0.0);
+    }
+
+    public Fmi2Status Fmi2EnterContinuousTimeMode()
+    {
+        return Fmi2Status.Fmi2Ok;
+    }
+
+    public (Fmi2Status, bool, bool) Fmi2CompletedIntegratorStep(bool noSetFmuStatePriorToCurrentPoint)
+    {
+        return (Fmi2Status.Fmi2Ok, false, false);
+    }
+
+    public Fmi2Status Fmi2SetTime(double time)
+    {
+        return Fmi2Status.Fmi2Ok;
+    }
+
+    // The skeleton is generated for Co-Simulation, so the following Model
+    // Exchange functions only exchange empty lists.
+
+    public Fmi2Status Fmi2SetContinuousStates(IEnumerable<double> continuousStates)
+    {
+        return Fmi2Status.Fmi2Ok;
+    }
+
+    public (Fmi2Status, IEnumerable<double>) Fmi2GetDerivatives()
+    {
+        return (Fmi2Status.Fmi2Ok, new List<double>());
+    }
+
+    public (Fmi2Status, IEnumerable<double>) Fmi2GetEventIndicators()
+    {
+        return (Fmi2Status.Fmi2Ok, new List<double>());
+    }
+
+    public (Fmi2Status, IEnumerable<double>) Fmi2GetContinuousStates()
+    {
+        return (Fmi2Status.Fmi2Ok, new List<double>());
+    }
+
+    public (Fmi2Status, IEnumerable<double>) Fmi2GetNominalsOfContinuousStates()
+    {
+        return (Fmi2Status.Fmi2Ok, new List<double>());
+    }
+
+    /// <summary>UniFMU logging function
+    /// <para>
+    /// Call this function whenever something should be logged.
+    /// This will send a message thourgh the UniFMU layer to
+    /// the importer if the importer has enabled logging and
+    /// is interested in the given logging category.
+    /// </para>
+    /// </summary>
+    /// <param name="message">the message to be logged
+    /// </param>
+    /// <param name="status">the Fmi2Status that the FMU expects to next return (default: Fmi2Status.Fmi2Ok)</param>
+    /// <param name="category">the logging category that this log event falls under</param>
+    private void Log(String message, Fmi2Status status = Fmi2Status.Fmi2Ok, String category = "logAll") {
+        this.log_callback(status, category, message);
+    }
+
+    private Fmi2Status SetValueReflection<T>(IEnumerable<uint> references, IEnumerable<T> values)
+    {
+        foreach (var (r, v) in references.Zip(values))
+        {
+            this.reference_to_attributes[r].SetValue(this, (object)v);
+        }
+
+        return Fmi2Status.Fmi2Ok;
+    }
+
+    private (Fmi2Status, IEnumerable<T>) GetValueReflection<T>(IEnumerable<uint> references)
+    {
+        var values = new List<T>(references.Count());
+
+        foreach (var r in references)
+        {
+            values.Add((T)this.reference_to_attributes[r].GetValue(this));
+        }
+
+        return (Fmi2Status.Fmi2Ok, values);
+    }
+}
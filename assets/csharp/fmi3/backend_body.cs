@@ -52,16 +52,26 @@ namespace Launch
                     LogMessage = message
                 }});
 
-                Fmi3Command command = RecvCommand();
+                AwaitCallbackContinue();
+            };
 
-                switch (command.CommandCase)
-                {
-                    case Fmi3Command.CommandOneofCase.Fmi3CallbackContinue:
-                        break;
-                    default:
-                        HandleUnexpectedCommand(command);
-                        break;
-                }
+            // Only given to the model when instantiated for Scheduled Execution
+            Action clockUpdateCallback = () => {
+                SendReply(new Fmi3Return{ClockUpdate = new Fmi3ClockUpdateReturn()});
+
+                AwaitCallbackContinue();
+            };
+
+            Action lockPreemptionCallback = () => {
+                SendReply(new Fmi3Return{LockPreemption = new Fmi3LockPreemptionReturn()});
+
+                AwaitCallbackContinue();
+            };
+
+            Action unlockPreemptionCallback = () => {
+                SendReply(new Fmi3Return{UnlockPreemption = new Fmi3UnlockPreemptionReturn()});
+
+                AwaitCallbackContinue();
             };
 
             while (true)
@@ -91,6 +101,34 @@ namespace Launch
                         SendReply(new Fmi3Return{Empty = new Fmi3EmptyReturn()});
                         break;
 
+                    case Fmi3Command.CommandOneofCase.Fmi3InstantiateScheduledExecution:
+                        model = new Model(
+                            command.Fmi3InstantiateScheduledExecution.InstanceName,
+                            command.Fmi3InstantiateScheduledExecution.InstantiationToken,
+                            command.Fmi3InstantiateScheduledExecution.ResourcePath,
+                            command.Fmi3InstantiateScheduledExecution.Visible,
+                            command.Fmi3InstantiateScheduledExecution.LoggingOn,
+                            false,
+                            false,
+                            new List<uint>(),
+                            logCallback,
+                            clockUpdateCallback,
+                            lockPreemptionCallback,
+                            unlockPreemptionCallback
+                        );
+
+                        SendReply(new Fmi3Return{Empty = new Fmi3EmptyReturn()});
+                        break;
+
+                    case Fmi3Command.CommandOneofCase.Fmi3ActivateModelPartition:
+                        SendStatusReply(
+                            model.Fmi3ActivateModelPartition(
+                                command.Fmi3ActivateModelPartition.ClockReference,
+                                command.Fmi3ActivateModelPartition.ActivationTime
+                            )
+                        );
+                        break;
+
                     case Fmi3Command.CommandOneofCase.Fmi3SetDebugLogging:
                         SendStatusReply(
                             model.Fmi3SetDebugLogging(
@@ -149,6 +187,10 @@ namespace Launch
                         }
                         break;
 
+                    case Fmi3Command.CommandOneofCase.Fmi3EvaluateDiscreteStates:
+                        SendStatusReply(model.Fmi3EvaluateDiscreteStates());
+                        break;
+
                     case Fmi3Command.CommandOneofCase.Fmi3UpdateDiscreteStates:
                         {
                             Fmi3Return result = new Fmi3Return {
@@ -608,6 +650,53 @@ namespace Launch
                         }
                         break;
 
+                    case Fmi3Command.CommandOneofCase.Fmi3GetDirectionalDerivative:
+                        {
+                            (var status, var values) = model.Fmi3GetDirectionalDerivative(
+                                command.Fmi3GetDirectionalDerivative.Unknowns,
+                                command.Fmi3GetDirectionalDerivative.Knowns,
+                                command.Fmi3GetDirectionalDerivative.Seed
+                            );
+                            Fmi3Return result = new Fmi3Return {
+                                GetDirectionalDerivative = new Fmi3GetDirectionalDerivativeReturn()
+                            };
+                            result.GetDirectionalDerivative.Status = status;
+                            result.GetDirectionalDerivative.Values.AddRange(values);
+                            SendReply(result);
+                        }
+                        break;
+
+                    case Fmi3Command.CommandOneofCase.Fmi3GetOutputDerivatives:
+                        {
+                            (var status, var values) = model.Fmi3GetOutputDerivatives(
+                                command.Fmi3GetOutputDerivatives.ValueReferences,
+                                command.Fmi3GetOutputDerivatives.Orders
+                            );
+                            Fmi3Return result = new Fmi3Return {
+                                GetOutputDerivatives = new Fmi3GetOutputDerivativesReturn()
+                            };
+                            result.GetOutputDerivatives.Status = status;
+                            result.GetOutputDerivatives.Values.AddRange(values);
+                            SendReply(result);
+                        }
+                        break;
+
+                    case Fmi3Command.CommandOneofCase.Fmi3GetAdjointDerivative:
+                        {
+                            (var status, var values) = model.Fmi3GetAdjointDerivative(
+                                command.Fmi3GetAdjointDerivative.Unknowns,
+                                command.Fmi3GetAdjointDerivative.Knowns,
+                                command.Fmi3GetAdjointDerivative.Seed
+                            );
+                            Fmi3Return result = new Fmi3Return {
+                                GetAdjointDerivative = new Fmi3GetAdjointDerivativeReturn()
+                            };
+                            result.GetAdjointDerivative.Status = status;
+                            result.GetAdjointDerivative.Values.AddRange(values);
+                            SendReply(result);
+                        }
+                        break;
+
 
                     case Fmi3Command.CommandOneofCase.Fmi3Reset:
                         SendStatusReply(model.Fmi3Reset());
@@ -651,6 +740,20 @@ namespace Launch
             }
         }
 
+        private static void AwaitCallbackContinue()
+        {
+            Fmi3Command command = RecvCommand();
+
+            switch (command.CommandCase)
+            {
+                case Fmi3Command.CommandOneofCase.Fmi3CallbackContinue:
+                    break;
+                default:
+                    HandleUnexpectedCommand(command);
+                    break;
+            }
+        }
+
         private static void HandleUnexpectedCommand(Fmi3Command command)
         {
             Console.Error.WriteLine(
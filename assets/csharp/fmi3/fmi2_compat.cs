@@ -0,0 +1,329 @@
+using System;
+using System.Collections.Generic;
+using System.IO;
+using System.Linq;
+using Fmi2Messages;
+using Fmi3Messages;
+
+public delegate void Fmi3LogCallback(Fmi3Status status, String category, String message);
+
+/// <summary>
+/// Compatibility shim placed between the FMI3 backend and the model of an
+/// FMU converted from FMI2 by `unifmu convert`.
+///
+/// The FMI3 calls of the backend are translated to the FMI2 methods of the
+/// model, with the FMI3 types mapped to the FMI2 types as in the converted
+/// modelDescription.xml: Float64 to Real, Int32 to Integer, and Boolean and
+/// String as they are. The independent variable `time` isn't known to the
+/// model, so it is kept here. Calls that have no FMI2 counterpart are
+/// answered with an error.
+/// </summary>
+public class Fmi2CompatibleModel
+{
+    private const uint TIME_VALUE_REFERENCE = {{time_value_reference}};
+
+    private readonly Model model;
+    private readonly Fmi3LogCallback log_callback;
+    private double time = 0.0;
+
+    public Fmi2CompatibleModel(
+        string instance_name,
+        string instantiation_token,
+        string resource_path,
+        bool visible,
+        bool logging_on,
+        bool event_mode_used,
+        bool early_return_allowed,
+        List<uint> required_intermediate_variables,
+        Fmi3LogCallback log_callback,
+        Action clock_update_callback = null,
+        Action lock_preemption_callback = null,
+        Action unlock_preemption_callback = null
+    ) {
+        this.log_callback = log_callback;
+        this.model = new Model(
+            (status, category, message) => log_callback(ToFmi3Status(status), category, message)
+        );
+    }
+
+    // ================= FMI3 =================
+
+    public (Fmi3Status, bool, bool, bool, double) Fmi3DoStep(double currentCommunicationPoint, double communicationStepSize, bool noStepPrior)
+    {
+        var status = ToFmi3Status(model.Fmi2DoStep(currentCommunicationPoint, communicationStepSize, noStepPrior));
+
+        if (status <= Fmi3Status.Fmi3Warning)
+        {
+            time = currentCommunicationPoint + communicationStepSize;
+        }
+
+        return (status, false, false, false, time);
+    }
+
+    public Fmi3Status Fmi3EnterInitializationMode(Fmi3Messages.Fmi3EnterInitializationMode command)
+    {
+        time = command.StartTime;
+
+        var status = ToFmi3Status(model.Fmi2SetupExperiment(
+            command.StartTime,
+            command.StopTimeDefined ? command.StopTime : null,
+            command.ToleranceDefined ? command.Tolerance : null
+        ));
+        if (status > Fmi3Status.Fmi3Warning)
+        {
+            return status;
+        }
+
+        var enterStatus = ToFmi3Status(model.Fmi2EnterInitializationMode());
+        return enterStatus > status ? enterStatus : status;
+    }
+
+    public Fmi3Status Fmi3ExitInitializationMode()
+    {
+        return ToFmi3Status(model.Fmi2ExitInitializationMode());
+    }
+
+    public Fmi3Status Fmi3Terminate()
+    {
+        return ToFmi3Status(model.Fmi2Terminate());
+    }
+
+    public Fmi3Status Fmi3Reset()
+    {
+        time = 0.0;
+        return ToFmi3Status(model.Fmi2Reset());
+    }
+
+    public Fmi3Status Fmi3SetDebugLogging(IEnumerable<String> categories, bool loggingOn)
+    {
+        return ToFmi3Status(model.Fmi2SetDebugLogging(categories, loggingOn));
+    }
+
+    public (Fmi3Status, byte[]) Fmi3SerializeFmuState()
+    {
+        (var status, var state) = model.Fmi2SerializeFmuState();
+
+        using (MemoryStream m = new MemoryStream())
+        {
+            using (BinaryWriter writer = new BinaryWriter(m))
+            {
+                writer.Write(time);
+                writer.Write(state);
+            }
+            return (ToFmi3Status(status), m.ToArray());
+        }
+    }
+
+    public Fmi3Status Fmi3DeserializeFmuState(byte[] state)
+    {
+        double stateTime = BitConverter.ToDouble(state, 0);
+
+        var status = ToFmi3Status(model.Fmi2DeserializeFmuState(state.Skip(sizeof(double)).ToArray()));
+        if (status <= Fmi3Status.Fmi3Warning)
+        {
+            time = stateTime;
+        }
+
+        return status;
+    }
+
+    public (Fmi3Status, IEnumerable<double>) Fmi3GetFloat64(IEnumerable<uint> references)
+    {
+        var modelReferences = references.Where(r => r != TIME_VALUE_REFERENCE).ToList();
+
+        var status = Fmi3Status.Fmi3Ok;
+        IEnumerator<double> modelValues = Enumerable.Empty<double>().GetEnumerator();
+
+        if (modelReferences.Count > 0)
+        {
+            (var modelStatus, var values) = model.Fmi2GetReal(modelReferences);
+            status = ToFmi3Status(modelStatus);
+            if (status > Fmi3Status.Fmi3Warning)
+            {
+                return (status, new List<double>());
+            }
+            modelValues = values.GetEnumerator();
+        }
+
+        var result = new List<double>();
+        foreach (var reference in references)
+        {
+            if (reference == TIME_VALUE_REFERENCE)
+            {
+                result.Add(time);
+            }
+            else
+            {
+                modelValues.MoveNext();
+                result.Add(modelValues.Current);
+            }
+        }
+
+        return (status, result);
+    }
+
+    public (Fmi3Status, IEnumerable<int>) Fmi3GetInt32(IEnumerable<uint> references)
+    {
+        (var status, var values) = model.Fmi2GetInteger(references);
+        return (ToFmi3Status(status), values);
+    }
+
+    public (Fmi3Status, IEnumerable<bool>) Fmi3GetBoolean(IEnumerable<uint> references)
+    {
+        (var status, var values) = model.Fmi2GetBoolean(references);
+        return (ToFmi3Status(status), values);
+    }
+
+    public (Fmi3Status, IEnumerable<string>) Fmi3GetString(IEnumerable<uint> references)
+    {
+        (var status, var values) = model.Fmi2GetString(references);
+        return (ToFmi3Status(status), values);
+    }
+
+    public Fmi3Status Fmi3SetFloat64(IEnumerable<uint> references, IEnumerable<double> values)
+    {
+        var pairs = references.Zip(values).Where(pair => pair.First != TIME_VALUE_REFERENCE).ToList();
+
+        if (pairs.Count == 0)
+        {
+            return Fmi3Status.Fmi3Ok;
+        }
+
+        return ToFmi3Status(model.Fmi2SetReal(
+            pairs.Select(pair => pair.First).ToList(),
+            pairs.Select(pair => pair.Second).ToList()
+        ));
+    }
+
+    public Fmi3Status Fmi3SetInt32(IEnumerable<uint> references, IEnumerable<int> values)
+    {
+        return ToFmi3Status(model.Fmi2SetInteger(references, values));
+    }
+
+    public Fmi3Status Fmi3SetBoolean(IEnumerable<uint> references, IEnumerable<bool> values)
+    {
+        return ToFmi3Status(model.Fmi2SetBoolean(references, values));
+    }
+
+    public Fmi3Status Fmi3SetString(IEnumerable<uint> references, IEnumerable<string> values)
+    {
+        return ToFmi3Status(model.Fmi2SetString(references, values));
+    }
+
+    public (Fmi3Status, IEnumerable<double>) Fmi3GetOutputDerivatives(IEnumerable<uint> references, IEnumerable<int> orders)
+    {
+        // Looked up at runtime, as models generated before output derivatives
+        // were supported don't implement Fmi2GetRealOutputDerivatives.
+        var method = typeof(Model).GetMethod("Fmi2GetRealOutputDerivatives");
+        if (method == null)
+        {
+            return (Unsupported("Fmi3GetOutputDerivatives"), new List<double>());
+        }
+
+        (var status, var values) = ((Fmi2Status, IEnumerable<double>)) method.Invoke(model, new object[] { references, orders });
+        return (ToFmi3Status(status), values);
+    }
+
+    // ================= Unsupported =================
+
+    public Fmi3Status Fmi3EvaluateDiscreteStates() => Unsupported("Fmi3EvaluateDiscreteStates");
+
+    public (Fmi3Status, bool, bool, bool, bool, bool, double) Fmi3UpdateDiscreteStates() =>
+        (Unsupported("Fmi3UpdateDiscreteStates"), false, false, false, false, false, 0.0);
+
+    public Fmi3Status Fmi3EnterEventMode() => Unsupported("Fmi3EnterEventMode");
+
+    public Fmi3Status Fmi3ActivateModelPartition(uint clockReference, double activationTime) =>
+        Unsupported("Fmi3ActivateModelPartition");
+
+    public Fmi3Status Fmi3EnterStepMode() => Unsupported("Fmi3EnterStepMode");
+
+    public Fmi3Status Fmi3EnterConfigurationMode() => Unsupported("Fmi3EnterConfigurationMode");
+
+    public Fmi3Status Fmi3ExitConfigurationMode() => Unsupported("Fmi3ExitConfigurationMode");
+
+    public Fmi3Status Fmi3SetFloat32(IEnumerable<uint> references, IEnumerable<float> values) => Unsupported("Fmi3SetFloat32");
+
+    public Fmi3Status Fmi3SetInt8(IEnumerable<uint> references, IEnumerable<int> values) => Unsupported("Fmi3SetInt8");
+
+    public Fmi3Status Fmi3SetUInt8(IEnumerable<uint> references, IEnumerable<uint> values) => Unsupported("Fmi3SetUInt8");
+
+    public Fmi3Status Fmi3SetInt16(IEnumerable<uint> references, IEnumerable<int> values) => Unsupported("Fmi3SetInt16");
+
+    public Fmi3Status Fmi3SetUInt16(IEnumerable<uint> references, IEnumerable<uint> values) => Unsupported("Fmi3SetUInt16");
+
+    public Fmi3Status Fmi3SetUInt32(IEnumerable<uint> references, IEnumerable<uint> values) => Unsupported("Fmi3SetUInt32");
+
+    public Fmi3Status Fmi3SetInt64(IEnumerable<uint> references, IEnumerable<long> values) => Unsupported("Fmi3SetInt64");
+
+    public Fmi3Status Fmi3SetUInt64(IEnumerable<uint> references, IEnumerable<ulong> values) => Unsupported("Fmi3SetUInt64");
+
+    public Fmi3Status Fmi3SetBinary(IEnumerable<uint> references, IEnumerable<ulong> valueSizes, IEnumerable<byte[]> values) => Unsupported("Fmi3SetBinary");
+
+    public Fmi3Status Fmi3SetClock(IEnumerable<uint> references, IEnumerable<bool> values) => Unsupported("Fmi3SetClock");
+
+    public Fmi3Status Fmi3SetIntervalDecimal(IEnumerable<uint> references, IEnumerable<double> intervals) => Unsupported("Fmi3SetIntervalDecimal");
+
+    public Fmi3Status Fmi3SetIntervalFraction(IEnumerable<uint> references, IEnumerable<ulong> counters, IEnumerable<ulong> resolutions) => Unsupported("Fmi3SetIntervalFraction");
+
+    public Fmi3Status Fmi3SetShiftDecimal(IEnumerable<uint> references, IEnumerable<double> shifts) => Unsupported("Fmi3SetShiftDecimal");
+
+    public Fmi3Status Fmi3SetShiftFraction(IEnumerable<uint> references, IEnumerable<ulong> counters, IEnumerable<ulong> resolutions) => Unsupported("Fmi3SetShiftFraction");
+
+    public (Fmi3Status, IEnumerable<float>) Fmi3GetFloat32(IEnumerable<uint> references) => (Unsupported("Fmi3GetFloat32"), new List<float>());
+
+    public (Fmi3Status, IEnumerable<int>) Fmi3GetInt8(IEnumerable<uint> references) => (Unsupported("Fmi3GetInt8"), new List<int>());
+
+    public (Fmi3Status, IEnumerable<uint>) Fmi3GetUInt8(IEnumerable<uint> references) => (Unsupported("Fmi3GetUInt8"), new List<uint>());
+
+    public (Fmi3Status, IEnumerable<int>) Fmi3GetInt16(IEnumerable<uint> references) => (Unsupported("Fmi3GetInt16"), new List<int>());
+
+    public (Fmi3Status, IEnumerable<uint>) Fmi3GetUInt16(IEnumerable<uint> references) => (Unsupported("Fmi3GetUInt16"), new List<uint>());
+
+    public (Fmi3Status, IEnumerable<uint>) Fmi3GetUInt32(IEnumerable<uint> references) => (Unsupported("Fmi3GetUInt32"), new List<uint>());
+
+    public (Fmi3Status, IEnumerable<long>) Fmi3GetInt64(IEnumerable<uint> references) => (Unsupported("Fmi3GetInt64"), new List<long>());
+
+    public (Fmi3Status, IEnumerable<ulong>) Fmi3GetUInt64(IEnumerable<uint> references) => (Unsupported("Fmi3GetUInt64"), new List<ulong>());
+
+    public (Fmi3Status, IEnumerable<byte[]>) Fmi3GetBinary(IEnumerable<uint> references) => (Unsupported("Fmi3GetBinary"), new List<byte[]>());
+
+    public (Fmi3Status, IEnumerable<bool>) Fmi3GetClock(IEnumerable<uint> references) => (Unsupported("Fmi3GetClock"), new List<bool>());
+
+    public (Fmi3Status, IEnumerable<double>, IEnumerable<int>) Fmi3GetIntervalDecimal(IEnumerable<uint> references) =>
+        (Unsupported("Fmi3GetIntervalDecimal"), new List<double>(), new List<int>());
+
+    public (Fmi3Status, IEnumerable<ulong>, IEnumerable<ulong>, IEnumerable<int>) Fmi3GetIntervalFraction(IEnumerable<uint> references) =>
+        (Unsupported("Fmi3GetIntervalFraction"), new List<ulong>(), new List<ulong>(), new List<int>());
+
+    public (Fmi3Status, IEnumerable<double>) Fmi3GetShiftDecimal(IEnumerable<uint> references) => (Unsupported("Fmi3GetShiftDecimal"), new List<double>());
+
+    public (Fmi3Status, IEnumerable<ulong>, IEnumerable<ulong>) Fmi3GetShiftFraction(IEnumerable<uint> references) =>
+        (Unsupported("Fmi3GetShiftFraction"), new List<ulong>(), new List<ulong>());
+
+    public (Fmi3Status, IEnumerable<double>) Fmi3GetDirectionalDerivative(IEnumerable<uint> unknowns, IEnumerable<uint> knowns, IEnumerable<double> seed) =>
+        (Unsupported("Fmi3GetDirectionalDerivative"), new List<double>());
+
+    public (Fmi3Status, IEnumerable<double>) Fmi3GetAdjointDerivative(IEnumerable<uint> unknowns, IEnumerable<uint> knowns, IEnumerable<double> seed) =>
+        (Unsupported("Fmi3GetAdjointDerivative"), new List<double>());
+
+    // ================= Helpers =================
+
+    private Fmi3Status Unsupported(string name)
+    {
+        log_callback(Fmi3Status.Fmi3Error, "logStatusError", $"{name} isn't supported by a model converted from FMI2.");
+        return Fmi3Status.Fmi3Error;
+    }
+
+    /// <summary>
+    /// FMI3 dropped the pending status along with asynchronous steps.
+    /// </summary>
+    private static Fmi3Status ToFmi3Status(Fmi2Status status)
+    {
+        if (status == Fmi2Status.Fmi2Pending)
+        {
+            return Fmi3Status.Fmi3Error;
+        }
+        return (Fmi3Status)(int)status;
+    }
+}
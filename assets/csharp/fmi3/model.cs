@@ -159,6 +159,9 @@ public class Model
     private List<uint> required_intermediate_variables;
 
     private FMIState state = FMIState.FMIInstantiatedState;
+    // The value of clocked_variable_c when Event Mode was last entered,
+    // which event iteration evaluates the clocked variable from.
+    private int event_entry_clocked_variable_c = 0;
     public float float32_a { get; set; } = 0.0f;
     public float float32_b { get; set; } = 0.0f;
     public float float32_c { get; set; } = 0.0f;
@@ -264,8 +267,21 @@ public class Model
 
     private Dictionary<uint, PropertyInfo> all_parameters = new Dictionary<uint, PropertyInfo>();
 
+    // The partial derivatives of the continuous outputs with respect to the
+    // continuous inputs, used by Fmi3GetDirectionalDerivative and
+    // Fmi3GetAdjointDerivative. Derivatives not listed are zero.
+    private Dictionary<uint, Dictionary<uint, double>> partial_derivatives = new Dictionary<uint, Dictionary<uint, double>>{
+        {2, new Dictionary<uint, double>{{0, 1.0}, {1, 1.0}}},
+        {5, new Dictionary<uint, double>{{3, 1.0}, {4, 1.0}}}
+    };
+
     private LogCallback log_callback { get; set; }
 
+    // Only given when the FMU is instantiated for Scheduled Execution
+    private Action clock_update_callback { get; set; }
+    private Action lock_preemption_callback { get; set; }
+    private Action unlock_preemption_callback { get; set; }
+
     public Model(
         string instance_name,
         string instantiation_token,
@@ -275,7 +291,10 @@ public class Model
         bool event_mode_used,
         bool early_return_allowed,
         List<uint> required_intermediate_variables,
-        LogCallback log_callback
+        LogCallback log_callback,
+        Action clock_update_callback = null,
+        Action lock_preemption_callback = null,
+        Action unlock_preemption_callback = null
     ) {
         this.instance_name = instance_name;
         this.instantiation_token = instantiation_token;
@@ -286,6 +305,9 @@ public class Model
         this.early_return_allowed = early_return_allowed;
         this.required_intermediate_variables = required_intermediate_variables;
         this.log_callback = log_callback;
+        this.clock_update_callback = clock_update_callback;
+        this.lock_preemption_callback = lock_preemption_callback;
+        this.unlock_preemption_callback = unlock_preemption_callback;
         var type = this.GetType();
 
         this.reference_to_attributes = new Dictionary<uint, PropertyInfo>
@@ -392,6 +414,11 @@ public class Model
 
     public (Fmi3Status, bool, bool, bool, double) Fmi3DoStep(double currentCommunicationPoint, double communicationStepSize, bool noStepPrior)
     {
+        if (this.state != FMIState.FMIStepModeState) {
+            Log("Fmi3DoStep called outside of Step Mode.", Fmi3Status.Fmi3Error, "logStatusError");
+            return (Fmi3Status.Fmi3Error, false, false, false, currentCommunicationPoint);
+        }
+
         UpdateOutputs();
         bool event_handling_needed = false;
         bool terminate_simulation = false;
@@ -400,19 +427,63 @@ public class Model
         return (Fmi3Status.Fmi3Ok,event_handling_needed,terminate_simulation,early_return,last_successful_time);
     }
 
+    public Fmi3Status Fmi3EvaluateDiscreteStates(){
+        if (this.state != FMIState.FMIEventModeState) {
+            Log("Fmi3EvaluateDiscreteStates called outside of Event Mode.", Fmi3Status.Fmi3Error, "logStatusError");
+            return Fmi3Status.Fmi3Error;
+        }
+
+        EvaluateDiscreteStates();
+
+        return Fmi3Status.Fmi3Ok;
+    }
+
     public (Fmi3Status, bool, bool, bool, bool, bool, double) Fmi3UpdateDiscreteStates(){
-        bool discrete_states_need_update = false;
+        if (this.state != FMIState.FMIEventModeState) {
+            Log("Fmi3UpdateDiscreteStates called outside of Event Mode.", Fmi3Status.Fmi3Error, "logStatusError");
+            return (Fmi3Status.Fmi3Error, false, false, false, false, false, 0.0);
+        }
+
+        int previous_clocked_variable_c = this.clocked_variable_c;
+        EvaluateDiscreteStates();
+
+        // The event iteration has converged once an iteration leaves the
+        // discrete states unchanged.
+        bool discrete_states_need_update = this.clocked_variable_c != previous_clocked_variable_c;
         bool terminate_simulation = false;
         bool nominals_continuous_states_changed = false;
         bool values_continuous_states_changed = false;
         bool next_event_time_defined = true;
         double next_event_time = 1.0;
 
-        UpdateClockedOutputs();
-
         return (Fmi3Status.Fmi3Ok, discrete_states_need_update, terminate_simulation, nominals_continuous_states_changed, values_continuous_states_changed, next_event_time_defined, next_event_time);
     }
 
+    /* Scheduled Execution */
+
+    public Fmi3Status Fmi3ActivateModelPartition(uint clockReference, double activationTime)
+    {
+        if (clockReference != 1001 && clockReference != 1002) {
+            Log($"Clock #{clockReference}# isn't an input clock.", Fmi3Status.Fmi3Error, "logStatusError");
+            return Fmi3Status.Fmi3Error;
+        }
+
+        this.clocked_variables[clockReference].SetValue(this, true);
+        UpdateClocks();
+
+        if (this.clock_c) {
+            // Updating the clocked outputs mustn't be preempted by the
+            // activation of other model partitions.
+            LockPreemption();
+            UpdateClockedOutputs();
+            UnlockPreemption();
+            // Informs the importer that the output clock ticked
+            ClockUpdate();
+        }
+
+        return Fmi3Status.Fmi3Ok;
+    }
+
     /* Initialization, Enter, Termination, and Reset */
 
     public Fmi3Status Fmi3EnterInitializationMode()
@@ -423,21 +494,31 @@ public class Model
 
     public Fmi3Status Fmi3ExitInitializationMode()
     {
+        this.UpdateOutputs();
         if (this.event_mode_used) {
-            this.state = FMIState.FMIEventModeState;
+            EnterEventMode();
         } else{
             this.state = FMIState.FMIStepModeState;
         }
-        this.UpdateOutputs();
         return Fmi3Status.Fmi3Ok;
     }
 
     public Fmi3Status Fmi3EnterEventMode(){
-        this.state = FMIState.FMIEventModeState;
+        if (this.state != FMIState.FMIStepModeState) {
+            Log("Fmi3EnterEventMode called outside of Step Mode.", Fmi3Status.Fmi3Error, "logStatusError");
+            return Fmi3Status.Fmi3Error;
+        }
+
+        EnterEventMode();
         return Fmi3Status.Fmi3Ok;
     }
 
     public Fmi3Status Fmi3EnterStepMode(){
+        if (this.state != FMIState.FMIEventModeState) {
+            Log("Fmi3EnterStepMode called outside of Event Mode.", Fmi3Status.Fmi3Error, "logStatusError");
+            return Fmi3Status.Fmi3Error;
+        }
+
         this.state = FMIState.FMIStepModeState;
         return Fmi3Status.Fmi3Ok;
     }
@@ -559,6 +640,7 @@ public class Model
         this.clocked_variable_a = 0;
         this.clocked_variable_b = 0;
         this.clocked_variable_c = 0;
+        this.event_entry_clocked_variable_c = 0;
         this.clock_reference_to_interval = new Dictionary<uint,double>{{1001, 1.0}};
         this.clock_reference_to_shift = new Dictionary<uint,double>{{1001, 1.0}};
         UpdateOutputs();
@@ -841,6 +923,62 @@ public class Model
         return (Fmi3Status.Fmi3Ok, counters, resolutions);
     }
 
+    /* Derivatives */
+
+    public (Fmi3Status, IEnumerable<double>) Fmi3GetDirectionalDerivative(IEnumerable<uint> unknowns, IEnumerable<uint> knowns, IEnumerable<double> seed)
+    {
+        var sensitivity = unknowns
+            .Select(unknown => knowns
+                .Zip(seed, (known, seedValue) => PartialDerivative(unknown, known) * seedValue)
+                .Sum())
+            .ToList();
+
+        return (Fmi3Status.Fmi3Ok, sensitivity);
+    }
+
+    public (Fmi3Status, IEnumerable<double>) Fmi3GetAdjointDerivative(IEnumerable<uint> unknowns, IEnumerable<uint> knowns, IEnumerable<double> seed)
+    {
+        var sensitivity = knowns
+            .Select(known => unknowns
+                .Zip(seed, (unknown, seedValue) => seedValue * PartialDerivative(unknown, known))
+                .Sum())
+            .ToList();
+
+        return (Fmi3Status.Fmi3Ok, sensitivity);
+    }
+
+    public (Fmi3Status, IEnumerable<double>) Fmi3GetOutputDerivatives(IEnumerable<uint> references, IEnumerable<int> orders)
+    {
+        var values = new List<double>();
+
+        foreach (var reference in references)
+        {
+            if (!partial_derivatives.ContainsKey(reference))
+            {
+                Log($"Variable #{reference}# isn't a continuous output.", Fmi3Status.Fmi3Error, "logStatusError");
+                return (Fmi3Status.Fmi3Error, new List<double>());
+            }
+
+            // The continuous outputs only depend on the inputs, which are
+            // constant between communication points, so all of their
+            // derivatives with respect to time are zero.
+            values.Add(0.0);
+        }
+
+        return (Fmi3Status.Fmi3Ok, values);
+    }
+
+    private double PartialDerivative(uint unknown, uint known)
+    {
+        if (partial_derivatives.TryGetValue(unknown, out var derivatives)
+            && derivatives.TryGetValue(known, out var derivative))
+        {
+            return derivative;
+        }
+
+        return 0.0;
+    }
+
     /* Serialization */
 
     public (Fmi3Status, byte[]) Fmi3SerializeFmuState()
@@ -985,6 +1123,20 @@ public class Model
         this.clocked_variable_c += this.clocked_variable_a + this.clocked_variable_b;
     }
 
+    private void EnterEventMode()
+    {
+        this.state = FMIState.FMIEventModeState;
+        this.event_entry_clocked_variable_c = this.clocked_variable_c;
+    }
+
+    private void EvaluateDiscreteStates()
+    {
+        // Evaluated from the value at Event Mode entry, so that the clocked
+        // variable only advances once per event no matter the number of
+        // event iterations.
+        this.clocked_variable_c = this.event_entry_clocked_variable_c + this.clocked_variable_a + this.clocked_variable_b;
+    }
+
     public Fmi3Status Fmi3SetDebugLogging(IEnumerable<String> categories, bool loggingOn)
     {
         return Fmi3Status.Fmi3Ok;
@@ -1068,6 +1220,38 @@ public class Model
         this.log_callback(status, category, message);
     }
 
+    /// <summary>UniFMU clock update function
+    /// <para>
+    /// Call this function during Scheduled Execution to inform
+    /// the importer that an output clock has ticked or that a
+    /// countdown clock has been set. Does nothing if the FMU
+    /// wasn't instantiated for Scheduled Execution.
+    /// </para>
+    /// </summary>
+    private void ClockUpdate()
+    {
+        this.clock_update_callback?.Invoke();
+    }
+
+    /// <summary>UniFMU preemption lock functions
+    /// <para>
+    /// Call LockPreemption() before, and UnlockPreemption()
+    /// after, a critical section that must not be preempted by
+    /// the activation of other model partitions during
+    /// Scheduled Execution. Does nothing if the FMU wasn't
+    /// instantiated for Scheduled Execution.
+    /// </para>
+    /// </summary>
+    private void LockPreemption()
+    {
+        this.lock_preemption_callback?.Invoke();
+    }
+
+    private void UnlockPreemption()
+    {
+        this.unlock_preemption_callback?.Invoke();
+    }
+
     public Fmi3Status SetValueReflection<T>(IEnumerable<uint> references, IEnumerable<T> values)
     {
         var status = Fmi3Status.Fmi3Ok;
@@ -0,0 +1,740 @@
+using System;
+using System.Collections.Generic;
+using System.IO;
+using System.Reflection;
+using System.Numerics;
+
+using System.Linq;
+using Fmi3Messages;
+
+public delegate void LogCallback(Fmi3Status status, String category, String message);
+
+public class Model
+{
+    private string instance_name = "";
+    private string instantiation_token = "";
+    private string resource_path = "";
+    private bool visible = false;
+    private bool logging_on = false;
+    private bool event_mode_used = false;
+    private bool early_return_allowed = false;
+    private List<uint> required_intermediate_variables;
+
+    private FMIState state = FMIState.FMIInstantiatedState;
+
+    // {{declarations}}
+
+    public Dictionary<uint,double> clock_reference_to_interval = new Dictionary<uint,double>();
+    public Dictionary<uint,double> clock_reference_to_shift = new Dictionary<uint,double>();
+
+    private Dictionary<uint, PropertyInfo> reference_to_attributes = new Dictionary<uint, PropertyInfo>();
+    private List<uint> structural_parameters = new List<uint>();
+
+    private LogCallback log_callback { get; set; }
+
+    // Only given when the FMU is instantiated for Scheduled Execution
+    private Action clock_update_callback { get; set; }
+    private Action lock_preemption_callback { get; set; }
+    private Action unlock_preemption_callback { get; set; }
+
+    public Model(
+        string instance_name,
+        string instantiation_token,
+        string resource_path,
+        bool visible,
+        bool logging_on,
+        bool event_mode_used,
+        bool early_return_allowed,
+        List<uint> required_intermediate_variables,
+        LogCallback log_callback,
+        Action clock_update_callback = null,
+        Action lock_preemption_callback = null,
+        Action unlock_preemption_callback = null
+    ) {
+        this.instance_name = instance_name;
+        this.instantiation_token = instantiation_token;
+        this.resource_path = resource_path;
+        this.visible = visible;
+        this.logging_on = logging_on;
+        this.event_mode_used = event_mode_used;
+        this.early_return_allowed = early_return_allowed;
+        this.required_intermediate_variables = required_intermediate_variables;
+        this.log_callback = log_callback;
+        this.clock_update_callback = clock_update_callback;
+        this.lock_preemption_callback = lock_preemption_callback;
+        this.unlock_preemption_callback = unlock_preemption_callback;
+        var type = this.GetType();
+
+        this.reference_to_attributes = new Dictionary<uint, PropertyInfo>
+        {
+            // {{references}}
+        };
+
+        this.structural_parameters = new List<uint>
+        {
+            // {{structural_parameters}}
+        };
+
+        Fmi3Reset();
+    }
+
+    /* doStep and updateDiscreteStates */
+
+    public (Fmi3Status, bool, bool, bool, double) Fmi3DoStep(double currentCommunicationPoint, double communicationStepSize, bool noStepPrior)
+    {
+        if (this.state != FMIState.FMIStepModeState) {
+            Log("Fmi3DoStep called outside of Step Mode.", Fmi3Status.Fmi3Error, "logStatusError");
+            return (Fmi3Status.Fmi3Error, false, false, false, currentCommunicationPoint);
+        }
+
+        // Advance the model from currentCommunicationPoint to
+        // currentCommunicationPoint + communicationStepSize here, updating
+        // the outputs from the inputs.
+
+        bool event_handling_needed = false;
+        bool terminate_simulation = false;
+        bool early_return = false;
+        double last_successful_time = currentCommunicationPoint + communicationStepSize;
+        return (Fmi3Status.Fmi3Ok,event_handling_needed,terminate_simulation,early_return,last_successful_time);
+    }
+
+    public Fmi3Status Fmi3EvaluateDiscreteStates(){
+        if (this.state != FMIState.FMIEventModeState) {
+            Log("Fmi3EvaluateDiscreteStates called outside of Event Mode.", Fmi3Status.Fmi3Error, "logStatusError");
+            return Fmi3Status.Fmi3Error;
+        }
+
+        return Fmi3Status.Fmi3Ok;
+    }
+
+    public (Fmi3Status, bool, bool, bool, bool, bool, double) Fmi3UpdateDiscreteStates(){
+        if (this.state != FMIState.FMIEventModeState) {
+            Log("Fmi3UpdateDiscreteStates called outside of Event Mode.", Fmi3Status.Fmi3Error, "logStatusError");
+            return (Fmi3Status.Fmi3Error, false, false, false, false, false, 0.0);
+        }
+
+        bool discrete_states_need_update = false;
+        bool terminate_simulation = false;
+        bool nominals_continuous_states_changed = false;
+        bool values_continuous_states_changed = false;
+        bool next_event_time_defined = false;
+        double next_event_time = 0.0;
+
+        return (Fmi3Status.Fmi3Ok, discrete_states_need_update, terminate_simulation, nominals_continuous_states_changed, values_continuous_states_changed, next_event_time_defined, next_event_time);
+    }
+
+    /* Scheduled Execution */
+
+    public Fmi3Status Fmi3ActivateModelPartition(uint clockReference, double activationTime)
+    {
+        return Fmi3Status.Fmi3Ok;
+    }
+
+    /* Initialization, Enter, Termination, and Reset */
+
+    public Fmi3Status Fmi3EnterInitializationMode()
+    {
+        this.state = FMIState.FMIInitializationModeState;
+        return Fmi3Status.Fmi3Ok;
+    }
+
+    public Fmi3Status Fmi3ExitInitializationMode()
+    {
+        if (this.event_mode_used) {
+            this.state = FMIState.FMIEventModeState;
+        } else{
+            this.state = FMIState.FMIStepModeState;
+        }
+        return Fmi3Status.Fmi3Ok;
+    }
+
+    public Fmi3Status Fmi3EnterEventMode(){
+        if (this.state != FMIState.FMIStepModeState) {
+            Log("Fmi3EnterEventMode called outside of Step Mode.", Fmi3Status.Fmi3Error, "logStatusError");
+            return Fmi3Status.Fmi3Error;
+        }
+
+        this.state = FMIState.FMIEventModeState;
+        return Fmi3Status.Fmi3Ok;
+    }
+
+    public Fmi3Status Fmi3EnterStepMode(){
+        if (this.state != FMIState.FMIEventModeState) {
+            Log("Fmi3EnterStepMode called outside of Event Mode.", Fmi3Status.Fmi3Error, "logStatusError");
+            return Fmi3Status.Fmi3Error;
+        }
+
+        this.state = FMIState.FMIStepModeState;
+        return Fmi3Status.Fmi3Ok;
+    }
+
+    public Fmi3Status Fmi3EnterConfigurationMode(){
+        if (this.structural_parameters.Count > 0) {
+            if (this.state == FMIState.FMIInstantiatedState){
+                this.state = FMIState.FMIConfigurationModeState;
+            } else{
+                this.state = FMIState.FMIReconfigurationModeState;
+            }
+        }
+        else {
+            return Fmi3Status.Fmi3Error;
+        }
+        return Fmi3Status.Fmi3Ok;
+    }
+
+    public Fmi3Status Fmi3ExitConfigurationMode(){
+        if (this.state == FMIState.FMIConfigurationModeState){
+            this.state = FMIState.FMIInstantiatedState;
+        }
+        else if (this.state == FMIState.FMIReconfigurationModeState){
+            this.state = FMIState.FMIStepModeState;
+        }
+        else{
+            return Fmi3Status.Fmi3Error;
+        }
+        return Fmi3Status.Fmi3Ok;
+    }
+
+    public Fmi3Status Fmi3Reset()
+    {
+        this.state = FMIState.FMIInstantiatedState;
+        // {{start_values}}
+        this.clock_reference_to_interval = new Dictionary<uint,double>
+        {
+            // {{clock_intervals}}
+        };
+        this.clock_reference_to_shift = new Dictionary<uint,double>
+        {
+            // {{clock_shifts}}
+        };
+        return Fmi3Status.Fmi3Ok;
+    }
+
+    public Fmi3Status Fmi3Terminate()
+    {
+        this.state = FMIState.FMITerminatedState;
+        return Fmi3Status.Fmi3Ok;
+    }
+
+    /* Setters */
+
+    public Fmi3Status Fmi3SetFloat32(IEnumerable<uint> references, IEnumerable<float> values)
+    {
+        return SetValueReflection(references, values);
+    }
+
+    public Fmi3Status Fmi3SetFloat64(IEnumerable<uint> references, IEnumerable<double> values)
+    {
+        return SetValueReflection(references, values);
+    }
+
+    public Fmi3Status Fmi3SetInt8(IEnumerable<uint> references, IEnumerable<int> values)
+    {
+        return SetValueReflection(references, values);
+    }
+
+    public Fmi3Status Fmi3SetUInt8(IEnumerable<uint> references, IEnumerable<uint> values)
+    {
+        return SetValueReflection(references, values);
+    }
+
+    public Fmi3Status Fmi3SetInt16(IEnumerable<uint> references, IEnumerable<int> values)
+    {
+        return SetValueReflection(references, values);
+    }
+
+    public Fmi3Status Fmi3SetUInt16(IEnumerable<uint> references, IEnumerable<uint> values)
+    {
+        return SetValueReflection(references, values);
+    }
+
+    public Fmi3Status Fmi3SetInt32(IEnumerable<uint> references, IEnumerable<int> values)
+    {
+        return SetValueReflection(references, values);
+    }
+
+    public Fmi3Status Fmi3SetUInt32(IEnumerable<uint> references, IEnumerable<uint> values)
+    {
+        return SetValueReflection(references, values);
+    }
+
+    public Fmi3Status Fmi3SetInt64(IEnumerable<uint> references, IEnumerable<long> values)
+    {
+        return SetValueReflection(references, values);
+    }
+
+    public Fmi3Status Fmi3SetUInt64(IEnumerable<uint> references, IEnumerable<ulong> values)
+    {
+        return SetValueReflection(references, values);
+    }
+
+    public Fmi3Status Fmi3SetBinary(IEnumerable<uint> references, IEnumerable<ulong> valueSizes, IEnumerable<byte[]> values)
+    {
+        return SetValueReflection(references, values);
+    }
+
+    public Fmi3Status Fmi3SetClock(IEnumerable<uint> references, IEnumerable<bool> values)
+    {
+        return SetValueReflection(references, values);
+    }
+
+    public Fmi3Status Fmi3SetBoolean(IEnumerable<uint> references, IEnumerable<bool> values)
+    {
+        return SetValueReflection(references, values);
+    }
+
+    public Fmi3Status Fmi3SetString(IEnumerable<uint> references, IEnumerable<string> values)
+    {
+        return SetValueReflection(references, values);
+    }
+
+    public Fmi3Status Fmi3SetIntervalDecimal(IEnumerable<uint> references, IEnumerable<double> intervals)
+    {
+        foreach (var (reference, interval) in references.Zip(intervals))
+        {
+            clock_reference_to_interval[reference] = interval;
+        }
+
+        return Fmi3Status.Fmi3Ok;
+    }
+
+    public Fmi3Status Fmi3SetIntervalFraction(IEnumerable<uint> references, IEnumerable<ulong> counters, IEnumerable<ulong> resolutions)
+    {
+        foreach (var ((reference, counter), resolution) in references.Zip(counters).Zip(resolutions))
+        {
+            clock_reference_to_interval[reference] = (double)counter / resolution;
+        }
+
+        return Fmi3Status.Fmi3Ok;
+    }
+
+    public Fmi3Status Fmi3SetShiftDecimal(IEnumerable<uint> references, IEnumerable<double> shifts)
+    {
+        foreach (var (reference, shift) in references.Zip(shifts))
+        {
+            clock_reference_to_shift[reference] = shift;
+        }
+
+        return Fmi3Status.Fmi3Ok;
+    }
+
+    public Fmi3Status Fmi3SetShiftFraction(IEnumerable<uint> references, IEnumerable<ulong> counters, IEnumerable<ulong> resolutions)
+    {
+        foreach (var ((reference, counter), resolution) in references.Zip(counters).Zip(resolutions))
+        {
+            clock_reference_to_shift[reference] = (double)counter / resolution;
+        }
+
+        return Fmi3Status.Fmi3Ok;
+    }
+
+    /* Getters */
+
+    public (Fmi3Status, IEnumerable<float>) Fmi3GetFloat32(IEnumerable<uint> references)
+    {
+        return GetValueReflection<float>(references);
+    }
+
+    public (Fmi3Status, IEnumerable<double>) Fmi3GetFloat64(IEnumerable<uint> references)
+    {
+        return GetValueReflection<double>(references);
+    }
+
+    public (Fmi3Status, IEnumerable<int>) Fmi3GetInt8(IEnumerable<uint> references)
+    {
+        return GetValueReflection<int>(references);
+    }
+
+    public (Fmi3Status, IEnumerable<uint>) Fmi3GetUInt8(IEnumerable<uint> references)
+    {
+        return GetValueReflection<uint>(references);
+    }
+
+    public (Fmi3Status, IEnumerable<int>) Fmi3GetInt16(IEnumerable<uint> references)
+    {
+        return GetValueReflection<int>(references);
+    }
+
+    public (Fmi3Status, IEnumerable<uint>) Fmi3GetUInt16(IEnumerable<uint> references)
+    {
+        return GetValueReflection<uint>(references);
+    }
+
+    public (Fmi3Status, IEnumerable<int>) Fmi3GetInt32(IEnumerable<uint> references)
+    {
+        return GetValueReflection<int>(references);
+    }
+
+    public (Fmi3Status, IEnumerable<uint>) Fmi3GetUInt32(IEnumerable<uint> references)
+    {
+        return GetValueReflection<uint>(references);
+    }
+
+    public (Fmi3Status, IEnumerable<long>) Fmi3GetInt64(IEnumerable<uint> references)
+    {
+        return GetValueReflection<long>(references);
+    }
+
+    public (Fmi3Status, IEnumerable<ulong>) Fmi3GetUInt64(IEnumerable<uint> references)
+    {
+        return GetValueReflection<ulong>(references);
+    }
+
+    public (Fmi3Status, IEnumerable<byte[]>) Fmi3GetBinary(IEnumerable<uint> references)
+    {
+        return GetValueReflection<byte[]>(references);
+    }
+
+    public (Fmi3Status, IEnumerable<bool>) Fmi3GetClock(IEnumerable<uint> references)
+    {
+        return GetValueReflection<bool>(references);
+    }
+
+    public (Fmi3Status, IEnumerable<bool>) Fmi3GetBoolean(IEnumerable<uint> references)
+    {
+        return GetValueReflection<bool>(references);
+    }
+
+    public (Fmi3Status, IEnumerable<string>) Fmi3GetString(IEnumerable<uint> references)
+    {
+        return GetValueReflection<string>(references);
+    }
+
+    public (Fmi3Status, IEnumerable<double>, IEnumerable<int>) Fmi3GetIntervalDecimal(IEnumerable<uint> references)
+    {
+        var intervals = new List<double>();
+        var qualifiers = new List<int>();
+
+        foreach (var reference in references)
+        {
+            intervals.Add(clock_reference_to_interval[reference]);
+            qualifiers.Add(2);
+        }
+
+        return (Fmi3Status.Fmi3Ok, intervals, qualifiers);
+    }
+
+    public (Fmi3Status, IEnumerable<ulong>, IEnumerable<ulong>, IEnumerable<int>) Fmi3GetIntervalFraction(IEnumerable<uint> references)
+    {
+        var counters = new List<ulong>();
+        var resolutions = new List<ulong>();
+        var qualifiers = new List<int>();
+
+        foreach (var reference in references)
+        {
+            var fraction = new Fraction(clock_reference_to_interval[reference].ToString());
+            counters.Add(fraction.Numerator);
+            resolutions.Add(fraction.Denominator);
+            qualifiers.Add(2);
+        }
+
+        return (Fmi3Status.Fmi3Ok, counters, resolutions, qualifiers);
+    }
+
+    public (Fmi3Status, IEnumerable<double>) Fmi3GetShiftDecimal(IEnumerable<uint> references)
+    {
+        var shifts = new List<double>();
+
+        foreach (var reference in references)
+        {
+            shifts.Add(clock_reference_to_shift[reference]);
+        }
+
+        return (Fmi3Status.Fmi3Ok, shifts);
+    }
+
+    public (Fmi3Status, IEnumerable<ulong>, IEnumerable<ulong>) Fmi3GetShiftFraction(IEnumerable<uint> references)
+    {
+        var counters = new List<ulong>();
+        var resolutions = new List<ulong>();
+
+        foreach (var reference in references)
+        {
+            var fraction = new Fraction(clock_reference_to_shift[reference].ToString());
+            counters.Add(fraction.Numerator);
+            resolutions.Add(fraction.Denominator);
+        }
+
+        return (Fmi3Status.Fmi3Ok, counters, resolutions);
+    }
+
+    /* Derivatives */
+
+    public (Fmi3Status, IEnumerable<double>) Fmi3GetDirectionalDerivative(IEnumerable<uint> unknowns, IEnumerable<uint> knowns, IEnumerable<double> seed)
+    {
+        Log("The model can't compute directional derivatives.", Fmi3Status.Fmi3Error, "logStatusError");
+        return (Fmi3Status.Fmi3Error, new List<double>());
+    }
+
+    public (Fmi3Status, IEnumerable<double>) Fmi3GetAdjointDerivative(IEnumerable<uint> unknowns, IEnumerable<uint> knowns, IEnumerable<double> seed)
+    {
+        Log("The model can't compute adjoint derivatives.", Fmi3Status.Fmi3Error, "logStatusError");
+        return (Fmi3Status.Fmi3Error, new List<double>());
+    }
+
+    public (Fmi3Status, IEnumerable<double>) Fmi3GetOutputDerivatives(IEnumerable<uint> references, IEnumerable<int> orders)
+    {
+        Log("The model can't compute output derivatives.", Fmi3Status.Fmi3Error, "logStatusError");
+        return (Fmi3Status.Fmi3Error, new List<double>());
+    }
+
+    /* Serialization */
+
+    public (Fmi3Status, byte[]) Fmi3SerializeFmuState()
+    {
+        using (MemoryStream m = new MemoryStream())
+        {
+            using (BinaryWriter writer = new BinaryWriter(m))
+            {
+                writer.Write((int)this.state);
+                foreach (var entry in this.reference_to_attributes.OrderBy(e => e.Key))
+                {
+                    WriteValue(writer, entry.Value.GetValue(this));
+                }
+                foreach (var clocks in new[] { clock_reference_to_interval, clock_reference_to_shift })
+                {
+                    writer.Write(clocks.Count);
+                    foreach (var (reference, value) in clocks)
+                    {
+                        writer.Write(reference);
+                        writer.Write(value);
+                    }
+                }
+            }
+            return (Fmi3Status.Fmi3Ok, m.ToArray());
+        }
+    }
+
+    public Fmi3Status Fmi3DeserializeFmuState(byte[] state)
+    {
+        using (MemoryStream m = new MemoryStream(state))
+        {
+            using (BinaryReader reader = new BinaryReader(m))
+            {
+                this.state = (FMIState)reader.ReadInt32();
+                foreach (var entry in this.reference_to_attributes.OrderBy(e => e.Key))
+                {
+                    entry.Value.SetValue(this, ReadValue(reader, entry.Value.PropertyType));
+                }
+                foreach (var clocks in new[] { clock_reference_to_interval, clock_reference_to_shift })
+                {
+                    clocks.Clear();
+                    int count = reader.ReadInt32();
+                    for (int i = 0; i < count; i++)
+                    {
+                        clocks[reader.ReadUInt32()] = reader.ReadDouble();
+                    }
+                }
+            }
+        }
+        return Fmi3Status.Fmi3Ok;
+    }
+
+    private static void WriteValue(BinaryWriter writer, object value)
+    {
+        switch (value)
+        {
+            case float f: writer.Write(f); break;
+            case double d: writer.Write(d); break;
+            case int i: writer.Write(i); break;
+            case uint ui: writer.Write(ui); break;
+            case long l: writer.Write(l); break;
+            case ulong ul: writer.Write(ul); break;
+            case bool bo: writer.Write(bo); break;
+            case string str: writer.Write(str ?? ""); break;
+            case byte[] bytes:
+                writer.Write(bytes.Length);
+                writer.Write(bytes);
+                break;
+            case Array array:
+                writer.Write(array.Length);
+                foreach (var element in array)
+                    WriteValue(writer, element);
+                break;
+            default:
+                throw new InvalidOperationException($"Unsupported type {value?.GetType()}");
+        }
+    }
+
+    private static object ReadValue(BinaryReader reader, Type type)
+    {
+        if (type == typeof(float)) return reader.ReadSingle();
+        else if (type == typeof(double)) return reader.ReadDouble();
+        else if (type == typeof(int)) return reader.ReadInt32();
+        else if (type == typeof(uint)) return reader.ReadUInt32();
+        else if (type == typeof(long)) return reader.ReadInt64();
+        else if (type == typeof(ulong)) return reader.ReadUInt64();
+        else if (type == typeof(bool)) return reader.ReadBoolean();
+        else if (type == typeof(string)) return reader.ReadString();
+        else if (type == typeof(byte[])) return reader.ReadBytes(reader.ReadInt32());
+        else if (type.IsArray)
+        {
+            var element_type = type.GetElementType();
+            var array = Array.CreateInstance(element_type, reader.ReadInt32());
+            for (int i = 0; i < array.Length; i++)
+                array.SetValue(ReadValue(reader, element_type), i);
+            return array;
+        }
+        else throw new InvalidOperationException($"Unsupported type {type}");
+    }
+
+    public Fmi3Status Fmi3SetDebugLogging(IEnumerable<String> categories, bool loggingOn)
+    {
+        return Fmi3Status.Fmi3Ok;
+    }
+
+    /// <summary>UniFMU logging function
+    /// <para>
+    /// Call this function whenever something should be logged.
+    /// This will send a message thourgh the UniFMU layer to
+    /// the importer if the importer has enabled logging and
+    /// is interested in the given logging category.
+    /// </para>
+    /// </summary>
+    /// <param name="message">the message to be logged
+    /// </param>
+    /// <param name="status">the Fmi3Status that the FMU expects to next return (default: Fmi3Status.Fmi3Ok)</param>
+    /// <param name="category">the logging category that this log event falls under</param>
+    private void Log(
+        String message,
+        Fmi3Status status = Fmi3Status.Fmi3Ok,
+        String category = "logEvents"
+    )
+    {
+        this.log_callback(status, category, message);
+    }
+
+    /// <summary>UniFMU clock update function
+    /// <para>
+    /// Call this function during Scheduled Execution to inform
+    /// the importer that an output clock has ticked or that a
+    /// countdown clock has been set. Does nothing if the FMU
+    /// wasn't instantiated for Scheduled Execution.
+    /// </para>
+    /// </summary>
+    private void ClockUpdate()
+    {
+        this.clock_update_callback?.Invoke();
+    }
+
+    /// <summary>UniFMU preemption lock functions
+    /// <para>
+    /// Call LockPreemption() before, and UnlockPreemption()
+    /// after, a critical section that must not be preempted by
+    /// the activation of other model partitions during
+    /// Scheduled Execution. Does nothing if the FMU wasn't
+    /// instantiated for Scheduled Execution.
+    /// </para>
+    /// </summary>
+    private void LockPreemption()
+    {
+        this.lock_preemption_callback?.Invoke();
+    }
+
+    private void UnlockPreemption()
+    {
+        this.unlock_preemption_callback?.Invoke();
+    }
+
+    public Fmi3Status SetValueReflection<T>(IEnumerable<uint> references, IEnumerable<T> values)
+    {
+        IEnumerator<T> value_enumerator = values.GetEnumerator();
+
+        foreach (var reference in references)
+        {
+            var attribute = this.reference_to_attributes[reference];
+
+            if (attribute.GetValue(this) is T[] array)
+            {
+                for (int i = 0; i < array.Length; i++)
+                {
+                    if (!value_enumerator.MoveNext())
+                    {
+                        Log($"Too few values given to set array #{reference}#.", Fmi3Status.Fmi3Error, "logStatusError");
+                        return Fmi3Status.Fmi3Error;
+                    }
+                    array[i] = value_enumerator.Current;
+                }
+            }
+            else
+            {
+                if (!value_enumerator.MoveNext())
+                {
+                    Log($"No value given to set #{reference}#.", Fmi3Status.Fmi3Error, "logStatusError");
+                    return Fmi3Status.Fmi3Error;
+                }
+                attribute.SetValue(this, (object)value_enumerator.Current);
+            }
+        }
+
+        return Fmi3Status.Fmi3Ok;
+    }
+
+    public (Fmi3Status, IEnumerable<T>) GetValueReflection<T>(IEnumerable<uint> references)
+    {
+        var values = new List<T>(references.Count());
+        foreach (var r in references)
+        {
+            var value = this.reference_to_attributes[r].GetValue(this);
+            if (value is T[] array)
+            {
+                values.AddRange(array);
+            }
+            else
+            {
+                values.Add((T)value);
+            }
+        }
+        return (Fmi3Status.Fmi3Ok, values);
+    }
+
+    [Flags]
+    public enum FMIState
+    {
+        FMIStartAndEndState         = 1 << 0,
+        FMIInstantiatedState        = 1 << 1,
+        FMIInitializationModeState  = 1 << 2,
+        FMITerminatedState          = 1 << 3,
+        FMIConfigurationModeState   = 1 << 4,
+        FMIReconfigurationModeState = 1 << 5,
+        FMIEventModeState           = 1 << 6,
+        FMIContinuousTimeModeState  = 1 << 7,
+        FMIStepModeState            = 1 << 8,
+        FMIClockActivationMode      = 1 << 9
+    }
+
+    public class Fraction
+    {
+        public ulong Numerator { get; }
+        public ulong Denominator { get; }
+
+        public Fraction(string decimalValue)
+        {
+            decimal decimalNumber = Decimal.Parse(decimalValue);
+            int scale = GetScale(decimalValue);
+
+            BigInteger den = BigInteger.Pow(10, scale);
+            BigInteger num = (BigInteger)(decimalNumber * (decimal)den);
+
+            BigInteger gcd = BigInteger.GreatestCommonDivisor(num, den);
+            this.Numerator = (ulong)(num / gcd);
+            this.Denominator = (ulong)(den / gcd);
+        }
+
+        private int GetScale(string decimalValue)
+        {
+            int scale = 0;
+            int index = decimalValue.IndexOf('.');
+            if (index >= 0)
+            {
+                scale = decimalValue.Length - index - 1;
+            }
+            return scale;
+        }
+
+        public override string ToString()
+        {
+            return $"{Numerator}/{Denominator}";
+        }
+    }
+}
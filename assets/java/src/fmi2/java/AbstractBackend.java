@@ -2,6 +2,9 @@ import com.google.protobuf.ByteString;
 import com.google.protobuf.Message;
 import com.google.protobuf.InvalidProtocolBufferException;
 
+import java.util.ArrayList;
+import java.util.List;
+
 import org.zeromq.SocketType;
 import org.zeromq.ZMQ;
 import org.zeromq.ZContext;
@@ -10,6 +13,14 @@ public abstract class AbstractBackend {
     static ZMQ.Socket socket;
     static Model model;
 
+    // Thread running an asynchronous fmi2DoStep, if any. Log events emitted
+    // from it are buffered until the next command is received, as only the
+    // main thread may use the socket.
+    static Thread stepThread;
+    static volatile Model.Fmi2Status stepStatus;
+    static String pendingMessage = "";
+    static final List<Runnable> bufferedLogs = new ArrayList<>();
+
     static Fmi2Messages.Fmi2Command recvCommand() throws InvalidProtocolBufferException {
         return Fmi2Messages.Fmi2Command.parseFrom(socket.recv());
     }
@@ -36,6 +47,13 @@ public abstract class AbstractBackend {
     }
 
     public static void loggingCallback(Model.Fmi2Status status, String category, String message) {
+        if (Thread.currentThread() == stepThread) {
+            synchronized (bufferedLogs) {
+                bufferedLogs.add(() -> loggingCallback(status, category, message));
+            }
+            return;
+        }
+
         sendReply(
             Fmi2Messages.Fmi2Return
                 .newBuilder()
@@ -70,6 +88,46 @@ public abstract class AbstractBackend {
         }
     }
 
+    static void flushBufferedLogs() {
+        List<Runnable> logs;
+        synchronized (bufferedLogs) {
+            logs = new ArrayList<>(bufferedLogs);
+            bufferedLogs.clear();
+        }
+
+        for (Runnable log : logs) {
+            log.run();
+        }
+    }
+
+    /**
+     * Runs fmi2DoStep of the model on a separate thread, so that the importer
+     * can query the status of the step or cancel it while it is running.
+     */
+    static void startAsynchronousStep(Fmi2Messages.Fmi2DoStep c) {
+        stepStatus = Model.Fmi2Status.Pending;
+        // Cleared before the step thread starts, so that a cancellation
+        // arriving right after the step was started isn't lost.
+        model.step_canceled = false;
+        pendingMessage = "fmi2DoStep from time " + c.getCurrentTime()
+            + " with step size " + c.getStepSize() + " is running";
+
+        stepThread = new Thread(() -> {
+            try {
+                stepStatus = model.fmi2DoStep(
+                    c.getCurrentTime(),
+                    c.getStepSize(),
+                    c.getNoSetFmuStatePriorToCurrentPoint()
+                );
+            }
+            catch (Exception e) {
+                stepStatus = Model.Fmi2Status.Error;
+            }
+        });
+        stepThread.setDaemon(true);
+        stepThread.start();
+    }
+
     static void handshake() {
         sendReply(
             UnifmuHandshake.HandshakeReply
@@ -88,6 +146,8 @@ public abstract class AbstractBackend {
         while (true) {
             Fmi2Messages.Fmi2Command command = recvCommand();
 
+            flushBufferedLogs();
+
             switch (command.getCommandCase()) {        
                     
                 case FMI2INSTANTIATE:
@@ -240,18 +300,85 @@ public abstract class AbstractBackend {
                     break;
                 }
 
-                case FMI2DOSTEP: {
-                    var c = command.getFmi2DoStep();
+                case FMI2SETREALINPUTDERIVATIVES: {
+                    var c = command.getFmi2SetRealInputDerivatives();
                     sendStatusReply(
-                        model.fmi2DoStep(
-                            c.getCurrentTime(),
-                            c.getStepSize(),
-                            c.getNoSetFmuStatePriorToCurrentPoint()
+                        model.fmi2SetRealInputDerivatives(
+                            c.getReferencesList(),
+                            c.getOrdersList(),
+                            c.getValuesList()
                         )
                     );
                     break;
                 }
 
+                case FMI2GETREALOUTPUTDERIVATIVES: {
+                    var c = command.getFmi2GetRealOutputDerivatives();
+                    var res = model.fmi2GetRealOutputDerivatives(
+                        c.getReferencesList(),
+                        c.getOrdersList()
+                    );
+                    sendReply(
+                        Fmi2Messages.Fmi2Return
+                            .newBuilder()
+                            .setGetRealOutputDerivatives(
+                                Fmi2Messages.Fmi2GetRealOutputDerivativesReturn
+                                    .newBuilder()
+                                    .setStatus(
+                                        Fmi2Messages.Fmi2Status
+                                            .forNumber(res.status.ordinal())
+                                    )
+                                    .addAllValues(res.values)
+                                    .build()
+                            )
+                            .build()
+                    );
+                    break;
+                }
+
+                case FMI2DOSTEP: {
+                    var c = command.getFmi2DoStep();
+                    if (c.getRunAsynchronously()) {
+                        startAsynchronousStep(c);
+                        sendStatusReply(Model.Fmi2Status.Pending);
+                    } else {
+                        sendStatusReply(
+                            model.fmi2DoStep(
+                                c.getCurrentTime(),
+                                c.getStepSize(),
+                                c.getNoSetFmuStatePriorToCurrentPoint()
+                            )
+                        );
+                    }
+                    break;
+                }
+
+                case FMI2GETDOSTEPSTATUS: {
+                    // The status of a finished step is kept, as the API may
+                    // query it again after it has been reported once.
+                    Model.Fmi2Status status = Model.Fmi2Status.Pending;
+                    if (stepThread == null || !stepThread.isAlive()) {
+                        status = stepStatus;
+                        stepThread = null;
+                    }
+                    sendReply(
+                        Fmi2Messages.Fmi2Return
+                            .newBuilder()
+                            .setGetDoStepStatus(
+                                Fmi2Messages.Fmi2GetDoStepStatusReturn
+                                    .newBuilder()
+                                    .setStatus(
+                                        Fmi2Messages.Fmi2Status
+                                            .forNumber(status.ordinal())
+                                    )
+                                    .setPendingMessage(pendingMessage)
+                                    .build()
+                            )
+                            .build()
+                    );
+                    break;
+                }
+
                 case FMI2SETUPEXPERIMENT: {
                     var c = command.getFmi2SetupExperiment();
                     sendStatusReply(
@@ -283,9 +410,16 @@ public abstract class AbstractBackend {
                     sendStatusReply(model.fmi2Terminate());
                     break;
 
-                case FMI2CANCELSTEP:
-                    sendStatusReply(model.fmi2CancelStep());
+                case FMI2CANCELSTEP: {
+                    var status = model.fmi2CancelStep();
+                    if (stepThread != null) {
+                        stepThread.join();
+                        stepThread = null;
+                    }
+                    flushBufferedLogs();
+                    sendStatusReply(status);
                     break;
+                }
 
                 case FMI2SERIALIZEFMUSTATE: {
                     var res = model.fmi2SerializeFmuState();
@@ -328,6 +462,158 @@ public abstract class AbstractBackend {
                     break;
                 }
 
+                case FMI2ENTEREVENTMODE:
+                    sendStatusReply(model.fmi2EnterEventMode());
+                    break;
+
+                case FMI2NEWDISCRETESTATES: {
+                    var res = model.fmi2NewDiscreteStates();
+                    sendReply(
+                        Fmi2Messages.Fmi2Return
+                            .newBuilder()
+                            .setNewDiscreteStates(
+                                Fmi2Messages.Fmi2NewDiscreteStatesReturn
+                                    .newBuilder()
+                                    .setStatus(
+                                        Fmi2Messages.Fmi2Status
+                                            .forNumber(res.status.ordinal())
+                                    )
+                                    .setNewDiscreteStatesNeeded(res.new_discrete_states_needed)
+                                    .setTerminateSimulation(res.terminate_simulation)
+                                    .setNominalsOfContinuousStatesChanged(res.nominals_of_continuous_states_changed)
+                                    .setValuesOfContinuousStatesChanged(res.values_of_continuous_states_changed)
+                                    .setNextEventTimeDefined(res.next_event_time_defined)
+                                    .setNextEventTime(res.next_event_time)
+                                    .build()
+                            )
+                            .build()
+                    );
+                    break;
+                }
+
+                case FMI2ENTERCONTINUOUSTIMEMODE:
+                    sendStatusReply(model.fmi2EnterContinuousTimeMode());
+                    break;
+
+                case FMI2COMPLETEDINTEGRATORSTEP: {
+                    var res = model.fmi2CompletedIntegratorStep(
+                        command.getFmi2CompletedIntegratorStep()
+                            .getNoSetFmuStatePriorToCurrentPoint()
+                    );
+                    sendReply(
+                        Fmi2Messages.Fmi2Return
+                            .newBuilder()
+                            .setCompletedIntegratorStep(
+                                Fmi2Messages.Fmi2CompletedIntegratorStepReturn
+                                    .newBuilder()
+                                    .setStatus(
+                                        Fmi2Messages.Fmi2Status
+                                            .forNumber(res.status.ordinal())
+                                    )
+                                    .setEnterEventMode(res.enter_event_mode)
+                                    .setTerminateSimulation(res.terminate_simulation)
+                                    .build()
+                            )
+                            .build()
+                    );
+                    break;
+                }
+
+                case FMI2SETTIME:
+                    sendStatusReply(
+                        model.fmi2SetTime(command.getFmi2SetTime().getTime())
+                    );
+                    break;
+
+                case FMI2SETCONTINUOUSSTATES:
+                    sendStatusReply(
+                        model.fmi2SetContinuousStates(
+                            command.getFmi2SetContinuousStates()
+                                .getContinuousStatesList()
+                        )
+                    );
+                    break;
+
+                case FMI2GETDERIVATIVES: {
+                    var res = model.fmi2GetDerivatives();
+                    sendReply(
+                        Fmi2Messages.Fmi2Return
+                            .newBuilder()
+                            .setGetDerivatives(
+                                Fmi2Messages.Fmi2GetDerivativesReturn
+                                    .newBuilder()
+                                    .setStatus(
+                                        Fmi2Messages.Fmi2Status
+                                            .forNumber(res.status.ordinal())
+                                    )
+                                    .addAllDerivatives(res.values)
+                                    .build()
+                            )
+                            .build()
+                    );
+                    break;
+                }
+
+                case FMI2GETEVENTINDICATORS: {
+                    var res = model.fmi2GetEventIndicators();
+                    sendReply(
+                        Fmi2Messages.Fmi2Return
+                            .newBuilder()
+                            .setGetEventIndicators(
+                                Fmi2Messages.Fmi2GetEventIndicatorsReturn
+                                    .newBuilder()
+                                    .setStatus(
+                                        Fmi2Messages.Fmi2Status
+                                            .forNumber(res.status.ordinal())
+                                    )
+                                    .addAllEventIndicators(res.values)
+                                    .build()
+                            )
+                            .build()
+                    );
+                    break;
+                }
+
+                case FMI2GETCONTINUOUSSTATES: {
+                    var res = model.fmi2GetContinuousStates();
+                    sendReply(
+                        Fmi2Messages.Fmi2Return
+                            .newBuilder()
+                            .setGetContinuousStates(
+                                Fmi2Messages.Fmi2GetContinuousStatesReturn
+                                    .newBuilder()
+                                    .setStatus(
+                                        Fmi2Messages.Fmi2Status
+                                            .forNumber(res.status.ordinal())
+                                    )
+                                    .addAllContinuousStates(res.values)
+                                    .build()
+                            )
+                            .build()
+                    );
+                    break;
+                }
+
+                case FMI2GETNOMINALSOFCONTINUOUSSTATES: {
+                    var res = model.fmi2GetNominalsOfContinuousStates();
+                    sendReply(
+                        Fmi2Messages.Fmi2Return
+                            .newBuilder()
+                            .setGetNominalsOfContinuousStates(
+                                Fmi2Messages.Fmi2GetNominalsOfContinuousStatesReturn
+                                    .newBuilder()
+                                    .setStatus(
+                                        Fmi2Messages.Fmi2Status
+                                            .forNumber(res.status.ordinal())
+                                    )
+                                    .addAllNominals(res.values)
+                                    .build()
+                            )
+                            .build()
+                    );
+                    break;
+                }
+
                 case COMMAND_NOT_SET:
                     break;
 
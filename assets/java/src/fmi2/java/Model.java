@@ -1,5 +1,7 @@
 import java.lang.reflect.Field;
 import java.util.ArrayList;
+import java.util.Collections;
+import java.util.HashMap;
 import java.util.Iterator;
 import java.util.List;
 import java.util.function.Consumer;
@@ -27,6 +29,15 @@ public class Model implements Serializable {
     public String string_c;
 
     private transient ArrayList<Field> references_to_attributes;
+    private transient List<Integer> real_inputs = List.of(0, 1);
+    private transient List<Integer> real_outputs = List.of(2);
+    // The time derivatives of the real inputs given by the importer through
+    // fmi2SetRealInputDerivatives, indexed by value reference and derivative
+    // order. Used to interpolate the inputs during fmi2DoStep.
+    private HashMap<Integer, HashMap<Integer, Double>> input_derivatives = new HashMap<>();
+    // Set by fmi2CancelStep while an asynchronous fmi2DoStep is running on
+    // another thread. A long running step should check it and return early.
+    public transient volatile boolean step_canceled;
 
     public Model() throws Exception {
         super();
@@ -90,12 +101,17 @@ public class Model implements Serializable {
         Backend.loggingCallback(status, category, message);
     }
 
-    public Fmi2Status fmi2DoStep(double current_time, double step_size, boolean noStepPrior) {
+    public Fmi2Status fmi2DoStep(double current_time, double step_size, boolean noStepPrior) throws Exception {
+        interpolate_inputs(step_size);
         update_outputs();
         return Fmi2Status.OK;
     }
 
     public Fmi2Status fmi2SetReal(Iterable<Integer> references, Iterable<Double> values) throws Exception {
+        // A new input value starts a new interpolation of that input
+        for (Integer reference : references) {
+            this.input_derivatives.remove(reference);
+        }
 
         SetValue(references, values);
         return Fmi2Status.OK;
@@ -137,6 +153,65 @@ public class Model implements Serializable {
         return new Fmi2GetValuePair<String>(Fmi2Status.OK, values);
     }
 
+    public Fmi2Status fmi2SetRealInputDerivatives(List<Integer> references, List<Integer> orders, List<Double> values) {
+        for (int i = 0; i < references.size(); i++) {
+            Integer reference = references.get(i);
+            Integer order = orders.get(i);
+
+            if (!real_inputs.contains(reference)) {
+                this.log(
+                    String.format("Variable #%s# isn't a real input.", reference),
+                    Fmi2Status.Error,
+                    "logStatusError"
+                );
+                return Fmi2Status.Error;
+            }
+            if (order < 1) {
+                this.log(
+                    String.format("Can't set derivative of order %s for input #%s#.", order, reference),
+                    Fmi2Status.Error,
+                    "logStatusError"
+                );
+                return Fmi2Status.Error;
+            }
+
+            this.input_derivatives
+                .computeIfAbsent(reference, r -> new HashMap<>())
+                .put(order, values.get(i));
+        }
+
+        return Fmi2Status.OK;
+    }
+
+    public Fmi2GetValuePair<Double> fmi2GetRealOutputDerivatives(List<Integer> references, List<Integer> orders) {
+        ArrayList<Double> values = new ArrayList<>();
+
+        for (int i = 0; i < references.size(); i++) {
+            Integer reference = references.get(i);
+
+            if (!real_outputs.contains(reference)) {
+                this.log(
+                    String.format("Variable #%s# isn't a real output.", reference),
+                    Fmi2Status.Error,
+                    "logStatusError"
+                );
+                return new Fmi2GetValuePair<Double>(Fmi2Status.Error, new ArrayList<Double>());
+            }
+
+            // As real_c = real_a + real_b, its derivatives are the sums of
+            // the derivatives of the inputs.
+            double value = 0.0;
+            for (Integer input : real_inputs) {
+                value += this.input_derivatives
+                    .getOrDefault(input, new HashMap<>())
+                    .getOrDefault(orders.get(i), 0.0);
+            }
+            values.add(value);
+        }
+
+        return new Fmi2GetValuePair<Double>(Fmi2Status.OK, values);
+    }
+
     public Fmi2Status fmi2EnterInitializationMode() {
         return Fmi2Status.OK;
     }
@@ -159,6 +234,8 @@ public class Model implements Serializable {
         this.boolean_b = false;
         this.string_a = "";
         this.string_b = "";
+        this.input_derivatives.clear();
+        this.step_canceled = false;
         update_outputs();
 
         return Fmi2Status.OK;
@@ -169,6 +246,7 @@ public class Model implements Serializable {
     }
 
     public Fmi2Status fmi2CancelStep() {
+        this.step_canceled = true;
         return Fmi2Status.OK;
     }
 
@@ -199,6 +277,7 @@ public class Model implements Serializable {
                 this.string_a = other.string_a;
                 this.string_b = other.string_b;
                 this.string_c = other.string_c;
+                this.input_derivatives = other.input_derivatives;
             }
         }
 
@@ -209,6 +288,61 @@ public class Model implements Serializable {
         return Fmi2Status.OK;
     }
 
+    public Fmi2Status fmi2EnterEventMode() {
+        return Fmi2Status.OK;
+    }
+
+    public Fmi2NewDiscreteStatesResult fmi2NewDiscreteStates() {
+        update_outputs();
+
+        return new Fmi2NewDiscreteStatesResult(
+            Fmi2Status.OK,
+            false,
+            false,
+            false,
+            false,
+            false,
+            0.0
+        );
+    }
+
+    public Fmi2Status fmi2EnterContinuousTimeMode() {
+        return Fmi2Status.OK;
+    }
+
+    public Fmi2CompletedIntegratorStepResult fmi2CompletedIntegratorStep(boolean no_set_fmu_state_prior_to_current_point) {
+        update_outputs();
+
+        return new Fmi2CompletedIntegratorStepResult(Fmi2Status.OK, false, false);
+    }
+
+    public Fmi2Status fmi2SetTime(double time) {
+        return Fmi2Status.OK;
+    }
+
+    // The placeholder model has no continuous states or event indicators,
+    // so the following functions only exchange empty lists.
+
+    public Fmi2Status fmi2SetContinuousStates(Iterable<Double> continuous_states) {
+        return Fmi2Status.OK;
+    }
+
+    public Fmi2GetValuePair<Double> fmi2GetDerivatives() {
+        return new Fmi2GetValuePair<Double>(Fmi2Status.OK, new ArrayList<Double>());
+    }
+
+    public Fmi2GetValuePair<Double> fmi2GetEventIndicators() {
+        return new Fmi2GetValuePair<Double>(Fmi2Status.OK, new ArrayList<Double>());
+    }
+
+    public Fmi2GetValuePair<Double> fmi2GetContinuousStates() {
+        return new Fmi2GetValuePair<Double>(Fmi2Status.OK, new ArrayList<Double>());
+    }
+
+    public Fmi2GetValuePair<Double> fmi2GetNominalsOfContinuousStates() {
+        return new Fmi2GetValuePair<Double>(Fmi2Status.OK, new ArrayList<Double>());
+    }
+
     private <T> ArrayList<T> GetValue(Iterable<Integer> references) throws Exception {
         var values = new ArrayList<T>();
 
@@ -232,6 +366,38 @@ public class Model implements Serializable {
         }
     }
 
+    // Moves each interpolated input and its derivatives to the end of the
+    // step along the Taylor polynomial given by the input derivatives.
+    private void interpolate_inputs(double step_size) throws Exception {
+        for (var entry : this.input_derivatives.entrySet()) {
+            Field attribute = this.references_to_attributes.get(entry.getKey());
+            HashMap<Integer, Double> derivatives = entry.getValue();
+            int max_order = Collections.max(derivatives.keySet());
+
+            double[] coefficients = new double[max_order + 1];
+            coefficients[0] = (Double) attribute.get(this);
+            for (var derivative : derivatives.entrySet()) {
+                coefficients[derivative.getKey()] = derivative.getValue();
+            }
+
+            double[] shifted = new double[max_order + 1];
+            for (int k = 0; k <= max_order; k++) {
+                double term = 1.0;
+                for (int j = k; j <= max_order; j++) {
+                    // term is step_size^(j - k) / (j - k)!
+                    shifted[k] += coefficients[j] * term;
+                    term *= step_size / (j - k + 1);
+                }
+            }
+
+            attribute.set(this, shifted[0]);
+            derivatives.clear();
+            for (int order = 1; order <= max_order; order++) {
+                derivatives.put(order, shifted[order]);
+            }
+        }
+    }
+
     private void update_outputs() {
         this.real_c = this.real_a + this.real_b;
         this.integer_c = this.integer_a + this.integer_b;
@@ -262,6 +428,50 @@ public class Model implements Serializable {
         }
     }
 
+    class Fmi2NewDiscreteStatesResult {
+        public Fmi2Status status;
+        public boolean new_discrete_states_needed;
+        public boolean terminate_simulation;
+        public boolean nominals_of_continuous_states_changed;
+        public boolean values_of_continuous_states_changed;
+        public boolean next_event_time_defined;
+        public double next_event_time;
+
+        Fmi2NewDiscreteStatesResult(
+            Fmi2Status status,
+            boolean new_discrete_states_needed,
+            boolean terminate_simulation,
+            boolean nominals_of_continuous_states_changed,
+            boolean values_of_continuous_states_changed,
+            boolean next_event_time_defined,
+            double next_event_time
+        ) {
+            this.status = status;
+            this.new_discrete_states_needed = new_discrete_states_needed;
+            this.terminate_simulation = terminate_simulation;
+            this.nominals_of_continuous_states_changed = nominals_of_continuous_states_changed;
+            this.values_of_continuous_states_changed = values_of_continuous_states_changed;
+            this.next_event_time_defined = next_event_time_defined;
+            this.next_event_time = next_event_time;
+        }
+    }
+
+    class Fmi2CompletedIntegratorStepResult {
+        public Fmi2Status status;
+        public boolean enter_event_mode;
+        public boolean terminate_simulation;
+
+        Fmi2CompletedIntegratorStepResult(
+            Fmi2Status status,
+            boolean enter_event_mode,
+            boolean terminate_simulation
+        ) {
+            this.status = status;
+            this.enter_event_mode = enter_event_mode;
+            this.terminate_simulation = terminate_simulation;
+        }
+    }
+
     enum Fmi2Status {
         OK,
         Warning,
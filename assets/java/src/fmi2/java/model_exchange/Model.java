@@ -0,0 +1,384 @@
+import java.lang.reflect.Field;
+import java.util.ArrayList;
+import java.util.Iterator;
+import java.util.List;
+import java.io.ByteArrayInputStream;
+import java.io.ByteArrayOutputStream;
+import java.io.ObjectInputStream;
+import java.io.ObjectOutputStream;
+import java.io.Serializable;
+
+public class Model implements Serializable {
+
+    private static final long serialVersionUID = 1L;
+
+    public Double time;
+    public Double h;
+    public Double der_h;
+    public Double v;
+    public Double der_v;
+    public Double g;
+    public Double e;
+
+    private transient ArrayList<Field> references_to_attributes;
+    // The continuous states of the model, in the order that they are
+    // exchanged with the importer.
+    private transient List<Field> continuous_states;
+    // The derivatives of the continuous states, which can't be set.
+    private transient List<Field> derivatives;
+    // Set by fmi2CancelStep. Model Exchange FMUs are never stepped, but the
+    // backend shares its step handling with Co-Simulation FMUs.
+    public transient volatile boolean step_canceled;
+
+    public Model() throws Exception {
+        super();
+
+        this.references_to_attributes = new ArrayList<Field>();
+        this.references_to_attributes.add(this.getClass().getField("time"));
+        this.references_to_attributes.add(this.getClass().getField("h"));
+        this.references_to_attributes.add(this.getClass().getField("der_h"));
+        this.references_to_attributes.add(this.getClass().getField("v"));
+        this.references_to_attributes.add(this.getClass().getField("der_v"));
+        this.references_to_attributes.add(this.getClass().getField("g"));
+        this.references_to_attributes.add(this.getClass().getField("e"));
+
+        this.continuous_states = List.of(
+            this.getClass().getField("h"),
+            this.getClass().getField("v")
+        );
+        this.derivatives = List.of(
+            this.getClass().getField("der_h"),
+            this.getClass().getField("der_v")
+        );
+
+        fmi2Reset();
+    }
+
+    /**
+     * Sends a logging message to the importer of the FMU.
+     *
+     * @param message The message to be logged.
+     * @param status The status of the FMU at the moment of logging. This is
+     *   used to determine the severity of the message.
+     * @param category The logging category that this message falls under.
+     *   Only categories set in the modelDescription.xml are recognized by the
+     *   FMU importer.
+     */
+    public void log(String message, Fmi2Status status, String category) {
+        Backend.loggingCallback(status, category, message);
+    }
+
+    public Fmi2Status fmi2DoStep(double current_time, double step_size, boolean noStepPrior) throws Exception {
+        this.log("Model Exchange FMUs can't be stepped.", Fmi2Status.Error, "logStatusError");
+        return Fmi2Status.Error;
+    }
+
+    public Fmi2Status fmi2SetReal(Iterable<Integer> references, Iterable<Double> values) throws Exception {
+        return SetValue(references, values);
+    }
+
+    public Fmi2Status fmi2SetInteger(Iterable<Integer> references, Iterable<Integer> values) throws Exception {
+        return SetValue(references, values);
+    }
+
+    public Fmi2Status fmi2SetBoolean(Iterable<Integer> references, Iterable<Boolean> values) throws Exception {
+        return SetValue(references, values);
+    }
+
+    public Fmi2Status fmi2SetString(Iterable<Integer> references, Iterable<String> values) throws Exception {
+        return SetValue(references, values);
+    }
+
+    public Fmi2GetValuePair<Double> fmi2GetReal(Iterable<Integer> references) throws Exception {
+        ArrayList<Double> values = this.GetValue(references);
+        return new Fmi2GetValuePair<Double>(Fmi2Status.OK, values);
+    }
+
+    public Fmi2GetValuePair<Integer> fmi2GetInteger(Iterable<Integer> references) throws Exception {
+        ArrayList<Integer> values = this.GetValue(references);
+        return new Fmi2GetValuePair<Integer>(Fmi2Status.OK, values);
+    }
+
+    public Fmi2GetValuePair<Boolean> fmi2GetBoolean(Iterable<Integer> references) throws Exception {
+        ArrayList<Boolean> values = this.GetValue(references);
+        return new Fmi2GetValuePair<Boolean>(Fmi2Status.OK, values);
+    }
+
+    public Fmi2GetValuePair<String> fmi2GetString(Iterable<Integer> references) throws Exception {
+        ArrayList<String> values = this.GetValue(references);
+        return new Fmi2GetValuePair<String>(Fmi2Status.OK, values);
+    }
+
+    public Fmi2Status fmi2SetRealInputDerivatives(List<Integer> references, List<Integer> orders, List<Double> values) {
+        this.log("The model doesn't interpolate its inputs.", Fmi2Status.Error, "logStatusError");
+        return Fmi2Status.Error;
+    }
+
+    public Fmi2GetValuePair<Double> fmi2GetRealOutputDerivatives(List<Integer> references, List<Integer> orders) {
+        this.log("The model doesn't provide output derivatives.", Fmi2Status.Error, "logStatusError");
+        return new Fmi2GetValuePair<Double>(Fmi2Status.Error, new ArrayList<Double>());
+    }
+
+    public Fmi2Status fmi2EnterInitializationMode() {
+        return Fmi2Status.OK;
+    }
+
+    public Fmi2Status fmi2ExitInitializationMode() {
+        update_derivatives();
+        return Fmi2Status.OK;
+    }
+
+    public Fmi2Status fmi2SetupExperiment(double start_time, Double stop_time, Double tolerance) {
+        this.time = start_time;
+        return Fmi2Status.OK;
+    }
+
+    public Fmi2Status fmi2Reset() {
+        this.time = 0.0;
+        this.h = 1.0;
+        this.v = 0.0;
+        this.g = -9.81;
+        this.e = 0.7;
+        this.step_canceled = false;
+        update_derivatives();
+
+        return Fmi2Status.OK;
+    }
+
+    public Fmi2Status fmi2Terminate() {
+        return Fmi2Status.OK;
+    }
+
+    public Fmi2Status fmi2CancelStep() {
+        this.step_canceled = true;
+        return Fmi2Status.OK;
+    }
+
+    public Fmi2SerializeFmuStatePair fmi2SerializeFmuState() throws Exception {
+
+        var b = new ByteArrayOutputStream();
+        var o = new ObjectOutputStream(b);
+
+        o.writeObject(this);
+
+        return new Fmi2SerializeFmuStatePair(Fmi2Status.OK, b.toByteArray());
+    }
+
+    public Fmi2Status fmi2DeserializeFmuState(byte[] bytes) throws Exception {
+
+        try (ByteArrayInputStream b = new ByteArrayInputStream(bytes)) {
+            try (ObjectInputStream o = new ObjectInputStream(b)) {
+                var other = (Model) o.readObject();
+                this.time = other.time;
+                this.h = other.h;
+                this.v = other.v;
+                this.g = other.g;
+                this.e = other.e;
+                update_derivatives();
+            }
+        }
+
+        return Fmi2Status.OK;
+    }
+
+    public Fmi2Status fmi2SetDebugLogging(Iterable<String> categories, Boolean logging_on) {
+        return Fmi2Status.OK;
+    }
+
+    public Fmi2Status fmi2EnterEventMode() {
+        return Fmi2Status.OK;
+    }
+
+    public Fmi2NewDiscreteStatesResult fmi2NewDiscreteStates() {
+        boolean values_of_continuous_states_changed = false;
+
+        if (this.h <= 0.0 && this.v < 0.0) {
+            this.h = 0.0;
+            this.v = -this.e * this.v;
+            values_of_continuous_states_changed = true;
+            update_derivatives();
+        }
+
+        return new Fmi2NewDiscreteStatesResult(
+            Fmi2Status.OK,
+            false,
+            false,
+            false,
+            values_of_continuous_states_changed,
+            false,
+            0.0
+        );
+    }
+
+    public Fmi2Status fmi2EnterContinuousTimeMode() {
+        return Fmi2Status.OK;
+    }
+
+    public Fmi2CompletedIntegratorStepResult fmi2CompletedIntegratorStep(boolean no_set_fmu_state_prior_to_current_point) {
+        return new Fmi2CompletedIntegratorStepResult(Fmi2Status.OK, false, false);
+    }
+
+    public Fmi2Status fmi2SetTime(double time) {
+        this.time = time;
+        return Fmi2Status.OK;
+    }
+
+    public Fmi2Status fmi2SetContinuousStates(Iterable<Double> continuous_states) throws Exception {
+        Iterator<Field> i1 = this.continuous_states.iterator();
+        Iterator<Double> i2 = continuous_states.iterator();
+        while (i1.hasNext() && i2.hasNext()) {
+            i1.next().set(this, i2.next());
+        }
+        update_derivatives();
+
+        return Fmi2Status.OK;
+    }
+
+    public Fmi2GetValuePair<Double> fmi2GetDerivatives() throws Exception {
+        return new Fmi2GetValuePair<Double>(Fmi2Status.OK, this.GetFields(this.derivatives));
+    }
+
+    public Fmi2GetValuePair<Double> fmi2GetEventIndicators() {
+        // The ball hits the ground when the height crosses zero
+        return new Fmi2GetValuePair<Double>(Fmi2Status.OK, List.of(this.h));
+    }
+
+    public Fmi2GetValuePair<Double> fmi2GetContinuousStates() throws Exception {
+        return new Fmi2GetValuePair<Double>(Fmi2Status.OK, this.GetFields(this.continuous_states));
+    }
+
+    public Fmi2GetValuePair<Double> fmi2GetNominalsOfContinuousStates() {
+        var nominals = new ArrayList<Double>();
+        for (int i = 0; i < this.continuous_states.size(); i++) {
+            nominals.add(1.0);
+        }
+        return new Fmi2GetValuePair<Double>(Fmi2Status.OK, nominals);
+    }
+
+    private ArrayList<Double> GetFields(List<Field> fields) throws Exception {
+        var values = new ArrayList<Double>();
+        for (var field : fields) {
+            values.add((Double) field.get(this));
+        }
+        return values;
+    }
+
+    private <T> ArrayList<T> GetValue(Iterable<Integer> references) throws Exception {
+        var values = new ArrayList<T>();
+
+        for (var ref : references) {
+
+            @SuppressWarnings("unchecked")
+            var val = (T) this.references_to_attributes.get(ref).get(this);
+            values.add(val);
+        }
+
+        return values;
+
+    }
+
+    private <T> Fmi2Status SetValue(Iterable<Integer> references, Iterable<T> values) throws Exception {
+
+        Iterator<Integer> i1 = references.iterator();
+        Iterator<T> i2 = values.iterator();
+        while (i1.hasNext() && i2.hasNext()) {
+            Integer reference = i1.next();
+            Field attribute = this.references_to_attributes.get(reference);
+
+            if (this.derivatives.contains(attribute)) {
+                this.log(
+                    String.format("Variable #%s# can't be set.", reference),
+                    Fmi2Status.Error,
+                    "logStatusError"
+                );
+                return Fmi2Status.Error;
+            }
+
+            attribute.set(this, i2.next());
+        }
+        update_derivatives();
+
+        return Fmi2Status.OK;
+    }
+
+    private void update_derivatives() {
+        this.der_h = this.v;
+        this.der_v = this.g;
+    }
+
+    class Fmi2GetValuePair<T> {
+        Fmi2Status status;
+        List<T> values;
+
+        Fmi2GetValuePair(Fmi2Status status, List<T> values)
+
+        {
+            this.status = status;
+            this.values = values;
+        }
+    }
+
+    class Fmi2SerializeFmuStatePair {
+        public Fmi2Status status;
+        public byte[] bytes;
+
+        Fmi2SerializeFmuStatePair(Fmi2Status status, byte[] bytes) {
+            this.status = status;
+            this.bytes = bytes;
+        }
+    }
+
+    class Fmi2NewDiscreteStatesResult {
+        public Fmi2Status status;
+        public boolean new_discrete_states_needed;
+        public boolean terminate_simulation;
+        public boolean nominals_of_continuous_states_changed;
+        public boolean values_of_continuous_states_changed;
+        public boolean next_event_time_defined;
+        public double next_event_time;
+
+        Fmi2NewDiscreteStatesResult(
+            Fmi2Status status,
+            boolean new_discrete_states_needed,
+            boolean terminate_simulation,
+            boolean nominals_of_continuous_states_changed,
+            boolean values_of_continuous_states_changed,
+            boolean next_event_time_defined,
+            double next_event_time
+        ) {
+            this.status = status;
+            this.new_discrete_states_needed = new_discrete_states_needed;
+            this.terminate_simulation = terminate_simulation;
+            this.nominals_of_continuous_states_changed = nominals_of_continuous_states_changed;
+            this.values_of_continuous_states_changed = values_of_continuous_states_changed;
+            this.next_event_time_defined = next_event_time_defined;
+            this.next_event_time = next_event_time;
+        }
+    }
+
+    class Fmi2CompletedIntegratorStepResult {
+        public Fmi2Status status;
+        public boolean enter_event_mode;
+        public boolean terminate_simulation;
+
+        Fmi2CompletedIntegratorStepResult(
+            Fmi2Status status,
+            boolean enter_event_mode,
+            boolean terminate_simulation
+        ) {
+            this.status = status;
+            this.enter_event_mode = enter_event_mode;
+            this.terminate_simulation = terminate_simulation;
+        }
+    }
+
+    enum Fmi2Status {
+        OK,
+        Warning,
+        Discard,
+        Error,
+        Fatal,
+        Pending
+    }
+
+}
@@ -0,0 +1,290 @@
+import java.lang.reflect.Field;
+import java.util.ArrayList;
+import java.util.HashMap;
+import java.util.Iterator;
+import java.util.List;
+import java.io.ByteArrayInputStream;
+import java.io.ByteArrayOutputStream;
+import java.io.ObjectInputStream;
+import java.io.ObjectOutputStream;
+import java.io.Serializable;
+
+public class Model implements Serializable {
+
+    private static final long serialVersionUID = 1L;
+
+    // {{declarations}}
+
+    private transient HashMap<Integer, Field> references_to_attributes;
+
+    public Model() throws Exception {
+        super();
+
+        this.references_to_attributes = new HashMap<Integer, Field>();
+        // {{references}}
+
+        fmi2Reset();
+    }
+
+    /**
+     * Sends a logging message to the importer of the FMU.
+     *
+     * The UniFMU API layer decides whether or not to forward the message to
+     * the importer based on the category and the categories enabled by the
+     * importer through fmi2SetDebugLogging().
+     *
+     * @param message The message to be logged.
+     * @param status The status of the FMU at the moment of logging.
+     * @param category The logging category that this message falls under.
+     */
+    public void log(String message, Fmi2Status status, String category) {
+        Backend.loggingCallback(status, category, message);
+    }
+
+    public Fmi2Status fmi2DoStep(double current_time, double step_size, boolean noStepPrior) throws Exception {
+        // Advance the model from current_time to current_time + step_size
+        // here, updating the outputs from the inputs.
+        return Fmi2Status.OK;
+    }
+
+    public Fmi2Status fmi2SetReal(Iterable<Integer> references, Iterable<Double> values) throws Exception {
+        SetValue(references, values);
+        return Fmi2Status.OK;
+    }
+
+    public Fmi2Status fmi2SetInteger(Iterable<Integer> references, Iterable<Integer> values) throws Exception {
+        SetValue(references, values);
+        return Fmi2Status.OK;
+    }
+
+    public Fmi2Status fmi2SetBoolean(Iterable<Integer> references, Iterable<Boolean> values) throws Exception {
+        SetValue(references, values);
+        return Fmi2Status.OK;
+    }
+
+    public Fmi2Status fmi2SetString(Iterable<Integer> references, Iterable<String> values) throws Exception {
+        SetValue(references, values);
+        return Fmi2Status.OK;
+    }
+
+    public Fmi2GetValuePair<Double> fmi2GetReal(Iterable<Integer> references) throws Exception {
+        ArrayList<Double> values = this.GetValue(references);
+        return new Fmi2GetValuePair<Double>(Fmi2Status.OK, values);
+    }
+
+    public Fmi2GetValuePair<Integer> fmi2GetInteger(Iterable<Integer> references) throws Exception {
+        ArrayList<Integer> values = this.GetValue(references);
+        return new Fmi2GetValuePair<Integer>(Fmi2Status.OK, values);
+    }
+
+    public Fmi2GetValuePair<Boolean> fmi2GetBoolean(Iterable<Integer> references) throws Exception {
+        ArrayList<Boolean> values = this.GetValue(references);
+        return new Fmi2GetValuePair<Boolean>(Fmi2Status.OK, values);
+    }
+
+    public Fmi2GetValuePair<String> fmi2GetString(Iterable<Integer> references) throws Exception {
+        ArrayList<String> values = this.GetValue(references);
+        return new Fmi2GetValuePair<String>(Fmi2Status.OK, values);
+    }
+
+    public Fmi2Status fmi2SetRealInputDerivatives(List<Integer> references, List<Integer> orders, List<Double> values) {
+        this.log("The model can't interpolate inputs.", Fmi2Status.Error, "logStatusError");
+        return Fmi2Status.Error;
+    }
+
+    public Fmi2GetValuePair<Double> fmi2GetRealOutputDerivatives(List<Integer> references, List<Integer> orders) {
+        this.log("The model can't compute output derivatives.", Fmi2Status.Error, "logStatusError");
+        return new Fmi2GetValuePair<Double>(Fmi2Status.Error, new ArrayList<Double>());
+    }
+
+    public Fmi2Status fmi2EnterInitializationMode() {
+        return Fmi2Status.OK;
+    }
+
+    public Fmi2Status fmi2ExitInitializationMode() {
+        return Fmi2Status.OK;
+    }
+
+    public Fmi2Status fmi2SetupExperiment(double start_time, Double stop_time, Double tolerance) {
+        return Fmi2Status.OK;
+    }
+
+    public Fmi2Status fmi2Reset() {
+        // {{start_values}}
+        return Fmi2Status.OK;
+    }
+
+    public Fmi2Status fmi2Terminate() {
+        return Fmi2Status.OK;
+    }
+
+    public Fmi2Status fmi2CancelStep() {
+        return Fmi2Status.OK;
+    }
+
+    public Fmi2SerializeFmuStatePair fmi2SerializeFmuState() throws Exception {
+        var b = new ByteArrayOutputStream();
+        var o = new ObjectOutputStream(b);
+        o.writeObject(this);
+        return new Fmi2SerializeFmuStatePair(Fmi2Status.OK, b.toByteArray());
+    }
+
+    public Fmi2Status fmi2DeserializeFmuState(byte[] bytes) throws Exception {
+        try (ByteArrayInputStream b = new ByteArrayInputStream(bytes)) {
+            try (ObjectInputStream o = new ObjectInputStream(b)) {
+                var other = (Model) o.readObject();
+                for (Field field : this.references_to_attributes.values()) {
+                    field.set(this, field.get(other));
+                }
+            }
+        }
+        return Fmi2Status.OK;
+    }
+
+    public Fmi2Status fmi2SetDebugLogging(Iterable<String> categories, Boolean logging_on) {
+        return Fmi2Status.OK;
+    }
+
+    // The skeleton is generated for Co-Simulation, so the following Model
+    // Exchange functions do nothing and only exchange empty lists.
+
+    public Fmi2Status fmi2EnterEventMode() {
+        return Fmi2Status.OK;
+    }
+
+    public Fmi2NewDiscreteStatesResult fmi2NewDiscreteStates() {
+        return new Fmi2NewDiscreteStatesResult(
+            Fmi2Status.OK,
+            false,
+            false,
+            false,
+            false,
+            false,
+            0.0
+        );
+    }
+
+    public Fmi2Status fmi2EnterContinuousTimeMode() {
+        return Fmi2Status.OK;
+    }
+
+    public Fmi2CompletedIntegratorStepResult fmi2CompletedIntegratorStep(boolean no_set_fmu_state_prior_to_current_point) {
+        return new Fmi2CompletedIntegratorStepResult(Fmi2Status.OK, false, false);
+    }
+
+    public Fmi2Status fmi2SetTime(double time) {
+        return Fmi2Status.OK;
+    }
+
+    public Fmi2Status fmi2SetContinuousStates(Iterable<Double> continuous_states) {
+        return Fmi2Status.OK;
+    }
+
+    public Fmi2GetValuePair<Double> fmi2GetDerivatives() {
+        return new Fmi2GetValuePair<Double>(Fmi2Status.OK, new ArrayList<Double>());
+    }
+
+    public Fmi2GetValuePair<Double> fmi2GetEventIndicators() {
+        return new Fmi2GetValuePair<Double>(Fmi2Status.OK, new ArrayList<Double>());
+    }
+
+    public Fmi2GetValuePair<Double> fmi2GetContinuousStates() {
+        return new Fmi2GetValuePair<Double>(Fmi2Status.OK, new ArrayList<Double>());
+    }
+
+    public Fmi2GetValuePair<Double> fmi2GetNominalsOfContinuousStates() {
+        return new Fmi2GetValuePair<Double>(Fmi2Status.OK, new ArrayList<Double>());
+    }
+
+    private <T> ArrayList<T> GetValue(Iterable<Integer> references) throws Exception {
+        var values = new ArrayList<T>();
+        for (var ref : references) {
+            @SuppressWarnings("unchecked")
+            var val = (T) this.references_to_attributes.get(ref).get(this);
+            values.add(val);
+        }
+        return values;
+    }
+
+    private <T> void SetValue(Iterable<Integer> references, Iterable<T> values) throws Exception {
+        Iterator<Integer> i1 = references.iterator();
+        Iterator<T> i2 = values.iterator();
+        while (i1.hasNext() && i2.hasNext()) {
+            this.references_to_attributes.get(i1.next()).set(this, i2.next());
+        }
+    }
+
+    class Fmi2GetValuePair<T> {
+        Fmi2Status status;
+        List<T> values;
+
+        Fmi2GetValuePair(Fmi2Status status, List<T> values)
+        {
+            this.status = status;
+            this.values = values;
+        }
+    }
+
+    class Fmi2SerializeFmuStatePair {
+        public Fmi2Status status;
+        public byte[] bytes;
+
+        Fmi2SerializeFmuStatePair(Fmi2Status status, byte[] bytes) {
+            this.status = status;
+            this.bytes = bytes;
+        }
+    }
+
+    class Fmi2NewDiscreteStatesResult {
+        public Fmi2Status status;
+        public boolean new_discrete_states_needed;
+        public boolean terminate_simulation;
+        public boolean nominals_of_continuous_states_changed;
+        public boolean values_of_continuous_states_changed;
+        public boolean next_event_time_defined;
+        public double next_event_time;
+
+        Fmi2NewDiscreteStatesResult(
+            Fmi2Status status,
+            boolean new_discrete_states_needed,
+            boolean terminate_simulation,
+            boolean nominals_of_continuous_states_changed,
+            boolean values_of_continuous_states_changed,
+            boolean next_event_time_defined,
+            double next_event_time
+        ) {
+            this.status = status;
+            this.new_discrete_states_needed = new_discrete_states_needed;
+            this.terminate_simulation = terminate_simulation;
+            this.nominals_of_continuous_states_changed = nominals_of_continuous_states_changed;
+            this.values_of_continuous_states_changed = values_of_continuous_states_changed;
+            this.next_event_time_defined = next_event_time_defined;
+            this.next_event_time = next_event_time;
+        }
+    }
+
+    class Fmi2CompletedIntegratorStepResult {
+        public Fmi2Status status;
+        public boolean enter_event_mode;
+        public boolean terminate_simulation;
+
+        Fmi2CompletedIntegratorStepResult(
+            Fmi2Status status,
+            boolean enter_event_mode,
+            boolean terminate_simulation
+        ) {
+            this.status = status;
+            this.enter_event_mode = enter_event_mode;
+            this.terminate_simulation = terminate_simulation;
+        }
+    }
+
+    enum Fmi2Status {
+        OK,
+        Warning,
+        Discard,
+        Error,
+        Fatal,
+        Pending
+    }
+}
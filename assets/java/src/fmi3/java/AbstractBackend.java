@@ -16,6 +16,11 @@ public abstract class AbstractBackend {
     static ZMQ.Socket socket;
     static Model model;
 
+    // Whether the model was instantiated for Scheduled Execution, in which
+    // case the clock update and preemption callbacks are relayed to the
+    // importer.
+    static boolean scheduledExecution = false;
+
     static Fmi3Messages.Fmi3Command recvCommand() throws InvalidProtocolBufferException {
         return Fmi3Messages.Fmi3Command.parseFrom(socket.recv());
     }
@@ -61,6 +66,67 @@ public abstract class AbstractBackend {
                 .build()
         );
 
+        awaitCallbackContinue();
+    }
+
+    public static void clockUpdateCallback() {
+        if (!scheduledExecution) {
+            return;
+        }
+
+        sendReply(
+            Fmi3Messages.Fmi3Return
+                .newBuilder()
+                .setClockUpdate(
+                    Fmi3Messages.Fmi3ClockUpdateReturn
+                        .newBuilder()
+                        .build()
+                )
+                .build()
+        );
+
+        awaitCallbackContinue();
+    }
+
+    public static void lockPreemptionCallback() {
+        if (!scheduledExecution) {
+            return;
+        }
+
+        sendReply(
+            Fmi3Messages.Fmi3Return
+                .newBuilder()
+                .setLockPreemption(
+                    Fmi3Messages.Fmi3LockPreemptionReturn
+                        .newBuilder()
+                        .build()
+                )
+                .build()
+        );
+
+        awaitCallbackContinue();
+    }
+
+    public static void unlockPreemptionCallback() {
+        if (!scheduledExecution) {
+            return;
+        }
+
+        sendReply(
+            Fmi3Messages.Fmi3Return
+                .newBuilder()
+                .setUnlockPreemption(
+                    Fmi3Messages.Fmi3UnlockPreemptionReturn
+                        .newBuilder()
+                        .build()
+                )
+                .build()
+        );
+
+        awaitCallbackContinue();
+    }
+
+    static void awaitCallbackContinue() {
         try {
             Fmi3Messages.Fmi3Command command = recvCommand();
 
@@ -68,7 +134,7 @@ public abstract class AbstractBackend {
                 case FMI3CALLBACKCONTINUE:
                     break;
                 default:
-                    System.out.println("Unexpected command received after replying with a logging message.");
+                    System.out.println("Unexpected command received after replying with a callback.");
                     System.exit(1);
             }
         }
@@ -139,6 +205,43 @@ public abstract class AbstractBackend {
                     break;
                 }
 
+                case FMI3INSTANTIATESCHEDULEDEXECUTION: {
+                    var c = command.getFmi3InstantiateScheduledExecution();
+                    scheduledExecution = true;
+                    model = new Model(
+                        c.getInstanceName(),
+                        c.getInstantiationToken(),
+                        c.getResourcePath(),
+                        c.getVisible(),
+                        c.getLoggingOn(),
+                        false,
+                        false,
+                        new ArrayList<Integer>()
+                    );
+                    sendReply(
+                        Fmi3Messages.Fmi3Return
+                            .newBuilder()
+                            .setEmpty(
+                                Fmi3Messages.Fmi3EmptyReturn
+                                    .newBuilder()
+                                    .build()
+                            )
+                            .build()
+                    );
+                    break;
+                }
+
+                case FMI3ACTIVATEMODELPARTITION: {
+                    var c = command.getFmi3ActivateModelPartition();
+                    sendStatusReply(
+                        model.fmi3ActivateModelPartition(
+                            c.getClockReference(),
+                            c.getActivationTime()
+                        )
+                    );
+                    break;
+                }
+
                 case FMI3SETFLOAT32: {
                     var c = command.getFmi3SetFloat32();
                     sendStatusReply(
@@ -671,6 +774,60 @@ public abstract class AbstractBackend {
                     );
                     break;
                 }
+
+                case FMI3GETDIRECTIONALDERIVATIVE: {
+                    var c = command.getFmi3GetDirectionalDerivative();
+                    var res = model.fmi3GetDirectionalDerivative(c.getUnknownsList(), c.getKnownsList(), c.getSeedList());
+                    sendReply(
+                        Fmi3Messages.Fmi3Return
+                            .newBuilder()
+                            .setGetDirectionalDerivative(
+                                Fmi3Messages.Fmi3GetDirectionalDerivativeReturn
+                                    .newBuilder()
+                                    .setStatus(toProtobufStatus(res.status))
+                                    .addAllValues(res.values)
+                                    .build()
+                            )
+                            .build()
+                    );
+                    break;
+                }
+
+                case FMI3GETOUTPUTDERIVATIVES: {
+                    var c = command.getFmi3GetOutputDerivatives();
+                    var res = model.fmi3GetOutputDerivatives(c.getValueReferencesList(), c.getOrdersList());
+                    sendReply(
+                        Fmi3Messages.Fmi3Return
+                            .newBuilder()
+                            .setGetOutputDerivatives(
+                                Fmi3Messages.Fmi3GetOutputDerivativesReturn
+                                    .newBuilder()
+                                    .setStatus(toProtobufStatus(res.status))
+                                    .addAllValues(res.values)
+                                    .build()
+                            )
+                            .build()
+                    );
+                    break;
+                }
+
+                case FMI3GETADJOINTDERIVATIVE: {
+                    var c = command.getFmi3GetAdjointDerivative();
+                    var res = model.fmi3GetAdjointDerivative(c.getUnknownsList(), c.getKnownsList(), c.getSeedList());
+                    sendReply(
+                        Fmi3Messages.Fmi3Return
+                            .newBuilder()
+                            .setGetAdjointDerivative(
+                                Fmi3Messages.Fmi3GetAdjointDerivativeReturn
+                                    .newBuilder()
+                                    .setStatus(toProtobufStatus(res.status))
+                                    .addAllValues(res.values)
+                                    .build()
+                            )
+                            .build()
+                    );
+                    break;
+                }
     
 
                 case FMI3DOSTEP: {
@@ -694,6 +851,10 @@ public abstract class AbstractBackend {
                     break;
                 }
 
+                case FMI3EVALUATEDISCRETESTATES:
+                    sendStatusReply(model.fmi3EvaluateDiscreteStates());
+                    break;
+
                 case FMI3UPDATEDISCRETESTATES: {
                     var c = command.getFmi3UpdateDiscreteStates();
                     var res = model.fmi3UpdateDiscreteStates();
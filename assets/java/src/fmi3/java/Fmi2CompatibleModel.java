@@ -0,0 +1,474 @@
+import java.lang.reflect.Method;
+import java.nio.ByteBuffer;
+import java.util.ArrayList;
+import java.util.Iterator;
+import java.util.List;
+
+/**
+ * Compatibility shim placed between the FMI3 backend and the model of an FMU
+ * converted from FMI2 by `unifmu convert`.
+ *
+ * The FMI3 calls of the backend are translated to the FMI2 methods of the
+ * model, with the FMI3 types mapped to the FMI2 types as in the converted
+ * modelDescription.xml: Float64 to Real, Int32 to Integer, and Boolean and
+ * String as they are. The independent variable `time` isn't known to the
+ * model, so it is kept here. Calls that have no FMI2 counterpart are answered
+ * with an error.
+ */
+public class Fmi2CompatibleModel {
+    private static final int TIME_VALUE_REFERENCE = {{time_value_reference}};
+
+    private final Model model;
+    private double time = 0.0;
+
+    public Fmi2CompatibleModel(String instance_name, String instantiation_token, String resource_path, Boolean visible, Boolean logging_on, Boolean event_mode_used, Boolean early_return_allowed, List<Integer> required_intermediate_variables) throws Exception {
+        this.model = new Model();
+    }
+
+    // ================= FMI3 =================
+
+    public Fmi3DoStepResult fmi3DoStep(double currentCommunicationPoint, double communicationStepSize, boolean noStepPrior) throws Exception {
+        Fmi3Status status = toFmi3Status(
+            model.fmi2DoStep(currentCommunicationPoint, communicationStepSize, noStepPrior)
+        );
+
+        if (status == Fmi3Status.OK || status == Fmi3Status.Warning) {
+            time = currentCommunicationPoint + communicationStepSize;
+        }
+
+        return new Fmi3DoStepResult(status, false, false, false, time);
+    }
+
+    public Fmi3Status fmi3EnterInitializationMode(Fmi3Messages.Fmi3EnterInitializationMode c) throws Exception {
+        time = c.getStartTime();
+
+        Fmi3Status status = toFmi3Status(
+            model.fmi2SetupExperiment(
+                c.getStartTime(),
+                c.getStopTimeDefined() ? c.getStopTime() : null,
+                c.getToleranceDefined() ? c.getTolerance() : null
+            )
+        );
+        if (status.ordinal() > Fmi3Status.Warning.ordinal()) {
+            return status;
+        }
+
+        return worst(status, toFmi3Status(model.fmi2EnterInitializationMode()));
+    }
+
+    public Fmi3Status fmi3ExitInitializationMode() throws Exception {
+        return toFmi3Status(model.fmi2ExitInitializationMode());
+    }
+
+    public Fmi3Status fmi3Terminate() throws Exception {
+        return toFmi3Status(model.fmi2Terminate());
+    }
+
+    public Fmi3Status fmi3Reset() throws Exception {
+        time = 0.0;
+        return toFmi3Status(model.fmi2Reset());
+    }
+
+    public Fmi3Status fmi3SetDebugLogging(Iterable<String> categories, Boolean logging_on) throws Exception {
+        return toFmi3Status(model.fmi2SetDebugLogging(categories, logging_on));
+    }
+
+    public Fmi3SerializeFmuStatePair fmi3SerializeFmuState() throws Exception {
+        var pair = model.fmi2SerializeFmuState();
+
+        ByteBuffer bytes = ByteBuffer.allocate(Double.BYTES + pair.bytes.length);
+        bytes.putDouble(time).put(pair.bytes).flip();
+
+        return new Fmi3SerializeFmuStatePair(toFmi3Status(pair.status), bytes);
+    }
+
+    public Fmi3Status fmi3DeserializeFmuState(ByteBuffer bytes) throws Exception {
+        double state_time = bytes.getDouble();
+        byte[] state = new byte[bytes.remaining()];
+        bytes.get(state);
+
+        Fmi3Status status = toFmi3Status(model.fmi2DeserializeFmuState(state));
+        if (status.ordinal() <= Fmi3Status.Warning.ordinal()) {
+            time = state_time;
+        }
+
+        return status;
+    }
+
+    public Fmi3GetValuePair<Double> fmi3GetFloat64(Iterable<Integer> references) throws Exception {
+        List<Integer> model_references = new ArrayList<>();
+        for (Integer reference : references) {
+            if (reference != TIME_VALUE_REFERENCE) {
+                model_references.add(reference);
+            }
+        }
+
+        Fmi3Status status = Fmi3Status.OK;
+        Iterator<Double> model_values = new ArrayList<Double>().iterator();
+
+        if (!model_references.isEmpty()) {
+            var pair = model.fmi2GetReal(model_references);
+            status = toFmi3Status(pair.status);
+            if (status.ordinal() > Fmi3Status.Warning.ordinal()) {
+                return new Fmi3GetValuePair<>(status, new ArrayList<>());
+            }
+            model_values = pair.values.iterator();
+        }
+
+        List<Double> values = new ArrayList<>();
+        for (Integer reference : references) {
+            values.add(reference == TIME_VALUE_REFERENCE ? time : model_values.next());
+        }
+
+        return new Fmi3GetValuePair<>(status, values);
+    }
+
+    public Fmi3GetValuePair<Integer> fmi3GetInt32(Iterable<Integer> references) throws Exception {
+        var pair = model.fmi2GetInteger(references);
+        return new Fmi3GetValuePair<>(toFmi3Status(pair.status), pair.values);
+    }
+
+    public Fmi3GetValuePair<Boolean> fmi3GetBoolean(Iterable<Integer> references) throws Exception {
+        var pair = model.fmi2GetBoolean(references);
+        return new Fmi3GetValuePair<>(toFmi3Status(pair.status), pair.values);
+    }
+
+    public Fmi3GetValuePair<String> fmi3GetString(Iterable<Integer> references) throws Exception {
+        var pair = model.fmi2GetString(references);
+        return new Fmi3GetValuePair<>(toFmi3Status(pair.status), pair.values);
+    }
+
+    public Fmi3Status fmi3SetFloat64(Iterable<Integer> references, Iterable<Double> values) throws Exception {
+        List<Integer> model_references = new ArrayList<>();
+        List<Double> model_values = new ArrayList<>();
+
+        Iterator<Double> value_iterator = values.iterator();
+        for (Integer reference : references) {
+            Double value = value_iterator.next();
+            if (reference != TIME_VALUE_REFERENCE) {
+                model_references.add(reference);
+                model_values.add(value);
+            }
+        }
+
+        if (model_references.isEmpty()) {
+            return Fmi3Status.OK;
+        }
+
+        return toFmi3Status(model.fmi2SetReal(model_references, model_values));
+    }
+
+    public Fmi3Status fmi3SetInt32(Iterable<Integer> references, Iterable<Integer> values) throws Exception {
+        return toFmi3Status(model.fmi2SetInteger(references, values));
+    }
+
+    public Fmi3Status fmi3SetBoolean(Iterable<Integer> references, Iterable<Boolean> values) throws Exception {
+        return toFmi3Status(model.fmi2SetBoolean(references, values));
+    }
+
+    public Fmi3Status fmi3SetString(Iterable<Integer> references, Iterable<String> values) throws Exception {
+        return toFmi3Status(model.fmi2SetString(references, values));
+    }
+
+    @SuppressWarnings("unchecked")
+    public Fmi3GetValuePair<Double> fmi3GetOutputDerivatives(List<Integer> references, List<Integer> orders) throws Exception {
+        // Looked up at runtime, as models generated before output derivatives
+        // were supported don't implement fmi2GetRealOutputDerivatives.
+        Method method;
+        try {
+            method = Model.class.getMethod("fmi2GetRealOutputDerivatives", List.class, List.class);
+        } catch (NoSuchMethodException e) {
+            return new Fmi3GetValuePair<>(unsupported("fmi3GetOutputDerivatives"), new ArrayList<>());
+        }
+
+        var pair = (Model.Fmi2GetValuePair<Double>) method.invoke(model, references, orders);
+        return new Fmi3GetValuePair<>(toFmi3Status(pair.status), pair.values);
+    }
+
+    // ================= Unsupported =================
+
+    public Fmi3Status fmi3EvaluateDiscreteStates() {
+        return unsupported("fmi3EvaluateDiscreteStates");
+    }
+
+    public Fmi3UpdateDiscreteStatesResult fmi3UpdateDiscreteStates() {
+        return new Fmi3UpdateDiscreteStatesResult(unsupported("fmi3UpdateDiscreteStates"), false, false, false, false, false, 0.0);
+    }
+
+    public Fmi3Status fmi3EnterEventMode() {
+        return unsupported("fmi3EnterEventMode");
+    }
+
+    public Fmi3Status fmi3ActivateModelPartition(int clockReference, double activationTime) {
+        return unsupported("fmi3ActivateModelPartition");
+    }
+
+    public Fmi3Status fmi3EnterStepMode() {
+        return unsupported("fmi3EnterStepMode");
+    }
+
+    public Fmi3Status fmi3EnterConfigurationMode() {
+        return unsupported("fmi3EnterConfigurationMode");
+    }
+
+    public Fmi3Status fmi3ExitConfigurationMode() {
+        return unsupported("fmi3ExitConfigurationMode");
+    }
+
+    public Fmi3Status fmi3SetFloat32(Iterable<Integer> references, Iterable<Float> values) {
+        return unsupported("fmi3SetFloat32");
+    }
+
+    public Fmi3Status fmi3SetInt8(Iterable<Integer> references, Iterable<Integer> values) {
+        return unsupported("fmi3SetInt8");
+    }
+
+    public Fmi3Status fmi3SetUInt8(Iterable<Integer> references, Iterable<Integer> values) {
+        return unsupported("fmi3SetUInt8");
+    }
+
+    public Fmi3Status fmi3SetInt16(Iterable<Integer> references, Iterable<Integer> values) {
+        return unsupported("fmi3SetInt16");
+    }
+
+    public Fmi3Status fmi3SetUInt16(Iterable<Integer> references, Iterable<Integer> values) {
+        return unsupported("fmi3SetUInt16");
+    }
+
+    public Fmi3Status fmi3SetUInt32(Iterable<Integer> references, Iterable<Integer> values) {
+        return unsupported("fmi3SetUInt32");
+    }
+
+    public Fmi3Status fmi3SetInt64(Iterable<Integer> references, Iterable<Long> values) {
+        return unsupported("fmi3SetInt64");
+    }
+
+    public Fmi3Status fmi3SetUInt64(Iterable<Integer> references, Iterable<Long> values) {
+        return unsupported("fmi3SetUInt64");
+    }
+
+    public Fmi3Status fmi3SetBinary(Iterable<Integer> references, Iterable<Long> valueSizes, Iterable<ByteBuffer> values) {
+        return unsupported("fmi3SetBinary");
+    }
+
+    public Fmi3Status fmi3SetClock(Iterable<Integer> references, Iterable<Boolean> values) {
+        return unsupported("fmi3SetClock");
+    }
+
+    public Fmi3Status fmi3SetIntervalDecimal(Iterable<Integer> references, Iterable<Double> intervals) {
+        return unsupported("fmi3SetIntervalDecimal");
+    }
+
+    public Fmi3Status fmi3SetIntervalFraction(Iterable<Integer> references, Iterable<Long> counters, Iterable<Long> resolutions) {
+        return unsupported("fmi3SetIntervalFraction");
+    }
+
+    public Fmi3Status fmi3SetShiftDecimal(Iterable<Integer> references, Iterable<Double> shifts) {
+        return unsupported("fmi3SetShiftDecimal");
+    }
+
+    public Fmi3Status fmi3SetShiftFraction(Iterable<Integer> references, Iterable<Long> counters, Iterable<Long> resolutions) {
+        return unsupported("fmi3SetShiftFraction");
+    }
+
+    public Fmi3GetValuePair<Float> fmi3GetFloat32(Iterable<Integer> references) {
+        return new Fmi3GetValuePair<>(unsupported("fmi3GetFloat32"), new ArrayList<>());
+    }
+
+    public Fmi3GetValuePair<Integer> fmi3GetInt8(Iterable<Integer> references) {
+        return new Fmi3GetValuePair<>(unsupported("fmi3GetInt8"), new ArrayList<>());
+    }
+
+    public Fmi3GetValuePair<Integer> fmi3GetUInt8(Iterable<Integer> references) {
+        return new Fmi3GetValuePair<>(unsupported("fmi3GetUInt8"), new ArrayList<>());
+    }
+
+    public Fmi3GetValuePair<Integer> fmi3GetInt16(Iterable<Integer> references) {
+        return new Fmi3GetValuePair<>(unsupported("fmi3GetInt16"), new ArrayList<>());
+    }
+
+    public Fmi3GetValuePair<Integer> fmi3GetUInt16(Iterable<Integer> references) {
+        return new Fmi3GetValuePair<>(unsupported("fmi3GetUInt16"), new ArrayList<>());
+    }
+
+    public Fmi3GetValuePair<Integer> fmi3GetUInt32(Iterable<Integer> references) {
+        return new Fmi3GetValuePair<>(unsupported("fmi3GetUInt32"), new ArrayList<>());
+    }
+
+    public Fmi3GetValuePair<Long> fmi3GetInt64(Iterable<Integer> references) {
+        return new Fmi3GetValuePair<>(unsupported("fmi3GetInt64"), new ArrayList<>());
+    }
+
+    public Fmi3GetValuePair<Long> fmi3GetUInt64(Iterable<Integer> references) {
+        return new Fmi3GetValuePair<>(unsupported("fmi3GetUInt64"), new ArrayList<>());
+    }
+
+    public Fmi3GetValuePair<ByteBuffer> fmi3GetBinary(Iterable<Integer> references) {
+        return new Fmi3GetValuePair<>(unsupported("fmi3GetBinary"), new ArrayList<>());
+    }
+
+    public Fmi3GetValuePair<Boolean> fmi3GetClock(Iterable<Integer> references) {
+        return new Fmi3GetValuePair<>(unsupported("fmi3GetClock"), new ArrayList<>());
+    }
+
+    public Fmi3GetIntervalDecimalPair<Double,Integer> fmi3GetIntervalDecimal(Iterable<Integer> references) {
+        return new Fmi3GetIntervalDecimalPair<>(unsupported("fmi3GetIntervalDecimal"), new ArrayList<>(), new ArrayList<>());
+    }
+
+    public Fmi3GetIntervalFractionPair<Long,Integer> fmi3GetIntervalFraction(Iterable<Integer> references) {
+        return new Fmi3GetIntervalFractionPair<>(unsupported("fmi3GetIntervalFraction"), new ArrayList<>(), new ArrayList<>(), new ArrayList<>());
+    }
+
+    public Fmi3GetShiftDecimalPair<Double> fmi3GetShiftDecimal(Iterable<Integer> references) {
+        return new Fmi3GetShiftDecimalPair<>(unsupported("fmi3GetShiftDecimal"), new ArrayList<>());
+    }
+
+    public Fmi3GetShiftFractionPair<Long> fmi3GetShiftFraction(Iterable<Integer> references) {
+        return new Fmi3GetShiftFractionPair<>(unsupported("fmi3GetShiftFraction"), new ArrayList<>(), new ArrayList<>());
+    }
+
+    public Fmi3GetValuePair<Double> fmi3GetDirectionalDerivative(List<Integer> unknowns, List<Integer> knowns, List<Double> seed) {
+        return new Fmi3GetValuePair<>(unsupported("fmi3GetDirectionalDerivative"), new ArrayList<>());
+    }
+
+    public Fmi3GetValuePair<Double> fmi3GetAdjointDerivative(List<Integer> unknowns, List<Integer> knowns, List<Double> seed) {
+        return new Fmi3GetValuePair<>(unsupported("fmi3GetAdjointDerivative"), new ArrayList<>());
+    }
+
+    // ================= Helpers =================
+
+    private Fmi3Status unsupported(String name) {
+        Backend.loggingCallback(
+            Fmi3Status.Error,
+            "logStatusError",
+            name + " isn't supported by a model converted from FMI2."
+        );
+        return Fmi3Status.Error;
+    }
+
+    private static Fmi3Status worst(Fmi3Status a, Fmi3Status b) {
+        return a.ordinal() >= b.ordinal() ? a : b;
+    }
+
+    /**
+     * FMI3 dropped the pending status along with asynchronous steps.
+     */
+    static Fmi3Status toFmi3Status(Model.Fmi2Status status) {
+        if (status == Model.Fmi2Status.Pending) {
+            return Fmi3Status.Error;
+        }
+        return Fmi3Status.values()[status.ordinal()];
+    }
+
+    class Fmi3DoStepResult {
+        Fmi3Status status;
+        Boolean event_handling_needed;
+        Boolean terminate_simulation;
+        Boolean early_return;
+        Double last_successful_time;
+
+        Fmi3DoStepResult(Fmi3Status status, Boolean event_handling_needed, Boolean terminate_simulation, Boolean early_return, Double last_successful_time) {
+            this.status = status;
+            this.event_handling_needed = event_handling_needed;
+            this.terminate_simulation = terminate_simulation;
+            this.early_return = early_return;
+            this.last_successful_time = last_successful_time;
+        }
+    }
+
+    class Fmi3UpdateDiscreteStatesResult {
+        Fmi3Status status;
+        Boolean discrete_states_need_update;
+        Boolean terminate_simulation;
+        Boolean nominals_continuous_states_changed;
+        Boolean values_continuous_states_changed;
+        Boolean next_event_time_defined;
+        Double next_event_time;
+
+        Fmi3UpdateDiscreteStatesResult(Fmi3Status status, Boolean discrete_states_need_update, Boolean terminate_simulation, Boolean nominals_continuous_states_changed, Boolean values_continuous_states_changed, Boolean next_event_time_defined, Double next_event_time) {
+            this.status = status;
+            this.discrete_states_need_update = discrete_states_need_update;
+            this.terminate_simulation = terminate_simulation;
+            this.nominals_continuous_states_changed = nominals_continuous_states_changed;
+            this.values_continuous_states_changed = values_continuous_states_changed;
+            this.next_event_time_defined = next_event_time_defined;
+            this.next_event_time = next_event_time;
+        }
+    }
+
+    class Fmi3GetValuePair<T> {
+        Fmi3Status status;
+        List<T> values;
+
+        Fmi3GetValuePair(Fmi3Status status, List<T> values) {
+            this.status = status;
+            this.values = values;
+        }
+    }
+
+    class Fmi3GetIntervalDecimalPair<T, U> {
+        Fmi3Status status;
+        List<T> intervals;
+        List<U> qualifiers;
+
+        Fmi3GetIntervalDecimalPair(Fmi3Status status, List<T> intervals, List<U> qualifiers) {
+            this.status = status;
+            this.intervals = intervals;
+            this.qualifiers = qualifiers;
+        }
+    }
+
+    class Fmi3GetIntervalFractionPair<T, U> {
+        Fmi3Status status;
+        List<T> counters;
+        List<T> resolutions;
+        List<U> qualifiers;
+
+        Fmi3GetIntervalFractionPair(Fmi3Status status, List<T> counters, List<T> resolutions, List<U> qualifiers) {
+            this.status = status;
+            this.counters = counters;
+            this.resolutions = resolutions;
+            this.qualifiers = qualifiers;
+        }
+    }
+
+    class Fmi3GetShiftDecimalPair<T> {
+        Fmi3Status status;
+        List<T> shifts;
+
+        Fmi3GetShiftDecimalPair(Fmi3Status status, List<T> shifts) {
+            this.status = status;
+            this.shifts = shifts;
+        }
+    }
+
+    class Fmi3GetShiftFractionPair<T> {
+        Fmi3Status status;
+        List<T> counters;
+        List<T> resolutions;
+
+        Fmi3GetShiftFractionPair(Fmi3Status status, List<T> counters, List<T> resolutions) {
+            this.status = status;
+            this.counters = counters;
+            this.resolutions = resolutions;
+        }
+    }
+
+    class Fmi3SerializeFmuStatePair {
+        public Fmi3Status status;
+        public ByteBuffer bytes;
+
+        Fmi3SerializeFmuStatePair(Fmi3Status status, ByteBuffer bytes) {
+            this.status = status;
+            this.bytes = bytes;
+        }
+    }
+
+    enum Fmi3Status {
+        OK,
+        Warning,
+        Discard,
+        Error,
+        Fatal
+    }
+}
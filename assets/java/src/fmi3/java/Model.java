@@ -134,6 +134,9 @@ public class Model implements Serializable {
     private transient List<Integer> required_intermediate_variables;
 
     private int state = FMIState.FMIInstantiatedState;
+    // The value of clocked_variable_c when Event Mode was last entered,
+    // which event iteration evaluates the clocked variable from.
+    private Integer event_entry_clocked_variable_c = 0;
     public Float float32_a = 0.0f;
     public Float float32_b = 0.0f;
     public Float float32_c = 0.0f;
@@ -239,6 +242,11 @@ public class Model implements Serializable {
 
     private transient ArrayList<Field> all_parameters;
 
+    // The partial derivatives of the continuous outputs with respect to the
+    // continuous inputs, used by fmi3GetDirectionalDerivative and
+    // fmi3GetAdjointDerivative. Derivatives not listed are zero.
+    private Map<Integer,Map<Integer,Double>> partial_derivatives;
+
     public Model(String instance_name, String instantiation_token, String resource_path, Boolean visible, Boolean logging_on, Boolean event_mode_used, Boolean early_return_allowed, List<Integer> required_intermediate_variables) throws Exception {
 
         super();
@@ -258,6 +266,10 @@ public class Model implements Serializable {
 
         this.clock_reference_to_shift = new HashMap<>();
         this.clock_reference_to_shift.put(1001, 1.0);
+
+        this.partial_derivatives = new HashMap<>();
+        this.partial_derivatives.put(2, Map.of(0, 1.0, 1, 1.0));
+        this.partial_derivatives.put(5, Map.of(3, 1.0, 4, 1.0));
         
         this.references_to_attributes = new ArrayList<Field>();
         this.references_to_attributes.add(this.getClass().getField("float32_a"));
@@ -430,9 +442,40 @@ public class Model implements Serializable {
         Backend.loggingCallback(status, category, message);
     }
 
+    /**
+     * UniFMU clock update function
+     *
+     * Call this function during Scheduled Execution to inform the importer
+     * that an output clock has ticked or that a countdown clock has been set.
+     * Does nothing if the FMU wasn't instantiated for Scheduled Execution.
+     */
+    public void clockUpdate() {
+        Backend.clockUpdateCallback();
+    }
+
+    /**
+     * UniFMU preemption lock functions
+     *
+     * Call lockPreemption() before, and unlockPreemption() after, a critical
+     * section that must not be preempted by the activation of other model
+     * partitions during Scheduled Execution. Does nothing if the FMU wasn't
+     * instantiated for Scheduled Execution.
+     */
+    public void lockPreemption() {
+        Backend.lockPreemptionCallback();
+    }
+
+    public void unlockPreemption() {
+        Backend.unlockPreemptionCallback();
+    }
+
     /* doStep and updateDiscreteStates */
 
     public Fmi3DoStepResult fmi3DoStep(double currentCommunicationPoint, double communicationStepSize, boolean noStepPrior) {
+        if (this.state != FMIState.FMIStepModeState) {
+            this.log("fmi3DoStep called outside of Step Mode.", Fmi3Status.Error, "logStatusError");
+            return new Fmi3DoStepResult<>(Fmi3Status.Error, false, false, false, currentCommunicationPoint);
+        }
 
         update_outputs();
         Boolean event_handling_needed = false;
@@ -443,17 +486,35 @@ public class Model implements Serializable {
         return new Fmi3DoStepResult<>(Fmi3Status.OK,event_handling_needed,terminate_simulation,early_return,last_successful_time);
     }
 
+    public Fmi3Status fmi3EvaluateDiscreteStates(){
+        if (this.state != FMIState.FMIEventModeState) {
+            this.log("fmi3EvaluateDiscreteStates called outside of Event Mode.", Fmi3Status.Error, "logStatusError");
+            return Fmi3Status.Error;
+        }
+
+        evaluate_discrete_states();
+
+        return Fmi3Status.OK;
+    }
+
     public Fmi3UpdateDiscreteStatesResult fmi3UpdateDiscreteStates(){
-        Boolean discrete_states_need_update = false;
+        if (this.state != FMIState.FMIEventModeState) {
+            this.log("fmi3UpdateDiscreteStates called outside of Event Mode.", Fmi3Status.Error, "logStatusError");
+            return new Fmi3UpdateDiscreteStatesResult<>(Fmi3Status.Error, false, false, false, false, false, 0.0);
+        }
+
+        Integer previous_clocked_variable_c = this.clocked_variable_c;
+        evaluate_discrete_states();
+
+        // The event iteration has converged once an iteration leaves the
+        // discrete states unchanged.
+        Boolean discrete_states_need_update = !this.clocked_variable_c.equals(previous_clocked_variable_c);
         Boolean terminate_simulation = false;
         Boolean nominals_continuous_states_changed = false;
         Boolean values_continuous_states_changed = false;
         Boolean next_event_time_defined = true;
         Double next_event_time = 1.0;
 
-
-        update_clocked_outputs();
-
         return new Fmi3UpdateDiscreteStatesResult<>(Fmi3Status.OK, discrete_states_need_update, terminate_simulation, nominals_continuous_states_changed, values_continuous_states_changed, next_event_time_defined, next_event_time);
     }
         
@@ -707,6 +768,88 @@ public class Model implements Serializable {
         return new Fmi3GetShiftFractionPair<Long>(Fmi3Status.OK, counters, resolutions);
     }
 
+    /* Derivatives */
+
+    public Fmi3GetValuePair<Double> fmi3GetDirectionalDerivative(List<Integer> unknowns, List<Integer> knowns, List<Double> seed) {
+        List<Double> sensitivity = new ArrayList<>();
+
+        for (Integer unknown : unknowns) {
+            double value = 0.0;
+            for (int i = 0; i < knowns.size() && i < seed.size(); i++) {
+                value += partialDerivative(unknown, knowns.get(i)) * seed.get(i);
+            }
+            sensitivity.add(value);
+        }
+
+        return new Fmi3GetValuePair<Double>(Fmi3Status.OK, sensitivity);
+    }
+
+    public Fmi3GetValuePair<Double> fmi3GetAdjointDerivative(List<Integer> unknowns, List<Integer> knowns, List<Double> seed) {
+        List<Double> sensitivity = new ArrayList<>();
+
+        for (Integer known : knowns) {
+            double value = 0.0;
+            for (int i = 0; i < unknowns.size() && i < seed.size(); i++) {
+                value += seed.get(i) * partialDerivative(unknowns.get(i), known);
+            }
+            sensitivity.add(value);
+        }
+
+        return new Fmi3GetValuePair<Double>(Fmi3Status.OK, sensitivity);
+    }
+
+    public Fmi3GetValuePair<Double> fmi3GetOutputDerivatives(List<Integer> references, List<Integer> orders) {
+        List<Double> values = new ArrayList<>();
+
+        for (Integer reference : references) {
+            if (!partial_derivatives.containsKey(reference)) {
+                this.log(
+                    String.format("Variable #%s# isn't a continuous output.", reference),
+                    Fmi3Status.Error,
+                    "logStatusError"
+                );
+                return new Fmi3GetValuePair<Double>(Fmi3Status.Error, new ArrayList<>());
+            }
+
+            // The continuous outputs only depend on the inputs, which are
+            // constant between communication points, so all of their
+            // derivatives with respect to time are zero.
+            values.add(0.0);
+        }
+
+        return new Fmi3GetValuePair<Double>(Fmi3Status.OK, values);
+    }
+
+    private double partialDerivative(Integer unknown, Integer known) {
+        return partial_derivatives
+            .getOrDefault(unknown, Map.of())
+            .getOrDefault(known, 0.0);
+    }
+
+    /* Scheduled Execution */
+
+    public Fmi3Status fmi3ActivateModelPartition(int clockReference, double activationTime) throws Exception {
+        if (clockReference != 1001 && clockReference != 1002) {
+            this.log("Clock #" + clockReference + "# isn't an input clock.", Fmi3Status.Error, "logStatusError");
+            return Fmi3Status.Error;
+        }
+
+        this.map_to_attributes.get(clockReference).set(this, true);
+        update_clocks();
+
+        if (this.clock_c) {
+            // Updating the clocked outputs mustn't be preempted by the
+            // activation of other model partitions.
+            this.lockPreemption();
+            update_clocked_outputs();
+            this.unlockPreemption();
+            // Informs the importer that the output clock ticked
+            this.clockUpdate();
+        }
+
+        return Fmi3Status.OK;
+    }
+
     /* Initialization, Enter, Termination, and Reset */
 
     public Fmi3Status fmi3EnterInitializationMode() {
@@ -715,21 +858,31 @@ public class Model implements Serializable {
     }
 
     public Fmi3Status fmi3ExitInitializationMode() {
+        update_outputs();
         if (this.event_mode_used) {
-            this.state = FMIState.FMIEventModeState;
+            enter_event_mode();
         } else{
             this.state = FMIState.FMIStepModeState;
         }
-        update_outputs();
         return Fmi3Status.OK;
     }
 
     public Fmi3Status fmi3EnterEventMode(){
-        this.state = FMIState.FMIEventModeState;
+        if (this.state != FMIState.FMIStepModeState) {
+            this.log("fmi3EnterEventMode called outside of Step Mode.", Fmi3Status.Error, "logStatusError");
+            return Fmi3Status.Error;
+        }
+
+        enter_event_mode();
         return Fmi3Status.OK;
     }
 
     public Fmi3Status fmi3EnterStepMode(){
+        if (this.state != FMIState.FMIEventModeState) {
+            this.log("fmi3EnterStepMode called outside of Event Mode.", Fmi3Status.Error, "logStatusError");
+            return Fmi3Status.Error;
+        }
+
         this.state = FMIState.FMIStepModeState;
         return Fmi3Status.OK;
     }
@@ -850,6 +1003,7 @@ public class Model implements Serializable {
         this.clocked_variable_a = 0;
         this.clocked_variable_b = 0;
         this.clocked_variable_c = 0;
+        this.event_entry_clocked_variable_c = 0;
         this.clock_reference_to_interval = new HashMap<>();
         this.clock_reference_to_interval.put(1001, 1.0);
         this.clock_reference_to_shift = new HashMap<>();
@@ -1143,6 +1297,18 @@ public class Model implements Serializable {
         this.clocked_variable_c += this.clocked_variable_a + this.clocked_variable_b;
     }
 
+    private void enter_event_mode(){
+        this.state = FMIState.FMIEventModeState;
+        this.event_entry_clocked_variable_c = this.clocked_variable_c;
+    }
+
+    private void evaluate_discrete_states(){
+        // Evaluated from the value at Event Mode entry, so that the clocked
+        // variable only advances once per event no matter the number of
+        // event iterations.
+        this.clocked_variable_c = this.event_entry_clocked_variable_c + this.clocked_variable_a + this.clocked_variable_b;
+    }
+
     class Fmi3DoStepResult<T> {
         Fmi3Status status;
         Boolean event_handling_needed;
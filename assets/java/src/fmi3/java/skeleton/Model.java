@@ -0,0 +1,706 @@
+import java.lang.reflect.Array;
+import java.lang.reflect.Field;
+import java.math.BigInteger;
+import java.math.BigDecimal;
+import java.nio.ByteBuffer;
+import java.util.ArrayList;
+import java.util.HashMap;
+import java.util.Iterator;
+import java.util.List;
+import java.io.ByteArrayInputStream;
+import java.io.ByteArrayOutputStream;
+import java.io.ObjectInputStream;
+import java.io.ObjectOutputStream;
+import java.util.Map;
+import java.io.Serializable;
+
+public class Model implements Serializable {
+
+    private static final long serialVersionUID = 1L;
+
+    private String instance_name = "";
+    private String instantiation_token = "";
+    private String resource_path = "";
+    private Boolean visible = false;
+    private Boolean logging_on = false;
+    private Boolean event_mode_used = false;
+    private Boolean early_return_allowed = false;
+    private transient List<Integer> required_intermediate_variables;
+
+    private int state = FMIState.FMIInstantiatedState;
+
+    // {{declarations}}
+
+    public Map<Integer,Double> clock_reference_to_interval;
+    public Map<Integer,Double> clock_reference_to_shift;
+
+    private transient HashMap<Integer, Field> references_to_attributes;
+    private transient List<Integer> structural_parameters;
+
+    public Model(String instance_name, String instantiation_token, String resource_path, Boolean visible, Boolean logging_on, Boolean event_mode_used, Boolean early_return_allowed, List<Integer> required_intermediate_variables) throws Exception {
+        super();
+
+        this.instance_name = instance_name;
+        this.instantiation_token = instantiation_token;
+        this.resource_path = resource_path;
+        this.visible = visible;
+        this.logging_on = logging_on;
+        this.event_mode_used = event_mode_used;
+        this.early_return_allowed = early_return_allowed;
+        this.required_intermediate_variables = required_intermediate_variables;
+
+        this.references_to_attributes = new HashMap<Integer, Field>();
+        // {{references}}
+
+        this.structural_parameters = new ArrayList<Integer>();
+        // {{structural_parameters}}
+
+        fmi3Reset();
+    }
+
+    public Fmi3Status fmi3SetDebugLogging(Iterable<String> categories, Boolean logging_on) {
+        return Fmi3Status.OK;
+    }
+
+    /**
+     * Sends a logging message to the importer of the FMU.
+     *
+     * The UniFMU API layer decides whether or not to forward the message to
+     * the importer based on the category and the categories enabled by the
+     * importer through fmi3SetDebugLogging().
+     *
+     * @param message The message to be logged.
+     * @param status The status of the FMU at the moment of logging.
+     * @param category The logging category that this message falls under.
+     */
+    public void log(String message, Fmi3Status status, String category) {
+        Backend.loggingCallback(status, category, message);
+    }
+
+    /**
+     * UniFMU clock update function
+     *
+     * Call this function during Scheduled Execution to inform the importer
+     * that an output clock has ticked or that a countdown clock has been set.
+     * Does nothing if the FMU wasn't instantiated for Scheduled Execution.
+     */
+    public void clockUpdate() {
+        Backend.clockUpdateCallback();
+    }
+
+    /**
+     * UniFMU preemption lock functions
+     *
+     * Call lockPreemption() before, and unlockPreemption() after, a critical
+     * section that must not be preempted by the activation of other model
+     * partitions during Scheduled Execution. Does nothing if the FMU wasn't
+     * instantiated for Scheduled Execution.
+     */
+    public void lockPreemption() {
+        Backend.lockPreemptionCallback();
+    }
+
+    public void unlockPreemption() {
+        Backend.unlockPreemptionCallback();
+    }
+
+    /* doStep and updateDiscreteStates */
+
+    public Fmi3DoStepResult fmi3DoStep(double currentCommunicationPoint, double communicationStepSize, boolean noStepPrior) {
+        if (this.state != FMIState.FMIStepModeState) {
+            this.log("fmi3DoStep called outside of Step Mode.", Fmi3Status.Error, "logStatusError");
+            return new Fmi3DoStepResult<>(Fmi3Status.Error, false, false, false, currentCommunicationPoint);
+        }
+
+        // Advance the model from currentCommunicationPoint to
+        // currentCommunicationPoint + communicationStepSize here, updating
+        // the outputs from the inputs.
+
+        Boolean event_handling_needed = false;
+        Boolean terminate_simulation = false;
+        Boolean early_return = false;
+        Double last_successful_time = currentCommunicationPoint + communicationStepSize;
+
+        return new Fmi3DoStepResult<>(Fmi3Status.OK,event_handling_needed,terminate_simulation,early_return,last_successful_time);
+    }
+
+    public Fmi3Status fmi3EvaluateDiscreteStates(){
+        if (this.state != FMIState.FMIEventModeState) {
+            this.log("fmi3EvaluateDiscreteStates called outside of Event Mode.", Fmi3Status.Error, "logStatusError");
+            return Fmi3Status.Error;
+        }
+
+        return Fmi3Status.OK;
+    }
+
+    public Fmi3UpdateDiscreteStatesResult fmi3UpdateDiscreteStates(){
+        if (this.state != FMIState.FMIEventModeState) {
+            this.log("fmi3UpdateDiscreteStates called outside of Event Mode.", Fmi3Status.Error, "logStatusError");
+            return new Fmi3UpdateDiscreteStatesResult<>(Fmi3Status.Error, false, false, false, false, false, 0.0);
+        }
+
+        Boolean discrete_states_need_update = false;
+        Boolean terminate_simulation = false;
+        Boolean nominals_continuous_states_changed = false;
+        Boolean values_continuous_states_changed = false;
+        Boolean next_event_time_defined = false;
+        Double next_event_time = 0.0;
+
+        return new Fmi3UpdateDiscreteStatesResult<>(Fmi3Status.OK, discrete_states_need_update, terminate_simulation, nominals_continuous_states_changed, values_continuous_states_changed, next_event_time_defined, next_event_time);
+    }
+
+    /* Setters */
+
+    public Fmi3Status fmi3SetFloat32(Iterable<Integer> references, Iterable<Float> values) throws Exception {
+        return SetValue(references, values);
+    }
+
+    public Fmi3Status fmi3SetFloat64(Iterable<Integer> references, Iterable<Double> values) throws Exception {
+        return SetValue(references, values);
+    }
+
+    public Fmi3Status fmi3SetInt8(Iterable<Integer> references, Iterable<Integer> values) throws Exception {
+        return SetValue(references, values);
+    }
+
+    public Fmi3Status fmi3SetUInt8(Iterable<Integer> references, Iterable<Integer> values) throws Exception {
+        return SetValue(references, values);
+    }
+
+    public Fmi3Status fmi3SetInt16(Iterable<Integer> references, Iterable<Integer> values) throws Exception {
+        return SetValue(references, values);
+    }
+
+    public Fmi3Status fmi3SetUInt16(Iterable<Integer> references, Iterable<Integer> values) throws Exception {
+        return SetValue(references, values);
+    }
+
+    public Fmi3Status fmi3SetInt32(Iterable<Integer> references, Iterable<Integer> values) throws Exception {
+        return SetValue(references, values);
+    }
+
+    public Fmi3Status fmi3SetUInt32(Iterable<Integer> references, Iterable<Integer> values) throws Exception {
+        return SetValue(references, values);
+    }
+
+    public Fmi3Status fmi3SetInt64(Iterable<Integer> references, Iterable<Long> values) throws Exception {
+        return SetValue(references, values);
+    }
+
+    public Fmi3Status fmi3SetUInt64(Iterable<Integer> references, Iterable<Long> values) throws Exception {
+        return SetValue(references, values);
+    }
+
+    public Fmi3Status fmi3SetBoolean(Iterable<Integer> references, Iterable<Boolean> values) throws Exception {
+        return SetValue(references, values);
+    }
+
+    public Fmi3Status fmi3SetString(Iterable<Integer> references, Iterable<String> values) throws Exception {
+        return SetValue(references, values);
+    }
+
+    public Fmi3Status fmi3SetBinary(Iterable<Integer> references, Iterable<Long> valueSizes, Iterable<ByteBuffer> values) throws Exception {
+        return SetValue(references, values);
+    }
+
+    public Fmi3Status fmi3SetClock(Iterable<Integer> references, Iterable<Boolean> values) throws Exception {
+        return SetValue(references, values);
+    }
+
+    public Fmi3Status fmi3SetIntervalDecimal(Iterable<Integer> references, Iterable<Double> intervals) {
+        Iterator<Integer> i1 = references.iterator();
+        Iterator<Double> i2 = intervals.iterator();
+        while (i1.hasNext() && i2.hasNext()) {
+            clock_reference_to_interval.put(i1.next(), i2.next());
+        }
+        return Fmi3Status.OK;
+    }
+
+    public Fmi3Status fmi3SetIntervalFraction(Iterable<Integer> references, Iterable<Long> counters, Iterable<Long> resolutions) {
+        Iterator<Integer> i1 = references.iterator();
+        Iterator<Long> i2 = counters.iterator();
+        Iterator<Long> i3 = resolutions.iterator();
+        while (i1.hasNext() && i2.hasNext() && i3.hasNext()) {
+            clock_reference_to_interval.put(i1.next(), i2.next().doubleValue() / i3.next().doubleValue());
+        }
+        return Fmi3Status.OK;
+    }
+
+    public Fmi3Status fmi3SetShiftDecimal(Iterable<Integer> references, Iterable<Double> shifts) {
+        Iterator<Integer> i1 = references.iterator();
+        Iterator<Double> i2 = shifts.iterator();
+        while (i1.hasNext() && i2.hasNext()) {
+            clock_reference_to_shift.put(i1.next(), i2.next());
+        }
+        return Fmi3Status.OK;
+    }
+
+    public Fmi3Status fmi3SetShiftFraction(Iterable<Integer> references, Iterable<Long> counters, Iterable<Long> resolutions) {
+        Iterator<Integer> i1 = references.iterator();
+        Iterator<Long> i2 = counters.iterator();
+        Iterator<Long> i3 = resolutions.iterator();
+        while (i1.hasNext() && i2.hasNext() && i3.hasNext()) {
+            clock_reference_to_shift.put(i1.next(), i2.next().doubleValue() / i3.next().doubleValue());
+        }
+        return Fmi3Status.OK;
+    }
+
+    /* Getters */
+
+    public Fmi3GetValuePair<Float> fmi3GetFloat32(Iterable<Integer> references) throws Exception {
+        return this.GetValue(references);
+    }
+
+    public Fmi3GetValuePair<Double> fmi3GetFloat64(Iterable<Integer> references) throws Exception {
+        return this.GetValue(references);
+    }
+
+    public Fmi3GetValuePair<Integer> fmi3GetInt8(Iterable<Integer> references) throws Exception {
+        return this.GetValue(references);
+    }
+
+    public Fmi3GetValuePair<Integer> fmi3GetUInt8(Iterable<Integer> references) throws Exception {
+        return this.GetValue(references);
+    }
+
+    public Fmi3GetValuePair<Integer> fmi3GetInt16(Iterable<Integer> references) throws Exception {
+        return this.GetValue(references);
+    }
+
+    public Fmi3GetValuePair<Integer> fmi3GetUInt16(Iterable<Integer> references) throws Exception {
+        return this.GetValue(references);
+    }
+
+    public Fmi3GetValuePair<Integer> fmi3GetInt32(Iterable<Integer> references) throws Exception {
+        return this.GetValue(references);
+    }
+
+    public Fmi3GetValuePair<Integer> fmi3GetUInt32(Iterable<Integer> references) throws Exception {
+        return this.GetValue(references);
+    }
+
+    public Fmi3GetValuePair<Long> fmi3GetInt64(Iterable<Integer> references) throws Exception {
+        return this.GetValue(references);
+    }
+
+    public Fmi3GetValuePair<Long> fmi3GetUInt64(Iterable<Integer> references) throws Exception {
+        return this.GetValue(references);
+    }
+
+    public Fmi3GetValuePair<Boolean> fmi3GetBoolean(Iterable<Integer> references) throws Exception {
+        return this.GetValue(references);
+    }
+
+    public Fmi3GetValuePair<String> fmi3GetString(Iterable<Integer> references) throws Exception {
+        return this.GetValue(references);
+    }
+
+    public Fmi3GetValuePair<ByteBuffer> fmi3GetBinary(Iterable<Integer> references) throws Exception {
+        return this.GetValue(references);
+    }
+
+    public Fmi3GetValuePair<Boolean> fmi3GetClock(Iterable<Integer> references) throws Exception {
+        return this.GetValue(references);
+    }
+
+    public Fmi3GetIntervalDecimalPair<Double,Integer> fmi3GetIntervalDecimal(Iterable<Integer> references) {
+        List<Double> intervals = new ArrayList<>();
+        List<Integer> qualifiers = new ArrayList<>();
+        for (Integer reference : references) {
+            intervals.add(clock_reference_to_interval.get(reference));
+            qualifiers.add(2);
+        }
+        return new Fmi3GetIntervalDecimalPair<Double,Integer>(Fmi3Status.OK, intervals, qualifiers);
+    }
+
+    public Fmi3GetIntervalFractionPair<Long,Integer> fmi3GetIntervalFraction(Iterable<Integer> references) {
+        List<Long> counters = new ArrayList<>();
+        List<Long> resolutions = new ArrayList<>();
+        List<Integer> qualifiers = new ArrayList<>();
+        for (Integer reference : references) {
+            Fraction fraction = new Fraction(String.valueOf(clock_reference_to_interval.get(reference)));
+            counters.add(fraction.getNumerator());
+            resolutions.add(fraction.getDenominator());
+            qualifiers.add(2);
+        }
+        return new Fmi3GetIntervalFractionPair<Long,Integer>(Fmi3Status.OK, counters, resolutions, qualifiers);
+    }
+
+    public Fmi3GetShiftDecimalPair<Double> fmi3GetShiftDecimal(Iterable<Integer> references) {
+        List<Double> shifts = new ArrayList<>();
+        for (Integer reference : references) {
+            shifts.add(clock_reference_to_shift.get(reference));
+        }
+        return new Fmi3GetShiftDecimalPair<Double>(Fmi3Status.OK, shifts);
+    }
+
+    public Fmi3GetShiftFractionPair<Long> fmi3GetShiftFraction(Iterable<Integer> references) {
+        List<Long> counters = new ArrayList<>();
+        List<Long> resolutions = new ArrayList<>();
+        for (Integer reference : references) {
+            Fraction fraction = new Fraction(String.valueOf(clock_reference_to_shift.get(reference)));
+            counters.add(fraction.getNumerator());
+            resolutions.add(fraction.getDenominator());
+        }
+        return new Fmi3GetShiftFractionPair<Long>(Fmi3Status.OK, counters, resolutions);
+    }
+
+    /* Derivatives */
+
+    public Fmi3GetValuePair<Double> fmi3GetDirectionalDerivative(List<Integer> unknowns, List<Integer> knowns, List<Double> seed) {
+        this.log("The model can't compute directional derivatives.", Fmi3Status.Error, "logStatusError");
+        return new Fmi3GetValuePair<Double>(Fmi3Status.Error, new ArrayList<>());
+    }
+
+    public Fmi3GetValuePair<Double> fmi3GetAdjointDerivative(List<Integer> unknowns, List<Integer> knowns, List<Double> seed) {
+        this.log("The model can't compute adjoint derivatives.", Fmi3Status.Error, "logStatusError");
+        return new Fmi3GetValuePair<Double>(Fmi3Status.Error, new ArrayList<>());
+    }
+
+    public Fmi3GetValuePair<Double> fmi3GetOutputDerivatives(List<Integer> references, List<Integer> orders) {
+        this.log("The model can't compute output derivatives.", Fmi3Status.Error, "logStatusError");
+        return new Fmi3GetValuePair<Double>(Fmi3Status.Error, new ArrayList<>());
+    }
+
+    /* Scheduled Execution */
+
+    public Fmi3Status fmi3ActivateModelPartition(int clockReference, double activationTime) {
+        return Fmi3Status.OK;
+    }
+
+    /* Initialization, Enter, Termination, and Reset */
+
+    public Fmi3Status fmi3EnterInitializationMode() {
+        this.state = FMIState.FMIInitializationModeState;
+        return Fmi3Status.OK;
+    }
+
+    public Fmi3Status fmi3ExitInitializationMode() {
+        if (this.event_mode_used) {
+            this.state = FMIState.FMIEventModeState;
+        } else{
+            this.state = FMIState.FMIStepModeState;
+        }
+        return Fmi3Status.OK;
+    }
+
+    public Fmi3Status fmi3EnterEventMode(){
+        if (this.state != FMIState.FMIStepModeState) {
+            this.log("fmi3EnterEventMode called outside of Step Mode.", Fmi3Status.Error, "logStatusError");
+            return Fmi3Status.Error;
+        }
+
+        this.state = FMIState.FMIEventModeState;
+        return Fmi3Status.OK;
+    }
+
+    public Fmi3Status fmi3EnterStepMode(){
+        if (this.state != FMIState.FMIEventModeState) {
+            this.log("fmi3EnterStepMode called outside of Event Mode.", Fmi3Status.Error, "logStatusError");
+            return Fmi3Status.Error;
+        }
+
+        this.state = FMIState.FMIStepModeState;
+        return Fmi3Status.OK;
+    }
+
+    public Fmi3Status fmi3EnterConfigurationMode(){
+        if (this.structural_parameters.size() > 0) {
+            if (this.state == FMIState.FMIInstantiatedState){
+                this.state = FMIState.FMIConfigurationModeState;
+            } else{
+                this.state = FMIState.FMIReconfigurationModeState;
+            }
+        } else {
+            return Fmi3Status.Error;
+        }
+        return Fmi3Status.OK;
+    }
+
+    public Fmi3Status fmi3ExitConfigurationMode(){
+        if (this.state == FMIState.FMIConfigurationModeState){
+            this.state = FMIState.FMIInstantiatedState;
+        }
+        else if (this.state == FMIState.FMIReconfigurationModeState){
+            this.state = FMIState.FMIStepModeState;
+        }
+        else{
+            return Fmi3Status.Error;
+        }
+        return Fmi3Status.OK;
+    }
+
+    public Fmi3Status fmi3Reset() {
+        this.state = FMIState.FMIInstantiatedState;
+        // {{start_values}}
+        this.clock_reference_to_interval = new HashMap<>();
+        // {{clock_intervals}}
+        this.clock_reference_to_shift = new HashMap<>();
+        // {{clock_shifts}}
+        return Fmi3Status.OK;
+    }
+
+    public Fmi3Status fmi3Terminate() {
+        this.state = FMIState.FMITerminatedState;
+        return Fmi3Status.OK;
+    }
+
+    /* Serialization */
+
+    public Fmi3SerializeFmuStatePair fmi3SerializeFmuState() throws Exception {
+        var b = new ByteArrayOutputStream();
+        var o = new ObjectOutputStream(b);
+        o.writeObject(this);
+        return new Fmi3SerializeFmuStatePair(Fmi3Status.OK, ByteBuffer.wrap(b.toByteArray()));
+    }
+
+    public Fmi3Status fmi3DeserializeFmuState(ByteBuffer bytes) throws Exception {
+        byte[] state_byte_array = new byte[bytes.remaining()];
+        bytes.get(state_byte_array);
+        try (ByteArrayInputStream b = new ByteArrayInputStream(state_byte_array)) {
+            try (ObjectInputStream o = new ObjectInputStream(b)) {
+                var other = (Model) o.readObject();
+                this.state = other.state;
+                for (Field field : this.references_to_attributes.values()) {
+                    field.set(this, field.get(other));
+                }
+                this.clock_reference_to_interval = other.clock_reference_to_interval;
+                this.clock_reference_to_shift = other.clock_reference_to_shift;
+            }
+        }
+        return Fmi3Status.OK;
+    }
+
+    /* Helpers */
+
+    @SuppressWarnings("unchecked")
+    private <T> Fmi3GetValuePair<T> GetValue(Iterable<Integer> references) throws Exception {
+        var values = new ArrayList<T>();
+        for (var ref : references) {
+            var val = this.references_to_attributes.get(ref).get(this);
+            if (is_array(val)) {
+                for (int i = 0; i < Array.getLength(val); i++) {
+                    values.add((T) to_value(Array.get(val, i)));
+                }
+            } else {
+                values.add((T) to_value(val));
+            }
+        }
+        return new Fmi3GetValuePair<T>(Fmi3Status.OK, values);
+    }
+
+    private <T> Fmi3Status SetValue(Iterable<Integer> references, Iterable<T> values) throws Exception {
+        Iterator<Integer> i1 = references.iterator();
+        Iterator<T> i2 = values.iterator();
+        while (i1.hasNext()) {
+            Integer r = i1.next();
+            Field field = this.references_to_attributes.get(r);
+            var current = field.get(this);
+            boolean is_array = is_array(current);
+            int count = is_array ? Array.getLength(current) : 1;
+            for (int i = 0; i < count; i++) {
+                if (!i2.hasNext()) {
+                    this.log(
+                        String.format("Not enough values were supplied to set #%s#.", r),
+                        Fmi3Status.Error,
+                        "logStatusError"
+                    );
+                    return Fmi3Status.Error;
+                }
+                var v = to_attribute(i2.next());
+                if (is_array) {
+                    Array.set(current, i, v);
+                } else {
+                    field.set(this, v);
+                }
+            }
+        }
+        if (i2.hasNext()) {
+            this.log(
+                "More values were supplied than needed for the given references.",
+                Fmi3Status.Error,
+                "logStatusError"
+            );
+            return Fmi3Status.Error;
+        }
+        return Fmi3Status.OK;
+    }
+
+    // Binary values are held as byte arrays, so those aren't FMI arrays.
+    private static boolean is_array(Object attribute) {
+        return attribute != null && attribute.getClass().isArray() && !(attribute instanceof byte[]);
+    }
+
+    // Binary values are exchanged as ByteBuffers but held as byte arrays.
+    private static Object to_value(Object attribute) {
+        if (attribute instanceof byte[] bytes) {
+            return ByteBuffer.wrap(bytes);
+        }
+        return attribute;
+    }
+
+    private static Object to_attribute(Object value) {
+        if (value instanceof ByteBuffer byte_buffer) {
+            byte[] byte_array = new byte[byte_buffer.remaining()];
+            byte_buffer.get(byte_array);
+            return byte_array;
+        }
+        return value;
+    }
+
+    class Fmi3DoStepResult<T> {
+        Fmi3Status status;
+        Boolean event_handling_needed;
+        Boolean terminate_simulation;
+        Boolean early_return;
+        Double last_successful_time;
+
+        Fmi3DoStepResult(Fmi3Status status, Boolean event_handling_needed, Boolean terminate_simulation, Boolean early_return, Double last_successful_time)
+        {
+            this.status = status;
+            this.event_handling_needed = event_handling_needed;
+            this.terminate_simulation = terminate_simulation;
+            this.early_return = early_return;
+            this.last_successful_time = last_successful_time;
+        }
+    }
+
+    class Fmi3UpdateDiscreteStatesResult<T> {
+        Fmi3Status status;
+        Boolean discrete_states_need_update;
+        Boolean terminate_simulation;
+        Boolean nominals_continuous_states_changed;
+        Boolean values_continuous_states_changed;
+        Boolean next_event_time_defined;
+        Double next_event_time;
+
+        Fmi3UpdateDiscreteStatesResult(Fmi3Status status, Boolean discrete_states_need_update, Boolean terminate_simulation, Boolean nominals_continuous_states_changed, Boolean values_continuous_states_changed, Boolean next_event_time_defined, Double next_event_time)
+        {
+            this.status = status;
+            this.discrete_states_need_update = discrete_states_need_update;
+            this.terminate_simulation = terminate_simulation;
+            this.nominals_continuous_states_changed = nominals_continuous_states_changed;
+            this.values_continuous_states_changed = values_continuous_states_changed;
+            this.next_event_time_defined = next_event_time_defined;
+            this.next_event_time = next_event_time;
+        }
+    }
+
+    class Fmi3GetValuePair<T> {
+        Fmi3Status status;
+        List<T> values;
+
+        Fmi3GetValuePair(Fmi3Status status, List<T> values)
+        {
+            this.status = status;
+            this.values = values;
+        }
+    }
+
+    class Fmi3GetIntervalDecimalPair<T, U> {
+        Fmi3Status status;
+        List<T> intervals;
+        List<U> qualifiers;
+
+        Fmi3GetIntervalDecimalPair(Fmi3Status status, List<T> intervals, List<U> qualifiers)
+        {
+            this.status = status;
+            this.intervals = intervals;
+            this.qualifiers = qualifiers;
+        }
+    }
+
+    class Fmi3GetIntervalFractionPair<T, U> {
+        Fmi3Status status;
+        List<T> counters;
+        List<T> resolutions;
+        List<U> qualifiers;
+
+        Fmi3GetIntervalFractionPair(Fmi3Status status, List<T> counters, List<T> resolutions, List<U> qualifiers)
+        {
+            this.status = status;
+            this.counters = counters;
+            this.resolutions = resolutions;
+            this.qualifiers = qualifiers;
+        }
+    }
+
+    class Fmi3GetShiftDecimalPair<T> {
+        Fmi3Status status;
+        List<T> shifts;
+
+        Fmi3GetShiftDecimalPair(Fmi3Status status, List<T> shifts)
+        {
+            this.status = status;
+            this.shifts = shifts;
+        }
+    }
+
+    class Fmi3GetShiftFractionPair<T> {
+        Fmi3Status status;
+        List<T> counters;
+        List<T> resolutions;
+
+        Fmi3GetShiftFractionPair(Fmi3Status status, List<T> counters, List<T> resolutions)
+        {
+            this.status = status;
+            this.counters = counters;
+            this.resolutions = resolutions;
+        }
+    }
+
+    class Fmi3SerializeFmuStatePair {
+        public Fmi3Status status;
+        public ByteBuffer bytes;
+
+        Fmi3SerializeFmuStatePair(Fmi3Status status, ByteBuffer bytes) {
+            this.status = status;
+            this.bytes = bytes;
+        }
+    }
+
+    enum Fmi3Status {
+        OK,
+        Warning,
+        Discard,
+        Error,
+        Fatal,
+        Pending
+    }
+
+    class FMIState {
+        public static final int FMIStartAndEndState         = 1 << 0;
+        public static final int FMIInstantiatedState        = 1 << 1;
+        public static final int FMIInitializationModeState  = 1 << 2;
+        public static final int FMITerminatedState          = 1 << 3;
+        public static final int FMIConfigurationModeState   = 1 << 4;
+        public static final int FMIReconfigurationModeState = 1 << 5;
+        public static final int FMIEventModeState           = 1 << 6;
+        public static final int FMIContinuousTimeModeState  = 1 << 7;
+        public static final int FMIStepModeState            = 1 << 8;
+        public static final int FMIClockActivationMode      = 1 << 9;
+    }
+
+    class Fraction {
+        private final Long numerator;
+        private final Long denominator;
+
+        public Fraction(String decimal) {
+            BigDecimal bd = new BigDecimal(decimal);
+            int scale = bd.scale();
+            BigInteger den = BigInteger.TEN.pow(scale);
+            BigInteger num = bd.multiply(new BigDecimal(den)).toBigIntegerExact();
+            BigInteger gcd = num.gcd(den);
+            this.numerator = num.divide(gcd).longValue();
+            this.denominator = den.divide(gcd).longValue();
+        }
+
+        public Long getNumerator() {
+            return numerator;
+        }
+
+        public Long getDenominator() {
+            return denominator;
+        }
+    }
+}
@@ -0,0 +1,152 @@
+classdef Model < handle
+    % The model of the FMU.
+    %
+    % Each FMI2 function called on the FMU is forwarded by the Python
+    % backend to the method of the same name, with the model as its first
+    % argument. Value references are given as int64 arrays and values as
+    % arrays of the type of the variables; strings are given and returned
+    % as cell arrays of character vectors.
+
+    properties
+        real_a = 0.0
+        real_b = 0.0
+        real_c = 0.0
+        integer_a = int32(0)
+        integer_b = int32(0)
+        integer_c = int32(0)
+        boolean_a = false
+        boolean_b = false
+        boolean_c = false
+        string_a = ''
+        string_b = ''
+        string_c = ''
+    end
+
+    properties (Constant)
+        % Names of the variables, indexed by value reference + 1.
+        reference_to_attribute = { ...
+            'real_a', 'real_b', 'real_c', ...
+            'integer_a', 'integer_b', 'integer_c', ...
+            'boolean_a', 'boolean_b', 'boolean_c', ...
+            'string_a', 'string_b', 'string_c' ...
+        }
+    end
+
+    methods
+        function obj = Model()
+            obj.fmi2Reset();
+        end
+
+        % ================= FMI2 =================
+
+        function status = fmi2DoStep(obj, current_time, step_size, no_set_fmu_state_prior_to_current_point)
+            obj.update_outputs();
+            status = Fmi2Status.ok;
+        end
+
+        function status = fmi2SetDebugLogging(obj, categories, logging_on)
+            status = Fmi2Status.ok;
+        end
+
+        function status = fmi2SetupExperiment(obj, start_time, stop_time, tolerance)
+            status = Fmi2Status.ok;
+        end
+
+        function status = fmi2EnterInitializationMode(obj)
+            status = Fmi2Status.ok;
+        end
+
+        function status = fmi2ExitInitializationMode(obj)
+            obj.update_outputs();
+            status = Fmi2Status.ok;
+        end
+
+        function status = fmi2Terminate(obj)
+            status = Fmi2Status.ok;
+        end
+
+        function status = fmi2Reset(obj)
+            obj.real_a = 0.0;
+            obj.real_b = 0.0;
+            obj.integer_a = int32(0);
+            obj.integer_b = int32(0);
+            obj.boolean_a = false;
+            obj.boolean_b = false;
+            obj.string_a = '';
+            obj.string_b = '';
+            obj.update_outputs();
+            status = Fmi2Status.ok;
+        end
+
+        function [status, bytes] = fmi2SerializeFmuState(obj)
+            state = cellfun(@(name) obj.(name), obj.reference_to_attribute, 'UniformOutput', false);
+            bytes = getByteStreamFromArray(state);
+            status = Fmi2Status.ok;
+        end
+
+        function status = fmi2DeserializeFmuState(obj, bytes)
+            state = getArrayFromByteStream(bytes);
+            for i = 1:numel(state)
+                obj.(obj.reference_to_attribute{i}) = state{i};
+            end
+            status = Fmi2Status.ok;
+        end
+
+        function [status, values] = fmi2GetReal(obj, references)
+            [status, values] = obj.get_values(references, false);
+        end
+
+        function [status, values] = fmi2GetInteger(obj, references)
+            [status, values] = obj.get_values(references, false);
+        end
+
+        function [status, values] = fmi2GetBoolean(obj, references)
+            [status, values] = obj.get_values(references, false);
+        end
+
+        function [status, values] = fmi2GetString(obj, references)
+            [status, values] = obj.get_values(references, true);
+        end
+
+        function status = fmi2SetReal(obj, references, values)
+            status = obj.set_values(references, num2cell(values));
+        end
+
+        function status = fmi2SetInteger(obj, references, values)
+            status = obj.set_values(references, num2cell(values));
+        end
+
+        function status = fmi2SetBoolean(obj, references, values)
+            status = obj.set_values(references, num2cell(values));
+        end
+
+        function status = fmi2SetString(obj, references, values)
+            status = obj.set_values(references, values);
+        end
+    end
+
+    methods (Access = private)
+        function [status, values] = get_values(obj, references, as_cell)
+            values = arrayfun( ...
+                @(reference) obj.(obj.reference_to_attribute{reference + 1}), ...
+                double(references), ...
+                'UniformOutput', ~as_cell ...
+            );
+            status = Fmi2Status.ok;
+        end
+
+        function status = set_values(obj, references, values)
+            for i = 1:numel(references)
+                obj.(obj.reference_to_attribute{double(references(i)) + 1}) = values{i};
+            end
+            status = Fmi2Status.ok;
+        end
+
+        function update_outputs(obj)
+            obj.real_c = obj.real_a + obj.real_b;
+            obj.integer_c = obj.integer_a + obj.integer_b;
+            obj.boolean_c = obj.boolean_a || obj.boolean_b;
+            obj.string_c = [obj.string_a obj.string_b];
+        end
+    end
+end
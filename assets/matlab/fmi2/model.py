@@ -0,0 +1,186 @@
+import os
+
+import matlab
+import matlab.engine
+
+
+class Model:
+    """ Bridge between the backend and the model written in MATLAB.
+
+    Starts a MATLAB engine in the `matlabcode` directory next to this file and
+    creates an instance of the MATLAB class `Model` found there. Every FMI2
+    call is forwarded to the method of the same name of that instance, with
+    the value references and values converted to MATLAB arrays. Errors raised
+    in MATLAB are logged and answered with an error status.
+
+    The model itself lives in `matlabcode/Model.m`; this file should only need
+    changing if a method of the MATLAB model takes or returns something else.
+    """
+
+    def __init__(self, _log_callback) -> None:
+        self._log_callback = _log_callback # Removing this line will break logging
+
+        self.engine = matlab.engine.start_matlab()
+        self.engine.cd(
+            os.path.join(os.path.dirname(os.path.abspath(__file__)), "matlabcode"),
+            nargout=0
+        )
+        self.model = self.engine.Model(nargout=1)
+
+    # ================= FMI2 =================
+
+    def fmi2DoStep(
+        self, current_time, step_size, no_set_fmu_state_prior_to_current_point
+    ):
+        return self._status(
+            "fmi2DoStep",
+            float(current_time),
+            float(step_size),
+            bool(no_set_fmu_state_prior_to_current_point)
+        )
+
+    def fmi2CancelStep(self):
+        # The engine runs one call at a time, so a running fmi2DoStep can't be
+        # interrupted; the backend waits for it to finish instead.
+        return Fmi2Status.ok
+
+    def fmi2SetDebugLogging(self, categories, logging_on):
+        return self._status(
+            "fmi2SetDebugLogging", list(categories), bool(logging_on)
+        )
+
+    def fmi2SetupExperiment(self, start_time, stop_time, tolerance):
+        return self._status(
+            "fmi2SetupExperiment",
+            float(start_time),
+            float(stop_time),
+            float(tolerance)
+        )
+
+    def fmi2EnterInitializationMode(self):
+        return self._status("fmi2EnterInitializationMode")
+
+    def fmi2ExitInitializationMode(self):
+        return self._status("fmi2ExitInitializationMode")
+
+    def fmi2Terminate(self):
+        return self._status("fmi2Terminate")
+
+    def fmi2Reset(self):
+        return self._status("fmi2Reset")
+
+    def fmi2SerializeFmuState(self):
+        result = self._call("fmi2SerializeFmuState", nargout=2)
+        if result is None:
+            return Fmi2Status.error, b""
+
+        status, state = result
+        return status, bytes(_to_list(state, int))
+
+    def fmi2DeserializeFmuState(self, bytes):
+        return self._status("fmi2DeserializeFmuState", matlab.uint8(list(bytes)))
+
+    def fmi2GetReal(self, references):
+        return self._get("fmi2GetReal", references, float)
+
+    def fmi2GetInteger(self, references):
+        return self._get("fmi2GetInteger", references, int)
+
+    def fmi2GetBoolean(self, references):
+        return self._get("fmi2GetBoolean", references, bool)
+
+    def fmi2GetString(self, references):
+        return self._get("fmi2GetString", references, str)
+
+    def fmi2SetReal(self, references, values):
+        return self._status(
+            "fmi2SetReal", _references(references), matlab.double(list(values))
+        )
+
+    def fmi2SetInteger(self, references, values):
+        return self._status(
+            "fmi2SetInteger", _references(references), matlab.int32(list(values))
+        )
+
+    def fmi2SetBoolean(self, references, values):
+        return self._status(
+            "fmi2SetBoolean", _references(references), matlab.logical(list(values))
+        )
+
+    def fmi2SetString(self, references, values):
+        # A list of strings is passed to MATLAB as a cell array.
+        return self._status("fmi2SetString", _references(references), list(values))
+
+    # ================= Logging =================
+
+    def log(self, message, status, category = "logAll"):
+        # Removing the line below will break logging.
+        self._log_callback(status, category, message)
+
+    # ================= Helpers =================
+
+    def _call(self, name, *args, nargout=1):
+        try:
+            return getattr(self.engine, name)(self.model, *args, nargout=nargout)
+        except matlab.engine.MatlabExecutionError as e:
+            self.log(
+                f"{name} failed in MATLAB: {e}",
+                Fmi2Status.error,
+                "logStatusError"
+            )
+            return None
+
+    def _status(self, name, *args):
+        status = self._call(name, *args)
+        if status is None:
+            return Fmi2Status.error
+
+        return int(status)
+
+    def _get(self, name, references, convert):
+        result = self._call(name, _references(references), nargout=2)
+        if result is None:
+            return Fmi2Status.error, []
+
+        status, values = result
+        return int(status), _to_list(values, convert)
+
+
+def _references(references):
+    return matlab.int64([int(r) for r in references])
+
+
+def _to_list(values, convert):
+    # The engine gives a cell array as a list, a 1x1 array as a scalar and
+    # any other array as a MATLAB array, which is iterated by rows.
+    if isinstance(values, list):
+        return [convert(v) for v in values]
+    if isinstance(values, (bool, int, float, str)):
+        return [convert(values)]
+
+    return [convert(v) for row in values for v in row]
+
+
+class Fmi2Status:
+    """
+    Represents the status of an FMI2 FMU or the results of function calls.
+
+    Values:
+        * ok: all well
+        * warning: an issue has arisen, but the computation can continue.
+        * discard: an operation has resulted in invalid output, which must be discarded
+        * error: an error has ocurred for this specific FMU instance.
+        * fatal: an fatal error has ocurred which has corrupted ALL FMU instances.
+        * pending: indicates that the FMu is doing work asynchronously, which can be retrived later.
+
+    Notes:
+        FMI section 2.1.3
+
+    """
+
+    ok = 0
+    warning = 1
+    discard = 2
+    error = 3
+    fatal = 4
+    pending = 5
@@ -0,0 +1,17 @@
+classdef Fmi3Status
+    % The status of an FMI3 FMU or the results of function calls.
+    %
+    %   ok:      all well
+    %   warning: an issue has arisen, but the computation can continue.
+    %   discard: an operation has resulted in invalid output, which must be discarded
+    %   error:   an error has ocurred for this specific FMU instance.
+    %   fatal:   an fatal error has ocurred which has corrupted ALL FMU instances.
+
+    properties (Constant)
+        ok = int32(0)
+        warning = int32(1)
+        discard = int32(2)
+        error = int32(3)
+        fatal = int32(4)
+    end
+end
@@ -0,0 +1,313 @@
+classdef Model < handle
+    % The model of the FMU.
+    %
+    % Each FMI3 function called on the FMU is forwarded by the Python
+    % backend to the method of the same name, with the model as its first
+    % argument. Value references are given as int64 arrays and values as
+    % arrays of the type of the variables; strings are given and returned
+    % as cell arrays of character vectors.
+
+    properties
+        instance_name
+        event_mode_used
+        time = 0.0
+
+        float32_a = single(0.0)
+        float32_b = single(0.0)
+        float32_c = single(0.0)
+        float64_a = 0.0
+        float64_b = 0.0
+        float64_c = 0.0
+        int8_a = int8(0)
+        int8_b = int8(0)
+        int8_c = int8(0)
+        uint8_a = uint8(0)
+        uint8_b = uint8(0)
+        uint8_c = uint8(0)
+        int16_a = int16(0)
+        int16_b = int16(0)
+        int16_c = int16(0)
+        uint16_a = uint16(0)
+        uint16_b = uint16(0)
+        uint16_c = uint16(0)
+        int32_a = int32(0)
+        int32_b = int32(0)
+        int32_c = int32(0)
+        uint32_a = uint32(0)
+        uint32_b = uint32(0)
+        uint32_c = uint32(0)
+        int64_a = int64(0)
+        int64_b = int64(0)
+        int64_c = int64(0)
+        uint64_a = uint64(0)
+        uint64_b = uint64(0)
+        uint64_c = uint64(0)
+        boolean_a = false
+        boolean_b = false
+        boolean_c = false
+        string_a = ''
+        string_b = ''
+        string_c = ''
+    end
+
+    properties (Constant)
+        % Names of the variables, indexed by value reference + 1.
+        reference_to_attribute = { ...
+            'float32_a', 'float32_b', 'float32_c', ...
+            'float64_a', 'float64_b', 'float64_c', ...
+            'int8_a', 'int8_b', 'int8_c', ...
+            'uint8_a', 'uint8_b', 'uint8_c', ...
+            'int16_a', 'int16_b', 'int16_c', ...
+            'uint16_a', 'uint16_b', 'uint16_c', ...
+            'int32_a', 'int32_b', 'int32_c', ...
+            'uint32_a', 'uint32_b', 'uint32_c', ...
+            'int64_a', 'int64_b', 'int64_c', ...
+            'uint64_a', 'uint64_b', 'uint64_c', ...
+            'boolean_a', 'boolean_b', 'boolean_c', ...
+            'string_a', 'string_b', 'string_c' ...
+        }
+        time_reference = 999
+        continuous_outputs = [2, 5]
+    end
+
+    methods
+        function obj = Model(instance_name, instantiation_token, resource_path, visible, logging_on, event_mode_used)
+            obj.instance_name = instance_name;
+            obj.event_mode_used = event_mode_used;
+            obj.fmi3Reset();
+        end
+
+        % ================= FMI3 =================
+
+        function [status, event_handling_needed, terminate_simulation, early_return, last_successful_time] = ...
+                fmi3DoStep(obj, current_communication_point, communication_step_size, no_set_fmu_state_prior_to_current_point)
+            obj.update_outputs();
+            obj.time = current_communication_point + communication_step_size;
+
+            event_handling_needed = false;
+            terminate_simulation = false;
+            early_return = false;
+            last_successful_time = obj.time;
+            status = Fmi3Status.ok;
+        end
+
+        function status = fmi3EnterInitializationMode(obj, tolerance_defined, tolerance, start_time, stop_time_defined, stop_time)
+            obj.time = start_time;
+            status = Fmi3Status.ok;
+        end
+
+        function status = fmi3ExitInitializationMode(obj)
+            obj.update_outputs();
+            status = Fmi3Status.ok;
+        end
+
+        function status = fmi3EnterEventMode(obj)
+            status = Fmi3Status.ok;
+        end
+
+        function status = fmi3EnterStepMode(obj)
+            status = Fmi3Status.ok;
+        end
+
+        function [status, discrete_states_need_update, terminate_simulation, nominals_continuous_states_changed, ...
+                values_continuous_states_changed, next_event_time_defined, next_event_time] = fmi3UpdateDiscreteStates(obj)
+            obj.update_outputs();
+
+            discrete_states_need_update = false;
+            terminate_simulation = false;
+            nominals_continuous_states_changed = false;
+            values_continuous_states_changed = false;
+            next_event_time_defined = false;
+            next_event_time = 0.0;
+            status = Fmi3Status.ok;
+        end
+
+        function status = fmi3SetDebugLogging(obj, categories, logging_on)
+            status = Fmi3Status.ok;
+        end
+
+        function status = fmi3Terminate(obj)
+            status = Fmi3Status.ok;
+        end
+
+        function status = fmi3Reset(obj)
+            obj.time = 0.0;
+            for i = 1:numel(obj.reference_to_attribute)
+                name = obj.reference_to_attribute{i};
+                if ischar(obj.(name))
+                    obj.(name) = '';
+                else
+                    obj.(name) = cast(0, 'like', obj.(name));
+                end
+            end
+            obj.update_outputs();
+            status = Fmi3Status.ok;
+        end
+
+        function [status, bytes] = fmi3SerializeFmuState(obj)
+            state = cellfun(@(name) obj.(name), obj.reference_to_attribute, 'UniformOutput', false);
+            bytes = getByteStreamFromArray({obj.time, state});
+            status = Fmi3Status.ok;
+        end
+
+        function status = fmi3DeserializeFmuState(obj, bytes)
+            state = getArrayFromByteStream(bytes);
+            obj.time = state{1};
+            for i = 1:numel(state{2})
+                obj.(obj.reference_to_attribute{i}) = state{2}{i};
+            end
+            status = Fmi3Status.ok;
+        end
+
+        function [status, values] = fmi3GetFloat32(obj, references)
+            [status, values] = obj.get_values(references, false);
+        end
+
+        function [status, values] = fmi3GetFloat64(obj, references)
+            [status, values] = obj.get_values(references, false);
+        end
+
+        function [status, values] = fmi3GetInt8(obj, references)
+            [status, values] = obj.get_values(references, false);
+        end
+
+        function [status, values] = fmi3GetUInt8(obj, references)
+            [status, values] = obj.get_values(references, false);
+        end
+
+        function [status, values] = fmi3GetInt16(obj, references)
+            [status, values] = obj.get_values(references, false);
+        end
+
+        function [status, values] = fmi3GetUInt16(obj, references)
+            [status, values] = obj.get_values(references, false);
+        end
+
+        function [status, values] = fmi3GetInt32(obj, references)
+            [status, values] = obj.get_values(references, false);
+        end
+
+        function [status, values] = fmi3GetUInt32(obj, references)
+            [status, values] = obj.get_values(references, false);
+        end
+
+        function [status, values] = fmi3GetInt64(obj, references)
+            [status, values] = obj.get_values(references, false);
+        end
+
+        function [status, values] = fmi3GetUInt64(obj, references)
+            [status, values] = obj.get_values(references, false);
+        end
+
+        function [status, values] = fmi3GetBoolean(obj, references)
+            [status, values] = obj.get_values(references, false);
+        end
+
+        function [status, values] = fmi3GetString(obj, references)
+            [status, values] = obj.get_values(references, true);
+        end
+
+        function status = fmi3SetFloat32(obj, references, values)
+            status = obj.set_values(references, num2cell(values));
+        end
+
+        function status = fmi3SetFloat64(obj, references, values)
+            status = obj.set_values(references, num2cell(values));
+        end
+
+        function status = fmi3SetInt8(obj, references, values)
+            status = obj.set_values(references, num2cell(values));
+        end
+
+        function status = fmi3SetUInt8(obj, references, values)
+            status = obj.set_values(references, num2cell(values));
+        end
+
+        function status = fmi3SetInt16(obj, references, values)
+            status = obj.set_values(references, num2cell(values));
+        end
+
+        function status = fmi3SetUInt16(obj, references, values)
+            status = obj.set_values(references, num2cell(values));
+        end
+
+        function status = fmi3SetInt32(obj, references, values)
+            status = obj.set_values(references, num2cell(values));
+        end
+
+        function status = fmi3SetUInt32(obj, references, values)
+            status = obj.set_values(references, num2cell(values));
+        end
+
+        function status = fmi3SetInt64(obj, references, values)
+            status = obj.set_values(references, num2cell(values));
+        end
+
+        function status = fmi3SetUInt64(obj, references, values)
+            status = obj.set_values(references, num2cell(values));
+        end
+
+        function status = fmi3SetBoolean(obj, references, values)
+            status = obj.set_values(references, num2cell(values));
+        end
+
+        function status = fmi3SetString(obj, references, values)
+            status = obj.set_values(references, values);
+        end
+
+        function [status, values] = fmi3GetOutputDerivatives(obj, references, orders)
+            if ~all(ismember(double(references), obj.continuous_outputs))
+                values = [];
+                status = Fmi3Status.error;
+                return
+            end
+
+            % The continuous outputs only depend on the inputs, which are
+            % constant between communication points, so all of their
+            % derivatives with respect to time are zero.
+            values = zeros(1, numel(references));
+            status = Fmi3Status.ok;
+        end
+    end
+
+    methods (Access = private)
+        function value = get_value(obj, reference)
+            if reference == obj.time_reference
+                value = obj.time;
+            else
+                value = obj.(obj.reference_to_attribute{reference + 1});
+            end
+        end
+
+        function [status, values] = get_values(obj, references, as_cell)
+            values = arrayfun( ...
+                @(reference) obj.get_value(reference), ...
+                double(references), ...
+                'UniformOutput', ~as_cell ...
+            );
+            status = Fmi3Status.ok;
+        end
+
+        function status = set_values(obj, references, values)
+            for i = 1:numel(references)
+                obj.(obj.reference_to_attribute{double(references(i)) + 1}) = values{i};
+            end
+            status = Fmi3Status.ok;
+        end
+
+        function update_outputs(obj)
+            obj.float32_c = obj.float32_a + obj.float32_b;
+            obj.float64_c = obj.float64_a + obj.float64_b;
+            obj.int8_c = obj.int8_a + obj.int8_b;
+            obj.uint8_c = obj.uint8_a + obj.uint8_b;
+            obj.int16_c = obj.int16_a + obj.int16_b;
+            obj.uint16_c = obj.uint16_a + obj.uint16_b;
+            obj.int32_c = obj.int32_a + obj.int32_b;
+            obj.uint32_c = obj.uint32_a + obj.uint32_b;
+            obj.int64_c = obj.int64_a + obj.int64_b;
+            obj.uint64_c = obj.uint64_a + obj.uint64_b;
+            obj.boolean_c = obj.boolean_a || obj.boolean_b;
+            obj.string_c = [obj.string_a obj.string_b];
+        end
+    end
+end
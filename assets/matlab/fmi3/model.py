@@ -0,0 +1,332 @@
+import os
+
+import matlab
+import matlab.engine
+
+
+class Model:
+    """ Bridge between the backend and the model written in MATLAB.
+
+    Starts a MATLAB engine in the `matlabcode` directory next to this file and
+    creates an instance of the MATLAB class `Model` found there. Every FMI3
+    call is forwarded to the method of the same name of that instance, with
+    the value references and values converted to MATLAB arrays. Errors raised
+    in MATLAB are logged and answered with an error status, as are the calls
+    that the MATLAB template doesn't cover, like those for binaries, clocks
+    and Model Exchange.
+
+    The model itself lives in `matlabcode/Model.m`; this file should only need
+    changing if a method of the MATLAB model takes or returns something else.
+    """
+
+    def __init__(
+            self,
+            instance_name,
+            instantiation_token,
+            resource_path,
+            visible,
+            logging_on,
+            event_mode_used = False,
+            early_return_allowed = False,
+            required_intermediate_variables = None,
+            _log_callback = None,
+            **_callbacks
+    ) -> None:
+        self._log_callback = _log_callback # Removing this line will break logging
+
+        self.engine = matlab.engine.start_matlab()
+        self.engine.cd(
+            os.path.join(os.path.dirname(os.path.abspath(__file__)), "matlabcode"),
+            nargout=0
+        )
+        self.model = self.engine.Model(
+            str(instance_name),
+            str(instantiation_token),
+            str(resource_path),
+            bool(visible),
+            bool(logging_on),
+            bool(event_mode_used),
+            nargout=1
+        )
+
+    # ================= FMI3 =================
+
+    def fmi3DoStep(
+            self,
+            current_communication_point,
+            communication_step_size,
+            no_set_fmu_state_prior_to_current_point
+    ):
+        result = self._call(
+            "fmi3DoStep",
+            float(current_communication_point),
+            float(communication_step_size),
+            bool(no_set_fmu_state_prior_to_current_point),
+            nargout=5
+        )
+        if result is None:
+            return (Fmi3Status.error, False, False, False, current_communication_point)
+
+        (
+            status,
+            event_handling_needed,
+            terminate_simulation,
+            early_return,
+            last_successful_time
+        ) = result
+
+        return (
+            int(status),
+            bool(event_handling_needed),
+            bool(terminate_simulation),
+            bool(early_return),
+            float(last_successful_time)
+        )
+
+    def fmi3EnterInitializationMode(
+            self,
+            tolerance_defined,
+            tolerance,
+            start_time,
+            stop_time_defined,
+            stop_time
+    ):
+        return self._status(
+            "fmi3EnterInitializationMode",
+            bool(tolerance_defined),
+            float(tolerance),
+            float(start_time),
+            bool(stop_time_defined),
+            float(stop_time)
+        )
+
+    def fmi3ExitInitializationMode(self):
+        return self._status("fmi3ExitInitializationMode")
+
+    def fmi3EnterEventMode(self):
+        return self._status("fmi3EnterEventMode")
+
+    def fmi3EnterStepMode(self):
+        return self._status("fmi3EnterStepMode")
+
+    def fmi3UpdateDiscreteStates(self):
+        result = self._call("fmi3UpdateDiscreteStates", nargout=7)
+        if result is None:
+            return (Fmi3Status.error, False, False, False, False, False, 0.0)
+
+        status, *flags, next_event_time = result
+        return (int(status), *[bool(flag) for flag in flags], float(next_event_time))
+
+    def fmi3SetDebugLogging(self, categories, logging_on):
+        return self._status(
+            "fmi3SetDebugLogging", list(categories), bool(logging_on)
+        )
+
+    def fmi3Terminate(self):
+        return self._status("fmi3Terminate")
+
+    def fmi3Reset(self):
+        return self._status("fmi3Reset")
+
+    def fmi3SerializeFmuState(self):
+        result = self._call("fmi3SerializeFmuState", nargout=2)
+        if result is None:
+            return Fmi3Status.error, b""
+
+        status, state = result
+        return int(status), bytes(_to_list(state, int))
+
+    def fmi3DeserializeFmuState(self, bytes):
+        return self._status("fmi3DeserializeFmuState", matlab.uint8(list(bytes)))
+
+    def fmi3GetFloat32(self, value_references):
+        return self._get("fmi3GetFloat32", value_references, float)
+
+    def fmi3GetFloat64(self, value_references):
+        return self._get("fmi3GetFloat64", value_references, float)
+
+    def fmi3GetInt8(self, value_references):
+        return self._get("fmi3GetInt8", value_references, int)
+
+    def fmi3GetUInt8(self, value_references):
+        return self._get("fmi3GetUInt8", value_references, int)
+
+    def fmi3GetInt16(self, value_references):
+        return self._get("fmi3GetInt16", value_references, int)
+
+    def fmi3GetUInt16(self, value_references):
+        return self._get("fmi3GetUInt16", value_references, int)
+
+    def fmi3GetInt32(self, value_references):
+        return self._get("fmi3GetInt32", value_references, int)
+
+    def fmi3GetUInt32(self, value_references):
+        return self._get("fmi3GetUInt32", value_references, int)
+
+    def fmi3GetInt64(self, value_references):
+        return self._get("fmi3GetInt64", value_references, int)
+
+    def fmi3GetUInt64(self, value_references):
+        return self._get("fmi3GetUInt64", value_references, int)
+
+    def fmi3GetBoolean(self, value_references):
+        return self._get("fmi3GetBoolean", value_references, bool)
+
+    def fmi3GetString(self, value_references):
+        return self._get("fmi3GetString", value_references, str)
+
+    def fmi3SetFloat32(self, value_references, values):
+        return self._set("fmi3SetFloat32", value_references, matlab.single(list(values)))
+
+    def fmi3SetFloat64(self, value_references, values):
+        return self._set("fmi3SetFloat64", value_references, matlab.double(list(values)))
+
+    def fmi3SetInt8(self, value_references, values):
+        return self._set("fmi3SetInt8", value_references, matlab.int8(list(values)))
+
+    def fmi3SetUInt8(self, value_references, values):
+        return self._set("fmi3SetUInt8", value_references, matlab.uint8(list(values)))
+
+    def fmi3SetInt16(self, value_references, values):
+        return self._set("fmi3SetInt16", value_references, matlab.int16(list(values)))
+
+    def fmi3SetUInt16(self, value_references, values):
+        return self._set("fmi3SetUInt16", value_references, matlab.uint16(list(values)))
+
+    def fmi3SetInt32(self, value_references, values):
+        return self._set("fmi3SetInt32", value_references, matlab.int32(list(values)))
+
+    def fmi3SetUInt32(self, value_references, values):
+        return self._set("fmi3SetUInt32", value_references, matlab.uint32(list(values)))
+
+    def fmi3SetInt64(self, value_references, values):
+        return self._set("fmi3SetInt64", value_references, matlab.int64(list(values)))
+
+    def fmi3SetUInt64(self, value_references, values):
+        return self._set("fmi3SetUInt64", value_references, matlab.uint64(list(values)))
+
+    def fmi3SetBoolean(self, value_references, values):
+        return self._set("fmi3SetBoolean", value_references, matlab.logical(list(values)))
+
+    def fmi3SetString(self, value_references, values):
+        # A list of strings is passed to MATLAB as a cell array.
+        return self._set("fmi3SetString", value_references, list(values))
+
+    def fmi3GetOutputDerivatives(self, value_references, orders):
+        result = self._call(
+            "fmi3GetOutputDerivatives",
+            _references(value_references),
+            matlab.int32(list(orders)),
+            nargout=2
+        )
+        if result is None:
+            return Fmi3Status.error, []
+
+        status, values = result
+        return int(status), _to_list(values, float)
+
+    # ================= Unsupported =================
+
+    def __getattr__(self, name):
+        # Only called for the FMI3 functions that aren't defined above.
+        if not name.startswith("fmi3"):
+            raise AttributeError(name)
+
+        return lambda *args: self._unsupported(name)
+
+    def _unsupported(self, name):
+        self.log(
+            f"{name} isn't supported by the MATLAB backend.",
+            Fmi3Status.error,
+            "logStatusError"
+        )
+
+        if name in _UNSUPPORTED_RESULTS:
+            return (Fmi3Status.error, *_UNSUPPORTED_RESULTS[name])
+        if name.startswith("fmi3Get"):
+            return Fmi3Status.error, []
+
+        return Fmi3Status.error
+
+    # ================= Logging =================
+
+    def log(self, message, status, category = "logEvents"):
+        # Removing the line below will break logging.
+        self._log_callback(status, category, message)
+
+    # ================= Helpers =================
+
+    def _call(self, name, *args, nargout=1):
+        try:
+            return getattr(self.engine, name)(self.model, *args, nargout=nargout)
+        except matlab.engine.MatlabExecutionError as e:
+            self.log(
+                f"{name} failed in MATLAB: {e}",
+                Fmi3Status.error,
+                "logStatusError"
+            )
+            return None
+
+    def _status(self, name, *args):
+        status = self._call(name, *args)
+        if status is None:
+            return Fmi3Status.error
+
+        return int(status)
+
+    def _get(self, name, value_references, convert):
+        result = self._call(name, _references(value_references), nargout=2)
+        if result is None:
+            return Fmi3Status.error, []
+
+        status, values = result
+        return int(status), _to_list(values, convert)
+
+    def _set(self, name, value_references, values):
+        return self._status(name, _references(value_references), values)
+
+
+# Results other than the status of the unsupported functions that return more
+# than the status and a list of values.
+_UNSUPPORTED_RESULTS = {
+    "fmi3GetIntervalDecimal": ([], []),
+    "fmi3GetIntervalFraction": ([], [], []),
+    "fmi3GetShiftFraction": ([], []),
+    "fmi3GetNumberOfEventIndicators": (0,),
+    "fmi3GetNumberOfContinuousStates": (0,),
+    "fmi3CompletedIntegratorStep": (False, False),
+}
+
+
+def _references(value_references):
+    return matlab.int64([int(r) for r in value_references])
+
+
+def _to_list(values, convert):
+    # The engine gives a cell array as a list, a 1x1 array as a scalar and
+    # any other array as a MATLAB array, which is iterated by rows.
+    if isinstance(values, list):
+        return [convert(v) for v in values]
+    if isinstance(values, (bool, int, float, str)):
+        return [convert(values)]
+
+    return [convert(v) for row in values for v in row]
+
+
+class Fmi3Status:
+    """
+    Represents the status of an FMI3 FMU or the results of function calls.
+
+    Values:
+        * ok: all well
+        * warning: an issue has arisen, but the computation can continue.
+        * discard: an operation has resulted in invalid output, which must be discarded
+        * error: an error has ocurred for this specific FMU instance.
+        * fatal: an fatal error has ocurred which has corrupted ALL FMU instances.
+    """
+
+    ok = 0
+    warning = 1
+    discard = 2
+    error = 3
+    fatal = 4
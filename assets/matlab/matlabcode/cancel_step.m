@@ -1,2 +0,0 @@
-function ok = cancel_step()
-ok = int32(0);
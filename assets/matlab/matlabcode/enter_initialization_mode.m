@@ -1,2 +0,0 @@
-function ok = enter_initialization_mode()
-ok = int32(0);
\ No newline at end of file
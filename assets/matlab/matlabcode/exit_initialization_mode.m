@@ -1,2 +0,0 @@
-function ok = exit_initialization_mode()
-ok = int32(0);
@@ -1,2 +0,0 @@
-function ok = get_directional_derivative()
-ok = int32(0);
@@ -1,2 +0,0 @@
-function ok = get_xxx_status()
-ok = int32(0);
@@ -1,2 +0,0 @@
-function ok = reset()
-ok = int32(0);
\ No newline at end of file
@@ -1,2 +0,0 @@
-function ok = set_debug_logging()
-ok = int32(0);
@@ -1,2 +0,0 @@
-function ok = set_input_derivatives()
-ok = int32(0);
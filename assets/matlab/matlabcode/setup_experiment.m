@@ -1,2 +0,0 @@
-function ok = setup_experiment(start_time, stop_time, tolerance)
-ok = int32(0);
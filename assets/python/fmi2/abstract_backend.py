@@ -1,4 +1,5 @@
 import logging
+import threading
 import zmq
 from abc import ABC, abstractmethod
 
@@ -20,6 +21,12 @@ class AbstractBackend(ABC):
     def __init__(self):
         self.context = zmq.Context()
         self.socket = self.context.socket(zmq.REQ)
+        # Thread running an asynchronous fmi2DoStep, if any. Log events
+        # emitted from it are buffered until the next command is received,
+        # as only the main thread may use the socket.
+        self.step_thread = None
+        self.buffered_logs = []
+        self.buffered_logs_lock = threading.Lock()
     
     def connect_to_endpoint(self, endpoint):
         self.socket.connect(endpoint)
@@ -47,6 +54,11 @@ class AbstractBackend(ABC):
         )
 
     def log_callback(self, status, category, message):
+        if threading.current_thread() is self.step_thread:
+            with self.buffered_logs_lock:
+                self.buffered_logs.append((status, category, message))
+            return
+
         self.send_reply(
             Fmi2Return(
                 log=Fmi2LogReturn(
@@ -65,6 +77,14 @@ class AbstractBackend(ABC):
             case _:
                 self.unknown_command(command_group)
 
+    def flush_buffered_logs(self):
+        with self.buffered_logs_lock:
+            buffered_logs = self.buffered_logs
+            self.buffered_logs = []
+
+        for status, category, message in buffered_logs:
+            self.log_callback(status, category, message)
+
     def handshake(self):
         self.send_reply(HandshakeReply(status=HandshakeStatus.OK))
 
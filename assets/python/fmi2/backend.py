@@ -1,19 +1,28 @@
 import logging
 import sys
+import threading
 
 from schemas.fmi2_messages_pb2 import (
     Fmi2Return,
     Fmi2EmptyReturn,
     Fmi2FreeInstanceReturn,
+    Fmi2GetDoStepStatusReturn,
     Fmi2SerializeFmuStateReturn,
     Fmi2GetRealReturn,
     Fmi2GetIntegerReturn,
     Fmi2GetBooleanReturn,
-    Fmi2GetStringReturn
+    Fmi2GetStringReturn,
+    Fmi2GetRealOutputDerivativesReturn,
+    Fmi2NewDiscreteStatesReturn,
+    Fmi2CompletedIntegratorStepReturn,
+    Fmi2GetDerivativesReturn,
+    Fmi2GetEventIndicatorsReturn,
+    Fmi2GetContinuousStatesReturn,
+    Fmi2GetNominalsOfContinuousStatesReturn
 )
 
 from abstract_backend import AbstractBackend
-from model import Model
+from model import Model, Fmi2Status
 
 logging.basicConfig(level=logging.DEBUG)
 logger = logging.getLogger(__file__)
@@ -23,6 +32,8 @@ class Backend(AbstractBackend):
         while True:
 
             group, data = self.recv_command()
+
+            self.flush_buffered_logs()
         
             match group:
                 case "Fmi2Instantiate":
@@ -34,14 +45,44 @@ class Backend(AbstractBackend):
                     )
 
                 case "Fmi2DoStep":
-                    self.status_reply(
-                        model.fmi2DoStep(
-                            data.current_time,
-                            data.step_size,
-                            data.no_set_fmu_state_prior_to_current_point
+                    if data.run_asynchronously:
+                        self.start_asynchronous_step(model, data)
+                        self.status_reply(Fmi2Status.pending)
+                    else:
+                        self.status_reply(
+                            model.fmi2DoStep(
+                                data.current_time,
+                                data.step_size,
+                                data.no_set_fmu_state_prior_to_current_point
+                            )
+                        )
+
+                case "Fmi2GetDoStepStatus":
+                    # The status of a finished step is kept, as the API may
+                    # query it again after it has been reported once.
+                    if self.step_thread is not None and self.step_thread.is_alive():
+                        status = Fmi2Status.pending
+                    else:
+                        status = self.step_status
+                        self.step_thread = None
+
+                    self.send_reply(
+                        Fmi2Return(
+                            get_do_step_status=Fmi2GetDoStepStatusReturn(
+                                status=status,
+                                pending_message=self.pending_message
+                            )
                         )
                     )
 
+                case "Fmi2CancelStep":
+                    status = model.fmi2CancelStep()
+                    if self.step_thread is not None:
+                        self.step_thread.join()
+                        self.step_thread = None
+                    self.flush_buffered_logs()
+                    self.status_reply(status)
+
                 case "Fmi2SetDebugLogging":
                     self.status_reply(
                         model.fmi2SetDebugLogging(
@@ -147,8 +188,150 @@ class Backend(AbstractBackend):
                 case "Fmi2SetString":
                     self.status_reply(model.fmi2SetString(data.references, data.values))
 
+                case "Fmi2SetRealInputDerivatives":
+                    self.status_reply(
+                        model.fmi2SetRealInputDerivatives(
+                            data.references,
+                            data.orders,
+                            data.values
+                        )
+                    )
+
+                case "Fmi2GetRealOutputDerivatives":
+                    status, values = model.fmi2GetRealOutputDerivatives(
+                        data.references,
+                        data.orders
+                    )
+                    self.send_reply(
+                        Fmi2Return(
+                            get_real_output_derivatives=Fmi2GetRealOutputDerivativesReturn(
+                                status=status,
+                                values=values
+                            )
+                        )
+                    )
+
+                case "Fmi2EnterEventMode":
+                    self.status_reply(model.fmi2EnterEventMode())
+
+                case "Fmi2NewDiscreteStates":
+                    (
+                        status,
+                        new_discrete_states_needed,
+                        terminate_simulation,
+                        nominals_of_continuous_states_changed,
+                        values_of_continuous_states_changed,
+                        next_event_time_defined,
+                        next_event_time
+                    ) = model.fmi2NewDiscreteStates()
+                    self.send_reply(
+                        Fmi2Return(
+                            new_discrete_states=Fmi2NewDiscreteStatesReturn(
+                                status=status,
+                                new_discrete_states_needed=new_discrete_states_needed,
+                                terminate_simulation=terminate_simulation,
+                                nominals_of_continuous_states_changed=nominals_of_continuous_states_changed,
+                                values_of_continuous_states_changed=values_of_continuous_states_changed,
+                                next_event_time_defined=next_event_time_defined,
+                                next_event_time=next_event_time
+                            )
+                        )
+                    )
+
+                case "Fmi2EnterContinuousTimeMode":
+                    self.status_reply(model.fmi2EnterContinuousTimeMode())
+
+                case "Fmi2CompletedIntegratorStep":
+                    status, enter_event_mode, terminate_simulation = model.fmi2CompletedIntegratorStep(
+                        data.no_set_fmu_state_prior_to_current_point
+                    )
+                    self.send_reply(
+                        Fmi2Return(
+                            completed_integrator_step=Fmi2CompletedIntegratorStepReturn(
+                                status=status,
+                                enter_event_mode=enter_event_mode,
+                                terminate_simulation=terminate_simulation
+                            )
+                        )
+                    )
+
+                case "Fmi2SetTime":
+                    self.status_reply(model.fmi2SetTime(data.time))
+
+                case "Fmi2SetContinuousStates":
+                    self.status_reply(
+                        model.fmi2SetContinuousStates(data.continuous_states)
+                    )
+
+                case "Fmi2GetDerivatives":
+                    status, derivatives = model.fmi2GetDerivatives()
+                    self.send_reply(
+                        Fmi2Return(
+                            get_derivatives=Fmi2GetDerivativesReturn(
+                                status=status,
+                                derivatives=derivatives
+                            )
+                        )
+                    )
+
+                case "Fmi2GetEventIndicators":
+                    status, event_indicators = model.fmi2GetEventIndicators()
+                    self.send_reply(
+                        Fmi2Return(
+                            get_event_indicators=Fmi2GetEventIndicatorsReturn(
+                                status=status,
+                                event_indicators=event_indicators
+                            )
+                        )
+                    )
+
+                case "Fmi2GetContinuousStates":
+                    status, continuous_states = model.fmi2GetContinuousStates()
+                    self.send_reply(
+                        Fmi2Return(
+                            get_continuous_states=Fmi2GetContinuousStatesReturn(
+                                status=status,
+                                continuous_states=continuous_states
+                            )
+                        )
+                    )
+
+                case "Fmi2GetNominalsOfContinuousStates":
+                    status, nominals = model.fmi2GetNominalsOfContinuousStates()
+                    self.send_reply(
+                        Fmi2Return(
+                            get_nominals_of_continuous_states=Fmi2GetNominalsOfContinuousStatesReturn(
+                                status=status,
+                                nominals=nominals
+                            )
+                        )
+                    )
+
                 case _:
                     self.unknown_command(group)
+
+    def start_asynchronous_step(self, model, data):
+        """Runs fmi2DoStep of the model on a separate thread, so that the
+        importer can query the status of the step or cancel it while it is
+        running."""
+        self.step_status = Fmi2Status.pending
+        # Cleared before the step thread starts, so that a cancellation
+        # arriving right after the step was started isn't lost.
+        model.step_canceled = False
+        self.pending_message = (
+            f"fmi2DoStep from time {data.current_time} "
+            f"with step size {data.step_size} is running"
+        )
+
+        def step():
+            self.step_status = model.fmi2DoStep(
+                data.current_time,
+                data.step_size,
+                data.no_set_fmu_state_prior_to_current_point
+            )
+
+        self.step_thread = threading.Thread(target=step, daemon=True)
+        self.step_thread.start()
     
     def unknown_command(self, command_group):
         logger.error(f"unrecognized command '{command_group}' received, shutting down")
@@ -14,7 +14,8 @@ from schemas.fmi2_messages_pb2 import (
     Fmi2GetRealReturn,
     Fmi2GetIntegerReturn,
     Fmi2GetBooleanReturn,
-    Fmi2GetStringReturn
+    Fmi2GetStringReturn,
+    Fmi2GetRealOutputDerivativesReturn
 )
 
 from abstract_backend import AbstractBackend
@@ -203,6 +204,20 @@ class BlackboxBackend(AbstractBackend):
                     self.fmu.setString(data.references,data.values)
                     self.status_reply(0)
 
+                case "Fmi2SetRealInputDerivatives":
+                    self.fmu.setRealInputDerivatives(data.references, data.orders, data.values)
+                    self.status_reply(0)
+
+                case "Fmi2GetRealOutputDerivatives":
+                    self.send_reply(
+                        Fmi2Return(
+                            get_real_output_derivatives=Fmi2GetRealOutputDerivativesReturn(
+                                status=0,
+                                values=self.fmu.getRealOutputDerivatives(data.references, data.orders)
+                            )
+                        )
+                    )
+
                 case _:
                     self.unknown_command(group)
     
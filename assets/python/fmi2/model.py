@@ -1,4 +1,5 @@
 import pickle
+from math import factorial
 
 class Model:
     def __init__(self, _log_callback) -> None:
@@ -12,6 +13,10 @@ class Model:
         self.boolean_b = False
         self.string_a = ""
         self.string_b = ""
+        # The time derivatives of the real inputs given by the importer
+        # through fmi2SetRealInputDerivatives, indexed by value reference and
+        # derivative order. Used to interpolate the inputs during fmi2DoStep.
+        self.input_derivatives = {}
 
         self.reference_to_attribute = {
             0: "real_a",
@@ -28,6 +33,9 @@ class Model:
             11: "string_c",
         }
 
+        self.real_inputs = [0, 1]
+        self.real_outputs = [2]
+
         self.fmi2Reset()
 
     # ================= FMI2 =================
@@ -35,9 +43,18 @@ class Model:
     def fmi2DoStep(
         self, current_time, step_size, no_set_fmu_state_prior_to_current_point
     ):
+        self._interpolate_inputs(step_size)
         self._update_outputs()
         return Fmi2Status.ok
 
+    def fmi2CancelStep(self):
+        # Called while an asynchronous fmi2DoStep is running on another
+        # thread. A long running step should check step_canceled and return
+        # early once it is set. The flag is cleared by the backend whenever a
+        # new asynchronous step starts.
+        self.step_canceled = True
+        return Fmi2Status.ok
+
     def fmi2SetDebugLogging(self, categories, logging_on):
         return Fmi2Status.ok
 
@@ -63,6 +80,8 @@ class Model:
         self.boolean_b = False
         self.string_a = ""
         self.string_b = ""
+        self.input_derivatives = {}
+        self.step_canceled = False
         self._update_outputs()
 
         return Fmi2Status.ok
@@ -82,6 +101,7 @@ class Model:
                 self.string_a,
                 self.string_b,
                 self.string_c,
+                self.input_derivatives,
             )
         )
         return Fmi2Status.ok, bytes
@@ -100,6 +120,7 @@ class Model:
             string_a,
             string_b,
             string_c,
+            input_derivatives,
         ) = pickle.loads(bytes)
         self.real_a = real_a
         self.real_b = real_b
@@ -113,6 +134,7 @@ class Model:
         self.string_a = string_a
         self.string_b = string_b
         self.string_c = string_c
+        self.input_derivatives = input_derivatives
 
         return Fmi2Status.ok
 
@@ -129,6 +151,9 @@ class Model:
         return self._get_value(references)
 
     def fmi2SetReal(self, references, values):
+        # A new input value starts a new interpolation of that input
+        for r in references:
+            self.input_derivatives.pop(r, None)
         return self._set_value(references, values)
 
     def fmi2SetInteger(self, references, values):
@@ -139,6 +164,49 @@ class Model:
 
     def fmi2SetString(self, references, values):
         return self._set_value(references, values)
+
+    # ================= Input and Output Derivatives =================
+
+    def fmi2SetRealInputDerivatives(self, references, orders, values):
+        for r, order, value in zip(references, orders, values):
+            if r not in self.real_inputs:
+                self.log(
+                    f"Variable #{r}# isn't a real input.",
+                    Fmi2Status.error,
+                    "logStatusError"
+                )
+                return Fmi2Status.error
+            if order < 1:
+                self.log(
+                    f"Can't set derivative of order {order} for input #{r}#.",
+                    Fmi2Status.error,
+                    "logStatusError"
+                )
+                return Fmi2Status.error
+
+            self.input_derivatives.setdefault(r, {})[order] = value
+
+        return Fmi2Status.ok
+
+    def fmi2GetRealOutputDerivatives(self, references, orders):
+        values = []
+        for r, order in zip(references, orders):
+            if r not in self.real_outputs:
+                self.log(
+                    f"Variable #{r}# isn't a real output.",
+                    Fmi2Status.error,
+                    "logStatusError"
+                )
+                return Fmi2Status.error, []
+
+            # As real_c = real_a + real_b, its derivatives are the sums of
+            # the derivatives of the inputs.
+            values.append(sum(
+                self.input_derivatives.get(i, {}).get(order, 0.0)
+                for i in self.real_inputs
+            ))
+
+        return Fmi2Status.ok, values
     
     # ================= Logging =================
 
@@ -196,6 +264,27 @@ class Model:
 
         return Fmi2Status.ok, values
 
+    def _interpolate_inputs(self, step_size):
+        # Moves each interpolated input and its derivatives to the end of the
+        # step along the Taylor polynomial given by the input derivatives.
+        for r, derivatives in self.input_derivatives.items():
+            attribute = self.reference_to_attribute[r]
+            coefficients = [getattr(self, attribute)] + [
+                derivatives.get(order, 0.0)
+                for order in range(1, max(derivatives) + 1)
+            ]
+            shifted = [
+                sum(
+                    coefficients[j] * step_size ** (j - k) / factorial(j - k)
+                    for j in range(k, len(coefficients))
+                )
+                for k in range(len(coefficients))
+            ]
+            setattr(self, attribute, shifted[0])
+            self.input_derivatives[r] = {
+                order: shifted[order] for order in range(1, len(shifted))
+            }
+
     def _update_outputs(self):
         self.real_c = self.real_a + self.real_b
         self.integer_c = self.integer_a + self.integer_b
@@ -0,0 +1,241 @@
+import pickle
+
+class Model:
+    def __init__(self, _log_callback) -> None:
+        self._log_callback = _log_callback # Removing this line will break logging
+
+        self.reference_to_attribute = {
+            0: "time",
+            1: "h",
+            2: "der_h",
+            3: "v",
+            4: "der_v",
+            5: "g",
+            6: "e",
+        }
+
+        # The continuous states of the model, in the order that they are
+        # exchanged with the importer.
+        self.continuous_states = ["h", "v"]
+
+        self.fmi2Reset()
+
+    @property
+    def der_h(self):
+        return self.v
+
+    @property
+    def der_v(self):
+        return self.g
+
+    # ================= FMI2 =================
+
+    def fmi2SetDebugLogging(self, categories, logging_on):
+        return Fmi2Status.ok
+
+    def fmi2SetupExperiment(self, start_time, stop_time, tolerance):
+        self.time = start_time
+        return Fmi2Status.ok
+
+    def fmi2EnterInitializationMode(self):
+        return Fmi2Status.ok
+
+    def fmi2ExitInitializationMode(self):
+        return Fmi2Status.ok
+
+    def fmi2Terminate(self):
+        return Fmi2Status.ok
+
+    def fmi2Reset(self):
+        self.time = 0.0
+        self.h = 1.0
+        self.v = 0.0
+        self.g = -9.81
+        self.e = 0.7
+
+        return Fmi2Status.ok
+
+    # ================= Model Exchange =================
+
+    def fmi2EnterEventMode(self):
+        return Fmi2Status.ok
+
+    def fmi2NewDiscreteStates(self):
+        new_discrete_states_needed = False
+        terminate_simulation = False
+        nominals_of_continuous_states_changed = False
+        values_of_continuous_states_changed = False
+        next_event_time_defined = False
+        next_event_time = 0.0
+
+        if self.h <= 0.0 and self.v < 0.0:
+            self.h = 0.0
+            self.v = -self.e * self.v
+            values_of_continuous_states_changed = True
+
+        return (Fmi2Status.ok, new_discrete_states_needed, terminate_simulation,
+                nominals_of_continuous_states_changed, values_of_continuous_states_changed,
+                next_event_time_defined, next_event_time)
+
+    def fmi2EnterContinuousTimeMode(self):
+        return Fmi2Status.ok
+
+    def fmi2CompletedIntegratorStep(self, no_set_fmu_state_prior_to_current_point):
+        enter_event_mode = False
+        terminate_simulation = False
+
+        return Fmi2Status.ok, enter_event_mode, terminate_simulation
+
+    def fmi2SetTime(self, time):
+        self.time = time
+        return Fmi2Status.ok
+
+    def fmi2SetContinuousStates(self, continuous_states):
+        for attribute, value in zip(self.continuous_states, continuous_states):
+            setattr(self, attribute, value)
+        return Fmi2Status.ok
+
+    def fmi2GetContinuousStates(self):
+        return Fmi2Status.ok, [
+            getattr(self, attribute) for attribute in self.continuous_states
+        ]
+
+    def fmi2GetDerivatives(self):
+        return Fmi2Status.ok, [self.der_h, self.der_v]
+
+    def fmi2GetNominalsOfContinuousStates(self):
+        return Fmi2Status.ok, [1.0 for _ in self.continuous_states]
+
+    def fmi2GetEventIndicators(self):
+        # The ball hits the ground when the height crosses zero
+        return Fmi2Status.ok, [self.h]
+
+    # ================= Serialization =================
+
+    def fmi2SerializeFmuState(self):
+        bytes = pickle.dumps(
+            (
+                self.time,
+                self.h,
+                self.v,
+                self.g,
+                self.e,
+            )
+        )
+        return Fmi2Status.ok, bytes
+
+    def fmi2DeserializeFmuState(self, bytes):
+        (
+            time,
+            h,
+            v,
+            g,
+            e,
+        ) = pickle.loads(bytes)
+        self.time = time
+        self.h = h
+        self.v = v
+        self.g = g
+        self.e = e
+
+        return Fmi2Status.ok
+
+    # ================= Getters and Setters =================
+
+    def fmi2GetReal(self, references):
+        return self._get_value(references)
+
+    def fmi2SetReal(self, references, values):
+        return self._set_value(references, values)
+
+    # ================= Logging =================
+
+    """ UniFMU logging function
+
+    Call this function whenever something should be logged.
+    This will send a message thourgh the UniFMU layer to the importer if the
+    importer has enabled logging and is interested in the given logging category.
+
+    Keyword arguments:
+    message  -- The message to log.
+    status   -- The Fmi2Status that the program is expected to return when log() is
+                called.
+    category -- The logging category. Must be defined in the modelDescription.xml
+                to be visible to the importer.
+    """
+    def log(self, message, status, category = "logAll"):
+        # Removing the line below will break logging.
+        self._log_callback(status, category, message)
+
+    # ================= Helpers =================
+
+    def _set_value(self, references, values):
+        for r, v in zip(references, values):
+            if self.reference_to_attribute[r] in ("der_h", "der_v"):
+                self.log(
+                    f"Variable #{r}# can't be set.",
+                    Fmi2Status.error,
+                    "logStatusError"
+                )
+                return Fmi2Status.error
+
+            setattr(self, self.reference_to_attribute[r], v)
+
+        return Fmi2Status.ok
+
+    def _get_value(self, references):
+        values = []
+
+        for r in references:
+            values.append(getattr(self, self.reference_to_attribute[r]))
+
+        return Fmi2Status.ok, values
+
+
+class Fmi2Status:
+    """
+    Represents the status of an FMI2 FMU or the results of function calls.
+
+    Values:
+        * ok: all well
+        * warning: an issue has arisen, but the computation can continue.
+        * discard: an operation has resulted in invalid output, which must be discarded
+        * error: an error has ocurred for this specific FMU instance.
+        * fatal: an fatal error has ocurred which has corrupted ALL FMU instances.
+        * pending: indicates that the FMu is doing work asynchronously, which can be retrived later.
+
+    Notes:
+        FMI section 2.1.3
+
+    """
+
+    ok = 0
+    warning = 1
+    discard = 2
+    error = 3
+    fatal = 4
+    pending = 5
+
+
+if __name__ == "__main__":
+    m = Model(lambda *args: None)
+
+    assert m.h == 1.0
+    assert m.v == 0.0
+
+    # Explicit euler until the ball hits the ground
+    step_size = 1e-3
+    while m.fmi2GetEventIndicators()[1][0] > 0.0:
+        _, derivatives = m.fmi2GetDerivatives()
+        _, states = m.fmi2GetContinuousStates()
+        m.fmi2SetContinuousStates(
+            [x + step_size * dx for x, dx in zip(states, derivatives)]
+        )
+
+    assert m.v < 0.0
+
+    _, _, _, _, values_changed, _, _ = m.fmi2NewDiscreteStates()
+
+    assert values_changed
+    assert m.h == 0.0
+    assert m.v > 0.0
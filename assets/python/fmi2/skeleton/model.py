@@ -0,0 +1,146 @@
+import pickle
+
+
+class Model:
+    def __init__(self, _log_callback) -> None:
+        self._log_callback = _log_callback # Removing this line will break logging
+
+        self.reference_to_attribute = {
+            # {{references}}
+        }
+
+        self.fmi2Reset()
+
+    # ================= FMI2 =================
+
+    def fmi2DoStep(
+        self, current_time, step_size, no_set_fmu_state_prior_to_current_point
+    ):
+        # Advance the model from current_time to current_time + step_size
+        # here, updating the outputs from the inputs.
+        return Fmi2Status.ok
+
+    def fmi2CancelStep(self):
+        return Fmi2Status.ok
+
+    def fmi2SetDebugLogging(self, categories, logging_on):
+        return Fmi2Status.ok
+
+    def fmi2EnterInitializationMode(self):
+        return Fmi2Status.ok
+
+    def fmi2ExitInitializationMode(self):
+        return Fmi2Status.ok
+
+    def fmi2SetupExperiment(self, start_time, stop_time, tolerance):
+        return Fmi2Status.ok
+
+    def fmi2Terminate(self):
+        return Fmi2Status.ok
+
+    def fmi2Reset(self):
+        # {{start_values}}
+
+        return Fmi2Status.ok
+
+    def fmi2SerializeFmuState(self):
+        bytes = pickle.dumps(
+            {
+                attribute: getattr(self, attribute)
+                for attribute in self.reference_to_attribute.values()
+            }
+        )
+        return Fmi2Status.ok, bytes
+
+    def fmi2DeserializeFmuState(self, bytes):
+        for attribute, value in pickle.loads(bytes).items():
+            setattr(self, attribute, value)
+
+        return Fmi2Status.ok
+
+    def fmi2GetReal(self, references):
+        return self._get_value(references)
+
+    def fmi2GetInteger(self, references):
+        return self._get_value(references)
+
+    def fmi2GetBoolean(self, references):
+        return self._get_value(references)
+
+    def fmi2GetString(self, references):
+        return self._get_value(references)
+
+    def fmi2SetReal(self, references, values):
+        return self._set_value(references, values)
+
+    def fmi2SetInteger(self, references, values):
+        return self._set_value(references, values)
+
+    def fmi2SetBoolean(self, references, values):
+        return self._set_value(references, values)
+
+    def fmi2SetString(self, references, values):
+        return self._set_value(references, values)
+
+    def fmi2SetRealInputDerivatives(self, references, orders, values):
+        self.log(
+            "The model can't interpolate inputs.",
+            Fmi2Status.error,
+            "logStatusError"
+        )
+        return Fmi2Status.error
+
+    def fmi2GetRealOutputDerivatives(self, references, orders):
+        self.log(
+            "The model can't compute output derivatives.",
+            Fmi2Status.error,
+            "logStatusError"
+        )
+        return Fmi2Status.error, []
+
+    # ================= Logging =================
+
+    def log(self, message, status, category = "logAll"):
+        # Removing the line below will break logging.
+        self._log_callback(status, category, message)
+
+    # ================= Helpers =================
+
+    def _set_value(self, references, values):
+        for r, v in zip(references, values):
+            setattr(self, self.reference_to_attribute[r], v)
+
+        return Fmi2Status.ok
+
+    def _get_value(self, references):
+        values = []
+
+        for r in references:
+            values.append(getattr(self, self.reference_to_attribute[r]))
+
+        return Fmi2Status.ok, values
+
+
+class Fmi2Status:
+    """
+    Represents the status of an FMI2 FMU or the results of function calls.
+
+    Values:
+        * ok: all well
+        * warning: an issue has arisen, but the computation can continue.
+        * discard: an operation has resulted in invalid output, which must be discarded
+        * error: an error has ocurred for this specific FMU instance.
+        * fatal: an fatal error has ocurred which has corrupted ALL FMU instances.
+        * pending: indicates that the FMu is doing work asynchronously, which can be retrived later.
+
+    Notes:
+        FMI section 2.1.3
+
+    """
+
+    ok = 0
+    warning = 1
+    discard = 2
+    error = 3
+    fatal = 4
+    pending = 5
@@ -1,12 +1,19 @@
 import logging
+import mmap
+import os
 import zmq
 from abc import ABC, abstractmethod
+from array import array
 
 from schemas.fmi3_messages_pb2 import (
     Fmi3Command,
     Fmi3Return,
     Fmi3LogReturn,
     Fmi3StatusReturn,
+    Fmi3ClockUpdateReturn,
+    Fmi3LockPreemptionReturn,
+    Fmi3UnlockPreemptionReturn,
+    Fmi3IntermediateUpdateReturn,
 )
 from schemas.unifmu_handshake_pb2 import (
     HandshakeStatus,
@@ -20,6 +27,7 @@ class AbstractBackend(ABC):
     def __init__(self):
         self.context = zmq.Context()
         self.socket = self.context.socket(zmq.REQ)
+        self.shared_memory = None
     
     def connect_to_endpoint(self, endpoint):
         self.socket.connect(endpoint)
@@ -57,6 +65,53 @@ class AbstractBackend(ABC):
             )
         )
 
+        self._await_callback_continue()
+
+    def clock_update_callback(self):
+        self.send_reply(Fmi3Return(clock_update=Fmi3ClockUpdateReturn()))
+
+        self._await_callback_continue()
+
+    def lock_preemption_callback(self):
+        self.send_reply(Fmi3Return(lock_preemption=Fmi3LockPreemptionReturn()))
+
+        self._await_callback_continue()
+
+    def unlock_preemption_callback(self):
+        self.send_reply(Fmi3Return(unlock_preemption=Fmi3UnlockPreemptionReturn()))
+
+        self._await_callback_continue()
+
+    def intermediate_update_callback(
+            self,
+            intermediate_update_time,
+            intermediate_variable_set_requested,
+            intermediate_variable_get_allowed,
+            intermediate_step_finished,
+            can_return_early
+    ):
+        self.send_reply(
+            Fmi3Return(
+                intermediate_update=Fmi3IntermediateUpdateReturn(
+                    intermediate_update_time=intermediate_update_time,
+                    intermediate_variable_set_requested=intermediate_variable_set_requested,
+                    intermediate_variable_get_allowed=intermediate_variable_get_allowed,
+                    intermediate_step_finished=intermediate_step_finished,
+                    can_return_early=can_return_early
+                )
+            )
+        )
+
+        (command_group, data) = self.recv_command()
+
+        match command_group:
+            case "Fmi3IntermediateUpdateContinue":
+                return data.early_return_requested, data.early_return_time
+            case _:
+                self.unknown_command(command_group)
+                return False, 0.0
+
+    def _await_callback_continue(self):
         (command_group, _) = self.recv_command()
 
         match command_group:
@@ -66,7 +121,42 @@ class AbstractBackend(ABC):
                 self.unknown_command(command_group)
 
     def handshake(self):
-        self.send_reply(HandshakeReply(status=HandshakeStatus.OK))
+        self.shared_memory = self._map_shared_memory()
+        self.send_reply(
+            HandshakeReply(
+                status=HandshakeStatus.OK,
+                shared_memory=self.shared_memory is not None
+            )
+        )
+
+    def _map_shared_memory(self):
+        """Maps the shared memory region offered by the FMU, if any.
+
+        The region holds the value references of a bulk exchange as uint32
+        from its start, followed by the values as doubles from the next
+        multiple of 8 bytes.
+        """
+        path = os.environ.get("UNIFMU_SHARED_MEMORY")
+        if path is None:
+            return None
+
+        try:
+            with open(path, "r+b") as file:
+                return memoryview(mmap.mmap(file.fileno(), 0))
+        except OSError as error:
+            logger.warning(f"couldn't map the shared memory region, exchanging values through the socket: {error}")
+            return None
+
+    def shared_value_references(self, n_value_references):
+        return self.shared_memory[:4 * n_value_references].cast("I").tolist()
+
+    def shared_values(self, n_value_references, n_values):
+        offset = _values_offset(n_value_references)
+        return self.shared_memory[offset:offset + 8 * n_values].cast("d").tolist()
+
+    def place_shared_values(self, n_value_references, values):
+        offset = _values_offset(n_value_references)
+        self.shared_memory[offset:offset + 8 * len(values)] = array("d", values).tobytes()
 
     @abstractmethod
     def command_reply_loop(self):
@@ -74,4 +164,7 @@ class AbstractBackend(ABC):
     
     @abstractmethod
     def unknown_command(self, command_group):
-        pass
\ No newline at end of file
+        pass
+
+def _values_offset(n_value_references):
+    return (4 * n_value_references + 7) // 8 * 8
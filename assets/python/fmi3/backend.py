@@ -9,6 +9,7 @@ from schemas.fmi3_messages_pb2 import (
     Fmi3SerializeFmuStateReturn,
     Fmi3GetFloat32Return,
     Fmi3GetFloat64Return,
+    Fmi3GetFloat64SharedReturn,
     Fmi3GetInt8Return,
     Fmi3GetUInt8Return,
     Fmi3GetInt16Return,
@@ -26,6 +27,16 @@ from schemas.fmi3_messages_pb2 import (
     Fmi3GetIntervalFractionReturn,
     Fmi3GetShiftDecimalReturn,
     Fmi3GetShiftFractionReturn,
+    Fmi3GetDirectionalDerivativeReturn,
+    Fmi3GetAdjointDerivativeReturn,
+    Fmi3GetOutputDerivativesReturn,
+    Fmi3CompletedIntegratorStepReturn,
+    Fmi3GetContinuousStateDerivativesReturn,
+    Fmi3GetEventIndicatorsReturn,
+    Fmi3GetContinuousStatesReturn,
+    Fmi3GetNominalsOfContinuousStatesReturn,
+    Fmi3GetNumberOfEventIndicatorsReturn,
+    Fmi3GetNumberOfContinuousStatesReturn,
 )
 
 from abstract_backend import AbstractBackend
@@ -50,6 +61,14 @@ class Backend(AbstractBackend):
                     )
 
                 case "Fmi3InstantiateModelExchange":
+                    model = Model(
+                        data.instance_name,
+                        data.instantiation_token,
+                        data.resource_path,
+                        data.visible,
+                        data.logging_on,
+                        _log_callback=self.log_callback
+                    )
                     self.send_reply(Fmi3Return(empty=Fmi3EmptyReturn()))
 
                 case "Fmi3InstantiateCoSimulation":
@@ -62,13 +81,36 @@ class Backend(AbstractBackend):
                         data.event_mode_used,
                         data.early_return_allowed,
                         data.required_intermediate_variables,
-                        _log_callback=self.log_callback
+                        _log_callback=self.log_callback,
+                        _intermediate_update_callback=self.intermediate_update_callback
                     )
                     self.send_reply(Fmi3Return(empty=Fmi3EmptyReturn()))
 
                 case "Fmi3InstantiateScheduledExecution":
+                    model = Model(
+                        data.instance_name,
+                        data.instantiation_token,
+                        data.resource_path,
+                        data.visible,
+                        data.logging_on,
+                        False,
+                        False,
+                        [],
+                        _log_callback=self.log_callback,
+                        _clock_update_callback=self.clock_update_callback,
+                        _lock_preemption_callback=self.lock_preemption_callback,
+                        _unlock_preemption_callback=self.unlock_preemption_callback
+                    )
                     self.send_reply(Fmi3Return(empty=Fmi3EmptyReturn()))
 
+                case "Fmi3ActivateModelPartition":
+                    self.status_reply(
+                        model.fmi3ActivateModelPartition(
+                            data.clock_reference,
+                            data.activation_time
+                        )
+                    )
+
                 case "Fmi3EnterStepMode":
                     self.status_reply(model.fmi3EnterStepMode())
 
@@ -175,6 +217,21 @@ class Backend(AbstractBackend):
                         )
                     )
 
+                case "Fmi3GetFloat64Shared":
+                    status, values = model.fmi3GetFloat64(
+                        self.shared_value_references(data.n_value_references)
+                    )
+                    if len(values) == data.n_values:
+                        self.place_shared_values(data.n_value_references, values)
+                    self.send_reply(
+                        Fmi3Return(
+                            get_float_64_shared=Fmi3GetFloat64SharedReturn(
+                                status=status,
+                                n_values=len(values)
+                            )
+                        )
+                    )
+
                 case "Fmi3GetInt8":
                     status, values = model.fmi3GetInt8(
                         data.value_references
@@ -391,6 +448,50 @@ class Backend(AbstractBackend):
                         )
                     )
                     
+                case "Fmi3GetDirectionalDerivative":
+                    status, values = model.fmi3GetDirectionalDerivative(
+                        data.unknowns,
+                        data.knowns,
+                        data.seed
+                    )
+                    self.send_reply(
+                        Fmi3Return(
+                            get_directional_derivative=Fmi3GetDirectionalDerivativeReturn(
+                                status=status,
+                                values=values
+                            )
+                        )
+                    )
+
+                case "Fmi3GetAdjointDerivative":
+                    status, values = model.fmi3GetAdjointDerivative(
+                        data.unknowns,
+                        data.knowns,
+                        data.seed
+                    )
+                    self.send_reply(
+                        Fmi3Return(
+                            get_adjoint_derivative=Fmi3GetAdjointDerivativeReturn(
+                                status=status,
+                                values=values
+                            )
+                        )
+                    )
+
+                case "Fmi3GetOutputDerivatives":
+                    status, values = model.fmi3GetOutputDerivatives(
+                        data.value_references,
+                        data.orders
+                    )
+                    self.send_reply(
+                        Fmi3Return(
+                            get_output_derivatives=Fmi3GetOutputDerivativesReturn(
+                                status=status,
+                                values=values
+                            )
+                        )
+                    )
+
                 case "Fmi3SetFloat32":
                     self.status_reply(
                         model.fmi3SetFloat32(
@@ -407,6 +508,14 @@ class Backend(AbstractBackend):
                         )
                     )
 
+                case "Fmi3SetFloat64Shared":
+                    self.status_reply(
+                        model.fmi3SetFloat64(
+                            self.shared_value_references(data.n_value_references),
+                            self.shared_values(data.n_value_references, data.n_values)
+                        )
+                    )
+
                 case "Fmi3SetInt8":
                     self.status_reply(
                         model.fmi3SetInt8(
@@ -537,6 +646,9 @@ class Backend(AbstractBackend):
                         )
                     )
 
+                case "Fmi3EvaluateDiscreteStates":
+                    self.status_reply(model.fmi3EvaluateDiscreteStates())
+
                 case "Fmi3UpdateDiscreteStates":
                     (
                         status,
@@ -561,6 +673,103 @@ class Backend(AbstractBackend):
                         )
                     )
 
+                case "Fmi3EnterContinuousTimeMode":
+                    self.status_reply(model.fmi3EnterContinuousTimeMode())
+
+                case "Fmi3CompletedIntegratorStep":
+                    (
+                        status,
+                        enter_event_mode,
+                        terminate_simulation
+                    ) = model.fmi3CompletedIntegratorStep(
+                        data.no_set_fmu_state_prior_to_current_point
+                    )
+                    self.send_reply(
+                        Fmi3Return(
+                            completed_integrator_step=Fmi3CompletedIntegratorStepReturn(
+                                status=status,
+                                enter_event_mode=enter_event_mode,
+                                terminate_simulation=terminate_simulation
+                            )
+                        )
+                    )
+
+                case "Fmi3SetTime":
+                    self.status_reply(model.fmi3SetTime(data.time))
+
+                case "Fmi3SetContinuousStates":
+                    self.status_reply(
+                        model.fmi3SetContinuousStates(
+                            list(data.continuous_states)
+                        )
+                    )
+
+                case "Fmi3GetContinuousStateDerivatives":
+                    status, derivatives = model.fmi3GetContinuousStateDerivatives()
+                    self.send_reply(
+                        Fmi3Return(
+                            get_continuous_state_derivatives=Fmi3GetContinuousStateDerivativesReturn(
+                                status=status,
+                                derivatives=derivatives
+                            )
+                        )
+                    )
+
+                case "Fmi3GetEventIndicators":
+                    status, event_indicators = model.fmi3GetEventIndicators()
+                    self.send_reply(
+                        Fmi3Return(
+                            get_event_indicators=Fmi3GetEventIndicatorsReturn(
+                                status=status,
+                                event_indicators=event_indicators
+                            )
+                        )
+                    )
+
+                case "Fmi3GetContinuousStates":
+                    status, continuous_states = model.fmi3GetContinuousStates()
+                    self.send_reply(
+                        Fmi3Return(
+                            get_continuous_states=Fmi3GetContinuousStatesReturn(
+                                status=status,
+                                continuous_states=continuous_states
+                            )
+                        )
+                    )
+
+                case "Fmi3GetNominalsOfContinuousStates":
+                    status, nominals = model.fmi3GetNominalsOfContinuousStates()
+                    self.send_reply(
+                        Fmi3Return(
+                            get_nominals_of_continuous_states=Fmi3GetNominalsOfContinuousStatesReturn(
+                                status=status,
+                                nominals=nominals
+                            )
+                        )
+                    )
+
+                case "Fmi3GetNumberOfEventIndicators":
+                    status, n_event_indicators = model.fmi3GetNumberOfEventIndicators()
+                    self.send_reply(
+                        Fmi3Return(
+                            get_number_of_event_indicators=Fmi3GetNumberOfEventIndicatorsReturn(
+                                status=status,
+                                n_event_indicators=n_event_indicators
+                            )
+                        )
+                    )
+
+                case "Fmi3GetNumberOfContinuousStates":
+                    status, n_continuous_states = model.fmi3GetNumberOfContinuousStates()
+                    self.send_reply(
+                        Fmi3Return(
+                            get_number_of_continuous_states=Fmi3GetNumberOfContinuousStatesReturn(
+                                status=status,
+                                n_continuous_states=n_continuous_states
+                            )
+                        )
+                    )
+
                 case _:
                     self.unknown_command(group)
     
@@ -485,6 +485,10 @@ class BlackboxBackend(AbstractBackend):
                     )
                     self.status_reply(0)
 
+                case "Fmi3EvaluateDiscreteStates":
+                    self.fmu.evaluateDiscreteStates()
+                    self.status_reply(0)
+
                 case "Fmi3UpdateDiscreteStates":
                     (
                         discrete_states_need_update,
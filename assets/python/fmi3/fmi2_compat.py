@@ -0,0 +1,243 @@
+import pickle
+
+from model import Model as Fmi2Model
+
+
+class Model:
+    """ Compatibility shim placed between the FMI3 backend and the model of an
+    FMU converted from FMI2 by `unifmu convert`.
+
+    The FMI3 calls of the backend are translated to the FMI2 methods of the
+    model, with the FMI3 types mapped to the FMI2 types as in the converted
+    modelDescription.xml: Float64 to Real, Int32 to Integer, and Boolean and
+    String as they are. The independent variable `time` isn't known to the
+    model, so it is kept here. Calls that have no FMI2 counterpart are
+    answered with an error.
+    """
+
+    def __init__(
+            self,
+            instance_name,
+            instantiation_token,
+            resource_path,
+            visible,
+            logging_on,
+            event_mode_used,
+            early_return_allowed,
+            required_intermediate_variables,
+            _log_callback,
+            **_callbacks
+    ) -> None:
+        self._log_callback = _log_callback
+        self.time_value_reference = {{time_value_reference}}
+        self.time = 0.0
+
+        self.model = Fmi2Model(
+            lambda status, category, message: _log_callback(
+                _to_fmi3_status(status), category, message
+            )
+        )
+
+    # ================= FMI3 =================
+
+    def fmi3DoStep(
+            self,
+            current_communication_point,
+            communication_step_size,
+            no_set_fmu_state_prior_to_current_point
+    ):
+        status = _to_fmi3_status(
+            self.model.fmi2DoStep(
+                current_communication_point,
+                communication_step_size,
+                no_set_fmu_state_prior_to_current_point
+            )
+        )
+
+        if status in (Fmi3Status.ok, Fmi3Status.warning):
+            self.time = current_communication_point + communication_step_size
+
+        return (status, False, False, False, self.time)
+
+    def fmi3EnterInitializationMode(
+            self,
+            tolerance_defined,
+            tolerance,
+            start_time,
+            stop_time_defined,
+            stop_time
+    ):
+        self.time = start_time
+
+        status = _to_fmi3_status(
+            self.model.fmi2SetupExperiment(start_time, stop_time, tolerance)
+        )
+        if status > Fmi3Status.warning:
+            return status
+
+        return max(
+            status,
+            _to_fmi3_status(self.model.fmi2EnterInitializationMode())
+        )
+
+    def fmi3ExitInitializationMode(self):
+        return _to_fmi3_status(self.model.fmi2ExitInitializationMode())
+
+    def fmi3Terminate(self):
+        return _to_fmi3_status(self.model.fmi2Terminate())
+
+    def fmi3Reset(self):
+        self.time = 0.0
+        return _to_fmi3_status(self.model.fmi2Reset())
+
+    def fmi3SetDebugLogging(self, categories, logging_on):
+        return _to_fmi3_status(
+            self.model.fmi2SetDebugLogging(categories, logging_on)
+        )
+
+    def fmi3SerializeFmuState(self):
+        status, state = self.model.fmi2SerializeFmuState()
+        return _to_fmi3_status(status), pickle.dumps((self.time, state))
+
+    def fmi3DeserializeFmuState(self, bytes):
+        time, state = pickle.loads(bytes)
+        status = _to_fmi3_status(self.model.fmi2DeserializeFmuState(state))
+
+        if status <= Fmi3Status.warning:
+            self.time = time
+
+        return status
+
+    def fmi3GetFloat64(self, value_references):
+        references = [
+            r for r in value_references if r != self.time_value_reference
+        ]
+
+        if not references:
+            return Fmi3Status.ok, [self.time for _ in value_references]
+
+        status, values = self.model.fmi2GetReal(references)
+        status = _to_fmi3_status(status)
+        if status > Fmi3Status.warning:
+            return status, []
+
+        values = iter(values)
+        return status, [
+            self.time if r == self.time_value_reference else next(values)
+            for r in value_references
+        ]
+
+    def fmi3GetInt32(self, value_references):
+        status, values = self.model.fmi2GetInteger(value_references)
+        return _to_fmi3_status(status), values
+
+    def fmi3GetBoolean(self, value_references):
+        status, values = self.model.fmi2GetBoolean(value_references)
+        return _to_fmi3_status(status), values
+
+    def fmi3GetString(self, value_references):
+        status, values = self.model.fmi2GetString(value_references)
+        return _to_fmi3_status(status), values
+
+    def fmi3SetFloat64(self, value_references, values):
+        pairs = [
+            (r, v) for r, v in zip(value_references, values)
+            if r != self.time_value_reference
+        ]
+
+        if not pairs:
+            return Fmi3Status.ok
+
+        return _to_fmi3_status(
+            self.model.fmi2SetReal(
+                [r for r, _ in pairs],
+                [v for _, v in pairs]
+            )
+        )
+
+    def fmi3SetInt32(self, value_references, values):
+        return _to_fmi3_status(
+            self.model.fmi2SetInteger(value_references, values)
+        )
+
+    def fmi3SetBoolean(self, value_references, values):
+        return _to_fmi3_status(
+            self.model.fmi2SetBoolean(value_references, values)
+        )
+
+    def fmi3SetString(self, value_references, values):
+        return _to_fmi3_status(
+            self.model.fmi2SetString(value_references, values)
+        )
+
+    def fmi3GetOutputDerivatives(self, value_references, orders):
+        if not hasattr(self.model, "fmi2GetRealOutputDerivatives"):
+            return self._unsupported("fmi3GetOutputDerivatives")
+
+        status, values = self.model.fmi2GetRealOutputDerivatives(
+            value_references, orders
+        )
+        return _to_fmi3_status(status), values
+
+    # ================= Unsupported =================
+
+    def __getattr__(self, name):
+        # Only called for the FMI3 functions that aren't defined above.
+        if not name.startswith("fmi3"):
+            raise AttributeError(name)
+
+        return lambda *args: self._unsupported(name)
+
+    def _unsupported(self, name):
+        self._log_callback(
+            Fmi3Status.error,
+            "logStatusError",
+            f"{name} isn't supported by a model converted from FMI2."
+        )
+
+        if name in _UNSUPPORTED_RESULTS:
+            return (Fmi3Status.error, *_UNSUPPORTED_RESULTS[name])
+        if name.startswith("fmi3Get"):
+            return Fmi3Status.error, []
+
+        return Fmi3Status.error
+
+
+# Results other than the status of the unsupported functions that return more
+# than the status and a list of values.
+_UNSUPPORTED_RESULTS = {
+    "fmi3GetIntervalDecimal": ([], []),
+    "fmi3GetIntervalFraction": ([], [], []),
+    "fmi3GetShiftFraction": ([], []),
+    "fmi3GetNumberOfEventIndicators": (0,),
+    "fmi3GetNumberOfContinuousStates": (0,),
+    "fmi3UpdateDiscreteStates": (False, False, False, False, False, 0.0),
+    "fmi3CompletedIntegratorStep": (False, False),
+}
+
+
+def _to_fmi3_status(status):
+    # FMI3 dropped the pending status along with asynchronous steps.
+    if status > Fmi3Status.fatal:
+        return Fmi3Status.error
+
+    return status
+
+
+class Fmi3Status:
+    """
+    Represents the status of an FMI3 FMU or the results of function calls.
+
+    Values:
+        * ok: all well
+        * warning: an issue has arisen, but the computation can continue.
+        * discard: an operation has resulted in invalid output, which must be discarded
+        * error: an error has ocurred for this specific FMU instance.
+        * fatal: an fatal error has ocurred which has corrupted ALL FMU instances.
+    """
+
+    ok = 0
+    warning = 1
+    discard = 2
+    error = 3
+    fatal = 4
@@ -13,7 +13,11 @@ class Model:
             event_mode_used,
             early_return_allowed,
             required_intermediate_variables,
-            _log_callback
+            _log_callback,
+            _clock_update_callback = None,
+            _lock_preemption_callback = None,
+            _unlock_preemption_callback = None,
+            _intermediate_update_callback = None
     ) -> None:
         self.instance_name = instance_name
         self.instantiation_token = instantiation_token
@@ -24,6 +28,12 @@ class Model:
         self.early_return_allowed = early_return_allowed
         self.required_intermediate_variables = required_intermediate_variables
         self._log_callback = _log_callback
+        # Only given when the FMU is instantiated for Scheduled Execution
+        self._clock_update_callback = _clock_update_callback
+        self._lock_preemption_callback = _lock_preemption_callback
+        self._unlock_preemption_callback = _unlock_preemption_callback
+        # Only given when the FMU is instantiated for Co-Simulation
+        self._intermediate_update_callback = _intermediate_update_callback
         self.state = FMIState.FMIInstantiatedState
         self.float32_a = 0.0
         self.float32_b = 0.0
@@ -95,6 +105,9 @@ class Model:
         self.clocked_variable_a = 0
         self.clocked_variable_b = 0
         self.clocked_variable_c = 0
+        # The value of clocked_variable_c when Event Mode was last entered,
+        # which event iteration evaluates the clocked variable from.
+        self.event_entry_clocked_variable_c = 0
         self.clock_reference_to_interval = {
             1001: 1.0,
         }
@@ -193,6 +206,14 @@ class Model:
                                **self.parameters,
                                **self.tunable_parameters}
 
+        # The partial derivatives of the continuous outputs with respect to
+        # the continuous inputs, used by fmi3GetDirectionalDerivative and
+        # fmi3GetAdjointDerivative. Derivatives not listed are zero.
+        self.partial_derivatives = {
+            2: {0: 1.0, 1: 1.0},
+            5: {3: 1.0, 4: 1.0},
+        }
+
         self._update_outputs()
         self._update_clocks()
         self._update_clocked_outputs()
@@ -206,6 +227,14 @@ class Model:
             communication_step_size: float,
             no_set_fmu_state_prior_to_current_point: bool,
     ):
+        if self.state != FMIState.FMIStepModeState:
+            self.log(
+                "fmi3DoStep called outside of Step Mode.",
+                Fmi3Status.error,
+                "logStatusError"
+            )
+            return (Fmi3Status.error, False, False, False, current_communication_point)
+
         self._update_outputs()
 
         event_handling_needed = False
@@ -213,6 +242,20 @@ class Model:
         early_return = False
         last_successful_time = current_communication_point + communication_step_size
 
+        if self.early_return_allowed:
+            # Informs the importer of the midpoint of the step, letting it
+            # request that the step returns early.
+            early_return_requested, early_return_time = self.intermediate_update(
+                current_communication_point + communication_step_size / 2,
+                can_return_early = True
+            )
+            if early_return_requested:
+                early_return = True
+                last_successful_time = min(
+                    max(early_return_time, current_communication_point),
+                    last_successful_time
+                )
+
         return (
             Fmi3Status.ok,
             event_handling_needed,
@@ -221,21 +264,69 @@ class Model:
             last_successful_time,
         )
     
+    def fmi3EvaluateDiscreteStates(self):
+        if self.state != FMIState.FMIEventModeState:
+            self.log(
+                "fmi3EvaluateDiscreteStates called outside of Event Mode.",
+                Fmi3Status.error,
+                "logStatusError"
+            )
+            return Fmi3Status.error
+
+        self._evaluate_discrete_states()
+
+        return Fmi3Status.ok
+
     def fmi3UpdateDiscreteStates(self):
+        if self.state != FMIState.FMIEventModeState:
+            self.log(
+                "fmi3UpdateDiscreteStates called outside of Event Mode.",
+                Fmi3Status.error,
+                "logStatusError"
+            )
+            return (Fmi3Status.error, False, False, False, False, False, 0.0)
+
+        previous_clocked_variable_c = self.clocked_variable_c
+        self._evaluate_discrete_states()
+
         status = Fmi3Status.ok
-        discrete_states_need_update = False
+        # The event iteration has converged once an iteration leaves the
+        # discrete states unchanged.
+        discrete_states_need_update = self.clocked_variable_c != previous_clocked_variable_c
         terminate_simulation = False
         nominals_continuous_states_changed = False
         values_continuous_states_changed = False
         next_event_time_defined = True
         next_event_time = 1.0
 
-
-        self._update_clocked_outputs()
-
         return (status, discrete_states_need_update, terminate_simulation, nominals_continuous_states_changed,
                 values_continuous_states_changed, next_event_time_defined, next_event_time)
 
+    # ================= Scheduled Execution =================
+
+    def fmi3ActivateModelPartition(self, clock_reference, activation_time):
+        if clock_reference not in (1001, 1002):
+            self.log(
+                f"Clock #{clock_reference}# isn't an input clock.",
+                Fmi3Status.error,
+                "logStatusError"
+            )
+            return Fmi3Status.error
+
+        setattr(self, self.clocked_variables[clock_reference], True)
+        self._update_clocks()
+
+        if self.clock_c:
+            # Updating the clocked outputs mustn't be preempted by the
+            # activation of other model partitions.
+            self.lock_preemption()
+            self._update_clocked_outputs()
+            self.unlock_preemption()
+            # Informs the importer that the output clock ticked
+            self.clock_update()
+
+        return Fmi3Status.ok
+
     # ================= Initialization, Enter, Termination, and Reset =================
 
     def fmi3EnterInitializationMode(
@@ -250,15 +341,34 @@ class Model:
         return Fmi3Status.ok
 
     def fmi3ExitInitializationMode(self):
-        self.state = FMIState.FMIEventModeState if self.event_mode_used else FMIState.FMIStepModeState
         self._update_outputs()
+        if self.event_mode_used:
+            self._enter_event_mode()
+        else:
+            self.state = FMIState.FMIStepModeState
         return Fmi3Status.ok
 
     def fmi3EnterEventMode(self):
-        self.state = FMIState.FMIEventModeState
+        if self.state != FMIState.FMIStepModeState:
+            self.log(
+                "fmi3EnterEventMode called outside of Step Mode.",
+                Fmi3Status.error,
+                "logStatusError"
+            )
+            return Fmi3Status.error
+
+        self._enter_event_mode()
         return Fmi3Status.ok
 
     def fmi3EnterStepMode(self):
+        if self.state != FMIState.FMIEventModeState:
+            self.log(
+                "fmi3EnterStepMode called outside of Event Mode.",
+                Fmi3Status.error,
+                "logStatusError"
+            )
+            return Fmi3Status.error
+
         self.state = FMIState.FMIStepModeState
         return Fmi3Status.ok
     
@@ -354,6 +464,9 @@ class Model:
         self.clocked_variable_a = 0
         self.clocked_variable_b = 0
         self.clocked_variable_c = 0
+        # The value of clocked_variable_c when Event Mode was last entered,
+        # which event iteration evaluates the clocked variable from.
+        self.event_entry_clocked_variable_c = 0
         self.clock_reference_to_interval = {
             1001: 1.0,
         }
@@ -699,6 +812,46 @@ class Model:
             self.clock_reference_to_shift[r] = float(counters[idx])/float(resolutions[idx])
         return Fmi3Status.ok
 
+    # ================= Derivatives =================
+
+    def fmi3GetDirectionalDerivative(self, unknowns, knowns, seed):
+        sensitivity = [
+            sum(
+                self._partial_derivative(unknown, known) * seed_value
+                for known, seed_value in zip(knowns, seed)
+            )
+            for unknown in unknowns
+        ]
+        return Fmi3Status.ok, sensitivity
+
+    def fmi3GetAdjointDerivative(self, unknowns, knowns, seed):
+        sensitivity = [
+            sum(
+                seed_value * self._partial_derivative(unknown, known)
+                for unknown, seed_value in zip(unknowns, seed)
+            )
+            for known in knowns
+        ]
+        return Fmi3Status.ok, sensitivity
+
+    def fmi3GetOutputDerivatives(self, value_references, orders):
+        values = []
+        for r in value_references:
+            if r not in self.partial_derivatives:
+                self.log(
+                    f"Variable #{r}# isn't a continuous output.",
+                    Fmi3Status.error,
+                    "logStatusError"
+                )
+                return Fmi3Status.error, []
+
+            # The continuous outputs only depend on the inputs, which are
+            # constant between communication points, so all of their
+            # derivatives with respect to time are zero.
+            values.append(0.0)
+
+        return Fmi3Status.ok, values
+
     # ================= Logging =================
 
     def fmi3SetDebugLogging(self, categories, logging_on):
@@ -739,8 +892,82 @@ class Model:
         # Removing the line below will break logging.
         self._log_callback(status, category, message)
 
+    # ================= Co-Simulation Callbacks =================
+
+    """ UniFMU intermediate update function
+
+    Call this function during fmi3DoStep() to inform the importer that the FMU
+    has reached an intermediate point of the step. The importer may use the
+    call to request that fmi3DoStep() returns early.
+
+    Returns a tuple (early_return_requested, early_return_time). If
+    early_return_requested is True, the FMU should return from fmi3DoStep()
+    at the latest at early_return_time, setting early_return to True.
+    Returns (False, 0.0) if the FMU wasn't instantiated for Co-Simulation.
+
+    Keyword arguments:
+    intermediate_update_time            -- The internal time of the FMU.
+    intermediate_variable_set_requested -- Whether the importer may set
+                                           intermediate input variables.
+    intermediate_variable_get_allowed   -- Whether the importer may get
+                                           intermediate output variables.
+    intermediate_step_finished          -- Whether an internal step of the FMU
+                                           has been completed.
+    can_return_early                    -- Whether the FMU can return early
+                                           from the current step. Should only
+                                           be True if early_return_allowed.
+    """
+    def intermediate_update(
+            self,
+            intermediate_update_time,
+            intermediate_variable_set_requested = False,
+            intermediate_variable_get_allowed = False,
+            intermediate_step_finished = True,
+            can_return_early = False
+    ):
+        if self._intermediate_update_callback is None:
+            return False, 0.0
+
+        return self._intermediate_update_callback(
+            intermediate_update_time,
+            intermediate_variable_set_requested,
+            intermediate_variable_get_allowed,
+            intermediate_step_finished,
+            can_return_early
+        )
+
+    # ================= Scheduled Execution Callbacks =================
+
+    """ UniFMU clock update function
+
+    Call this function during Scheduled Execution to inform the importer that
+    an output clock has ticked or that a countdown clock has been set. Does
+    nothing if the FMU wasn't instantiated for Scheduled Execution.
+    """
+    def clock_update(self):
+        if self._clock_update_callback is not None:
+            self._clock_update_callback()
+
+    """ UniFMU preemption lock functions
+
+    Call lock_preemption() before, and unlock_preemption() after, a critical
+    section that must not be preempted by the activation of other model
+    partitions during Scheduled Execution. Does nothing if the FMU wasn't
+    instantiated for Scheduled Execution.
+    """
+    def lock_preemption(self):
+        if self._lock_preemption_callback is not None:
+            self._lock_preemption_callback()
+
+    def unlock_preemption(self):
+        if self._unlock_preemption_callback is not None:
+            self._unlock_preemption_callback()
+
     # ================= Helpers =================
 
+    def _partial_derivative(self, unknown, known):
+        return self.partial_derivatives.get(unknown, {}).get(known, 0.0)
+
     def _set_value(self, references, values):
         status = Fmi3Status.ok
         for r in references:
@@ -834,6 +1061,20 @@ class Model:
     def _update_clocked_outputs(self):
         self.clocked_variable_c += self.clocked_variable_a + self.clocked_variable_b
 
+    def _enter_event_mode(self):
+        self.state = FMIState.FMIEventModeState
+        self.event_entry_clocked_variable_c = self.clocked_variable_c
+
+    def _evaluate_discrete_states(self):
+        # Evaluated from the value at Event Mode entry, so that the clocked
+        # variable only advances once per event no matter the number of
+        # event iterations.
+        self.clocked_variable_c = (
+            self.event_entry_clocked_variable_c
+            + self.clocked_variable_a
+            + self.clocked_variable_b
+        )
+
 """ Pops values from the "values" attribute and returns them formatted
 as the format of "current_value"
 
@@ -0,0 +1,353 @@
+import pickle
+from enum import IntFlag
+
+class Model:
+    def __init__(
+            self,
+            instance_name,
+            instantiation_token,
+            resource_path,
+            visible,
+            logging_on,
+            _log_callback
+    ) -> None:
+        self.instance_name = instance_name
+        self.instantiation_token = instantiation_token
+        self.resource_path = resource_path
+        self.visible = visible
+        self.logging_on = logging_on
+        self._log_callback = _log_callback
+        self.state = FMIState.FMIInstantiatedState
+        self.time = 0.0
+        self.h = 1.0
+        self.v = 0.0
+        self.g = -9.81
+        self.e = 0.7
+
+        self.reference_to_attribute = {
+            0: "time",
+            1: "h",
+            2: "der_h",
+            3: "v",
+            4: "der_v",
+            5: "g",
+            6: "e",
+        }
+
+        self.parameters = {
+            5: "g",
+        }
+
+        self.tunable_parameters = {
+            6: "e",
+        }
+
+        # The continuous states of the model, in the order that they are
+        # exchanged with the importer.
+        self.continuous_states = ["h", "v"]
+
+        # The partial derivatives of the state derivatives with respect to
+        # the states, used by fmi3GetDirectionalDerivative and
+        # fmi3GetAdjointDerivative. Derivatives not listed are zero.
+        self.partial_derivatives = {
+            2: {3: 1.0},
+        }
+
+    # ================= FMI3 =================
+
+    # ================= Model Exchange =================
+
+    @property
+    def der_h(self):
+        return self.v
+
+    @property
+    def der_v(self):
+        return self.g
+
+    def fmi3EnterContinuousTimeMode(self):
+        self.state = FMIState.FMIContinuousTimeModeState
+        return Fmi3Status.ok
+
+    def fmi3CompletedIntegratorStep(
+            self,
+            no_set_fmu_state_prior_to_current_point: bool
+    ):
+        enter_event_mode = False
+        terminate_simulation = False
+
+        return (Fmi3Status.ok, enter_event_mode, terminate_simulation)
+
+    def fmi3SetTime(self, time: float):
+        self.time = time
+        return Fmi3Status.ok
+
+    def fmi3SetContinuousStates(self, continuous_states):
+        for attribute, value in zip(self.continuous_states, continuous_states):
+            setattr(self, attribute, value)
+        return Fmi3Status.ok
+
+    def fmi3GetContinuousStates(self):
+        return Fmi3Status.ok, [
+            getattr(self, attribute) for attribute in self.continuous_states
+        ]
+
+    def fmi3GetContinuousStateDerivatives(self):
+        return Fmi3Status.ok, [self.der_h, self.der_v]
+
+    def fmi3GetNominalsOfContinuousStates(self):
+        return Fmi3Status.ok, [1.0 for _ in self.continuous_states]
+
+    def fmi3GetNumberOfContinuousStates(self):
+        return Fmi3Status.ok, len(self.continuous_states)
+
+    def fmi3GetEventIndicators(self):
+        # The ball hits the ground when the height crosses zero
+        return Fmi3Status.ok, [self.h]
+
+    def fmi3GetNumberOfEventIndicators(self):
+        return Fmi3Status.ok, 1
+
+    # ================= evaluateDiscreteStates and updateDiscreteStates =================
+
+    def fmi3EvaluateDiscreteStates(self):
+        # The bouncing ball has no discrete-time variables to evaluate
+        return Fmi3Status.ok
+
+    def fmi3UpdateDiscreteStates(self):
+        status = Fmi3Status.ok
+        discrete_states_need_update = False
+        terminate_simulation = False
+        nominals_continuous_states_changed = False
+        values_continuous_states_changed = False
+        next_event_time_defined = False
+        next_event_time = 0.0
+
+        if self.h <= 0.0 and self.v < 0.0:
+            self.h = 0.0
+            self.v = -self.e * self.v
+            values_continuous_states_changed = True
+
+        return (status, discrete_states_need_update, terminate_simulation, nominals_continuous_states_changed,
+                values_continuous_states_changed, next_event_time_defined, next_event_time)
+
+    # ================= Initialization, Enter, Termination, and Reset =================
+
+    def fmi3EnterInitializationMode(
+            self,
+            tolerance_defined: bool,
+            tolerance: float,
+            start_time: float,
+            stop_time_defined: bool,
+            stop_time: float
+    ):
+        self.time = start_time
+        self.state = FMIState.FMIInitializationModeState
+        return Fmi3Status.ok
+
+    def fmi3ExitInitializationMode(self):
+        self.state = FMIState.FMIEventModeState
+        return Fmi3Status.ok
+
+    def fmi3EnterEventMode(self):
+        self.state = FMIState.FMIEventModeState
+        return Fmi3Status.ok
+
+    def fmi3Terminate(self):
+        self.state = FMIState.FMITerminatedState
+        return Fmi3Status.ok
+
+    def fmi3Reset(self):
+        self.state = FMIState.FMIInstantiatedState
+        self.time = 0.0
+        self.h = 1.0
+        self.v = 0.0
+        self.g = -9.81
+        self.e = 0.7
+        return Fmi3Status.ok
+
+    # ================= Serialization =================
+
+    def fmi3SerializeFmuState(self):
+        bytes = pickle.dumps(
+            (
+                self.state,
+                self.time,
+                self.h,
+                self.v,
+                self.g,
+                self.e,
+            )
+        )
+        return Fmi3Status.ok, bytes
+
+    def fmi3DeserializeFmuState(self, bytes: bytes):
+        (
+            state,
+            time,
+            h,
+            v,
+            g,
+            e,
+        ) = pickle.loads(bytes)
+        self.state = state
+        self.time = time
+        self.h = h
+        self.v = v
+        self.g = g
+        self.e = e
+        return Fmi3Status.ok
+
+    # ================= Getters =================
+
+    def fmi3GetFloat64(self, value_references):
+        return self._get_value(value_references)
+
+    # ================= Setters =================
+
+    def fmi3SetFloat64(self, value_references, values):
+        return self._set_value(value_references, values)
+
+    # ================= Derivatives =================
+
+    def fmi3GetDirectionalDerivative(self, unknowns, knowns, seed):
+        sensitivity = [
+            sum(
+                self._partial_derivative(unknown, known) * seed_value
+                for known, seed_value in zip(knowns, seed)
+            )
+            for unknown in unknowns
+        ]
+        return Fmi3Status.ok, sensitivity
+
+    def fmi3GetAdjointDerivative(self, unknowns, knowns, seed):
+        sensitivity = [
+            sum(
+                seed_value * self._partial_derivative(unknown, known)
+                for unknown, seed_value in zip(unknowns, seed)
+            )
+            for known in knowns
+        ]
+        return Fmi3Status.ok, sensitivity
+
+    # ================= Logging =================
+
+    def fmi3SetDebugLogging(self, categories, logging_on):
+        return Fmi3Status.ok
+
+    """ UniFMU logging function
+
+    Call this function whenever something should be logged.
+    This will send a message thourgh the UniFMU layer to the importer if the
+    importer has enabled logging and is interested in the given logging category.
+
+    Keyword arguments:
+    message  -- The message to log.
+    status   -- The Fmi3Status that the program is expected to return when log() is
+                called.
+    category -- The logging category. Must be defined in the modelDescription.xml
+                to be visible to the importer.
+    """
+    def log(self, message, status, category = "logEvents"):
+        # Removing the line below will break logging.
+        self._log_callback(status, category, message)
+
+    # ================= Helpers =================
+
+    def _partial_derivative(self, unknown, known):
+        return self.partial_derivatives.get(unknown, {}).get(known, 0.0)
+
+    def _set_value(self, references, values):
+        status = Fmi3Status.ok
+        for r, v in zip(references, values):
+            if r in self.parameters:
+                if self.state != FMIState.FMIInitializationModeState:
+                    self.log(
+                        f"Set parameter #{r}# when not in initialization mode.",
+                        Fmi3Status.warning,
+                        "logStatusWarning"
+                    )
+                    status = Fmi3Status.warning
+            elif r in self.tunable_parameters:
+                if (
+                    self.state != FMIState.FMIEventModeState
+                    and self.state != FMIState.FMIInitializationModeState
+                ):
+                    self.log(
+                        f"Set tunable parameter #{r}# when neither in event mode nor in initialization mode.",
+                        Fmi3Status.warning,
+                        "logStatusWarning"
+                    )
+                    status = Fmi3Status.warning
+            elif self.reference_to_attribute[r] not in self.continuous_states:
+                self.log(
+                    f"Variable #{r}# can't be set.",
+                    Fmi3Status.error,
+                    "logStatusError"
+                )
+                return Fmi3Status.error
+
+            setattr(self, self.reference_to_attribute[r], v)
+        return status
+
+    def _get_value(self, references):
+        values = []
+        for r in references:
+            values.append(getattr(self, self.reference_to_attribute[r]))
+
+        return Fmi3Status.ok, values
+
+
+class Fmi3Status():
+    """
+    Represents the status of an FMI3 FMU or the results of function calls.
+
+    Values:
+        * ok: all well
+        * warning: an issue has arisen, but the computation can continue.
+        * discard: an operation has resulted in invalid output, which must be discarded
+        * error: an error has ocurred for this specific FMU instance.
+        * fatal: an fatal error has ocurred which has corrupted ALL FMU instances.
+    """
+
+    ok = 0
+    warning = 1
+    discard = 2
+    error = 3
+    fatal = 4
+
+class FMIState(IntFlag):
+    FMIStartAndEndState         = 1 << 0,
+    FMIInstantiatedState        = 1 << 1,
+    FMIInitializationModeState  = 1 << 2,
+    FMITerminatedState          = 1 << 3,
+    FMIConfigurationModeState   = 1 << 4,
+    FMIReconfigurationModeState = 1 << 5,
+    FMIEventModeState           = 1 << 6,
+    FMIContinuousTimeModeState  = 1 << 7,
+    FMIStepModeState            = 1 << 8,
+    FMIClockActivationMode      = 1 << 9
+
+
+if __name__ == "__main__":
+    m = Model("model", "", "", False, False, lambda *args: None)
+
+    assert m.h == 1.0
+    assert m.v == 0.0
+
+    # Explicit euler until the ball hits the ground
+    step_size = 1e-3
+    while m.fmi3GetEventIndicators()[1][0] > 0.0:
+        _, derivatives = m.fmi3GetContinuousStateDerivatives()
+        _, states = m.fmi3GetContinuousStates()
+        m.fmi3SetContinuousStates(
+            [x + step_size * dx for x, dx in zip(states, derivatives)]
+        )
+
+    assert m.v < 0.0
+
+    _, _, _, _, values_changed, _, _ = m.fmi3UpdateDiscreteStates()
+
+    assert values_changed
+    assert m.h == 0.0
+    assert m.v > 0.0
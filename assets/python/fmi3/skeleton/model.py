@@ -0,0 +1,483 @@
+import pickle
+from fractions import Fraction
+from enum import IntFlag
+
+class Model:
+    def __init__(
+            self,
+            instance_name,
+            instantiation_token,
+            resource_path,
+            visible,
+            logging_on,
+            event_mode_used,
+            early_return_allowed,
+            required_intermediate_variables,
+            _log_callback,
+            _clock_update_callback = None,
+            _lock_preemption_callback = None,
+            _unlock_preemption_callback = None,
+            _intermediate_update_callback = None
+    ) -> None:
+        self.instance_name = instance_name
+        self.instantiation_token = instantiation_token
+        self.resource_path = resource_path
+        self.visible = visible
+        self.logging_on = logging_on
+        self.event_mode_used = event_mode_used
+        self.early_return_allowed = early_return_allowed
+        self.required_intermediate_variables = required_intermediate_variables
+        self._log_callback = _log_callback
+        # Only given when the FMU is instantiated for Scheduled Execution
+        self._clock_update_callback = _clock_update_callback
+        self._lock_preemption_callback = _lock_preemption_callback
+        self._unlock_preemption_callback = _unlock_preemption_callback
+        # Only given when the FMU is instantiated for Co-Simulation
+        self._intermediate_update_callback = _intermediate_update_callback
+
+        self.reference_to_attribute = {
+            # {{references}}
+        }
+
+        self.structural_parameters = [
+            # {{structural_parameters}}
+        ]
+
+        self.fmi3Reset()
+
+    # ================= FMI3 =================
+
+    # ================= doStep and updateDiscreteStates =================
+    def fmi3DoStep(
+            self,
+            current_communication_point: float,
+            communication_step_size: float,
+            no_set_fmu_state_prior_to_current_point: bool,
+    ):
+        if self.state != FMIState.FMIStepModeState:
+            self.log(
+                "fmi3DoStep called outside of Step Mode.",
+                Fmi3Status.error,
+                "logStatusError"
+            )
+            return (Fmi3Status.error, False, False, False, current_communication_point)
+
+        # Advance the model from current_communication_point to
+        # current_communication_point + communication_step_size here,
+        # updating the outputs from the inputs.
+
+        event_handling_needed = False
+        terminate_simulation = False
+        early_return = False
+        last_successful_time = current_communication_point + communication_step_size
+
+        return (
+            Fmi3Status.ok,
+            event_handling_needed,
+            terminate_simulation,
+            early_return,
+            last_successful_time,
+        )
+
+    def fmi3EvaluateDiscreteStates(self):
+        if self.state != FMIState.FMIEventModeState:
+            self.log(
+                "fmi3EvaluateDiscreteStates called outside of Event Mode.",
+                Fmi3Status.error,
+                "logStatusError"
+            )
+            return Fmi3Status.error
+
+        return Fmi3Status.ok
+
+    def fmi3UpdateDiscreteStates(self):
+        if self.state != FMIState.FMIEventModeState:
+            self.log(
+                "fmi3UpdateDiscreteStates called outside of Event Mode.",
+                Fmi3Status.error,
+                "logStatusError"
+            )
+            return (Fmi3Status.error, False, False, False, False, False, 0.0)
+
+        status = Fmi3Status.ok
+        discrete_states_need_update = False
+        terminate_simulation = False
+        nominals_continuous_states_changed = False
+        values_continuous_states_changed = False
+        next_event_time_defined = False
+        next_event_time = 0.0
+
+        return (status, discrete_states_need_update, terminate_simulation, nominals_continuous_states_changed,
+                values_continuous_states_changed, next_event_time_defined, next_event_time)
+
+    # ================= Scheduled Execution =================
+
+    def fmi3ActivateModelPartition(self, clock_reference, activation_time):
+        return Fmi3Status.ok
+
+    # ================= Initialization, Enter, Termination, and Reset =================
+
+    def fmi3EnterInitializationMode(
+            self,
+            tolerance_defined: bool,
+            tolerance: float,
+            start_time: float,
+            stop_time_defined: bool,
+            stop_time: float
+    ):
+        self.state = FMIState.FMIInitializationModeState
+        return Fmi3Status.ok
+
+    def fmi3ExitInitializationMode(self):
+        if self.event_mode_used:
+            self.state = FMIState.FMIEventModeState
+        else:
+            self.state = FMIState.FMIStepModeState
+        return Fmi3Status.ok
+
+    def fmi3EnterEventMode(self):
+        if self.state != FMIState.FMIStepModeState:
+            self.log(
+                "fmi3EnterEventMode called outside of Step Mode.",
+                Fmi3Status.error,
+                "logStatusError"
+            )
+            return Fmi3Status.error
+
+        self.state = FMIState.FMIEventModeState
+        return Fmi3Status.ok
+
+    def fmi3EnterStepMode(self):
+        if self.state != FMIState.FMIEventModeState:
+            self.log(
+                "fmi3EnterStepMode called outside of Event Mode.",
+                Fmi3Status.error,
+                "logStatusError"
+            )
+            return Fmi3Status.error
+
+        self.state = FMIState.FMIStepModeState
+        return Fmi3Status.ok
+
+    def fmi3EnterConfigurationMode(self):
+        if len(self.structural_parameters)>0:
+            self.state = FMIState.FMIConfigurationModeState if self.state == FMIState.FMIInstantiatedState else FMIState.FMIReconfigurationModeState
+        else:
+            return Fmi3Status.error
+        return Fmi3Status.ok
+
+    def fmi3ExitConfigurationMode(self):
+        if self.state == FMIState.FMIConfigurationModeState:
+            self.state = FMIState.FMIInstantiatedState
+        elif self.state == FMIState.FMIReconfigurationModeState:
+            self.state = FMIState.FMIStepModeState
+        else:
+            return Fmi3Status.error
+        return Fmi3Status.ok
+
+    def fmi3Terminate(self):
+        self.state = FMIState.FMITerminatedState
+        return Fmi3Status.ok
+
+    def fmi3Reset(self):
+        self.state = FMIState.FMIInstantiatedState
+        # {{start_values}}
+        self.clock_reference_to_interval = {
+            # {{clock_intervals}}
+        }
+        self.clock_reference_to_shift = {
+            # {{clock_shifts}}
+        }
+        return Fmi3Status.ok
+
+    # ================= Serialization =================
+
+    def fmi3SerializeFmuState(self):
+        bytes = pickle.dumps(
+            (
+                self.state,
+                {
+                    attribute: getattr(self, attribute)
+                    for attribute in self.reference_to_attribute.values()
+                },
+                self.clock_reference_to_interval,
+                self.clock_reference_to_shift,
+            )
+        )
+        return Fmi3Status.ok, bytes
+
+    def fmi3DeserializeFmuState(self, bytes: bytes):
+        (
+            state,
+            attributes,
+            clock_reference_to_interval,
+            clock_reference_to_shift,
+        ) = pickle.loads(bytes)
+        self.state = state
+        for attribute, value in attributes.items():
+            setattr(self, attribute, value)
+        self.clock_reference_to_interval = clock_reference_to_interval
+        self.clock_reference_to_shift = clock_reference_to_shift
+
+        return Fmi3Status.ok
+
+    # ================= Getters =================
+
+    def fmi3GetFloat32(self, value_references):
+        return self._get_value(value_references)
+
+    def fmi3GetFloat64(self, value_references):
+        return self._get_value(value_references)
+
+    def fmi3GetInt8(self, value_references):
+        return self._get_value(value_references)
+
+    def fmi3GetUInt8(self, value_references):
+        return self._get_value(value_references)
+
+    def fmi3GetInt16(self, value_references):
+        return self._get_value(value_references)
+
+    def fmi3GetUInt16(self, value_references):
+        return self._get_value(value_references)
+
+    def fmi3GetInt32(self, value_references):
+        return self._get_value(value_references)
+
+    def fmi3GetUInt32(self, value_references):
+        return self._get_value(value_references)
+
+    def fmi3GetInt64(self, value_references):
+        return self._get_value(value_references)
+
+    def fmi3GetUInt64(self, value_references):
+        return self._get_value(value_references)
+
+    def fmi3GetBoolean(self, value_references):
+        return self._get_value(value_references)
+
+    def fmi3GetString(self, value_references):
+        return self._get_value(value_references)
+
+    def fmi3GetBinary(self, value_references):
+        return self._get_value(value_references)
+
+    def fmi3GetClock(self, value_references):
+        return self._get_value(value_references)
+
+    def fmi3GetIntervalDecimal(self, value_references):
+        intervals = []
+        qualifiers = []
+
+        for r in value_references:
+            intervals.append(self.clock_reference_to_interval[r])
+            qualifiers.append(2)
+
+        return Fmi3Status.ok, intervals, qualifiers
+
+    def fmi3GetIntervalFraction(self, value_references):
+        counters = []
+        resolutions = []
+        qualifiers = []
+
+        for r in value_references:
+            fraction = Fraction(str(self.clock_reference_to_interval[r]))
+            counters.append(fraction.numerator)
+            resolutions.append(fraction.denominator)
+            qualifiers.append(2)
+
+        return Fmi3Status.ok, counters, resolutions, qualifiers
+
+    def fmi3GetShiftDecimal(self, value_references):
+        shifts = []
+
+        for r in value_references:
+            shifts.append(self.clock_reference_to_shift[r])
+
+        return Fmi3Status.ok, shifts
+
+    def fmi3GetShiftFraction(self, value_references):
+        counters = []
+        resolutions = []
+
+        for r in value_references:
+            fraction = Fraction(str(self.clock_reference_to_shift[r]))
+            counters.append(fraction.numerator)
+            resolutions.append(fraction.denominator)
+
+        return Fmi3Status.ok, counters, resolutions
+
+    # ================= Setters =================
+
+    def fmi3SetFloat32(self, value_references, values):
+        return self._set_value(value_references, values)
+
+    def fmi3SetFloat64(self, value_references, values):
+        return self._set_value(value_references, values)
+
+    def fmi3SetInt8(self, value_references, values):
+        return self._set_value(value_references, values)
+
+    def fmi3SetUInt8(self, value_references, values):
+        return self._set_value(value_references, values)
+
+    def fmi3SetInt16(self, value_references, values):
+        return self._set_value(value_references, values)
+
+    def fmi3SetUInt16(self, value_references, values):
+        return self._set_value(value_references, values)
+
+    def fmi3SetInt32(self, value_references, values):
+        return self._set_value(value_references, values)
+
+    def fmi3SetUInt32(self, value_references, values):
+        return self._set_value(value_references, values)
+
+    def fmi3SetInt64(self, value_references, values):
+        return self._set_value(value_references, values)
+
+    def fmi3SetUInt64(self, value_references, values):
+        return self._set_value(value_references, values)
+
+    def fmi3SetBoolean(self, value_references, values):
+        return self._set_value(value_references, values)
+
+    def fmi3SetString(self, value_references, values):
+        return self._set_value(value_references, values)
+
+    def fmi3SetBinary(self, value_references, value_sizes, values):
+        return self._set_value(value_references, values)
+
+    def fmi3SetClock(self, value_references, values):
+        return self._set_value(value_references, values)
+
+    def fmi3SetIntervalDecimal(self, value_references, intervals):
+        for r, interval in zip(value_references, intervals):
+            self.clock_reference_to_interval[r] = interval
+        return Fmi3Status.ok
+
+    def fmi3SetIntervalFraction(self, value_references, counters, resolutions):
+        for r, counter, resolution in zip(value_references, counters, resolutions):
+            self.clock_reference_to_interval[r] = float(counter)/float(resolution)
+        return Fmi3Status.ok
+
+    def fmi3SetShiftDecimal(self, value_references, shifts):
+        for r, shift in zip(value_references, shifts):
+            self.clock_reference_to_shift[r] = shift
+        return Fmi3Status.ok
+
+    def fmi3SetShiftFraction(self, value_references, counters, resolutions):
+        for r, counter, resolution in zip(value_references, counters, resolutions):
+            self.clock_reference_to_shift[r] = float(counter)/float(resolution)
+        return Fmi3Status.ok
+
+    # ================= Derivatives =================
+
+    def fmi3GetDirectionalDerivative(self, unknowns, knowns, seed):
+        self.log(
+            "The model can't compute directional derivatives.",
+            Fmi3Status.error,
+            "logStatusError"
+        )
+        return Fmi3Status.error, []
+
+    def fmi3GetAdjointDerivative(self, unknowns, knowns, seed):
+        self.log(
+            "The model can't compute adjoint derivatives.",
+            Fmi3Status.error,
+            "logStatusError"
+        )
+        return Fmi3Status.error, []
+
+    def fmi3GetOutputDerivatives(self, value_references, orders):
+        self.log(
+            "The model can't compute output derivatives.",
+            Fmi3Status.error,
+            "logStatusError"
+        )
+        return Fmi3Status.error, []
+
+    # ================= Logging =================
+
+    def fmi3SetDebugLogging(self, categories, logging_on):
+        return Fmi3Status.ok
+
+    def log(self, message, status, category = "logEvents"):
+        # Removing the line below will break logging.
+        self._log_callback(status, category, message)
+
+    # ================= Helpers =================
+
+    def _set_value(self, references, values):
+        values = list(values)
+        for r in references:
+            attribute = self.reference_to_attribute[r]
+            setattr(
+                self,
+                attribute,
+                _pop_n_format_values_for_attribute(
+                    getattr(self, attribute),
+                    values
+                )
+            )
+        return Fmi3Status.ok
+
+    def _get_value(self, references):
+        values = []
+        for r in references:
+            _add_value_to_values(
+                getattr(self, self.reference_to_attribute[r]),
+                values
+            )
+
+        return Fmi3Status.ok, values
+
+
+""" Pops values from the front of "values" and returns them formatted as
+"current_value", which is either a scalar or a list of scalars.
+"""
+def _pop_n_format_values_for_attribute(current_value, values):
+    if isinstance(current_value, list):
+        return [
+            _pop_n_format_values_for_attribute(sub_value, values)
+            for sub_value in current_value
+        ]
+    else:
+        return values.pop(0)
+
+def _add_value_to_values(value, values):
+    if isinstance(value, list):
+        for sub_value in value:
+            _add_value_to_values(sub_value, values)
+    else:
+        values.append(value)
+
+class Fmi3Status():
+    """
+    Represents the status of an FMI3 FMU or the results of function calls.
+
+    Values:
+        * ok: all well
+        * warning: an issue has arisen, but the computation can continue.
+        * discard: an operation has resulted in invalid output, which must be discarded
+        * error: an error has ocurred for this specific FMU instance.
+        * fatal: an fatal error has ocurred which has corrupted ALL FMU instances.
+    """
+
+    ok = 0
+    warning = 1
+    discard = 2
+    error = 3
+    fatal = 4
+
+class FMIState(IntFlag):
+    FMIStartAndEndState         = 1 << 0,
+    FMIInstantiatedState        = 1 << 1,
+    FMIInitializationModeState  = 1 << 2,
+    FMITerminatedState          = 1 << 3,
+    FMIConfigurationModeState   = 1 << 4,
+    FMIReconfigurationModeState = 1 << 5,
+    FMIEventModeState           = 1 << 6,
+    FMIContinuousTimeModeState  = 1 << 7,
+    FMIStepModeState            = 1 << 8,
+    FMIClockActivationMode      = 1 << 9
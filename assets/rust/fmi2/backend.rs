@@ -0,0 +1,368 @@
+//! The FMI2 side of the backend, which answers every command of the
+//! dispatcher by calling the corresponding method of the model.
+
+use std::{cell::RefCell, rc::Rc};
+
+use crate::messages::{
+    fmi2_command::Command,
+    fmi2_return::ReturnMessage,
+    Fmi2CallbackContinue,
+    Fmi2Command,
+    Fmi2CompletedIntegratorStepReturn,
+    Fmi2EmptyReturn,
+    Fmi2FreeInstanceReturn,
+    Fmi2GetBooleanReturn,
+    Fmi2GetContinuousStatesReturn,
+    Fmi2GetDerivativesReturn,
+    Fmi2GetDirectionalDerivativesReturn,
+    Fmi2GetDoStepStatusReturn,
+    Fmi2GetEventIndicatorsReturn,
+    Fmi2GetIntegerReturn,
+    Fmi2GetNominalsOfContinuousStatesReturn,
+    Fmi2GetRealOutputDerivativesReturn,
+    Fmi2GetRealReturn,
+    Fmi2GetStringReturn,
+    Fmi2Instantiate,
+    Fmi2LogReturn,
+    Fmi2NewDiscreteStatesReturn,
+    Fmi2Return,
+    Fmi2SerializeFmuStateReturn,
+    Fmi2StatusReturn
+};
+use crate::socket::{Socket, SocketError, SocketResult};
+
+pub use crate::messages::Fmi2Status;
+
+/// The calls of the FMI2 co-simulation interface that the model answers.
+///
+/// Getters are given the value references of the variables to get and return
+/// their values in the same order. Setters are given the value references of
+/// the variables to set along with the new values.
+///
+/// Only the methods without a default implementation must be implemented.
+/// The defaults of the optional methods succeed if the call can be a no-op
+/// and fail with Fmi2Status::Fmi2Error otherwise.
+pub trait Model: Sized {
+    /// Creates the model when the importer instantiates the FMU.
+    ///
+    /// The logger can be kept by the model to send log messages to the
+    /// importer whenever needed.
+    fn instantiate(instance: &Fmi2Instantiate, logger: Logger) -> Self;
+
+    fn do_step(
+        &mut self,
+        current_time: f64,
+        step_size: f64,
+        no_set_fmu_state_prior_to_current_point: bool
+    ) -> Fmi2Status;
+
+    fn reset(&mut self) -> Fmi2Status;
+
+    fn get_real(&mut self, references: &[u32]) -> (Fmi2Status, Vec<f64>);
+
+    fn get_integer(&mut self, references: &[u32]) -> (Fmi2Status, Vec<i32>);
+
+    fn get_boolean(&mut self, references: &[u32]) -> (Fmi2Status, Vec<bool>);
+
+    fn get_string(&mut self, references: &[u32]) -> (Fmi2Status, Vec<String>);
+
+    fn set_real(&mut self, references: &[u32], values: &[f64]) -> Fmi2Status;
+
+    fn set_integer(&mut self, references: &[u32], values: &[i32]) -> Fmi2Status;
+
+    fn set_boolean(&mut self, references: &[u32], values: &[bool]) -> Fmi2Status;
+
+    fn set_string(&mut self, references: &[u32], values: &[String]) -> Fmi2Status;
+
+    fn set_debug_logging(
+        &mut self,
+        _categories: &[String],
+        _logging_on: bool
+    ) -> Fmi2Status {
+        Fmi2Status::Fmi2Ok
+    }
+
+    fn setup_experiment(
+        &mut self,
+        _start_time: f64,
+        _stop_time: Option<f64>,
+        _tolerance: Option<f64>
+    ) -> Fmi2Status {
+        Fmi2Status::Fmi2Ok
+    }
+
+    fn enter_initialization_mode(&mut self) -> Fmi2Status {
+        Fmi2Status::Fmi2Ok
+    }
+
+    fn exit_initialization_mode(&mut self) -> Fmi2Status {
+        Fmi2Status::Fmi2Ok
+    }
+
+    fn terminate(&mut self) -> Fmi2Status {
+        Fmi2Status::Fmi2Ok
+    }
+
+    /// Returns the complete state of the model as bytes that
+    /// Model::deserialize_fmu_state() can restore.
+    fn serialize_fmu_state(&mut self) -> (Fmi2Status, Vec<u8>) {
+        (Fmi2Status::Fmi2Error, Vec::new())
+    }
+
+    fn deserialize_fmu_state(&mut self, _state: &[u8]) -> Fmi2Status {
+        Fmi2Status::Fmi2Error
+    }
+
+    fn set_real_input_derivatives(
+        &mut self,
+        _references: &[u32],
+        _orders: &[i32],
+        _values: &[f64]
+    ) -> Fmi2Status {
+        Fmi2Status::Fmi2Error
+    }
+
+    fn get_real_output_derivatives(
+        &mut self,
+        _references: &[u32],
+        _orders: &[i32]
+    ) -> (Fmi2Status, Vec<f64>) {
+        (Fmi2Status::Fmi2Error, Vec::new())
+    }
+}
+
+/// Sends log messages to the importer of the FMU.
+///
+/// A log message can only be sent while the backend is answering a command,
+/// that is from within one of the methods of the model.
+#[derive(Clone)]
+pub struct Logger {
+    socket: Rc<RefCell<Socket>>
+}
+
+impl Logger {
+    pub fn log(&self, status: Fmi2Status, category: &str, message: &str) {
+        if let Err(error) = self.send_log(status, category, message) {
+            eprintln!("couldn't send log message '{}'; {}", message, error);
+        }
+    }
+
+    fn send_log(
+        &self,
+        status: Fmi2Status,
+        category: &str,
+        message: &str
+    ) -> SocketResult<()> {
+        let mut socket = self.socket.borrow_mut();
+
+        socket.send(&Fmi2Return {
+            return_message: Some(ReturnMessage::Log(Fmi2LogReturn {
+                status: status as i32,
+                category: category.to_owned(),
+                log_message: message.to_owned()
+            }))
+        })?;
+
+        match socket.recv::<Fmi2Command>()?.command {
+            Some(Command::Fmi2CallbackContinue(Fmi2CallbackContinue {})) => Ok(()),
+            command => Err(SocketError::UnexpectedCommand(command_name(command.as_ref())))
+        }
+    }
+}
+
+/// Completes the handshake with the dispatcher and answers the commands of
+/// the dispatcher until the instance is freed.
+pub fn command_reply_loop<M: Model>(mut socket: Socket) -> SocketResult<()> {
+    // FMI2 values are always exchanged through the socket.
+    socket.handshake(false)?;
+
+    let socket = Rc::new(RefCell::new(socket));
+    let logger = Logger {socket: socket.clone()};
+    let mut model: Option<M> = None;
+
+    loop {
+        let command = socket.borrow_mut().recv::<Fmi2Command>()?.command;
+
+        let reply = match (command, model.as_mut()) {
+            (Some(Command::Fmi2Instantiate(instance)), _) => {
+                model = Some(M::instantiate(&instance, logger.clone()));
+                ReturnMessage::Empty(Fmi2EmptyReturn {})
+            }
+            (Some(Command::Fmi2FreeInstance(_)), _) => {
+                socket.borrow_mut().send(&Fmi2Return {
+                    return_message: Some(ReturnMessage::FreeInstance(
+                        Fmi2FreeInstanceReturn {}
+                    ))
+                })?;
+                eprintln!("Fmi2FreeInstance received, shutting down");
+                return Ok(());
+            }
+            (Some(command), Some(model)) => reply_to_command(model, command, &logger)?,
+            (command, _) => {
+                return Err(SocketError::UnexpectedCommand(command_name(command.as_ref())));
+            }
+        };
+
+        socket.borrow_mut().send(&Fmi2Return {return_message: Some(reply)})?;
+    }
+}
+
+fn reply_to_command<M: Model>(
+    model: &mut M,
+    command: Command,
+    logger: &Logger
+) -> SocketResult<ReturnMessage> {
+    let reply = match command {
+        Command::Fmi2DoStep(step) => status_reply(model.do_step(
+            step.current_time,
+            step.step_size,
+            step.no_set_fmu_state_prior_to_current_point
+        )),
+        Command::Fmi2SetDebugLogging(logging) => status_reply(
+            model.set_debug_logging(&logging.categories, logging.logging_on)
+        ),
+        Command::Fmi2SetupExperiment(experiment) => status_reply(
+            model.setup_experiment(
+                experiment.start_time,
+                experiment.stop_time,
+                experiment.tolerance
+            )
+        ),
+        Command::Fmi2EnterInitializationMode(_) => status_reply(
+            model.enter_initialization_mode()
+        ),
+        Command::Fmi2ExitInitializationMode(_) => status_reply(
+            model.exit_initialization_mode()
+        ),
+        Command::Fmi2Terminate(_) => status_reply(model.terminate()),
+        Command::Fmi2Reset(_) => status_reply(model.reset()),
+        Command::Fmi2GetReal(get) => {
+            let (status, values) = model.get_real(&get.references);
+            ReturnMessage::GetReal(Fmi2GetRealReturn {status: status as i32, values})
+        }
+        Command::Fmi2GetInteger(get) => {
+            let (status, values) = model.get_integer(&get.references);
+            ReturnMessage::GetInteger(Fmi2GetIntegerReturn {status: status as i32, values})
+        }
+        Command::Fmi2GetBoolean(get) => {
+            let (status, values) = model.get_boolean(&get.references);
+            ReturnMessage::GetBoolean(Fmi2GetBooleanReturn {status: status as i32, values})
+        }
+        Command::Fmi2GetString(get) => {
+            let (status, values) = model.get_string(&get.references);
+            ReturnMessage::GetString(Fmi2GetStringReturn {status: status as i32, values})
+        }
+        Command::Fmi2SetReal(set) => status_reply(
+            model.set_real(&set.references, &set.values)
+        ),
+        Command::Fmi2SetInteger(set) => status_reply(
+            model.set_integer(&set.references, &set.values)
+        ),
+        Command::Fmi2SetBoolean(set) => status_reply(
+            model.set_boolean(&set.references, &set.values)
+        ),
+        Command::Fmi2SetString(set) => status_reply(
+            model.set_string(&set.references, &set.values)
+        ),
+        Command::Fmi2SerializeFmuState(_) => {
+            let (status, state) = model.serialize_fmu_state();
+            ReturnMessage::SerializeFmuState(Fmi2SerializeFmuStateReturn {
+                status: status as i32,
+                state
+            })
+        }
+        Command::Fmi2DeserializeFmuState(deserialize) => status_reply(
+            model.deserialize_fmu_state(&deserialize.state)
+        ),
+        Command::Fmi2SetRealInputDerivatives(set) => status_reply(
+            model.set_real_input_derivatives(&set.references, &set.orders, &set.values)
+        ),
+        Command::Fmi2GetRealOutputDerivatives(get) => {
+            let (status, values) = model.get_real_output_derivatives(
+                &get.references,
+                &get.orders
+            );
+            ReturnMessage::GetRealOutputDerivatives(Fmi2GetRealOutputDerivativesReturn {
+                status: status as i32,
+                values
+            })
+        }
+        Command::Fmi2CancelStep(_) => {
+            // Steps are always taken synchronously, so there is never a step
+            // to cancel.
+            status_reply(Fmi2Status::Fmi2Error)
+        }
+        Command::Fmi2GetDoStepStatus(_) => ReturnMessage::GetDoStepStatus(
+            Fmi2GetDoStepStatusReturn {
+                status: Fmi2Status::Fmi2Error as i32,
+                pending_message: String::from("no step is running asynchronously")
+            }
+        ),
+        command => unsupported_reply(command, logger)?
+    };
+
+    Ok(reply)
+}
+
+/// Logs that the Rust backend doesn't support the command and replies with
+/// Fmi2Status::Fmi2Error.
+fn unsupported_reply(
+    command: Command,
+    logger: &Logger
+) -> SocketResult<ReturnMessage> {
+    let name = command_name(Some(&command));
+    let error = Fmi2Status::Fmi2Error as i32;
+
+    let reply = match command {
+        Command::Fmi2GetDirectionalDerivatives(_) => ReturnMessage::GetDirectionalDerivatives(
+            Fmi2GetDirectionalDerivativesReturn {status: error, ..Default::default()}
+        ),
+        Command::Fmi2NewDiscreteStates(_) => ReturnMessage::NewDiscreteStates(
+            Fmi2NewDiscreteStatesReturn {status: error, ..Default::default()}
+        ),
+        Command::Fmi2CompletedIntegratorStep(_) => ReturnMessage::CompletedIntegratorStep(
+            Fmi2CompletedIntegratorStepReturn {status: error, ..Default::default()}
+        ),
+        Command::Fmi2GetDerivatives(_) => ReturnMessage::GetDerivatives(
+            Fmi2GetDerivativesReturn {status: error, ..Default::default()}
+        ),
+        Command::Fmi2GetEventIndicators(_) => ReturnMessage::GetEventIndicators(
+            Fmi2GetEventIndicatorsReturn {status: error, ..Default::default()}
+        ),
+        Command::Fmi2GetContinuousStates(_) => ReturnMessage::GetContinuousStates(
+            Fmi2GetContinuousStatesReturn {status: error, ..Default::default()}
+        ),
+        Command::Fmi2GetNominalsOfContinuousStates(_) => ReturnMessage::GetNominalsOfContinuousStates(
+            Fmi2GetNominalsOfContinuousStatesReturn {status: error, ..Default::default()}
+        ),
+        Command::Fmi2EnterEventMode(_)
+        | Command::Fmi2EnterContinuousTimeMode(_)
+        | Command::Fmi2SetTime(_)
+        | Command::Fmi2SetContinuousStates(_) => status_reply(Fmi2Status::Fmi2Error),
+        _ => return Err(SocketError::UnexpectedCommand(name))
+    };
+
+    logger.log(
+        Fmi2Status::Fmi2Error,
+        "logStatusError",
+        &format!("{} isn't supported by the Rust backend", name)
+    );
+
+    Ok(reply)
+}
+
+fn status_reply(status: Fmi2Status) -> ReturnMessage {
+    ReturnMessage::Status(Fmi2StatusReturn {status: status as i32})
+}
+
+/// The name of the command without its arguments.
+fn command_name(command: Option<&Command>) -> String {
+    match command {
+        Some(command) => format!("{:?}", command)
+            .split('(')
+            .next()
+            .unwrap_or_default()
+            .to_owned(),
+        None => String::from("empty command")
+    }
+}
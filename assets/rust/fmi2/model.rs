@@ -0,0 +1,232 @@
+//! The model of the FMU, an adder whose outputs are the sums of its inputs.
+
+use serde::{Deserialize, Serialize};
+
+use crate::backend::{self, Fmi2Status, Logger};
+use crate::messages::Fmi2Instantiate;
+
+#[derive(Serialize, Deserialize)]
+pub struct Model {
+    real_a: f64,
+    real_b: f64,
+    real_c: f64,
+    integer_a: i32,
+    integer_b: i32,
+    integer_c: i32,
+    boolean_a: bool,
+    boolean_b: bool,
+    boolean_c: bool,
+    string_a: String,
+    string_b: String,
+    string_c: String,
+    #[serde(skip)]
+    logger: Option<Logger>
+}
+
+impl Model {
+    fn new(logger: Logger) -> Self {
+        let mut model = Self {
+            real_a: 0.0,
+            real_b: 0.0,
+            real_c: 0.0,
+            integer_a: 0,
+            integer_b: 0,
+            integer_c: 0,
+            boolean_a: false,
+            boolean_b: false,
+            boolean_c: false,
+            string_a: String::new(),
+            string_b: String::new(),
+            string_c: String::new(),
+            logger: Some(logger)
+        };
+        model.update_outputs();
+        model
+    }
+
+    fn update_outputs(&mut self) {
+        self.real_c = self.real_a + self.real_b;
+        self.integer_c = self.integer_a.wrapping_add(self.integer_b);
+        self.boolean_c = self.boolean_a || self.boolean_b;
+        self.string_c = format!("{}{}", self.string_a, self.string_b);
+    }
+
+    fn log_error(&self, message: &str) {
+        if let Some(logger) = &self.logger {
+            logger.log(Fmi2Status::Fmi2Error, "logStatusError", message);
+        }
+    }
+
+    /// Calls get_value for each value reference, logging any reference that
+    /// isn't a variable of the type.
+    fn get<T>(
+        &self,
+        references: &[u32],
+        get_value: impl Fn(&Self, u32) -> Option<T>
+    ) -> (Fmi2Status, Vec<T>) {
+        let mut values = Vec::with_capacity(references.len());
+
+        for &reference in references {
+            match get_value(self, reference) {
+                Some(value) => values.push(value),
+                None => {
+                    self.log_error(&format!("no variable with value reference {}", reference));
+                    return (Fmi2Status::Fmi2Error, Vec::new());
+                }
+            }
+        }
+
+        (Fmi2Status::Fmi2Ok, values)
+    }
+
+    /// Calls set_value for each value reference and value, logging any
+    /// reference that isn't a variable of the type.
+    fn set<T: Clone>(
+        &mut self,
+        references: &[u32],
+        values: &[T],
+        set_value: impl Fn(&mut Self, u32, T) -> bool
+    ) -> Fmi2Status {
+        for (&reference, value) in references.iter().zip(values) {
+            if !set_value(self, reference, value.clone()) {
+                self.log_error(&format!("no variable with value reference {}", reference));
+                return Fmi2Status::Fmi2Error;
+            }
+        }
+
+        Fmi2Status::Fmi2Ok
+    }
+}
+
+impl backend::Model for Model {
+    fn instantiate(_instance: &Fmi2Instantiate, logger: Logger) -> Self {
+        Self::new(logger)
+    }
+
+    fn do_step(
+        &mut self,
+        _current_time: f64,
+        _step_size: f64,
+        _no_set_fmu_state_prior_to_current_point: bool
+    ) -> Fmi2Status {
+        self.update_outputs();
+        Fmi2Status::Fmi2Ok
+    }
+
+    fn exit_initialization_mode(&mut self) -> Fmi2Status {
+        self.update_outputs();
+        Fmi2Status::Fmi2Ok
+    }
+
+    fn reset(&mut self) -> Fmi2Status {
+        *self = Self::new(self.logger.take().expect("the model always has a logger"));
+        Fmi2Status::Fmi2Ok
+    }
+
+    fn serialize_fmu_state(&mut self) -> (Fmi2Status, Vec<u8>) {
+        match serde_json::to_vec(self) {
+            Ok(state) => (Fmi2Status::Fmi2Ok, state),
+            Err(error) => {
+                self.log_error(&format!("couldn't serialize state; {}", error));
+                (Fmi2Status::Fmi2Error, Vec::new())
+            }
+        }
+    }
+
+    fn deserialize_fmu_state(&mut self, state: &[u8]) -> Fmi2Status {
+        match serde_json::from_slice::<Self>(state) {
+            Ok(model) => {
+                *self = Self {logger: self.logger.take(), ..model};
+                Fmi2Status::Fmi2Ok
+            }
+            Err(error) => {
+                self.log_error(&format!("couldn't deserialize state; {}", error));
+                Fmi2Status::Fmi2Error
+            }
+        }
+    }
+
+    fn get_real(&mut self, references: &[u32]) -> (Fmi2Status, Vec<f64>) {
+        self.get(references, |model, reference| match reference {
+            0 => Some(model.real_a),
+            1 => Some(model.real_b),
+            2 => Some(model.real_c),
+            _ => None
+        })
+    }
+
+    fn get_integer(&mut self, references: &[u32]) -> (Fmi2Status, Vec<i32>) {
+        self.get(references, |model, reference| match reference {
+            3 => Some(model.integer_a),
+            4 => Some(model.integer_b),
+            5 => Some(model.integer_c),
+            _ => None
+        })
+    }
+
+    fn get_boolean(&mut self, references: &[u32]) -> (Fmi2Status, Vec<bool>) {
+        self.get(references, |model, reference| match reference {
+            6 => Some(model.boolean_a),
+            7 => Some(model.boolean_b),
+            8 => Some(model.boolean_c),
+            _ => None
+        })
+    }
+
+    fn get_string(&mut self, references: &[u32]) -> (Fmi2Status, Vec<String>) {
+        self.get(references, |model, reference| match reference {
+            9 => Some(model.string_a.clone()),
+            10 => Some(model.string_b.clone()),
+            11 => Some(model.string_c.clone()),
+            _ => None
+        })
+    }
+
+    fn set_real(&mut self, references: &[u32], values: &[f64]) -> Fmi2Status {
+        self.set(references, values, |model, reference, value| {
+            match reference {
+                0 => model.real_a = value,
+                1 => model.real_b = value,
+                2 => model.real_c = value,
+                _ => return false
+            }
+            true
+        })
+    }
+
+    fn set_integer(&mut self, references: &[u32], values: &[i32]) -> Fmi2Status {
+        self.set(references, values, |model, reference, value| {
+            match reference {
+                3 => model.integer_a = value,
+                4 => model.integer_b = value,
+                5 => model.integer_c = value,
+                _ => return false
+            }
+            true
+        })
+    }
+
+    fn set_boolean(&mut self, references: &[u32], values: &[bool]) -> Fmi2Status {
+        self.set(references, values, |model, reference, value| {
+            match reference {
+                6 => model.boolean_a = value,
+                7 => model.boolean_b = value,
+                8 => model.boolean_c = value,
+                _ => return false
+            }
+            true
+        })
+    }
+
+    fn set_string(&mut self, references: &[u32], values: &[String]) -> Fmi2Status {
+        self.set(references, values, |model, reference, value| {
+            match reference {
+                9 => model.string_a = value,
+                10 => model.string_b = value,
+                11 => model.string_c = value,
+                _ => return false
+            }
+            true
+        })
+    }
+}
@@ -0,0 +1,740 @@
+//! The FMI3 side of the backend, which answers every command of the
+//! dispatcher by calling the corresponding method of the model.
+
+use std::{cell::RefCell, fs::OpenOptions, rc::Rc};
+
+use memmap2::MmapMut;
+
+use crate::messages::{
+    fmi3_command::Command,
+    fmi3_return::ReturnMessage,
+    Fmi3CallbackContinue,
+    Fmi3Command,
+    Fmi3CompletedIntegratorStepReturn,
+    Fmi3DoStepReturn,
+    Fmi3EmptyReturn,
+    Fmi3FreeInstanceReturn,
+    Fmi3GetAdjointDerivativeReturn,
+    Fmi3GetBinaryReturn,
+    Fmi3GetBooleanReturn,
+    Fmi3GetClockReturn,
+    Fmi3GetContinuousStateDerivativesReturn,
+    Fmi3GetContinuousStatesReturn,
+    Fmi3GetDirectionalDerivativeReturn,
+    Fmi3GetEventIndicatorsReturn,
+    Fmi3GetFloat32Return,
+    Fmi3GetFloat64Return,
+    Fmi3GetFloat64SharedReturn,
+    Fmi3GetInt16Return,
+    Fmi3GetInt32Return,
+    Fmi3GetInt64Return,
+    Fmi3GetInt8Return,
+    Fmi3GetIntervalDecimalReturn,
+    Fmi3GetIntervalFractionReturn,
+    Fmi3GetNominalsOfContinuousStatesReturn,
+    Fmi3GetNumberOfContinuousStatesReturn,
+    Fmi3GetNumberOfEventIndicatorsReturn,
+    Fmi3GetOutputDerivativesReturn,
+    Fmi3GetShiftDecimalReturn,
+    Fmi3GetShiftFractionReturn,
+    Fmi3GetStringReturn,
+    Fmi3GetUInt16Return,
+    Fmi3GetUInt32Return,
+    Fmi3GetUInt64Return,
+    Fmi3GetUInt8Return,
+    Fmi3InstantiateCoSimulation,
+    Fmi3LogReturn,
+    Fmi3Return,
+    Fmi3SerializeFmuStateReturn,
+    Fmi3StatusReturn,
+    Fmi3UpdateDiscreteStatesReturn
+};
+use crate::socket::{Socket, SocketError, SocketResult};
+
+pub use crate::messages::Fmi3Status;
+
+/// The outcome of Model::do_step().
+pub struct DoStepResult {
+    pub status: Fmi3Status,
+    pub event_handling_needed: bool,
+    pub terminate_simulation: bool,
+    pub early_return: bool,
+    pub last_successful_time: f64
+}
+
+/// The outcome of Model::update_discrete_states().
+pub struct UpdateDiscreteStatesResult {
+    pub status: Fmi3Status,
+    pub discrete_states_need_update: bool,
+    pub terminate_simulation: bool,
+    pub nominals_continuous_states_changed: bool,
+    pub values_continuous_states_changed: bool,
+    pub next_event_time: Option<f64>
+}
+
+/// The calls of the FMI3 co-simulation interface that the model answers.
+///
+/// Getters are given the value references of the variables to get and return
+/// their values in the same order. Setters are given the value references of
+/// the variables to set along with the new values.
+///
+/// Only the methods without a default implementation must be implemented,
+/// along with the getters and setters of the types of variables that the
+/// model has. The defaults of the optional methods succeed if the call can be
+/// a no-op and fail with Fmi3Status::Fmi3Error otherwise.
+pub trait Model: Sized {
+    /// Creates the model when the importer instantiates the FMU.
+    ///
+    /// The logger can be kept by the model to send log messages to the
+    /// importer whenever needed.
+    fn instantiate_co_simulation(
+        instance: &Fmi3InstantiateCoSimulation,
+        logger: Logger
+    ) -> Self;
+
+    fn do_step(
+        &mut self,
+        current_communication_point: f64,
+        communication_step_size: f64,
+        no_set_fmu_state_prior_to_current_point: bool
+    ) -> DoStepResult;
+
+    fn reset(&mut self) -> Fmi3Status;
+
+    fn set_debug_logging(
+        &mut self,
+        _categories: &[String],
+        _logging_on: bool
+    ) -> Fmi3Status {
+        Fmi3Status::Fmi3Ok
+    }
+
+    fn enter_initialization_mode(
+        &mut self,
+        _tolerance: Option<f64>,
+        _start_time: f64,
+        _stop_time: Option<f64>
+    ) -> Fmi3Status {
+        Fmi3Status::Fmi3Ok
+    }
+
+    fn exit_initialization_mode(&mut self) -> Fmi3Status {
+        Fmi3Status::Fmi3Ok
+    }
+
+    fn enter_event_mode(&mut self) -> Fmi3Status {
+        Fmi3Status::Fmi3Ok
+    }
+
+    fn enter_step_mode(&mut self) -> Fmi3Status {
+        Fmi3Status::Fmi3Ok
+    }
+
+    fn enter_configuration_mode(&mut self) -> Fmi3Status {
+        Fmi3Status::Fmi3Ok
+    }
+
+    fn exit_configuration_mode(&mut self) -> Fmi3Status {
+        Fmi3Status::Fmi3Ok
+    }
+
+    fn update_discrete_states(&mut self) -> UpdateDiscreteStatesResult {
+        UpdateDiscreteStatesResult {
+            status: Fmi3Status::Fmi3Ok,
+            discrete_states_need_update: false,
+            terminate_simulation: false,
+            nominals_continuous_states_changed: false,
+            values_continuous_states_changed: false,
+            next_event_time: None
+        }
+    }
+
+    fn terminate(&mut self) -> Fmi3Status {
+        Fmi3Status::Fmi3Ok
+    }
+
+    /// Returns the complete state of the model as bytes that
+    /// Model::deserialize_fmu_state() can restore.
+    fn serialize_fmu_state(&mut self) -> (Fmi3Status, Vec<u8>) {
+        (Fmi3Status::Fmi3Error, Vec::new())
+    }
+
+    fn deserialize_fmu_state(&mut self, _state: &[u8]) -> Fmi3Status {
+        Fmi3Status::Fmi3Error
+    }
+
+    fn get_float32(&mut self, _references: &[u32]) -> (Fmi3Status, Vec<f32>) {
+        (Fmi3Status::Fmi3Error, Vec::new())
+    }
+
+    fn get_float64(&mut self, _references: &[u32]) -> (Fmi3Status, Vec<f64>) {
+        (Fmi3Status::Fmi3Error, Vec::new())
+    }
+
+    fn get_int8(&mut self, _references: &[u32]) -> (Fmi3Status, Vec<i8>) {
+        (Fmi3Status::Fmi3Error, Vec::new())
+    }
+
+    fn get_uint8(&mut self, _references: &[u32]) -> (Fmi3Status, Vec<u8>) {
+        (Fmi3Status::Fmi3Error, Vec::new())
+    }
+
+    fn get_int16(&mut self, _references: &[u32]) -> (Fmi3Status, Vec<i16>) {
+        (Fmi3Status::Fmi3Error, Vec::new())
+    }
+
+    fn get_uint16(&mut self, _references: &[u32]) -> (Fmi3Status, Vec<u16>) {
+        (Fmi3Status::Fmi3Error, Vec::new())
+    }
+
+    fn get_int32(&mut self, _references: &[u32]) -> (Fmi3Status, Vec<i32>) {
+        (Fmi3Status::Fmi3Error, Vec::new())
+    }
+
+    fn get_uint32(&mut self, _references: &[u32]) -> (Fmi3Status, Vec<u32>) {
+        (Fmi3Status::Fmi3Error, Vec::new())
+    }
+
+    fn get_int64(&mut self, _references: &[u32]) -> (Fmi3Status, Vec<i64>) {
+        (Fmi3Status::Fmi3Error, Vec::new())
+    }
+
+    fn get_uint64(&mut self, _references: &[u32]) -> (Fmi3Status, Vec<u64>) {
+        (Fmi3Status::Fmi3Error, Vec::new())
+    }
+
+    fn get_boolean(&mut self, _references: &[u32]) -> (Fmi3Status, Vec<bool>) {
+        (Fmi3Status::Fmi3Error, Vec::new())
+    }
+
+    fn get_string(&mut self, _references: &[u32]) -> (Fmi3Status, Vec<String>) {
+        (Fmi3Status::Fmi3Error, Vec::new())
+    }
+
+    fn get_binary(&mut self, _references: &[u32]) -> (Fmi3Status, Vec<Vec<u8>>) {
+        (Fmi3Status::Fmi3Error, Vec::new())
+    }
+
+    fn set_float32(&mut self, _references: &[u32], _values: &[f32]) -> Fmi3Status {
+        Fmi3Status::Fmi3Error
+    }
+
+    fn set_float64(&mut self, _references: &[u32], _values: &[f64]) -> Fmi3Status {
+        Fmi3Status::Fmi3Error
+    }
+
+    fn set_int8(&mut self, _references: &[u32], _values: &[i8]) -> Fmi3Status {
+        Fmi3Status::Fmi3Error
+    }
+
+    fn set_uint8(&mut self, _references: &[u32], _values: &[u8]) -> Fmi3Status {
+        Fmi3Status::Fmi3Error
+    }
+
+    fn set_int16(&mut self, _references: &[u32], _values: &[i16]) -> Fmi3Status {
+        Fmi3Status::Fmi3Error
+    }
+
+    fn set_uint16(&mut self, _references: &[u32], _values: &[u16]) -> Fmi3Status {
+        Fmi3Status::Fmi3Error
+    }
+
+    fn set_int32(&mut self, _references: &[u32], _values: &[i32]) -> Fmi3Status {
+        Fmi3Status::Fmi3Error
+    }
+
+    fn set_uint32(&mut self, _references: &[u32], _values: &[u32]) -> Fmi3Status {
+        Fmi3Status::Fmi3Error
+    }
+
+    fn set_int64(&mut self, _references: &[u32], _values: &[i64]) -> Fmi3Status {
+        Fmi3Status::Fmi3Error
+    }
+
+    fn set_uint64(&mut self, _references: &[u32], _values: &[u64]) -> Fmi3Status {
+        Fmi3Status::Fmi3Error
+    }
+
+    fn set_boolean(&mut self, _references: &[u32], _values: &[bool]) -> Fmi3Status {
+        Fmi3Status::Fmi3Error
+    }
+
+    fn set_string(&mut self, _references: &[u32], _values: &[String]) -> Fmi3Status {
+        Fmi3Status::Fmi3Error
+    }
+
+    fn set_binary(&mut self, _references: &[u32], _values: &[Vec<u8>]) -> Fmi3Status {
+        Fmi3Status::Fmi3Error
+    }
+
+    fn get_output_derivatives(
+        &mut self,
+        _references: &[u32],
+        _orders: &[i32]
+    ) -> (Fmi3Status, Vec<f64>) {
+        (Fmi3Status::Fmi3Error, Vec::new())
+    }
+
+    fn get_clock(&mut self, _references: &[u32]) -> (Fmi3Status, Vec<bool>) {
+        (Fmi3Status::Fmi3Error, Vec::new())
+    }
+
+    fn set_clock(&mut self, _references: &[u32], _values: &[bool]) -> Fmi3Status {
+        Fmi3Status::Fmi3Error
+    }
+}
+
+/// Sends log messages to the importer of the FMU.
+///
+/// A log message can only be sent while the backend is answering a command,
+/// that is from within one of the methods of the model.
+#[derive(Clone)]
+pub struct Logger {
+    socket: Rc<RefCell<Socket>>
+}
+
+impl Logger {
+    pub fn log(&self, status: Fmi3Status, category: &str, message: &str) {
+        if let Err(error) = self.send_log(status, category, message) {
+            eprintln!("couldn't send log message '{}'; {}", message, error);
+        }
+    }
+
+    fn send_log(
+        &self,
+        status: Fmi3Status,
+        category: &str,
+        message: &str
+    ) -> SocketResult<()> {
+        let mut socket = self.socket.borrow_mut();
+
+        socket.send(&Fmi3Return {
+            return_message: Some(ReturnMessage::Log(Fmi3LogReturn {
+                status: status as i32,
+                category: category.to_owned(),
+                log_message: message.to_owned()
+            }))
+        })?;
+
+        match socket.recv::<Fmi3Command>()?.command {
+            Some(Command::Fmi3CallbackContinue(Fmi3CallbackContinue {})) => Ok(()),
+            command => Err(SocketError::UnexpectedCommand(command_name(command.as_ref())))
+        }
+    }
+}
+
+/// Completes the handshake with the dispatcher, accepting the shared memory
+/// region it offered if the region can be mapped, and answers the commands
+/// of the dispatcher until the instance is freed.
+pub fn command_reply_loop<M: Model>(mut socket: Socket) -> SocketResult<()> {
+    let mut shared_memory = SharedMemory::offered();
+    socket.handshake(shared_memory.is_some())?;
+
+    let socket = Rc::new(RefCell::new(socket));
+    let logger = Logger {socket: socket.clone()};
+    let mut model: Option<M> = None;
+
+    loop {
+        let command = socket.borrow_mut().recv::<Fmi3Command>()?.command;
+
+        let reply = match (command, model.as_mut()) {
+            (Some(Command::Fmi3InstantiateCoSimulation(instance)), _) => {
+                model = Some(M::instantiate_co_simulation(&instance, logger.clone()));
+                ReturnMessage::Empty(Fmi3EmptyReturn {})
+            }
+            (Some(command @ Command::Fmi3InstantiateModelExchange(_)), _)
+            | (Some(command @ Command::Fmi3InstantiateScheduledExecution(_)), _) => {
+                unsupported_reply(command, &logger)?
+            }
+            (Some(Command::Fmi3FreeInstance(_)), _) => {
+                socket.borrow_mut().send(&Fmi3Return {
+                    return_message: Some(ReturnMessage::FreeInstance(
+                        Fmi3FreeInstanceReturn {}
+                    ))
+                })?;
+                eprintln!("Fmi3FreeInstance received, shutting down");
+                return Ok(());
+            }
+            (Some(command), Some(model)) => reply_to_command(
+                model,
+                command,
+                shared_memory.as_mut(),
+                &logger
+            )?,
+            (command, _) => {
+                return Err(SocketError::UnexpectedCommand(command_name(command.as_ref())));
+            }
+        };
+
+        socket.borrow_mut().send(&Fmi3Return {return_message: Some(reply)})?;
+    }
+}
+
+fn reply_to_command<M: Model>(
+    model: &mut M,
+    command: Command,
+    shared_memory: Option<&mut SharedMemory>,
+    logger: &Logger
+) -> SocketResult<ReturnMessage> {
+    let reply = match command {
+        Command::Fmi3DoStep(step) => {
+            let result = model.do_step(
+                step.current_communication_point,
+                step.communication_step_size,
+                step.no_set_fmu_state_prior_to_current_point
+            );
+            ReturnMessage::DoStep(Fmi3DoStepReturn {
+                status: result.status as i32,
+                event_handling_needed: result.event_handling_needed,
+                terminate_simulation: result.terminate_simulation,
+                early_return: result.early_return,
+                last_successful_time: result.last_successful_time
+            })
+        }
+        Command::Fmi3SetDebugLogging(logging) => status_reply(
+            model.set_debug_logging(&logging.categories, logging.logging_on)
+        ),
+        Command::Fmi3EnterInitializationMode(initialization) => status_reply(
+            model.enter_initialization_mode(
+                initialization.tolerance.filter(|_| initialization.tolerance_defined),
+                initialization.start_time,
+                initialization.stop_time.filter(|_| initialization.stop_time_defined)
+            )
+        ),
+        Command::Fmi3ExitInitializationMode(_) => status_reply(
+            model.exit_initialization_mode()
+        ),
+        Command::Fmi3EnterEventMode(_) => status_reply(model.enter_event_mode()),
+        Command::Fmi3EnterStepMode(_) => status_reply(model.enter_step_mode()),
+        Command::Fmi3EnterConfigurationMode(_) => status_reply(
+            model.enter_configuration_mode()
+        ),
+        Command::Fmi3ExitConfigurationMode(_) => status_reply(
+            model.exit_configuration_mode()
+        ),
+        Command::Fmi3UpdateDiscreteStates(_) => {
+            let result = model.update_discrete_states();
+            ReturnMessage::UpdateDiscreteStates(Fmi3UpdateDiscreteStatesReturn {
+                status: result.status as i32,
+                discrete_states_need_update: result.discrete_states_need_update,
+                terminate_simulation: result.terminate_simulation,
+                nominals_continuous_states_changed: result.nominals_continuous_states_changed,
+                values_continuous_states_changed: result.values_continuous_states_changed,
+                next_event_time_defined: result.next_event_time.is_some(),
+                next_event_time: result.next_event_time.unwrap_or_default()
+            })
+        }
+        Command::Fmi3Terminate(_) => status_reply(model.terminate()),
+        Command::Fmi3Reset(_) => status_reply(model.reset()),
+        Command::Fmi3SerializeFmuState(_) => {
+            let (status, state) = model.serialize_fmu_state();
+            ReturnMessage::SerializeFmuState(Fmi3SerializeFmuStateReturn {
+                status: status as i32,
+                state
+            })
+        }
+        Command::Fmi3DeserializeFmuState(deserialize) => status_reply(
+            model.deserialize_fmu_state(&deserialize.state)
+        ),
+        Command::Fmi3GetFloat32(get) => {
+            let (status, values) = model.get_float32(&get.value_references);
+            ReturnMessage::GetFloat32(Fmi3GetFloat32Return {status: status as i32, values})
+        }
+        Command::Fmi3GetFloat64(get) => {
+            let (status, values) = model.get_float64(&get.value_references);
+            ReturnMessage::GetFloat64(Fmi3GetFloat64Return {status: status as i32, values})
+        }
+        Command::Fmi3GetInt8(get) => {
+            let (status, values) = model.get_int8(&get.value_references);
+            ReturnMessage::GetInt8(Fmi3GetInt8Return {status: status as i32, values: widen(values)})
+        }
+        Command::Fmi3GetUInt8(get) => {
+            let (status, values) = model.get_uint8(&get.value_references);
+            ReturnMessage::GetUInt8(Fmi3GetUInt8Return {status: status as i32, values: widen(values)})
+        }
+        Command::Fmi3GetInt16(get) => {
+            let (status, values) = model.get_int16(&get.value_references);
+            ReturnMessage::GetInt16(Fmi3GetInt16Return {status: status as i32, values: widen(values)})
+        }
+        Command::Fmi3GetUInt16(get) => {
+            let (status, values) = model.get_uint16(&get.value_references);
+            ReturnMessage::GetUInt16(Fmi3GetUInt16Return {status: status as i32, values: widen(values)})
+        }
+        Command::Fmi3GetInt32(get) => {
+            let (status, values) = model.get_int32(&get.value_references);
+            ReturnMessage::GetInt32(Fmi3GetInt32Return {status: status as i32, values})
+        }
+        Command::Fmi3GetUInt32(get) => {
+            let (status, values) = model.get_uint32(&get.value_references);
+            ReturnMessage::GetUInt32(Fmi3GetUInt32Return {status: status as i32, values})
+        }
+        Command::Fmi3GetInt64(get) => {
+            let (status, values) = model.get_int64(&get.value_references);
+            ReturnMessage::GetInt64(Fmi3GetInt64Return {status: status as i32, values})
+        }
+        Command::Fmi3GetUInt64(get) => {
+            let (status, values) = model.get_uint64(&get.value_references);
+            ReturnMessage::GetUInt64(Fmi3GetUInt64Return {status: status as i32, values})
+        }
+        Command::Fmi3GetBoolean(get) => {
+            let (status, values) = model.get_boolean(&get.value_references);
+            ReturnMessage::GetBoolean(Fmi3GetBooleanReturn {status: status as i32, values})
+        }
+        Command::Fmi3GetString(get) => {
+            let (status, values) = model.get_string(&get.value_references);
+            ReturnMessage::GetString(Fmi3GetStringReturn {status: status as i32, values})
+        }
+        Command::Fmi3GetBinary(get) => {
+            let (status, values) = model.get_binary(&get.value_references);
+            ReturnMessage::GetBinary(Fmi3GetBinaryReturn {status: status as i32, values})
+        }
+        Command::Fmi3SetFloat32(set) => status_reply(
+            model.set_float32(&set.value_references, &set.values)
+        ),
+        Command::Fmi3SetFloat64(set) => status_reply(
+            model.set_float64(&set.value_references, &set.values)
+        ),
+        Command::Fmi3GetFloat64Shared(get) if shared_memory.is_some() => {
+            let region = shared_memory.expect("the region should be mapped");
+            let n_value_references = get.n_value_references as usize;
+            let (status, values) = model.get_float64(
+                &region.value_references(n_value_references)
+            );
+            if values.len() == get.n_values as usize {
+                region.place_float64(n_value_references, &values);
+            }
+            ReturnMessage::GetFloat64Shared(Fmi3GetFloat64SharedReturn {
+                status: status as i32,
+                n_values: values.len() as u64
+            })
+        }
+        Command::Fmi3SetFloat64Shared(set) if shared_memory.is_some() => {
+            let region = shared_memory.expect("the region should be mapped");
+            let n_value_references = set.n_value_references as usize;
+            status_reply(model.set_float64(
+                &region.value_references(n_value_references),
+                &region.float64(n_value_references, set.n_values as usize)
+            ))
+        }
+        command @ (Command::Fmi3GetFloat64Shared(_) | Command::Fmi3SetFloat64Shared(_)) => {
+            return Err(SocketError::UnexpectedCommand(command_name(Some(&command))));
+        }
+        Command::Fmi3SetInt8(set) => status_reply(
+            model.set_int8(&set.value_references, &narrow(&set.values))
+        ),
+        Command::Fmi3SetUInt8(set) => status_reply(
+            model.set_uint8(&set.value_references, &narrow(&set.values))
+        ),
+        Command::Fmi3SetInt16(set) => status_reply(
+            model.set_int16(&set.value_references, &narrow(&set.values))
+        ),
+        Command::Fmi3SetUInt16(set) => status_reply(
+            model.set_uint16(&set.value_references, &narrow(&set.values))
+        ),
+        Command::Fmi3SetInt32(set) => status_reply(
+            model.set_int32(&set.value_references, &set.values)
+        ),
+        Command::Fmi3SetUInt32(set) => status_reply(
+            model.set_uint32(&set.value_references, &set.values)
+        ),
+        Command::Fmi3SetInt64(set) => status_reply(
+            model.set_int64(&set.value_references, &set.values)
+        ),
+        Command::Fmi3SetUInt64(set) => status_reply(
+            model.set_uint64(&set.value_references, &set.values)
+        ),
+        Command::Fmi3SetBoolean(set) => status_reply(
+            model.set_boolean(&set.value_references, &set.values)
+        ),
+        Command::Fmi3SetString(set) => status_reply(
+            model.set_string(&set.value_references, &set.values)
+        ),
+        Command::Fmi3SetBinary(set) => status_reply(
+            model.set_binary(&set.value_references, &set.values)
+        ),
+        Command::Fmi3GetOutputDerivatives(get) => {
+            let (status, values) = model.get_output_derivatives(
+                &get.value_references,
+                &get.orders
+            );
+            ReturnMessage::GetOutputDerivatives(Fmi3GetOutputDerivativesReturn {
+                status: status as i32,
+                values
+            })
+        }
+        Command::Fmi3GetClock(get) => {
+            let (status, values) = model.get_clock(&get.value_references);
+            ReturnMessage::GetClock(Fmi3GetClockReturn {status: status as i32, values})
+        }
+        Command::Fmi3SetClock(set) => status_reply(
+            model.set_clock(&set.value_references, &set.values)
+        ),
+        command => unsupported_reply(command, logger)?
+    };
+
+    Ok(reply)
+}
+
+/// Logs that the Rust backend doesn't support the command and replies with
+/// Fmi3Status::Fmi3Error.
+fn unsupported_reply(
+    command: Command,
+    logger: &Logger
+) -> SocketResult<ReturnMessage> {
+    let name = command_name(Some(&command));
+    let error = Fmi3Status::Fmi3Error as i32;
+
+    let reply = match command {
+        Command::Fmi3GetDirectionalDerivative(_) => ReturnMessage::GetDirectionalDerivative(
+            Fmi3GetDirectionalDerivativeReturn {status: error, ..Default::default()}
+        ),
+        Command::Fmi3GetAdjointDerivative(_) => ReturnMessage::GetAdjointDerivative(
+            Fmi3GetAdjointDerivativeReturn {status: error, ..Default::default()}
+        ),
+        Command::Fmi3GetIntervalDecimal(_) => ReturnMessage::GetIntervalDecimal(
+            Fmi3GetIntervalDecimalReturn {status: error, ..Default::default()}
+        ),
+        Command::Fmi3GetIntervalFraction(_) => ReturnMessage::GetIntervalFraction(
+            Fmi3GetIntervalFractionReturn {status: error, ..Default::default()}
+        ),
+        Command::Fmi3GetShiftDecimal(_) => ReturnMessage::GetShiftDecimal(
+            Fmi3GetShiftDecimalReturn {status: error, ..Default::default()}
+        ),
+        Command::Fmi3GetShiftFraction(_) => ReturnMessage::GetShiftFraction(
+            Fmi3GetShiftFractionReturn {status: error, ..Default::default()}
+        ),
+        Command::Fmi3CompletedIntegratorStep(_) => ReturnMessage::CompletedIntegratorStep(
+            Fmi3CompletedIntegratorStepReturn {status: error, ..Default::default()}
+        ),
+        Command::Fmi3GetContinuousStateDerivatives(_) => ReturnMessage::GetContinuousStateDerivatives(
+            Fmi3GetContinuousStateDerivativesReturn {status: error, ..Default::default()}
+        ),
+        Command::Fmi3GetEventIndicators(_) => ReturnMessage::GetEventIndicators(
+            Fmi3GetEventIndicatorsReturn {status: error, ..Default::default()}
+        ),
+        Command::Fmi3GetContinuousStates(_) => ReturnMessage::GetContinuousStates(
+            Fmi3GetContinuousStatesReturn {status: error, ..Default::default()}
+        ),
+        Command::Fmi3GetNominalsOfContinuousStates(_) => ReturnMessage::GetNominalsOfContinuousStates(
+            Fmi3GetNominalsOfContinuousStatesReturn {status: error, ..Default::default()}
+        ),
+        Command::Fmi3GetNumberOfEventIndicators(_) => ReturnMessage::GetNumberOfEventIndicators(
+            Fmi3GetNumberOfEventIndicatorsReturn {status: error, ..Default::default()}
+        ),
+        Command::Fmi3GetNumberOfContinuousStates(_) => ReturnMessage::GetNumberOfContinuousStates(
+            Fmi3GetNumberOfContinuousStatesReturn {status: error, ..Default::default()}
+        ),
+        Command::Fmi3InstantiateModelExchange(_)
+        | Command::Fmi3InstantiateScheduledExecution(_)
+        | Command::Fmi3SetIntervalDecimal(_)
+        | Command::Fmi3SetIntervalFraction(_)
+        | Command::Fmi3SetShiftDecimal(_)
+        | Command::Fmi3SetShiftFraction(_)
+        | Command::Fmi3EnterContinuousTimeMode(_)
+        | Command::Fmi3SetTime(_)
+        | Command::Fmi3SetContinuousStates(_) => status_reply(Fmi3Status::Fmi3Error),
+        _ => return Err(SocketError::UnexpectedCommand(name))
+    };
+
+    logger.log(
+        Fmi3Status::Fmi3Error,
+        "logStatusError",
+        &format!("{} isn't supported by the Rust backend", name)
+    );
+
+    Ok(reply)
+}
+
+fn status_reply(status: Fmi3Status) -> ReturnMessage {
+    ReturnMessage::Status(Fmi3StatusReturn {status: status as i32})
+}
+
+/// Converts values of a model type into the wider type that the protobuf
+/// messages carry them in.
+fn widen<T, W: From<T>>(values: Vec<T>) -> Vec<W> {
+    values.into_iter().map(W::from).collect()
+}
+
+/// Converts values from the wider type of the protobuf messages into the
+/// model type. The values were narrowed by the FMU binary before being widened
+/// for sending, so they always fit.
+fn narrow<W: Copy, T: TryFrom<W>>(values: &[W]) -> Vec<T> {
+    values.iter()
+        .filter_map(|&value| T::try_from(value).ok())
+        .collect()
+}
+
+/// The name of the command without its arguments.
+fn command_name(command: Option<&Command>) -> String {
+    match command {
+        Some(command) => format!("{:?}", command)
+            .split('(')
+            .next()
+            .unwrap_or_default()
+            .to_owned(),
+        None => String::from("empty command")
+    }
+}
+
+/// The shared memory region offered by the dispatcher, through which bulk
+/// values are exchanged instead of through the socket.
+///
+/// An exchange places the value references as u32 from the start of the
+/// region, followed by the values from the next multiple of 8 bytes, all in
+/// the native byte order.
+struct SharedMemory {
+    map: MmapMut
+}
+
+impl SharedMemory {
+    /// Maps the region offered through the UNIFMU_SHARED_MEMORY environment
+    /// variable, if any.
+    fn offered() -> Option<Self> {
+        let path = std::env::var_os("UNIFMU_SHARED_MEMORY")?;
+
+        // The dispatcher never truncates the region while the backend runs.
+        let map = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(path)
+            .and_then(|file| unsafe { MmapMut::map_mut(&file) });
+
+        match map {
+            Ok(map) => Some(Self {map}),
+            Err(error) => {
+                eprintln!("couldn't map the shared memory region, exchanging values through the socket; {}", error);
+                None
+            }
+        }
+    }
+
+    fn value_references(&self, n_value_references: usize) -> Vec<u32> {
+        self.map[..n_value_references * 4]
+            .chunks_exact(4)
+            .map(|bytes| u32::from_ne_bytes(bytes.try_into().expect("chunks should be 4 bytes")))
+            .collect()
+    }
+
+    fn float64(&self, n_value_references: usize, n_values: usize) -> Vec<f64> {
+        let offset = values_offset(n_value_references);
+        self.map[offset..offset + n_values * 8]
+            .chunks_exact(8)
+            .map(|bytes| f64::from_ne_bytes(bytes.try_into().expect("chunks should be 8 bytes")))
+            .collect()
+    }
+
+    fn place_float64(&mut self, n_value_references: usize, values: &[f64]) {
+        let offset = values_offset(n_value_references);
+        for (bytes, value) in self.map[offset..offset + values.len() * 8]
+            .chunks_exact_mut(8)
+            .zip(values)
+        {
+            bytes.copy_from_slice(&value.to_ne_bytes());
+        }
+    }
+}
+
+/// The offset of the values following the given number of value references.
+fn values_offset(n_value_references: usize) -> usize {
+    (n_value_references * 4).next_multiple_of(8)
+}
@@ -0,0 +1,333 @@
+//! The model of the FMU, an adder whose outputs are the sums of its inputs.
+//!
+//! Each type of variable has the inputs `a` and `b` and the output `c`, held
+//! in an array in that order. The value references of the variables of a
+//! type follow each other, starting from the reference of `a`.
+
+use serde::{Deserialize, Serialize};
+
+use crate::backend::{self, DoStepResult, Fmi3Status, Logger};
+use crate::messages::Fmi3InstantiateCoSimulation;
+
+const TIME_REFERENCE: u32 = 999;
+
+#[derive(Serialize, Deserialize)]
+pub struct Model {
+    time: f64,
+    float32: [f32; 3],
+    float64: [f64; 3],
+    int8: [i8; 3],
+    uint8: [u8; 3],
+    int16: [i16; 3],
+    uint16: [u16; 3],
+    int32: [i32; 3],
+    uint32: [u32; 3],
+    int64: [i64; 3],
+    uint64: [u64; 3],
+    boolean: [bool; 3],
+    string: [String; 3],
+    #[serde(skip)]
+    logger: Option<Logger>
+}
+
+impl Model {
+    fn new(logger: Logger) -> Self {
+        let mut model = Self {
+            time: 0.0,
+            float32: [0.0; 3],
+            float64: [0.0; 3],
+            int8: [0; 3],
+            uint8: [0; 3],
+            int16: [0; 3],
+            uint16: [0; 3],
+            int32: [0; 3],
+            uint32: [0; 3],
+            int64: [0; 3],
+            uint64: [0; 3],
+            boolean: [false; 3],
+            string: Default::default(),
+            logger: Some(logger)
+        };
+        model.update_outputs();
+        model
+    }
+
+    fn update_outputs(&mut self) {
+        self.float32[2] = self.float32[0] + self.float32[1];
+        self.float64[2] = self.float64[0] + self.float64[1];
+        self.int8[2] = self.int8[0].wrapping_add(self.int8[1]);
+        self.uint8[2] = self.uint8[0].wrapping_add(self.uint8[1]);
+        self.int16[2] = self.int16[0].wrapping_add(self.int16[1]);
+        self.uint16[2] = self.uint16[0].wrapping_add(self.uint16[1]);
+        self.int32[2] = self.int32[0].wrapping_add(self.int32[1]);
+        self.uint32[2] = self.uint32[0].wrapping_add(self.uint32[1]);
+        self.int64[2] = self.int64[0].wrapping_add(self.int64[1]);
+        self.uint64[2] = self.uint64[0].wrapping_add(self.uint64[1]);
+        self.boolean[2] = self.boolean[0] || self.boolean[1];
+        self.string[2] = format!("{}{}", self.string[0], self.string[1]);
+    }
+
+    fn log_error(&self, message: &str) {
+        if let Some(logger) = &self.logger {
+            logger.log(Fmi3Status::Fmi3Error, "logStatusError", message);
+        }
+    }
+
+    /// Gets the variable of each value reference, logging any reference that
+    /// isn't a variable of the type.
+    fn get<T>(
+        &self,
+        references: &[u32],
+        variable: impl Fn(u32) -> Option<T>
+    ) -> (Fmi3Status, Vec<T>) {
+        let mut values = Vec::with_capacity(references.len());
+
+        for &reference in references {
+            match variable(reference) {
+                Some(value) => values.push(value),
+                None => {
+                    self.log_error(&format!("no variable with value reference {}", reference));
+                    return (Fmi3Status::Fmi3Error, Vec::new());
+                }
+            }
+        }
+
+        (Fmi3Status::Fmi3Ok, values)
+    }
+
+    /// Sets the variables of a type, returning the first reference that isn't
+    /// a variable of the type.
+    fn set<T: Clone>(
+        variables: &mut [T; 3],
+        first_reference: u32,
+        references: &[u32],
+        values: &[T]
+    ) -> Result<(), u32> {
+        for (&reference, value) in references.iter().zip(values) {
+            let variable = reference.checked_sub(first_reference)
+                .and_then(|index| variables.get_mut(index as usize))
+                .ok_or(reference)?;
+
+            *variable = value.clone();
+        }
+
+        Ok(())
+    }
+
+    fn set_status(&self, result: Result<(), u32>) -> Fmi3Status {
+        match result {
+            Ok(()) => Fmi3Status::Fmi3Ok,
+            Err(reference) => {
+                self.log_error(&format!("no variable with value reference {}", reference));
+                Fmi3Status::Fmi3Error
+            }
+        }
+    }
+}
+
+impl backend::Model for Model {
+    fn instantiate_co_simulation(
+        _instance: &Fmi3InstantiateCoSimulation,
+        logger: Logger
+    ) -> Self {
+        Self::new(logger)
+    }
+
+    fn do_step(
+        &mut self,
+        current_communication_point: f64,
+        communication_step_size: f64,
+        _no_set_fmu_state_prior_to_current_point: bool
+    ) -> DoStepResult {
+        self.update_outputs();
+        self.time = current_communication_point + communication_step_size;
+
+        DoStepResult {
+            status: Fmi3Status::Fmi3Ok,
+            event_handling_needed: false,
+            terminate_simulation: false,
+            early_return: false,
+            last_successful_time: self.time
+        }
+    }
+
+    fn reset(&mut self) -> Fmi3Status {
+        *self = Self::new(self.logger.take().expect("the model always has a logger"));
+        Fmi3Status::Fmi3Ok
+    }
+
+    fn enter_initialization_mode(
+        &mut self,
+        _tolerance: Option<f64>,
+        start_time: f64,
+        _stop_time: Option<f64>
+    ) -> Fmi3Status {
+        self.time = start_time;
+        Fmi3Status::Fmi3Ok
+    }
+
+    fn exit_initialization_mode(&mut self) -> Fmi3Status {
+        self.update_outputs();
+        Fmi3Status::Fmi3Ok
+    }
+
+    fn serialize_fmu_state(&mut self) -> (Fmi3Status, Vec<u8>) {
+        match serde_json::to_vec(self) {
+            Ok(state) => (Fmi3Status::Fmi3Ok, state),
+            Err(error) => {
+                self.log_error(&format!("couldn't serialize state; {}", error));
+                (Fmi3Status::Fmi3Error, Vec::new())
+            }
+        }
+    }
+
+    fn deserialize_fmu_state(&mut self, state: &[u8]) -> Fmi3Status {
+        match serde_json::from_slice::<Self>(state) {
+            Ok(model) => {
+                *self = Self {logger: self.logger.take(), ..model};
+                Fmi3Status::Fmi3Ok
+            }
+            Err(error) => {
+                self.log_error(&format!("couldn't deserialize state; {}", error));
+                Fmi3Status::Fmi3Error
+            }
+        }
+    }
+
+    fn get_float32(&mut self, references: &[u32]) -> (Fmi3Status, Vec<f32>) {
+        self.get(references, |reference| variable(&self.float32, 0, reference))
+    }
+
+    fn get_float64(&mut self, references: &[u32]) -> (Fmi3Status, Vec<f64>) {
+        self.get(references, |reference| match reference {
+            TIME_REFERENCE => Some(self.time),
+            _ => variable(&self.float64, 3, reference)
+        })
+    }
+
+    fn get_int8(&mut self, references: &[u32]) -> (Fmi3Status, Vec<i8>) {
+        self.get(references, |reference| variable(&self.int8, 6, reference))
+    }
+
+    fn get_uint8(&mut self, references: &[u32]) -> (Fmi3Status, Vec<u8>) {
+        self.get(references, |reference| variable(&self.uint8, 9, reference))
+    }
+
+    fn get_int16(&mut self, references: &[u32]) -> (Fmi3Status, Vec<i16>) {
+        self.get(references, |reference| variable(&self.int16, 12, reference))
+    }
+
+    fn get_uint16(&mut self, references: &[u32]) -> (Fmi3Status, Vec<u16>) {
+        self.get(references, |reference| variable(&self.uint16, 15, reference))
+    }
+
+    fn get_int32(&mut self, references: &[u32]) -> (Fmi3Status, Vec<i32>) {
+        self.get(references, |reference| variable(&self.int32, 18, reference))
+    }
+
+    fn get_uint32(&mut self, references: &[u32]) -> (Fmi3Status, Vec<u32>) {
+        self.get(references, |reference| variable(&self.uint32, 21, reference))
+    }
+
+    fn get_int64(&mut self, references: &[u32]) -> (Fmi3Status, Vec<i64>) {
+        self.get(references, |reference| variable(&self.int64, 24, reference))
+    }
+
+    fn get_uint64(&mut self, references: &[u32]) -> (Fmi3Status, Vec<u64>) {
+        self.get(references, |reference| variable(&self.uint64, 27, reference))
+    }
+
+    fn get_boolean(&mut self, references: &[u32]) -> (Fmi3Status, Vec<bool>) {
+        self.get(references, |reference| variable(&self.boolean, 30, reference))
+    }
+
+    fn get_string(&mut self, references: &[u32]) -> (Fmi3Status, Vec<String>) {
+        self.get(references, |reference| variable(&self.string, 33, reference))
+    }
+
+    fn set_float32(&mut self, references: &[u32], values: &[f32]) -> Fmi3Status {
+        let result = Self::set(&mut self.float32, 0, references, values);
+        self.set_status(result)
+    }
+
+    fn set_float64(&mut self, references: &[u32], values: &[f64]) -> Fmi3Status {
+        let result = Self::set(&mut self.float64, 3, references, values);
+        self.set_status(result)
+    }
+
+    fn set_int8(&mut self, references: &[u32], values: &[i8]) -> Fmi3Status {
+        let result = Self::set(&mut self.int8, 6, references, values);
+        self.set_status(result)
+    }
+
+    fn set_uint8(&mut self, references: &[u32], values: &[u8]) -> Fmi3Status {
+        let result = Self::set(&mut self.uint8, 9, references, values);
+        self.set_status(result)
+    }
+
+    fn set_int16(&mut self, references: &[u32], values: &[i16]) -> Fmi3Status {
+        let result = Self::set(&mut self.int16, 12, references, values);
+        self.set_status(result)
+    }
+
+    fn set_uint16(&mut self, references: &[u32], values: &[u16]) -> Fmi3Status {
+        let result = Self::set(&mut self.uint16, 15, references, values);
+        self.set_status(result)
+    }
+
+    fn set_int32(&mut self, references: &[u32], values: &[i32]) -> Fmi3Status {
+        let result = Self::set(&mut self.int32, 18, references, values);
+        self.set_status(result)
+    }
+
+    fn set_uint32(&mut self, references: &[u32], values: &[u32]) -> Fmi3Status {
+        let result = Self::set(&mut self.uint32, 21, references, values);
+        self.set_status(result)
+    }
+
+    fn set_int64(&mut self, references: &[u32], values: &[i64]) -> Fmi3Status {
+        let result = Self::set(&mut self.int64, 24, references, values);
+        self.set_status(result)
+    }
+
+    fn set_uint64(&mut self, references: &[u32], values: &[u64]) -> Fmi3Status {
+        let result = Self::set(&mut self.uint64, 27, references, values);
+        self.set_status(result)
+    }
+
+    fn set_boolean(&mut self, references: &[u32], values: &[bool]) -> Fmi3Status {
+        let result = Self::set(&mut self.boolean, 30, references, values);
+        self.set_status(result)
+    }
+
+    fn set_string(&mut self, references: &[u32], values: &[String]) -> Fmi3Status {
+        let result = Self::set(&mut self.string, 33, references, values);
+        self.set_status(result)
+    }
+
+    fn get_output_derivatives(
+        &mut self,
+        references: &[u32],
+        _orders: &[i32]
+    ) -> (Fmi3Status, Vec<f64>) {
+        // The inputs are constant between steps, so the outputs float32_c and
+        // float64_c never change within a step.
+        if references.iter().all(|reference| [2, 5].contains(reference)) {
+            (Fmi3Status::Fmi3Ok, vec![0.0; references.len()])
+        } else {
+            self.log_error("only float32_c and float64_c have output derivatives");
+            (Fmi3Status::Fmi3Error, Vec::new())
+        }
+    }
+}
+
+/// The variable of the value reference among the variables of a type.
+fn variable<T: Clone>(
+    variables: &[T; 3],
+    first_reference: u32,
+    reference: u32
+) -> Option<T> {
+    reference.checked_sub(first_reference)
+        .and_then(|index| variables.get(index as usize))
+        .cloned()
+}
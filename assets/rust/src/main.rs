@@ -0,0 +1,39 @@
+//! Backend of the FMU, running the model of `model.rs` for the FMU binary
+//! that launched it.
+
+mod backend;
+mod model;
+mod socket;
+
+// The protobuf messages exchanged with the FMU binary, as generated by
+// prost-build from the schemas of UniFMU.
+#[allow(dead_code, clippy::all)]
+mod messages;
+#[allow(dead_code, clippy::all)]
+mod unifmu_handshake;
+
+use std::process::ExitCode;
+
+use socket::Socket;
+
+fn main() -> ExitCode {
+    let dispatcher_endpoint = match std::env::var("UNIFMU_DISPATCHER_ENDPOINT") {
+        Ok(endpoint) => endpoint,
+        Err(_) => {
+            eprintln!("the environment variable UNIFMU_DISPATCHER_ENDPOINT isn't set; the backend must be launched by the FMU");
+            return ExitCode::FAILURE;
+        }
+    };
+    eprintln!("dispatcher endpoint received: {}", dispatcher_endpoint);
+
+    let result = Socket::connect(&dispatcher_endpoint)
+        .and_then(backend::command_reply_loop::<model::Model>);
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("backend shutting down: {}", error);
+            ExitCode::FAILURE
+        }
+    }
+}
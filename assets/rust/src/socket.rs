@@ -0,0 +1,103 @@
+//! The ZeroMQ socket through which the backend exchanges messages with the
+//! dispatcher of the FMU binary.
+
+use std::fmt::Display;
+
+use prost::{DecodeError, Message};
+use tokio::runtime::Runtime;
+use zeromq::{ReqSocket, Socket as _, SocketRecv, SocketSend, ZmqError};
+
+use crate::unifmu_handshake::{HandshakeReply, HandshakeStatus};
+
+/// The request socket of the backend.
+///
+/// The dispatcher opens every exchange by replying to a message of the
+/// backend, so the backend must alternate between sending a message with
+/// Socket::send() and receiving the next command with Socket::recv().
+pub struct Socket {
+    runtime: Runtime,
+    socket: ReqSocket
+}
+
+impl Socket {
+    pub fn connect(endpoint: &str) -> SocketResult<Self> {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(SocketError::Runtime)?;
+
+        let mut socket = ReqSocket::new();
+
+        runtime.block_on(socket.connect(endpoint))
+            .map_err(SocketError::Connect)?;
+
+        Ok(Self {runtime, socket})
+    }
+
+    /// Tells the dispatcher that the backend is ready for commands, and
+    /// whether it accepted the shared memory region offered by the
+    /// dispatcher.
+    pub fn handshake(&mut self, shared_memory: bool) -> SocketResult<()> {
+        self.send(&HandshakeReply {
+            status: HandshakeStatus::Ok as i32,
+            shared_memory
+        })
+    }
+
+    pub fn send<M: Message>(&mut self, message: &M) -> SocketResult<()> {
+        self.runtime.block_on(self.socket.send(message.encode_to_vec().into()))
+            .map_err(SocketError::Send)
+    }
+
+    pub fn recv<M: Message + Default>(&mut self) -> SocketResult<M> {
+        let message = self.runtime.block_on(self.socket.recv())
+            .map_err(SocketError::Receive)?;
+
+        let bytes = message.get(0).ok_or(SocketError::EmptyMessage)?;
+
+        M::decode(bytes.as_ref()).map_err(SocketError::Decode)
+    }
+}
+
+pub type SocketResult<T> = Result<T, SocketError>;
+
+#[derive(Debug)]
+pub enum SocketError {
+    Runtime(std::io::Error),
+    Connect(ZmqError),
+    Send(ZmqError),
+    Receive(ZmqError),
+    EmptyMessage,
+    Decode(DecodeError),
+    UnexpectedCommand(String)
+}
+
+impl Display for SocketError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Runtime(error) => write!(
+                f, "couldn't start the runtime of the socket; {}", error
+            ),
+            Self::Connect(error) => write!(
+                f, "couldn't connect to the dispatcher; {}", error
+            ),
+            Self::Send(error) => write!(
+                f, "sending on socket failed; {}", error
+            ),
+            Self::Receive(error) => write!(
+                f, "receiving on socket failed; {}", error
+            ),
+            Self::EmptyMessage => write!(
+                f, "no bytes in received message"
+            ),
+            Self::Decode(error) => write!(
+                f, "couldn't decode received message; {}", error
+            ),
+            Self::UnexpectedCommand(command) => write!(
+                f, "unexpected command '{}' received", command
+            )
+        }
+    }
+}
+
+impl std::error::Error for SocketError {}
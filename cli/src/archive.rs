@@ -0,0 +1,194 @@
+//! Contains the `pack` and `unpack` commands, which convert an FMU between
+//! the directory that is convenient to edit and the `.fmu` archive that
+//! importers expect.
+
+use std::{
+    error::Error,
+    fmt::Display,
+    fs::{self, File},
+    path::{Path, PathBuf}
+};
+use walkdir::WalkDir;
+use zip::{result::ZipError, CompressionMethod, ZipArchive};
+
+use crate::utils::zip_dir;
+
+/// Compresses the FMU in the given directory into an `.fmu` archive with the
+/// `modelDescription.xml` at its root, and returns the path of the archive.
+///
+/// The archive is written to the given path, or next to the directory with
+/// its name and the `.fmu` extension if none is given. Files are added in
+/// order of their paths, so that packing the same directory twice gives
+/// archives with the same layout. A reproducible archive is moreover the same
+/// byte for byte, as described for [`zip_dir`].
+pub fn pack(
+    fmu_directory: &Path,
+    outpath: Option<&Path>,
+    reproducible: bool
+) -> ArchiveResult<PathBuf> {
+    if !fmu_directory.join("modelDescription.xml").is_file() {
+        return Err(ArchiveError::NotAnFmu(fmu_directory.to_path_buf()));
+    }
+
+    let archive_path = match outpath {
+        Some(path) => path.to_path_buf(),
+        None => fmu_directory.with_extension("fmu")
+    };
+
+    if is_inside(&archive_path, fmu_directory) {
+        return Err(ArchiveError::ArchiveInsideFmu(archive_path));
+    }
+
+    let prefix = fmu_directory
+        .to_str()
+        .ok_or_else(|| ArchiveError::NotAnFmu(fmu_directory.to_path_buf()))?;
+
+    let file = File::create(&archive_path)
+        .map_err(|error| ArchiveError::IoError(archive_path.clone(), error))?;
+
+    let walkdir = WalkDir::new(fmu_directory).sort_by_file_name();
+
+    zip_dir(
+        &mut walkdir.into_iter().filter_map(|e| e.ok()),
+        prefix,
+        file,
+        CompressionMethod::Deflated,
+        reproducible,
+    )?;
+
+    Ok(archive_path)
+}
+
+/// Compresses the FMU in the given directory like `pack()`, replacing the
+/// file at the given path, if any, with the archive.
+///
+/// The archive is written next to the file under a temporary name and only
+/// renamed over the file once it is complete, so that the file is kept as it
+/// was if packing fails. The archive takes the permissions of the file.
+pub fn pack_replacing(
+    fmu_directory: &Path,
+    archive_path: &Path,
+    reproducible: bool
+) -> ArchiveResult<()> {
+    let Ok(metadata) = fs::metadata(archive_path) else {
+        // There is no file to keep.
+        pack(fmu_directory, Some(archive_path), reproducible)?;
+        return Ok(());
+    };
+
+    let directory = parent_directory(archive_path);
+
+    let staged = tempfile::Builder::new()
+        .prefix(".unifmu")
+        .suffix(".fmu")
+        .tempfile_in(directory)
+        .map_err(|error| ArchiveError::IoError(directory.to_path_buf(), error))?;
+
+    pack(fmu_directory, Some(staged.path()), reproducible)?;
+
+    fs::set_permissions(staged.path(), metadata.permissions())
+        .map_err(|error| ArchiveError::IoError(staged.path().to_path_buf(), error))?;
+
+    staged
+        .persist(archive_path)
+        .map_err(|error| ArchiveError::IoError(archive_path.to_path_buf(), error.error))?;
+
+    Ok(())
+}
+
+/// Extracts the given `.fmu` archive into a directory, and returns the path
+/// of the directory.
+///
+/// The archive is extracted into the given path, or next to the archive
+/// with its name stripped of the extension if none is given. The directory
+/// must not exist or be empty, so that no files of another FMU are mixed in.
+pub fn unpack(archive_path: &Path, outpath: Option<&Path>) -> ArchiveResult<PathBuf> {
+    let file = File::open(archive_path)
+        .map_err(|error| ArchiveError::IoError(archive_path.to_path_buf(), error))?;
+
+    let mut archive = ZipArchive::new(file)?;
+
+    if archive.index_for_name("modelDescription.xml").is_none() {
+        return Err(ArchiveError::NotAnFmu(archive_path.to_path_buf()));
+    }
+
+    let fmu_directory = match outpath {
+        Some(path) => path.to_path_buf(),
+        None => archive_path.with_extension("")
+    };
+
+    if fmu_directory.exists() && !is_empty_directory(&fmu_directory) {
+        return Err(ArchiveError::FileExists(fmu_directory));
+    }
+
+    fs::create_dir_all(&fmu_directory)
+        .map_err(|error| ArchiveError::IoError(fmu_directory.clone(), error))?;
+
+    archive.extract(&fmu_directory)?;
+
+    Ok(fmu_directory)
+}
+
+/// Whether the given file would be placed within the given directory, in
+/// which case packing the directory would add the archive to itself.
+fn is_inside(file: &Path, directory: &Path) -> bool {
+    match (parent_directory(file).canonicalize(), directory.canonicalize()) {
+        (Ok(parent), Ok(directory)) => parent.starts_with(directory),
+        _ => false
+    }
+}
+
+/// The directory that the given path would be placed in.
+pub fn parent_directory(path: &Path) -> &Path {
+    match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new(".")
+    }
+}
+
+fn is_empty_directory(path: &Path) -> bool {
+    fs::read_dir(path)
+        .map(|mut entries| entries.next().is_none())
+        .unwrap_or(false)
+}
+
+pub type ArchiveResult<T> = Result<T, ArchiveError>;
+
+#[derive(Debug)]
+pub enum ArchiveError {
+    NotAnFmu(PathBuf),
+    FileExists(PathBuf),
+    ArchiveInsideFmu(PathBuf),
+    IoError(PathBuf, std::io::Error),
+    ZipError(ZipError)
+}
+
+impl Display for ArchiveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotAnFmu(path) => {
+                write!(f, "'{}' has no modelDescription.xml at its root", path.display())
+            }
+            Self::FileExists(path) => {
+                write!(f, "'{}' already exists and isn't an empty directory", path.display())
+            }
+            Self::ArchiveInsideFmu(path) => {
+                write!(f, "the archive '{}' can't be placed inside the FMU directory", path.display())
+            }
+            Self::IoError(path, error) => {
+                write!(f, "couldn't access '{}': {}", path.display(), error)
+            }
+            Self::ZipError(error) => {
+                write!(f, "couldn't process the FMU archive: {}", error)
+            }
+        }
+    }
+}
+
+impl Error for ArchiveError {}
+
+impl From<ZipError> for ArchiveError {
+    fn from(value: ZipError) -> Self {
+        Self::ZipError(value)
+    }
+}
@@ -0,0 +1,706 @@
+//! Contains the `convert` command, which turns an FMI2 Co-Simulation FMU
+//! generated by UniFMU into an FMI3 FMU.
+//!
+//! The model description is rewritten for FMI3 and the binaries and backend
+//! are replaced with their FMI3 variants. The model of the FMU is kept as it
+//! is, behind a compatibility shim that translates the FMI3 calls of the
+//! backend into the FMI2 methods of the model.
+
+use log::{info, warn};
+use roxmltree::{Document, Node};
+use std::{
+    collections::HashMap,
+    error::Error,
+    fmt::{Display, Write},
+    fs,
+    path::{Path, PathBuf}
+};
+use tempfile::TempDir;
+use walkdir::WalkDir;
+
+use crate::archive::{pack_replacing, parent_directory, unpack, ArchiveError};
+use crate::model_description::{
+    escape, fmi_version, DescribeError, FmiVersion, Variable, VariableSpec, VARIABLES_FILE_NAME
+};
+use crate::{
+    copy_binaries, recognize_template, stamp_identity, Assets, FmiFmuVersion, Language,
+    CSHARPASSETS, JAVAASSETS, PYTHONASSETS
+};
+
+/// The placeholder in the compatibility shims that is replaced by the value
+/// reference of the independent variable.
+static TIME_PLACEHOLDER: &str = "{{time_value_reference}}";
+
+/// How the FMI3 backend of a language is placed in front of an FMI2 model.
+struct Shim {
+    /// Destination of the model, which is kept.
+    model: &'static str,
+    /// Source and destination of the compatibility shim.
+    shim: (&'static str, &'static str),
+    /// Replacements in the FMI3 backend that make it use the shim in place
+    /// of the model, given as destination, text and replacement.
+    patches: &'static [(&'static str, &'static str, &'static str)]
+}
+
+static PYTHON_SHIM: Shim = Shim {
+    model: "model.py",
+    shim: ("python/fmi3/fmi2_compat.py", "fmi2_compat.py"),
+    patches: &[
+        ("backend.py", "from model import Model", "from fmi2_compat import Model"),
+    ]
+};
+
+static CSHARP_SHIM: Shim = Shim {
+    model: "model.cs",
+    shim: ("csharp/fmi3/fmi2_compat.cs", "fmi2_compat.cs"),
+    patches: &[
+        ("backend_body.cs", "Model model = null;", "Fmi2CompatibleModel model = null;"),
+        ("backend_body.cs", "model = new Model(", "model = new Fmi2CompatibleModel("),
+        ("backend_body.cs", "LogCallback logCallback", "Fmi3LogCallback logCallback"),
+        (
+            "backend_body.cs",
+            "model.Fmi3EnterInitializationMode()",
+            "model.Fmi3EnterInitializationMode(command.Fmi3EnterInitializationMode)"
+        ),
+    ]
+};
+
+static JAVA_SHIM: Shim = Shim {
+    model: "src/main/java/Model.java",
+    shim: ("java/src/fmi3/java/Fmi2CompatibleModel.java", "src/main/java/Fmi2CompatibleModel.java"),
+    patches: &[
+        ("src/main/java/AbstractBackend.java", "static Model model;", "static Fmi2CompatibleModel model;"),
+        ("src/main/java/AbstractBackend.java", "model = new Model(", "model = new Fmi2CompatibleModel("),
+        ("src/main/java/AbstractBackend.java", "Model.Fmi3Status", "Fmi2CompatibleModel.Fmi3Status"),
+        (
+            "src/main/java/AbstractBackend.java",
+            "model.fmi3EnterInitializationMode()",
+            "model.fmi3EnterInitializationMode(command.getFmi3EnterInitializationMode())"
+        ),
+        (
+            "src/main/java/AbstractBackend.java",
+            "    public static void loggingCallback(",
+            "    public static void loggingCallback(Model.Fmi2Status status, String category, String message) {
+        loggingCallback(Fmi2CompatibleModel.toFmi3Status(status), category, message);
+    }
+
+    public static void loggingCallback("
+        ),
+    ]
+};
+
+/// Converts the FMU in the given directory or `.fmu` archive to the given FMI
+/// version, writing the result to the given path, or in place if none is
+/// given.
+///
+/// Only FMI2 Co-Simulation FMUs with a local Python, C# or Java backend can be
+/// converted, and only to FMI3.
+pub fn convert(fmu: &Path, to: &FmiFmuVersion, outpath: Option<&Path>) -> ConvertResult<()> {
+    if let FmiFmuVersion::FMI2 = to {
+        return Err(ConvertError::Unsupported(String::from(
+            "FMUs can only be converted from FMI2 to FMI3"
+        )));
+    }
+
+    if !fmu.exists() {
+        return Err(ConvertError::NotFound(fmu.to_path_buf()));
+    }
+
+    let outpath = outpath.unwrap_or(fmu);
+
+    if outpath != fmu && outpath.exists() {
+        return Err(ConvertError::FileExists(outpath.to_path_buf()));
+    }
+
+    if fmu.is_dir() {
+        // The FMU is converted next to the path that it is written to, so
+        // that it can be moved into place once it is complete.
+        let parent = parent_directory(outpath);
+        let staged = TempDir::new_in(parent)
+            .map_err(|error| ConvertError::IoError(parent.to_path_buf(), error))?;
+
+        copy_directory(fmu, staged.path())?;
+        convert_directory(staged.path())?;
+
+        let permissions = fs::metadata(fmu)
+            .map_err(|error| ConvertError::IoError(fmu.to_path_buf(), error))?
+            .permissions();
+        fs::set_permissions(staged.path(), permissions)
+            .map_err(|error| ConvertError::IoError(staged.path().to_path_buf(), error))?;
+
+        replace_directory(staged, outpath)?;
+    } else {
+        let directory = TempDir::new()
+            .map_err(|error| ConvertError::IoError(fmu.to_path_buf(), error))?;
+
+        unpack(fmu, Some(directory.path()))?;
+        convert_directory(directory.path())?;
+        pack_replacing(directory.path(), outpath, false)?;
+    }
+
+    Ok(())
+}
+
+/// Moves the staged FMU directory to the given path, which must be in the
+/// same directory as the staged one.
+///
+/// A directory at the path is moved aside first, and only removed once the
+/// staged directory has taken its place. It is moved back if that fails.
+fn replace_directory(staged: TempDir, destination: &Path) -> ConvertResult<()> {
+    let parent = parent_directory(destination);
+
+    let replaced = match destination.exists() {
+        true => {
+            let aside = TempDir::new_in(parent)
+                .map_err(|error| ConvertError::IoError(parent.to_path_buf(), error))?;
+            let replaced = aside.path().join("fmu");
+
+            fs::rename(destination, &replaced)
+                .map_err(|error| ConvertError::IoError(destination.to_path_buf(), error))?;
+
+            Some((aside, replaced))
+        }
+        false => None
+    };
+
+    if let Err(error) = fs::rename(staged.path(), destination) {
+        if let Some((_, replaced)) = &replaced {
+            let _ = fs::rename(replaced, destination);
+        }
+        return Err(ConvertError::IoError(destination.to_path_buf(), error));
+    }
+
+    // The staged directory is now at the destination.
+    let _ = staged.keep();
+
+    Ok(())
+}
+
+fn convert_directory(fmu_directory: &Path) -> ConvertResult<()> {
+    let model_description_path = fmu_directory.join("modelDescription.xml");
+
+    let model_description = fs::read_to_string(&model_description_path)
+        .map_err(|error| ConvertError::IoError(model_description_path.clone(), error))?;
+
+    if fmi_version(&model_description)? == FmiVersion::Fmi3 {
+        return Err(ConvertError::Unsupported(String::from("the FMU already targets FMI3")));
+    }
+
+    let resources = fmu_directory.join("resources");
+
+    let (language, _) = recognize_template(&resources, &FmiVersion::Fmi2)
+        .ok_or(ConvertError::UnknownBackend)?;
+
+    let (assets, shim) = match language {
+        Language::Python => (&*PYTHONASSETS, &PYTHON_SHIM),
+        Language::CSharp => (&*CSHARPASSETS, &CSHARP_SHIM),
+        Language::Java => (&*JAVAASSETS, &JAVA_SHIM),
+        Language::Matlab => {
+            return Err(ConvertError::Unsupported(String::from("there is no compatibility shim for MATLAB models")))
+        }
+        Language::Rust => {
+            return Err(ConvertError::Unsupported(String::from("there is no compatibility shim for Rust models")))
+        }
+    };
+
+    info!("converting the model description to FMI3");
+
+    let converted = convert_model_description(&model_description)?;
+
+    let model_description = stamp_identity(converted.model_description.as_bytes(), &FmiFmuVersion::FMI3, false);
+    write(&model_description_path, model_description)?;
+
+    let spec_path = resources.join(VARIABLES_FILE_NAME);
+    if spec_path.is_file() {
+        info!("converting the variable specification {:?} to FMI3", spec_path);
+
+        let spec = toml::to_string(&converted.spec)?;
+        write(&spec_path, format!("{}\n{}", VARIABLES_HEADER, spec))?;
+    }
+
+    // Only the resources that differ between the FMI2 and FMI3 templates are
+    // replaced, so that the model and any other edits to the FMU are kept.
+    let fmi3_resources = assets.fmi3_resources.iter()
+        .filter(|(source, _)| !assets.fmi2_resources.iter().any(|(fmi2_source, _)| fmi2_source == source))
+        .filter(|(_, destination)| *destination != shim.model);
+
+    for (source, destination) in fmi3_resources {
+        let destination = resources.join(destination);
+
+        info!("replacing resource {:?}", destination);
+
+        write(&destination, Assets::get(source).unwrap().data)?;
+    }
+
+    let (source, destination) = shim.shim;
+    let destination = resources.join(destination);
+
+    info!("adding compatibility shim {:?}", destination);
+
+    let shim_source = String::from_utf8_lossy(&Assets::get(source).unwrap().data)
+        .replace(TIME_PLACEHOLDER, &converted.time_value_reference.to_string());
+    write(&destination, shim_source)?;
+
+    for (destination, text, replacement) in shim.patches {
+        let destination = resources.join(destination);
+
+        let contents = fs::read_to_string(&destination)
+            .map_err(|error| ConvertError::IoError(destination.clone(), error))?;
+
+        if !contents.contains(text) {
+            return Err(ConvertError::PatchFailed(destination, text));
+        }
+
+        write(&destination, contents.replace(text, replacement))?;
+    }
+
+    let binaries = fmu_directory.join("binaries");
+    if binaries.exists() {
+        fs::remove_dir_all(&binaries)
+            .map_err(|error| ConvertError::IoError(binaries, error))?;
+    }
+
+    info!("replacing the binaries named '{}'", converted.model_identifier);
+    copy_binaries(&FmiFmuVersion::FMI3, fmu_directory, &converted.model_identifier)
+        .map_err(|_| ConvertError::NoBinaries)?;
+
+    Ok(())
+}
+
+static VARIABLES_HEADER: &str = "\
+# Variables of the model, converted from FMI2 by `unifmu convert` and
+# declared in the order they appear in the model description. Run
+# `unifmu describe <FMU>` after editing this file to rewrite the
+# <ModelVariables> and <ModelStructure> of modelDescription.xml from it.
+#
+# The independent variable is kept by the compatibility shim of the backend
+# rather than by the model.
+";
+
+/// An FMI2 model description converted to FMI3.
+struct ConvertedModelDescription {
+    model_description: String,
+    spec: VariableSpec,
+    model_identifier: String,
+    time_value_reference: u32
+}
+
+/// Converts an FMI2 Co-Simulation model description to FMI3.
+///
+/// The variables are read into a variable specification with their types
+/// mapped to FMI3, from which the `<ModelVariables>` and `<ModelStructure>`
+/// are rendered. An independent variable `time` is added if the FMU has none.
+/// Attributes and elements without an FMI3 counterpart are left out with a
+/// warning.
+fn convert_model_description(model_description: &str) -> ConvertResult<ConvertedModelDescription> {
+    let document = Document::parse(model_description)?;
+    let root = document.root_element();
+
+    if child(&root, "ModelExchange").is_some() {
+        return Err(ConvertError::Unsupported(String::from(
+            "only Co-Simulation FMUs can be converted"
+        )));
+    }
+
+    let co_simulation = child(&root, "CoSimulation")
+        .ok_or(DescribeError::MissingElement("CoSimulation"))?;
+
+    let model_identifier = co_simulation.attribute("modelIdentifier")
+        .ok_or(DescribeError::MissingElement("modelIdentifier"))?;
+
+    let mut spec = read_variables(&root)?;
+
+    let time_value_reference = match spec.variables.iter().find(|variable| variable.causality == "independent") {
+        Some(variable) => variable.value_reference,
+        None => {
+            let value_reference = spec.variables.iter()
+                .map(|variable| variable.value_reference + 1)
+                .max()
+                .unwrap_or(0);
+            spec.variables.insert(0, independent_time(value_reference));
+            value_reference
+        }
+    };
+
+    spec.validate(FmiVersion::Fmi3)?;
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+
+    let root_attributes = root.attributes()
+        .filter(|attribute| attribute.namespace().is_none())
+        .filter_map(|attribute| match attribute.name() {
+            "fmiVersion" => Some(("fmiVersion", "3.0")),
+            "guid" => Some(("instantiationToken", attribute.value())),
+            "numberOfEventIndicators" => None,
+            name => Some((name, attribute.value()))
+        });
+    writeln!(xml, "<fmiModelDescription{}>", render_attributes(root_attributes)).unwrap();
+
+    let co_simulation_attributes = co_simulation.attributes()
+        .filter_map(|attribute| match attribute.name() {
+            "canGetAndSetFMUstate" => Some(("canGetAndSetFMUState", attribute.value())),
+            "canSerializeFMUstate" => Some(("canSerializeFMUState", attribute.value())),
+            "canNotUseMemoryManagementFunctions" => None,
+            name @ ("canInterpolateInputs" | "canRunAsynchronuously" | "providesDirectionalDerivative") => {
+                if attribute.value() == "true" {
+                    warn!("leaving out the capability '{}', which FMI3 Co-Simulation doesn't have", name);
+                }
+                None
+            }
+            name => Some((name, attribute.value()))
+        });
+    writeln!(xml, "  <CoSimulation{} />", render_attributes(co_simulation_attributes)).unwrap();
+
+    for node in co_simulation.children().filter(Node::is_element) {
+        warn!("leaving out the element '{}' of CoSimulation, which FMI3 doesn't have", node.tag_name().name());
+    }
+
+    for node in root.children().filter(Node::is_element) {
+        match node.tag_name().name() {
+            "UnitDefinitions" | "LogCategories" | "DefaultExperiment" => {
+                writeln!(xml, "  {}", &model_description[node.range()]).unwrap();
+            }
+            "TypeDefinitions" => {
+                xml.push_str(&convert_type_definitions(&node)?);
+            }
+            "CoSimulation" | "ModelVariables" | "ModelStructure" => {}
+            name => {
+                warn!("leaving out the element '{}', which FMI3 doesn't have", name);
+            }
+        }
+    }
+
+    writeln!(xml, "  {}", spec.render(FmiVersion::Fmi3)?).unwrap();
+    xml.push_str("</fmiModelDescription>\n");
+
+    Ok(ConvertedModelDescription {
+        model_description: xml,
+        spec,
+        model_identifier: String::from(model_identifier),
+        time_value_reference
+    })
+}
+
+/// Reads the variables of an FMI2 model description into a variable
+/// specification for FMI3, referring to other variables by value reference
+/// rather than by index.
+fn read_variables(root: &Node) -> ConvertResult<VariableSpec> {
+    let model_variables = child(root, "ModelVariables")
+        .ok_or(DescribeError::MissingElement("ModelVariables"))?;
+
+    let scalar_variables: Vec<Node> = model_variables.children()
+        .filter(|node| node.has_tag_name("ScalarVariable"))
+        .collect();
+
+    let value_references = scalar_variables.iter()
+        .map(|variable| parse_u32(variable, "valueReference"))
+        .collect::<ConvertResult<Vec<u32>>>()?;
+
+    let by_index = |index: &str| -> ConvertResult<u32> {
+        index.parse::<usize>().ok()
+            .filter(|index| (1..=value_references.len()).contains(index))
+            .map(|index| value_references[index - 1])
+            .ok_or_else(|| invalid(format!("'{}' isn't the index of a variable", index)))
+    };
+
+    let mut variables = Vec::new();
+
+    for (scalar_variable, value_reference) in scalar_variables.iter().zip(&value_references) {
+        let name = scalar_variable.attribute("name")
+            .ok_or_else(|| invalid(String::from("a variable has no name")))?;
+
+        let type_element = scalar_variable.children()
+            .find(Node::is_element)
+            .ok_or_else(|| invalid(format!("variable '{}' has no type", name)))?;
+
+        let variable_type = fmi3_type(name, type_element.tag_name().name())?;
+
+        for attribute in scalar_variable.attributes() {
+            if !matches!(
+                attribute.name(),
+                "name" | "valueReference" | "causality" | "variability" | "initial" | "description"
+            ) {
+                warn!("leaving out the attribute '{}' of variable '{}', which FMI3 doesn't have", attribute.name(), name);
+            }
+        }
+
+        let mut start = None;
+        let mut derivative = None;
+        let mut attributes = std::collections::BTreeMap::new();
+
+        for attribute in type_element.attributes() {
+            match attribute.name() {
+                "start" => start = Some(start_value(variable_type, attribute.value())),
+                "derivative" => derivative = Some(by_index(attribute.value())?),
+                name => {
+                    attributes.insert(String::from(name), toml::Value::String(String::from(attribute.value())));
+                }
+            }
+        }
+
+        variables.push(Variable {
+            name: String::from(name),
+            value_reference: *value_reference,
+            variable_type: String::from(variable_type),
+            causality: String::from(scalar_variable.attribute("causality").unwrap_or("local")),
+            variability: scalar_variable.attribute("variability").map(String::from),
+            initial: scalar_variable.attribute("initial").map(String::from),
+            description: scalar_variable.attribute("description").map(String::from),
+            start,
+            derivative,
+            dependencies: None,
+            dimensions: Vec::new(),
+            clocks: Vec::new(),
+            event_indicator: false,
+            attributes
+        });
+    }
+
+    // The dependencies of the outputs and derivatives are declared in the
+    // model structure rather than on the variables.
+    let mut dependencies = HashMap::new();
+
+    let unknowns = child(root, "ModelStructure")
+        .into_iter()
+        .flat_map(|model_structure| model_structure.children())
+        .filter(|node| node.has_tag_name("Outputs") || node.has_tag_name("Derivatives"))
+        .flat_map(|unknowns| unknowns.children())
+        .filter(|node| node.has_tag_name("Unknown"));
+
+    for unknown in unknowns {
+        let index = unknown.attribute("index")
+            .ok_or_else(|| invalid(String::from("an unknown of the model structure has no index")))?;
+
+        if unknown.has_attribute("dependenciesKind") {
+            warn!("leaving out the dependenciesKind of the unknown with index {}, which FMI3 doesn't have", index);
+        }
+
+        if let Some(references) = unknown.attribute("dependencies") {
+            let references = references.split_whitespace()
+                .map(by_index)
+                .collect::<ConvertResult<Vec<u32>>>()?;
+            dependencies.insert(by_index(index)?, references);
+        }
+    }
+
+    for variable in &mut variables {
+        variable.dependencies = dependencies.remove(&variable.value_reference);
+    }
+
+    Ok(VariableSpec { variables })
+}
+
+/// Converts the simple types of an FMI2 model description to the type
+/// definitions of FMI3.
+fn convert_type_definitions(type_definitions: &Node) -> ConvertResult<String> {
+    let mut xml = String::from("  <TypeDefinitions>\n");
+
+    for simple_type in type_definitions.children().filter(|node| node.has_tag_name("SimpleType")) {
+        let name = simple_type.attribute("name")
+            .ok_or_else(|| invalid(String::from("a type definition has no name")))?;
+
+        let type_element = simple_type.children()
+            .find(Node::is_element)
+            .ok_or_else(|| invalid(format!("type definition '{}' has no type", name)))?;
+
+        let element = format!("{}Type", fmi3_type(name, type_element.tag_name().name())?);
+
+        let attributes = simple_type.attributes()
+            .chain(type_element.attributes())
+            .map(|attribute| (attribute.name(), attribute.value()));
+
+        writeln!(xml, "    <{}{} />", element, render_attributes(attributes)).unwrap();
+    }
+
+    xml.push_str("  </TypeDefinitions>\n");
+
+    Ok(xml)
+}
+
+/// Gives the FMI3 type of the variable or type definition with the given
+/// name and FMI2 type.
+fn fmi3_type(name: &str, fmi2_type: &str) -> ConvertResult<&'static str> {
+    match fmi2_type {
+        "Real" => Ok("Float64"),
+        "Integer" => Ok("Int32"),
+        "Boolean" => Ok("Boolean"),
+        "String" => Ok("String"),
+        _ => Err(ConvertError::Unsupported(format!(
+            "'{}' has type '{}', which the backends of UniFMU don't support",
+            name,
+            fmi2_type
+        )))
+    }
+}
+
+/// Reads a start value as a value of the given FMI3 type, falling back to the
+/// text of the attribute if it can't be read as such.
+fn start_value(variable_type: &str, text: &str) -> toml::Value {
+    let value = match variable_type {
+        "Float64" => text.parse().ok().map(toml::Value::Float),
+        "Int32" => text.parse().ok().map(toml::Value::Integer),
+        "Boolean" => match text {
+            "true" | "1" => Some(toml::Value::Boolean(true)),
+            "false" | "0" => Some(toml::Value::Boolean(false)),
+            _ => None
+        },
+        _ => None
+    };
+
+    value.unwrap_or_else(|| toml::Value::String(String::from(text)))
+}
+
+fn independent_time(value_reference: u32) -> Variable {
+    Variable {
+        name: String::from("time"),
+        value_reference,
+        variable_type: String::from("Float64"),
+        causality: String::from("independent"),
+        variability: Some(String::from("continuous")),
+        initial: None,
+        description: Some(String::from("Simulation time")),
+        start: None,
+        derivative: None,
+        dependencies: None,
+        dimensions: Vec::new(),
+        clocks: Vec::new(),
+        event_indicator: false,
+        attributes: Default::default()
+    }
+}
+
+fn child<'a, 'input>(node: &Node<'a, 'input>, name: &str) -> Option<Node<'a, 'input>> {
+    node.children().find(|child| child.has_tag_name(name))
+}
+
+fn parse_u32(node: &Node, attribute: &'static str) -> ConvertResult<u32> {
+    node.attribute(attribute)
+        .and_then(|value| value.parse().ok())
+        .ok_or_else(|| invalid(format!(
+            "variable '{}' has no valid {}",
+            node.attribute("name").unwrap_or_default(),
+            attribute
+        )))
+}
+
+fn render_attributes<'a>(attributes: impl Iterator<Item = (&'a str, &'a str)>) -> String {
+    attributes
+        .map(|(name, value)| format!(" {}=\"{}\"", name, escape(value)))
+        .collect()
+}
+
+fn invalid(message: String) -> ConvertError {
+    ConvertError::DescribeError(DescribeError::InvalidSpec(message))
+}
+
+fn write(path: &Path, contents: impl AsRef<[u8]>) -> ConvertResult<()> {
+    fs::create_dir_all(path.parent().unwrap())
+        .and_then(|_| fs::write(path, contents))
+        .map_err(|error| ConvertError::IoError(path.to_path_buf(), error))
+}
+
+/// Copies the contents of the source directory into the destination
+/// directory, keeping the permissions of the files.
+fn copy_directory(source: &Path, destination: &Path) -> ConvertResult<()> {
+    for entry in WalkDir::new(source) {
+        let entry = entry.map_err(|error| {
+            let path = error.path().unwrap_or(source).to_path_buf();
+            ConvertError::IoError(path, error.into())
+        })?;
+
+        let target = destination.join(entry.path().strip_prefix(source).unwrap());
+
+        let result = if entry.file_type().is_dir() {
+            fs::create_dir_all(&target)
+        } else {
+            fs::copy(entry.path(), &target).map(|_| ())
+        };
+
+        result.map_err(|error| ConvertError::IoError(target, error))?;
+    }
+
+    Ok(())
+}
+
+pub type ConvertResult<T> = Result<T, ConvertError>;
+
+#[derive(Debug)]
+pub enum ConvertError {
+    NotFound(PathBuf),
+    FileExists(PathBuf),
+    Unsupported(String),
+    UnknownBackend,
+    NoBinaries,
+    PatchFailed(PathBuf, &'static str),
+    IoError(PathBuf, std::io::Error),
+    ParseError(roxmltree::Error),
+    SerializeError(toml::ser::Error),
+    DescribeError(DescribeError),
+    ArchiveError(ArchiveError)
+}
+
+impl Display for ConvertError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotFound(path) => {
+                write!(f, "'{}' is neither an FMU directory nor an FMU archive", path.display())
+            }
+            Self::FileExists(path) => {
+                write!(f, "'{}' already exists", path.display())
+            }
+            Self::Unsupported(message) => {
+                write!(f, "the FMU can't be converted; {}", message)
+            }
+            Self::UnknownBackend => {
+                write!(f, "couldn't recognize the backend language of the FMU from its resources")
+            }
+            Self::NoBinaries => {
+                write!(f, "this version of UniFMU has no binaries to convert the FMU with")
+            }
+            Self::PatchFailed(path, text) => {
+                write!(f, "couldn't find '{}' in '{}' to place the compatibility shim", text, path.display())
+            }
+            Self::IoError(path, error) => {
+                write!(f, "couldn't access '{}': {}", path.display(), error)
+            }
+            Self::ParseError(error) => {
+                write!(f, "couldn't parse modelDescription.xml: {}", error)
+            }
+            Self::SerializeError(error) => {
+                write!(f, "couldn't write the variable specification: {}", error)
+            }
+            Self::DescribeError(error) => {
+                write!(f, "{}", error)
+            }
+            Self::ArchiveError(error) => {
+                write!(f, "{}", error)
+            }
+        }
+    }
+}
+
+impl Error for ConvertError {}
+
+impl From<roxmltree::Error> for ConvertError {
+    fn from(value: roxmltree::Error) -> Self {
+        Self::ParseError(value)
+    }
+}
+
+impl From<toml::ser::Error> for ConvertError {
+    fn from(value: toml::ser::Error) -> Self {
+        Self::SerializeError(value)
+    }
+}
+
+impl From<DescribeError> for ConvertError {
+    fn from(value: DescribeError) -> Self {
+        Self::DescribeError(value)
+    }
+}
+
+impl From<ArchiveError> for ConvertError {
+    fn from(value: ArchiveError) -> Self {
+        Self::ArchiveError(value)
+    }
+}
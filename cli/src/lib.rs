@@ -1,10 +1,12 @@
+use chrono::Utc;
 use clap::ValueEnum;
 use fs_extra::dir::CopyOptions;
 use lazy_static::lazy_static;
 use log::{error, info};
 use rust_embed::RustEmbed;
-use std::{fs::File, path::{Path, PathBuf}};
+use std::{cmp::Reverse, fs::File, path::{Path, PathBuf}};
 use tempfile::TempDir;
+use uuid::Uuid;
 use walkdir::WalkDir;
 use zip::{result::ZipError, CompressionMethod};
 use serde::Deserialize;
@@ -12,7 +14,9 @@ use serde::Serialize;
 use std::fs;
 
 
-use crate::utils::zip_dir;
+use crate::model_description::{describe, escape, FmiVersion, VariableSpec, VARIABLES_FILE_NAME};
+use crate::model_skeleton::render_model;
+use crate::utils::{reproducible_time, zip_dir};
 
 extern crate dlopen_derive;
 
@@ -21,6 +25,8 @@ pub enum Language {
     Python,
     CSharp,
     Java,
+    Matlab,
+    Rust,
 }
 
 #[derive(ValueEnum, Clone, Debug)]
@@ -33,7 +39,13 @@ pub enum FmiFmuVersion {
 #[folder = "../assets"]
 struct Assets;
 
+pub mod archive;
+pub mod convert;
+pub mod model_description;
+pub mod model_skeleton;
+pub mod upgrade;
 pub mod utils;
+pub mod validate;
 
 struct LanguageAssets {
     fmi2_resources: Vec<(&'static str, &'static str)>,
@@ -86,6 +98,48 @@ lazy_static! {
             ("python/README.md", "README.md"),
         ],
     };
+    static ref PYTHONASSETSMODELEXCHANGE: LanguageAssets = LanguageAssets {
+        fmi2_resources: vec![
+            ("python/compilation_resources/launch_with_pyinstaller.toml", "compilation_resources/launch_with_pyinstaller.toml"),
+            ("python/compilation_resources/unifmu_pyinstaller_build_script.sh", "compilation_resources/unifmu_pyinstaller_build_script.sh"),
+            ("python/compilation_resources/unifmu_pyinstaller_build_script.bat", "compilation_resources/unifmu_pyinstaller_build_script.bat"),
+            ("python/requirements.txt", "requirements.txt"),
+            ("python/main_local.py", "main.py"),
+            ("python/fmi2/abstract_backend.py", "abstract_backend.py"),
+            ("python/fmi2/backend.py", "backend.py"),
+            ("python/fmi2/model_exchange/model.py", "model.py"),
+            (
+                "auto_generated/fmi2_messages_pb2.py",
+                "schemas/fmi2_messages_pb2.py"
+            ),
+            (
+                "auto_generated/unifmu_handshake_pb2.py",
+                "schemas/unifmu_handshake_pb2.py"
+            ),
+            ("python/launch.toml", "launch.toml"),
+            ("python/README.md", "README.md"),
+        ],
+        fmi3_resources: vec![
+            ("python/compilation_resources/launch_with_pyinstaller.toml", "compilation_resources/launch_with_pyinstaller.toml"),
+            ("python/compilation_resources/unifmu_pyinstaller_build_script.sh", "compilation_resources/unifmu_pyinstaller_build_script.sh"),
+            ("python/compilation_resources/unifmu_pyinstaller_build_script.bat", "compilation_resources/unifmu_pyinstaller_build_script.bat"),
+            ("python/requirements.txt", "requirements.txt"),
+            ("python/main_local.py", "main.py"),
+            ("python/fmi3/abstract_backend.py", "abstract_backend.py"),
+            ("python/fmi3/backend.py", "backend.py"),
+            ("python/fmi3/model_exchange/model.py", "model.py"),
+            (
+                "auto_generated/fmi3_messages_pb2.py",
+                "schemas/fmi3_messages_pb2.py"
+            ),
+            (
+                "auto_generated/unifmu_handshake_pb2.py",
+                "schemas/unifmu_handshake_pb2.py"
+            ),
+            ("python/launch.toml", "launch.toml"),
+            ("python/README.md", "README.md"),
+        ],
+    };
     static ref CSHARPASSETS: LanguageAssets = LanguageAssets {
         fmi2_resources: vec![
             ("csharp/backend_local_head.cs", "backend_head.cs"),
@@ -114,6 +168,23 @@ lazy_static! {
             ("csharp/README.md", "README.md"),
         ],
     };
+    static ref CSHARPASSETSMODELEXCHANGE: LanguageAssets = LanguageAssets {
+        fmi2_resources: vec![
+            ("csharp/backend_local_head.cs", "backend_head.cs"),
+            ("csharp/fmi2/backend_body.cs", "backend_body.cs"),
+            ("csharp/fmi2/model_exchange/model.cs", "model.cs"),
+            ("csharp/model.csproj", "model.csproj"),
+            ("auto_generated/Fmi2Messages.cs", "schemas/Fmi2Messages.cs"),
+            (
+                "auto_generated/UnifmuHandshake.cs",
+                "schemas/UnifmuHandshake.cs"
+            ),
+            ("csharp/launch.toml", "launch.toml"),
+            ("csharp/README.md", "README.md"),
+        ],
+        // FMI3 Model Exchange FMUs are only generated for Python
+        fmi3_resources: vec![],
+    };
     static ref JAVAASSETS: LanguageAssets = LanguageAssets {
         fmi2_resources: vec![
             (
@@ -186,6 +257,111 @@ lazy_static! {
             ),
         ],
     };
+    static ref JAVAASSETSMODELEXCHANGE: LanguageAssets = LanguageAssets {
+        fmi2_resources: vec![
+            (
+                "java/src/common/java/LocalBackend.java",
+                "src/main/java/Backend.java"
+            ),
+            (
+                "java/src/fmi2/java/model_exchange/Model.java",
+                "src/main/java/Model.java"
+            ),
+            (
+                "java/src/fmi2/java/AbstractBackend.java",
+                "src/main/java/AbstractBackend.java"
+            ),
+            ("java/build.gradle", "build.gradle"),
+            ("java/gradlew", "gradlew"),
+            (
+                "java/gradle/wrapper/gradle-wrapper.jar",
+                "gradle/wrapper/gradle-wrapper.jar"
+            ),
+            (
+                "java/gradle/wrapper/gradle-wrapper.properties",
+                "gradle/wrapper/gradle-wrapper.properties"
+            ),
+            ("java/gradlew.bat", "gradlew.bat"),
+            ("java/launch.toml", "launch.toml"),
+            ("java/README.md", "README.md"),
+            (
+                "auto_generated/Fmi2Messages.java",
+                "src/main/java/Fmi2Messages.java"
+            ),
+            (
+                "auto_generated/UnifmuHandshake.java",
+                "src/main/java/UnifmuHandshake.java"
+            ),
+        ],
+        // FMI3 Model Exchange FMUs are only generated for Python
+        fmi3_resources: vec![],
+    };
+    static ref MATLABASSETS: LanguageAssets = LanguageAssets {
+        fmi2_resources: vec![
+            ("matlab/requirements.txt", "requirements.txt"),
+            ("python/main_local.py", "main.py"),
+            ("python/fmi2/abstract_backend.py", "abstract_backend.py"),
+            ("python/fmi2/backend.py", "backend.py"),
+            ("matlab/fmi2/model.py", "model.py"),
+            ("matlab/fmi2/matlabcode/Model.m", "matlabcode/Model.m"),
+            ("matlab/fmi2/matlabcode/Fmi2Status.m", "matlabcode/Fmi2Status.m"),
+            (
+                "auto_generated/fmi2_messages_pb2.py",
+                "schemas/fmi2_messages_pb2.py"
+            ),
+            (
+                "auto_generated/unifmu_handshake_pb2.py",
+                "schemas/unifmu_handshake_pb2.py"
+            ),
+            ("matlab/launch.toml", "launch.toml"),
+            ("matlab/README.md", "README.md"),
+        ],
+        fmi3_resources: vec![
+            ("matlab/requirements.txt", "requirements.txt"),
+            ("python/main_local.py", "main.py"),
+            ("python/fmi3/abstract_backend.py", "abstract_backend.py"),
+            ("python/fmi3/backend.py", "backend.py"),
+            ("matlab/fmi3/model.py", "model.py"),
+            ("matlab/fmi3/matlabcode/Model.m", "matlabcode/Model.m"),
+            ("matlab/fmi3/matlabcode/Fmi3Status.m", "matlabcode/Fmi3Status.m"),
+            ("matlab/fmi3/variables.toml", "variables.toml"),
+            (
+                "auto_generated/fmi3_messages_pb2.py",
+                "schemas/fmi3_messages_pb2.py"
+            ),
+            (
+                "auto_generated/unifmu_handshake_pb2.py",
+                "schemas/unifmu_handshake_pb2.py"
+            ),
+            ("matlab/launch.toml", "launch.toml"),
+            ("matlab/README.md", "README.md"),
+        ],
+    };
+    static ref RUSTASSETS: LanguageAssets = LanguageAssets {
+        fmi2_resources: vec![
+            ("rust/Cargo.toml", "Cargo.toml"),
+            ("rust/src/main.rs", "src/main.rs"),
+            ("rust/src/socket.rs", "src/socket.rs"),
+            ("rust/fmi2/backend.rs", "src/backend.rs"),
+            ("rust/fmi2/model.rs", "src/model.rs"),
+            ("auto_generated/fmi2_messages.rs", "src/messages.rs"),
+            ("auto_generated/unifmu_handshake.rs", "src/unifmu_handshake.rs"),
+            ("rust/launch.toml", "launch.toml"),
+            ("rust/README.md", "README.md"),
+        ],
+        fmi3_resources: vec![
+            ("rust/Cargo.toml", "Cargo.toml"),
+            ("rust/src/main.rs", "src/main.rs"),
+            ("rust/src/socket.rs", "src/socket.rs"),
+            ("rust/fmi3/backend.rs", "src/backend.rs"),
+            ("rust/fmi3/model.rs", "src/model.rs"),
+            ("rust/fmi3/variables.toml", "variables.toml"),
+            ("auto_generated/fmi3_messages.rs", "src/messages.rs"),
+            ("auto_generated/unifmu_handshake.rs", "src/unifmu_handshake.rs"),
+            ("rust/launch.toml", "launch.toml"),
+            ("rust/README.md", "README.md"),
+        ],
+    };
     static ref PYTHONASSETSREMOTE: LanguageAssets = LanguageAssets {
         fmi2_resources: vec![
             ("python/compilation_resources/launch_with_pyinstaller.toml", "compilation_resources/launch_with_pyinstaller.toml"),
@@ -393,12 +569,96 @@ struct Config {
    ip: String,
 }
 
+/// Optional features of a FMU generated by `generate()`.
+#[derive(Debug, Default)]
+pub struct GenerateOptions {
+    /// Generate a Model Exchange FMU instead of a Co-Simulation FMU.
+    pub model_exchange: bool,
+    /// Declare that the FMU provides directional and adjoint derivatives.
+    pub provides_derivatives: bool,
+    /// Declare that the FMU can interpolate inputs and provide output
+    /// derivatives up to the given order.
+    pub interpolation_order: Option<u32>,
+    /// Variable specification from which the model description and a model
+    /// skeleton are generated.
+    pub spec: Option<PathBuf>,
+    /// Make the generated FMU and its archive reproducible.
+    pub reproducible: bool,
+}
+
 pub fn generate(
     language: &Language,
     fmu_version: &FmiFmuVersion,
     outpath: &Path,
     zipped: bool,
+    options: &GenerateOptions,
 ) -> Result<(), GenerateError> {
+    let &GenerateOptions {
+        model_exchange,
+        provides_derivatives,
+        interpolation_order,
+        ref spec,
+        reproducible,
+    } = options;
+
+    if spec.is_some() && (model_exchange || provides_derivatives || interpolation_order.is_some()) {
+        error!(
+            "Model skeletons generated from a variable specification only support Co-Simulation without derivatives"
+        );
+        return Err(GenerateError::Error)
+    }
+
+    let spec = match spec.as_deref() {
+        None => None,
+        Some(path) => {
+            let version = match fmu_version {
+                FmiFmuVersion::FMI2 => FmiVersion::Fmi2,
+                FmiFmuVersion::FMI3 => FmiVersion::Fmi3,
+            };
+            match VariableSpec::from_file(path).and_then(|spec| spec.validate(version)) {
+                Ok(_) => Some(path),
+                Err(e) => {
+                    error!("{}", e);
+                    return Err(GenerateError::Error)
+                }
+            }
+        }
+    };
+
+    if interpolation_order.is_some()
+        && (model_exchange || matches!(fmu_version, FmiFmuVersion::FMI3))
+    {
+        error!(
+            "Input interpolation and output derivatives can only be declared for FMI2 Co-Simulation FMUs"
+        );
+        return Err(GenerateError::Error)
+    }
+
+    if provides_derivatives {
+        if let FmiFmuVersion::FMI2 = fmu_version {
+            error!(
+                "Directional and adjoint derivatives can't be provided for FMU version `{:?}`",
+                fmu_version
+            );
+            return Err(GenerateError::Error)
+        }
+    }
+
+    if model_exchange {
+        match (language, fmu_version) {
+            (Language::Python, _) => (),
+            (Language::CSharp | Language::Java, FmiFmuVersion::FMI2) => (),
+            _ => {
+                error!(
+                    "Model Exchange FMUs can't be generated for FMU version `{:?}` and language '{:?}'",
+                    fmu_version,
+                    language
+                );
+                return Err(GenerateError::Error)
+            }
+        }
+    }
+
     let tmpdir = TempDir::new().unwrap();
 
     info!(
@@ -409,97 +669,10 @@ pub fn generate(
         outpath
     );
 
-    match FMI3_OS_NAMES.iter()
-        .flat_map(|os_name| {
-            FMI3_ARCHITECTURES.iter()
-                .map(move |arch| {
-                    (*arch, *os_name)
-                })
-        })
-        .map(|platform_tuple| {
-            let library_name = match platform_tuple.1 {
-                "darwin" => "unifmu.dylib",
-                "linux" => "unifmu.so",
-                "windows" => "unifmu.dll",
-                _ => "unifmu.so"
-            };
-            let platform_name = format!(
-                "{}-{}", platform_tuple.0, platform_tuple.1
-            );
-            let asset_placement = format!(
-                "auto_generated/binaries/{}/{}", platform_name, library_name
-            );
-            match Assets::get(&asset_placement) {
-                None => Err(()),
-                Some(asset) => {
-                    let destination_folder_name = match fmu_version {
-                        FmiFmuVersion::FMI2 => match platform_tuple.0 {
-                            "x86" => match platform_tuple.1 {
-                                "windows" => "win32".to_owned(),
-                                _ => format!("{}32", platform_tuple.1)
-                            }
-                            "x86_64" => match platform_tuple.1 {
-                                "windows" => "win64".to_owned(),
-                                _ => format!("{}64", platform_tuple.1)
-                            }
-                            _ => platform_name
-                        }
-                        FmiFmuVersion::FMI3 => platform_name
-                    };
-
-                    let destination_folder_path = tmpdir
-                        .path()
-                        .join("binaries")
-                        .join(destination_folder_name);
-
-                    std::fs::create_dir_all(&destination_folder_path)
-                        .map_err(|io_error| {
-                            error!(
-                                "Couldn't create binary folder structure {}: {}",
-                                destination_folder_path.display(),
-                                io_error
-                            );
-                        })?;
-
-                    let destination_path = destination_folder_path
-                        .join(library_name);
-
-                    info!(
-                        "copying resource \"{}\" to \"{}\"",
-                        asset_placement,
-                        destination_path.display()
-                    );
-
-                    std::fs::write(
-                        &destination_path,
-                        asset.data
-                    ).map_err(|io_error| {
-                        error!(
-                            "Couldn't write binary {} to {}: {}",
-                            asset_placement,
-                            destination_path.display(),
-                            io_error
-                        );
-                    })
-                }
-            }
-        })
-        .reduce(|accumulator, result| {
-            match accumulator {
-                Ok(_) => Ok(()),
-                Err(_) => result
-            }
-        })
-        .unwrap_or_else(|| {
-            error!("No combination of os names and architechture found. Check statics FMI3_OS_NAMES and static FMI3_ARCHITECTURES");
-            Err(())
-        }) {
-            Ok(_) => (),
-            Err(_) => {
-                error!("Didn't move any API binaries from assets to generated FMI. Check that assets include compiled API binaries");
-                return Err(GenerateError::Error)
-            }
-        }
+    if copy_binaries(fmu_version, tmpdir.path(), "unifmu").is_err() {
+        error!("Didn't move any API binaries from assets to generated FMI. Check that assets include compiled API binaries");
+        return Err(GenerateError::Error)
+    }
 
     let md = tmpdir.path().join("modelDescription.xml");
 
@@ -512,22 +685,62 @@ pub fn generate(
 
         match fmu_version {
             FmiFmuVersion::FMI2 => {
-                std::fs::write(
-                    &md,
-                    Assets::get("common/fmi2/modelDescription.xml")
-                        .unwrap()
-                        .data,
-                )
-                .unwrap();
+                let model_description = match model_exchange {
+                    true => "common/fmi2/model_exchange/modelDescription.xml",
+                    false => "common/fmi2/modelDescription.xml",
+                };
+                let mut model_description = Assets::get(model_description)
+                    .unwrap()
+                    .data
+                    .into_owned();
+                if let Some(order) = interpolation_order {
+                    model_description = declare_attributes(
+                        &model_description,
+                        &[
+                            ("canInterpolateInputs", "true"),
+                            ("maxOutputDerivativeOrder", &order.to_string())
+                        ]
+                    )?;
+                }
+                std::fs::write(&md, model_description).unwrap();
             }
             FmiFmuVersion::FMI3 => {
-                std::fs::write(
-                    &md,
-                    Assets::get("common/fmi3/modelDescription.xml")
-                        .unwrap()
-                        .data,
-                )
-                .unwrap();
+                let model_description = match model_exchange {
+                    true => "common/fmi3/model_exchange/modelDescription.xml",
+                    false => "common/fmi3/modelDescription.xml",
+                };
+                let mut model_description = Assets::get(model_description)
+                    .unwrap()
+                    .data
+                    .into_owned();
+                if provides_derivatives {
+                    model_description = declare_attributes(
+                        &model_description,
+                        &[
+                            ("providesDirectionalDerivatives", "true"),
+                            ("providesAdjointDerivatives", "true")
+                        ]
+                    )?;
+                }
+                std::fs::write(&md, model_description).unwrap();
+            }
+        }
+
+        let variables = match (fmu_version, model_exchange) {
+            (FmiFmuVersion::FMI2, false) => "common/fmi2/variables.toml",
+            (FmiFmuVersion::FMI2, true) => "common/fmi2/model_exchange/variables.toml",
+            (FmiFmuVersion::FMI3, false) => "common/fmi3/variables.toml",
+            (FmiFmuVersion::FMI3, true) => "common/fmi3/model_exchange/variables.toml",
+        };
+        let dst_variables = tmpdir.path().join("resources").join(VARIABLES_FILE_NAME);
+        std::fs::create_dir_all(dst_variables.parent().unwrap()).unwrap();
+        match spec {
+            Some(path) => {
+                info!("copying variable specification {:?} to {:?}", path, dst_variables);
+                std::fs::copy(path, dst_variables).map_err(GenerateError::IoError)?;
+            }
+            None => {
+                std::fs::write(dst_variables, Assets::get(variables).unwrap().data).unwrap();
             }
         }
 
@@ -543,15 +756,50 @@ pub fn generate(
 
             std::fs::write(dst_resources, Assets::get(src).unwrap().data).unwrap();
         }
+
+        Ok(())
     };
 
     match language {
-        Language::Python => copy_to_resources(&PYTHONASSETS),
+        Language::Python => match model_exchange {
+            true => copy_to_resources(&PYTHONASSETSMODELEXCHANGE),
+            false => copy_to_resources(&PYTHONASSETS),
+        },
+
+        Language::CSharp => match model_exchange {
+            true => copy_to_resources(&CSHARPASSETSMODELEXCHANGE),
+            false => copy_to_resources(&CSHARPASSETS),
+        },
+
+        Language::Java => match model_exchange {
+            true => copy_to_resources(&JAVAASSETSMODELEXCHANGE),
+            false => copy_to_resources(&JAVAASSETS),
+        },
+
+        Language::Matlab => copy_to_resources(&MATLABASSETS),
+
+        Language::Rust => copy_to_resources(&RUSTASSETS),
+    }?;
+
+    // The FMI3 MATLAB and Rust templates declare fewer variables than the
+    // common model description, so they are rendered from the template's
+    // specification.
+    if let (Language::Matlab | Language::Rust, FmiFmuVersion::FMI3, None) = (language, fmu_version, spec) {
+        if let Err(e) = describe(tmpdir.path()) {
+            error!("Couldn't describe the {:?} template: {}", language, e);
+            return Err(GenerateError::Error)
+        }
+    }
 
-        Language::CSharp => copy_to_resources(&CSHARPASSETS),
+    if spec.is_some() {
+        if let Err(e) = generate_from_spec(language, fmu_version, tmpdir.path()) {
+            error!("Couldn't generate the FMU from the variable specification: {}", e);
+            return Err(GenerateError::Error)
+        }
+    }
 
-        Language::Java => copy_to_resources(&JAVAASSETS),
-    };
+    let model_description = std::fs::read(&md).unwrap();
+    std::fs::write(&md, stamp_identity(&model_description, fmu_version, reproducible)).unwrap();
 
     match zipped {
         // zip to temporary, change extension from 'zip' to 'fmu', then copy to output directory
@@ -578,6 +826,7 @@ pub fn generate(
                 tmpdir.path().to_str().unwrap(),
                 file,
                 method,
+                reproducible,
             ) {
                 Ok(_) => (),
                 Err(e) => return Err(GenerateError::ZipError(e)),
@@ -602,49 +851,156 @@ pub fn generate(
     }
 }
 
-pub fn generate_distributed(
+/// Rewrites the model description and replaces the placeholder model of the
+/// FMU in the given directory with a skeleton declaring the variables of the
+/// variable specification in its `resources` directory.
+fn generate_from_spec(
     language: &Language,
     fmu_version: &FmiFmuVersion,
-    outpath: &Path,
-    zipped: bool,
-    endpoint: String,
-    black_box_fmu: bool,
-) -> Result<(), GenerateError>  {
-    // creates two FMUs with a master and a slave for distributed co-simulation
-    let config = Config {
-        ip: endpoint.to_string(),
+    fmu_directory: &Path,
+) -> model_description::DescribeResult<()> {
+    describe(fmu_directory)?;
+
+    let (skeleton, model) = match (language, fmu_version) {
+        (Language::Python, FmiFmuVersion::FMI2) => ("python/fmi2/skeleton/model.py", "model.py"),
+        (Language::Python, FmiFmuVersion::FMI3) => ("python/fmi3/skeleton/model.py", "model.py"),
+        (Language::CSharp, FmiFmuVersion::FMI2) => ("csharp/fmi2/skeleton/model.cs", "model.cs"),
+        (Language::CSharp, FmiFmuVersion::FMI3) => ("csharp/fmi3/skeleton/model.cs", "model.cs"),
+        (Language::Java, FmiFmuVersion::FMI2) => {
+            ("java/src/fmi2/java/skeleton/Model.java", "src/main/java/Model.java")
+        }
+        (Language::Java, FmiFmuVersion::FMI3) => {
+            ("java/src/fmi3/java/skeleton/Model.java", "src/main/java/Model.java")
+        }
+        (Language::Matlab, _) => {
+            return Err(model_description::DescribeError::NoSkeleton("MATLAB"))
+        }
+        (Language::Rust, _) => {
+            return Err(model_description::DescribeError::NoSkeleton("Rust"))
+        }
     };
-    let tmpdir_proxy = TempDir::new().unwrap();
-    let tmpdir_private = TempDir::new().unwrap();
-    let output_string = outpath.to_str();
-    let proxy_string : &str = "_proxy";
-    let private_string : &str = "_private";
-    let output_proxy_string = output_string.unwrap();
-    let output_private_string = output_string.unwrap();
 
-    let output_proxy_string = format!("{}{}",output_proxy_string,proxy_string);
-    let output_private_string = format!("{}{}",output_private_string,private_string);
+    let spec = VariableSpec::from_fmu_directory(fmu_directory)?;
+    let skeleton = Assets::get(skeleton).unwrap();
+    let model_path = fmu_directory.join("resources").join(model);
 
+    info!("rendering model skeleton to {:?}", model_path);
 
-    let outpath_proxy = Path::new(&output_proxy_string);
-    let outpath_private = Path::new(&output_private_string);
-    let toml = toml::to_string(&config).unwrap();
-    let endpoint_file = "endpoint.toml";
-    let dst_endpoint_file = tmpdir_private.path().join(endpoint_file);
+    let rendered = render_model(&String::from_utf8_lossy(&skeleton.data), &spec, language)?;
 
-    info!(
-        "Generating virtual FMUs version `{:?}` for language '{:?}' with tmpdir (proxy) {:?}/tmpdir (private) {:?}  and final output paths {:?} / {:?}",
-        fmu_version,
-        language,
-        tmpdir_proxy.path(),
-        tmpdir_private.path(),
-        outpath_proxy,
-        outpath_private
-    );
+    fs::write(&model_path, rendered)
+        .map_err(|error| model_description::DescribeError::WriteError(model_path, error))
+}
 
-    // First FMU (Proxy)
-    // copy common files to root directory and binaries
-    match FMI3_OS_NAMES.iter()
+/// Declares the given attributes with the given values on the interface
+/// element of the model description, i.e. the element carrying the
+/// `modelIdentifier`. Attributes that the element already has are given the
+/// new values, and the rest are added after the `modelIdentifier`.
+/// 
+/// Fails if the model description can't be parsed or has no interface
+/// element.
+fn declare_attributes(
+    model_description: &[u8],
+    attributes: &[(&str, &str)]
+) -> Result<Vec<u8>, GenerateError> {
+    let model_description = String::from_utf8_lossy(model_description);
+
+    let document = match roxmltree::Document::parse(&model_description) {
+        Ok(document) => document,
+        Err(e) => {
+            error!("Couldn't parse the model description template: {}", e);
+            return Err(GenerateError::Error)
+        }
+    };
+
+    let Some(interface) = document.root_element()
+        .children()
+        .find(|node| node.has_attribute("modelIdentifier"))
+    else {
+        error!("The model description template has no element with a modelIdentifier");
+        return Err(GenerateError::Error)
+    };
+
+    let identifier_end = interface.attributes()
+        .find(|attribute| attribute.name() == "modelIdentifier")
+        .map(|attribute| attribute.range().end)
+        .unwrap_or_default();
+
+    let mut edits: Vec<(std::ops::Range<usize>, String)> = attributes.iter()
+        .map(|(name, value)| {
+            match interface.attributes().find(|attribute| attribute.name() == *name) {
+                Some(attribute) => (attribute.range_value(), escape(value)),
+                None => (
+                    identifier_end..identifier_end,
+                    format!(" {}=\"{}\"", name, escape(value))
+                )
+            }
+        })
+        .collect();
+
+    // Applied from the end of the document, such that the ranges of the
+    // remaining edits stay valid. Insertions at the same point are applied in
+    // reverse, keeping the attributes in the given order.
+    edits.sort_by_key(|(range, _)| range.start);
+
+    let mut model_description = model_description.to_string();
+    for (range, text) in edits.into_iter().rev() {
+        model_description.replace_range(range, &text);
+    }
+
+    Ok(model_description.into_bytes())
+}
+
+/// Gives the language and the resources of the template that the FMU with
+/// the given resources directory was most likely generated from, which is the
+/// one with the fewest of its required resources missing, and the most present
+/// among templates where one extends the other.
+pub(crate) fn recognize_template(
+    resources: &Path,
+    fmi_version: &FmiVersion
+) -> Option<(Language, &'static [(&'static str, &'static str)])> {
+    let templates: [(Language, &'static LanguageAssets); 5] = [
+        (Language::Python, &PYTHONASSETS),
+        (Language::CSharp, &CSHARPASSETS),
+        (Language::Java, &JAVAASSETS),
+        (Language::Matlab, &MATLABASSETS),
+        (Language::Rust, &RUSTASSETS),
+    ];
+
+    templates
+        .into_iter()
+        .map(|(language, assets)| match fmi_version {
+            FmiVersion::Fmi2 => (language, assets.fmi2_resources.as_slice()),
+            FmiVersion::Fmi3 => (language, assets.fmi3_resources.as_slice()),
+        })
+        .map(|(language, template)| {
+            let (present, missing): (Vec<&(&str, &str)>, Vec<_>) = template.iter()
+                .filter(|(_, destination)| is_required(destination))
+                .partition(|(_, destination)| resources.join(destination).is_file());
+            ((Reverse(missing.len()), present.len()), language, template)
+        })
+        .filter(|((_, present), _, _)| *present > 0)
+        .max_by_key(|(score, _, _)| *score)
+        .map(|(_, language, template)| (language, template))
+}
+
+/// Documentation and the tooling for packaging the backend aren't needed to
+/// run it.
+pub(crate) fn is_required(destination: &str) -> bool {
+    destination != "README.md" && !destination.starts_with("compilation_resources/")
+}
+
+/// Copies the API binaries embedded in this tool into the platform folders
+/// of the `binaries` directory of the given FMU, named after the given
+/// model identifier.
+///
+/// Fails if no binaries could be copied.
+fn copy_binaries(
+    fmu_version: &FmiFmuVersion,
+    fmu_directory: &Path,
+    library_stem: &str
+) -> Result<(), ()> {
+    FMI3_OS_NAMES.iter()
         .flat_map(|os_name| {
             FMI3_ARCHITECTURES.iter()
                 .map(move |arch| {
@@ -658,6 +1014,11 @@ pub fn generate_distributed(
                 "windows" => "unifmu.dll",
                 _ => "unifmu.so"
             };
+            let library_extension = match platform_tuple.1 {
+                "darwin" => "dylib",
+                "windows" => "dll",
+                _ => "so"
+            };
             let platform_name = format!(
                 "{}-{}", platform_tuple.0, platform_tuple.1
             );
@@ -682,22 +1043,27 @@ pub fn generate_distributed(
                         FmiFmuVersion::FMI3 => platform_name
                     };
 
-                    let destination_folder_path = tmpdir_proxy
-                        .path()
+                    let destination_folder_path = fmu_directory
                         .join("binaries")
                         .join(destination_folder_name);
 
                     std::fs::create_dir_all(&destination_folder_path)
                         .map_err(|io_error| {
                             error!(
-                                "Couldn't create binary fodler structure {}: {}",
+                                "Couldn't create binary folder structure {}: {}",
                                 destination_folder_path.display(),
                                 io_error
                             );
                         })?;
 
                     let destination_path = destination_folder_path
-                        .join(library_name);
+                        .join(format!("{}.{}", library_stem, library_extension));
+
+                    info!(
+                        "copying resource \"{}\" to \"{}\"",
+                        asset_placement,
+                        destination_path.display()
+                    );
 
                     std::fs::write(
                         &destination_path,
@@ -722,13 +1088,105 @@ pub fn generate_distributed(
         .unwrap_or_else(|| {
             error!("No combination of os names and architechture found. Check statics FMI3_OS_NAMES and static FMI3_ARCHITECTURES");
             Err(())
-        }) {
-            Ok(_) => (),
-            Err(_) => {
-                error!("Didn't move any API binaries from assets to generated FMI. Check that assets include compiled API binaries");
-                return Err(GenerateError::Error)
-            }
-        }
+        })
+}
+
+/// Gives the model description a fresh GUID (FMI2) or instantiation token
+/// (FMI3) and the current generation date and time, so that importers never
+/// mistake one generated FMU for another.
+///
+/// A reproducible FMU instead gets a token derived from its model description
+/// and the time given by [`reproducible_time`], so that generating the same
+/// FMU twice gives the same model description.
+fn stamp_identity(
+    model_description: &[u8],
+    fmu_version: &FmiFmuVersion,
+    reproducible: bool,
+) -> Vec<u8> {
+    let token_attribute = match fmu_version {
+        FmiFmuVersion::FMI2 => "guid",
+        FmiFmuVersion::FMI3 => "instantiationToken",
+    };
+    let (token, generation_date_and_time) = match reproducible {
+        true => (Uuid::new_v5(&Uuid::NAMESPACE_OID, model_description), reproducible_time()),
+        false => (Uuid::new_v4(), Utc::now()),
+    };
+    let token = token.to_string();
+    let generation_date_and_time = generation_date_and_time.format("%Y-%m-%dT%H:%M:%SZ").to_string();
+
+    let model_description = String::from_utf8_lossy(model_description);
+    let model_description = replace_attribute(&model_description, token_attribute, &token);
+    replace_attribute(&model_description, "generationDateAndTime", &generation_date_and_time)
+        .into_bytes()
+}
+
+/// Replaces the value of the first occurrence of the given attribute in the
+/// model description, leaving it unchanged if the attribute isn't declared.
+fn replace_attribute(model_description: &str, name: &str, value: &str) -> String {
+    let opening = format!(" {}=\"", name);
+
+    let Some(start) = model_description.find(&opening).map(|index| index + opening.len()) else {
+        return String::from(model_description);
+    };
+    let Some(length) = model_description[start..].find('"') else {
+        return String::from(model_description);
+    };
+
+    format!(
+        "{}{}{}",
+        &model_description[..start],
+        value,
+        &model_description[start + length..]
+    )
+}
+
+pub fn generate_distributed(
+    language: &Language,
+    fmu_version: &FmiFmuVersion,
+    outpath: &Path,
+    zipped: bool,
+    endpoint: String,
+    black_box_fmu: bool,
+    reproducible: bool,
+) -> Result<(), GenerateError>  {
+    // creates two FMUs with a master and a slave for distributed co-simulation
+    let config = Config {
+        ip: endpoint.to_string(),
+    };
+    let tmpdir_proxy = TempDir::new().unwrap();
+    let tmpdir_private = TempDir::new().unwrap();
+    let output_string = outpath.to_str();
+    let proxy_string : &str = "_proxy";
+    let private_string : &str = "_private";
+    let output_proxy_string = output_string.unwrap();
+    let output_private_string = output_string.unwrap();
+
+    let output_proxy_string = format!("{}{}",output_proxy_string,proxy_string);
+    let output_private_string = format!("{}{}",output_private_string,private_string);
+
+
+    let outpath_proxy = Path::new(&output_proxy_string);
+    let outpath_private = Path::new(&output_private_string);
+    let toml = toml::to_string(&config).unwrap();
+    let endpoint_file = "endpoint.toml";
+    let dst_endpoint_file = tmpdir_private.path().join(endpoint_file);
+
+    info!(
+        "Generating virtual FMUs version `{:?}` for language '{:?}' with tmpdir (proxy) {:?}/tmpdir (private) {:?}  and final output paths {:?} / {:?}",
+        fmu_version,
+        language,
+        tmpdir_proxy.path(),
+        tmpdir_private.path(),
+        outpath_proxy,
+        outpath_private
+    );
+
+    // First FMU (Proxy)
+    // copy common files to root directory and binaries
+    if copy_binaries(fmu_version, tmpdir_proxy.path(), "unifmu").is_err() {
+        error!("Didn't move any API binaries from assets to generated FMI. Check that assets include compiled API binaries");
+        return Err(GenerateError::Error)
+    }
 
     let md = tmpdir_proxy.path().join("modelDescription.xml");
 
@@ -736,26 +1194,23 @@ pub fn generate_distributed(
     let copy_to_resources_proxy =|assets: &Vec<(&'static str, &'static str)>| {
         let assets_all = assets.to_owned();
 
-        match fmu_version {
-            FmiFmuVersion::FMI2 => {
-                std::fs::write(
-                    &md,
-                    Assets::get("common/fmi2/modelDescription.xml")
-                        .unwrap()
-                        .data,
-                )
-                .unwrap();
-            }
-            FmiFmuVersion::FMI3 => {
-                std::fs::write(
-                    &md,
-                    Assets::get("common/fmi3/modelDescription.xml")
-                        .unwrap()
-                        .data,
-                )
-                .unwrap();
-            }
-        }
+        let model_description = match fmu_version {
+            FmiFmuVersion::FMI2 => "common/fmi2/modelDescription.xml",
+            FmiFmuVersion::FMI3 => "common/fmi3/modelDescription.xml",
+        };
+        std::fs::write(
+            &md,
+            stamp_identity(&Assets::get(model_description).unwrap().data, fmu_version, reproducible),
+        )
+        .unwrap();
+
+        let variables = match fmu_version {
+            FmiFmuVersion::FMI2 => "common/fmi2/variables.toml",
+            FmiFmuVersion::FMI3 => "common/fmi3/variables.toml",
+        };
+        let dst_variables = tmpdir_proxy.path().join("resources").join(VARIABLES_FILE_NAME);
+        std::fs::create_dir_all(dst_variables.parent().unwrap()).unwrap();
+        std::fs::write(dst_variables, Assets::get(variables).unwrap().data).unwrap();
 
         for (src, dst) in assets_all {
             let dst_resources = tmpdir_proxy.path().join("resources").join(dst);
@@ -817,6 +1272,14 @@ pub fn generate_distributed(
                 copy_to_resources(&JAVAASSETSREMOTE);
             }
         }
+
+        Language::Matlab | Language::Rust => {
+            error!(
+                "Distributed FMUs can't be generated for language '{:?}'",
+                language
+            );
+            return Err(GenerateError::Error)
+        }
     };
 
     copy_to_resources_proxy(&ASSETSPROXY);
@@ -863,6 +1326,7 @@ pub fn generate_distributed(
                 tmpdir_proxy.path().to_str().unwrap(),
                 file_proxy,
                 method,
+                reproducible,
             ) {
                 Ok(_) => (),
                 Err(e) => return Err(GenerateError::ZipError(e)),
@@ -4,8 +4,14 @@ use log::{error, info};
 use std::{path::PathBuf, process::exit};
 use unifmu::FmiFmuVersion;
 use unifmu::{
+    archive::{pack, unpack},
+    convert::convert,
     generate,
+    GenerateOptions,
+    model_description::describe,
     generate_distributed,
+    upgrade::upgrade,
+    validate::validate,
     Language,
 };
 
@@ -40,6 +46,26 @@ enum Command {
         /// Compress the generated FMU as a zip-archive and store with '.fmu' extension
         #[clap(short, long)]
         zipped: bool,
+
+        /// Generate a Model Exchange FMU instead of a Co-Simulation FMU (Python, or C# and Java for FMI2)
+        #[clap(short, long)]
+        model_exchange: bool,
+
+        /// Declare that the FMU provides directional and adjoint derivatives (FMI3 only)
+        #[clap(long)]
+        provides_derivatives: bool,
+
+        /// Declare that the FMU can interpolate inputs and provide output derivatives up to the given order (FMI2 Co-Simulation only)
+        #[clap(long, value_name = "ORDER", value_parser = clap::value_parser!(u32).range(1..))]
+        interpolation_order: Option<u32>,
+
+        /// Variable specification (TOML) from which the model description and a model skeleton are generated
+        #[clap(long, value_name = "SPEC")]
+        spec: Option<PathBuf>,
+
+        /// Make the generated FMU and its archive reproducible, with sorted entries, normalised permissions and timestamps set from SOURCE_DATE_EPOCH
+        #[clap(long)]
+        reproducible: bool,
     },
 
     /// Generates a pair of FMU/private folder for distributed co-simulation, where the FMU works as the proxy and the folder as the model
@@ -66,6 +92,67 @@ enum Command {
         /// Additional feature to handle when the private model is an existing black-box FMU with '.fmu' extension. In this case, the private backend always uses Python and its inner FMU requires to have the same name as the output directory or name of the FMU archive
         #[clap(short, long)]
         black_box_fmu: bool,
+
+        /// Make the generated proxy FMU and its archive reproducible, with sorted entries, normalised permissions and timestamps set from SOURCE_DATE_EPOCH
+        #[clap(long)]
+        reproducible: bool,
+    },
+
+    /// Rewrite the variables and model structure of an FMU's modelDescription.xml from the variable specification in 'resources/variables.toml'
+    Describe {
+        /// Directory of the (unzipped) FMU to describe
+        fmu_directory: PathBuf,
+    },
+
+    /// Check an FMU's launch.toml, binaries, modelDescription.xml and backend resources for problems
+    Validate {
+        /// Directory or '.fmu' archive of the FMU to validate
+        fmu: PathBuf,
+    },
+
+    /// Compress the directory of an FMU into a '.fmu' archive
+    Pack {
+        /// Directory of the FMU to compress
+        fmu_directory: PathBuf,
+
+        /// Path of the archive [default: the directory with the '.fmu' extension]
+        outpath: Option<PathBuf>,
+
+        /// Make the archive reproducible, with sorted entries, normalised permissions and timestamps set from SOURCE_DATE_EPOCH
+        #[clap(long)]
+        reproducible: bool,
+    },
+
+    /// Extract a '.fmu' archive into a directory
+    Unpack {
+        /// The '.fmu' archive to extract
+        fmu: PathBuf,
+
+        /// Directory to extract the FMU into [default: the archive without its extension]
+        outpath: Option<PathBuf>,
+    },
+
+    /// Replace the binaries and protobuf schemas of an FMU with those of this version of UniFMU, leaving the model and its modelDescription.xml untouched
+    Upgrade {
+        /// Directory or '.fmu' archive of the FMU to upgrade in place
+        fmu: PathBuf,
+
+        /// Make the upgraded archive reproducible, with sorted entries, normalised permissions and timestamps set from SOURCE_DATE_EPOCH
+        #[clap(long)]
+        reproducible: bool,
+    },
+
+    /// Convert an FMI2 FMU to FMI3, keeping its model behind a compatibility shim
+    Convert {
+        /// Directory or '.fmu' archive of the FMU to convert
+        fmu: PathBuf,
+
+        /// Version of the FMI specification to convert the FMU to
+        #[clap(long, value_enum)]
+        to: FmiFmuVersion,
+
+        /// Path of the converted FMU, in the same form as the FMU [default: the FMU itself]
+        outpath: Option<PathBuf>,
     }
 }
 
@@ -85,7 +172,18 @@ fn main() {
             fmu_version,
             outpath,
             zipped,
-        } => match generate(&language, &fmu_version, &outpath, zipped) {
+            model_exchange,
+            provides_derivatives,
+            interpolation_order,
+            spec,
+            reproducible,
+        } => match generate(&language, &fmu_version, &outpath, zipped, &GenerateOptions {
+            model_exchange,
+            provides_derivatives,
+            interpolation_order,
+            spec,
+            reproducible,
+        }) {
             Ok(_) => {
                 info!("the FMU was generated successfully");
             }
@@ -102,7 +200,8 @@ fn main() {
             zipped,
             endpoint,
             black_box_fmu,
-        } => match generate_distributed(&language, &fmu_version, &outpath, zipped, endpoint, black_box_fmu) {
+            reproducible,
+        } => match generate_distributed(&language, &fmu_version, &outpath, zipped, endpoint, black_box_fmu, reproducible) {
             Ok(_) => {
                 info!("the FMUs were generated successfully");
             }
@@ -111,5 +210,72 @@ fn main() {
                 exit(-1);
             }
         }
+
+        Command::Describe { fmu_directory } => match describe(&fmu_directory) {
+            Ok(_) => {
+                info!("the model description was written successfully");
+            }
+            Err(e) => {
+                error!("an error ocurred while describing the FMU: {}", e);
+                exit(-1);
+            }
+        }
+
+        Command::Validate { fmu } => match validate(&fmu) {
+            Ok(problems) if problems.is_empty() => {
+                info!("the FMU is valid");
+            }
+            Ok(problems) => {
+                for problem in &problems {
+                    error!("{}", problem);
+                }
+                error!("the FMU has {} problem(s)", problems.len());
+                exit(-1);
+            }
+            Err(e) => {
+                error!("an error ocurred while validating the FMU: {}", e);
+                exit(-1);
+            }
+        }
+
+        Command::Pack { fmu_directory, outpath, reproducible } => match pack(&fmu_directory, outpath.as_deref(), reproducible) {
+            Ok(archive) => {
+                info!("the FMU was packed into {:?}", archive);
+            }
+            Err(e) => {
+                error!("an error ocurred while packing the FMU: {}", e);
+                exit(-1);
+            }
+        }
+
+        Command::Unpack { fmu, outpath } => match unpack(&fmu, outpath.as_deref()) {
+            Ok(directory) => {
+                info!("the FMU was unpacked into {:?}", directory);
+            }
+            Err(e) => {
+                error!("an error ocurred while unpacking the FMU: {}", e);
+                exit(-1);
+            }
+        }
+
+        Command::Upgrade { fmu, reproducible } => match upgrade(&fmu, reproducible) {
+            Ok(_) => {
+                info!("the FMU was upgraded successfully");
+            }
+            Err(e) => {
+                error!("an error ocurred while upgrading the FMU: {}", e);
+                exit(-1);
+            }
+        }
+
+        Command::Convert { fmu, to, outpath } => match convert(&fmu, &to, outpath.as_deref()) {
+            Ok(_) => {
+                info!("the FMU was converted successfully");
+            }
+            Err(e) => {
+                error!("an error ocurred while converting the FMU: {}", e);
+                exit(-1);
+            }
+        }
     }
 }
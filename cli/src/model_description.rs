@@ -0,0 +1,590 @@
+//! Contains the declarative variable specification of a model, and the
+//! rendering of the `<ModelVariables>` and `<ModelStructure>` elements of an
+//! FMU's `modelDescription.xml` from such a specification.
+//!
+//! Generated FMUs ship the specification of their placeholder model as
+//! `resources/variables.toml`. The `describe` command rewrites the model
+//! description of an FMU from that file, so that the variables only need to
+//! be declared in one place.
+
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    error::Error,
+    fmt::{Display, Write},
+    fs,
+    path::{Path, PathBuf}
+};
+
+/// Name of the variable specification in the `resources` directory of an FMU.
+pub static VARIABLES_FILE_NAME: &str = "variables.toml";
+
+static FMI2_TYPES: [&str; 4] = ["Real", "Integer", "Boolean", "String"];
+static FMI3_TYPES: [&str; 14] = [
+    "Float32", "Float64",
+    "Int8", "UInt8", "Int16", "UInt16", "Int32", "UInt32", "Int64", "UInt64",
+    "Boolean", "String", "Binary", "Clock"
+];
+
+/// The FMI version of a model description, as it affects the layout of its
+/// variables and model structure.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FmiVersion {
+    Fmi2,
+    Fmi3
+}
+
+/// The variables of a model, in the order they are declared in the model
+/// description.
+#[derive(Deserialize, Serialize, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct VariableSpec {
+    #[serde(rename = "variable", default)]
+    pub variables: Vec<Variable>
+}
+
+/// A single variable of the model.
+///
+/// The attribute names and values are those of the model description of the
+/// targeted FMI version, except that value references are used to refer to
+/// other variables for both versions.
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct Variable {
+    pub name: String,
+    pub value_reference: u32,
+    /// The element name of the variable type, e.g. `Real` for FMI2 or
+    /// `Float64` for FMI3.
+    #[serde(rename = "type")]
+    pub variable_type: String,
+    #[serde(default = "default_causality")]
+    pub causality: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub variability: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub initial: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Start value. Arrays are written as space separated lists.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start: Option<toml::Value>,
+    /// Value reference of the continuous state this variable is the
+    /// derivative of.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub derivative: Option<u32>,
+    /// Value references of the variables this output or derivative depends
+    /// on. If left out, it may depend on every known variable.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dependencies: Option<Vec<u32>>,
+    /// Dimensions of an FMI3 array variable.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub dimensions: Vec<Dimension>,
+    /// Value references of the clocks of an FMI3 clocked variable.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub clocks: Vec<u32>,
+    /// Whether this FMI3 variable is an event indicator.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub event_indicator: bool,
+    /// Further attributes written as they are, on the variable element for
+    /// FMI3 and on the type element for FMI2.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub attributes: BTreeMap<String, toml::Value>
+}
+
+/// A dimension of an FMI3 array variable, either fixed or given by a
+/// structural parameter.
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(untagged)]
+pub enum Dimension {
+    Start(u64),
+    Variable { value_reference: u32 }
+}
+
+fn default_causality() -> String {
+    String::from("local")
+}
+
+impl VariableSpec {
+    /// Reads the variable specification in the `resources` directory of the
+    /// given FMU directory.
+    pub fn from_fmu_directory(fmu_directory: &Path) -> DescribeResult<Self> {
+        Self::from_file(&fmu_directory.join("resources").join(VARIABLES_FILE_NAME))
+    }
+
+    /// Reads a variable specification from the given TOML file.
+    pub fn from_file(path: &Path) -> DescribeResult<Self> {
+        let contents = fs::read_to_string(path)
+            .map_err(|error| DescribeError::ReadError(path.to_path_buf(), error))?;
+
+        toml::from_str(&contents)
+            .map_err(|error| DescribeError::ParseError(path.to_path_buf(), error))
+    }
+
+    /// Checks that the variables are unique, that their types exist in the
+    /// given FMI version, and that they only refer to declared variables.
+    pub fn validate(&self, version: FmiVersion) -> DescribeResult<()> {
+        let mut names = HashSet::new();
+        let mut value_references = HashSet::new();
+
+        for variable in &self.variables {
+            if !names.insert(variable.name.as_str()) {
+                return Err(invalid(format!(
+                    "variable name '{}' is declared more than once", variable.name
+                )));
+            }
+            if !value_references.insert(variable.value_reference) {
+                return Err(invalid(format!(
+                    "value reference {} is declared more than once",
+                    variable.value_reference
+                )));
+            }
+        }
+
+        for variable in &self.variables {
+            let known_types: &[&str] = match version {
+                FmiVersion::Fmi2 => &FMI2_TYPES,
+                FmiVersion::Fmi3 => &FMI3_TYPES
+            };
+
+            if !known_types.contains(&variable.variable_type.as_str()) {
+                return Err(invalid(format!(
+                    "variable '{}' has type '{}', which isn't one of {}",
+                    variable.name,
+                    variable.variable_type,
+                    known_types.join(", ")
+                )));
+            }
+
+            if version == FmiVersion::Fmi2
+                && (!variable.dimensions.is_empty()
+                    || !variable.clocks.is_empty()
+                    || variable.event_indicator)
+            {
+                return Err(invalid(format!(
+                    "variable '{}' declares dimensions, clocks or an event indicator, which only exist in FMI3",
+                    variable.name
+                )));
+            }
+
+            let references = variable.derivative.iter()
+                .chain(variable.dependencies.iter().flatten())
+                .chain(variable.clocks.iter())
+                .chain(variable.dimensions.iter().filter_map(|dimension| match dimension {
+                    Dimension::Variable { value_reference } => Some(value_reference),
+                    Dimension::Start(_) => None
+                }));
+
+            for reference in references {
+                if !value_references.contains(reference) {
+                    return Err(invalid(format!(
+                        "variable '{}' refers to value reference {}, which isn't declared",
+                        variable.name,
+                        reference
+                    )));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Renders the `<ModelVariables>` and `<ModelStructure>` elements for the
+    /// given FMI version, indented as children of `<fmiModelDescription>`.
+    pub fn render(&self, version: FmiVersion) -> DescribeResult<String> {
+        match version {
+            FmiVersion::Fmi2 => self.render_fmi2(),
+            FmiVersion::Fmi3 => self.render_fmi3()
+        }
+    }
+
+    fn render_fmi2(&self) -> DescribeResult<String> {
+        // FMI2 refers to variables by their one-based index in ModelVariables.
+        let indices: HashMap<u32, usize> = self.variables.iter()
+            .enumerate()
+            .map(|(index, variable)| (variable.value_reference, index + 1))
+            .collect();
+        let to_indices = |references: &[u32]| references.iter()
+            .map(|reference| indices[reference].to_string())
+            .collect::<Vec<_>>()
+            .join(" ");
+        let states = self.states();
+
+        let mut xml = String::from("<ModelVariables>\n");
+
+        for (index, variable) in self.variables.iter().enumerate() {
+            let mut attributes = vec![
+                ("name", variable.name.clone()),
+                ("valueReference", variable.value_reference.to_string())
+            ];
+            push_optional(&mut attributes, "variability", &variable.variability);
+            attributes.push(("causality", variable.causality.clone()));
+            push_optional(&mut attributes, "initial", &variable.initial);
+            push_optional(&mut attributes, "description", &variable.description);
+
+            let mut type_attributes = Vec::new();
+            if let Some(start) = &variable.start {
+                type_attributes.push((String::from("start"), render_value(start, variable)?));
+            }
+            if let Some(state) = variable.derivative {
+                type_attributes.push((String::from("derivative"), indices[&state].to_string()));
+            }
+            for (name, value) in &variable.attributes {
+                type_attributes.push((name.clone(), render_value(value, variable)?));
+            }
+
+            writeln!(xml, "    <!--Index of variable = \"{}\"-->", index + 1).unwrap();
+            writeln!(xml, "    <ScalarVariable{}>", render_attributes(&attributes)).unwrap();
+            writeln!(xml, "      <{}{} />", variable.variable_type, render_attributes(&type_attributes)).unwrap();
+            xml.push_str("    </ScalarVariable>\n");
+        }
+
+        xml.push_str("  </ModelVariables>\n  <ModelStructure>\n");
+
+        let render_unknowns = |xml: &mut String, element: &str, unknowns: Vec<&Variable>, with_dependencies: bool| {
+            if unknowns.is_empty() {
+                return;
+            }
+            writeln!(xml, "    <{}>", element).unwrap();
+            for unknown in unknowns {
+                let mut attributes = vec![
+                    ("index", indices[&unknown.value_reference].to_string())
+                ];
+                if let (true, Some(dependencies)) = (with_dependencies, &unknown.dependencies) {
+                    attributes.push(("dependencies", to_indices(dependencies)));
+                }
+                writeln!(xml, "      <Unknown{} />", render_attributes(&attributes)).unwrap();
+            }
+            writeln!(xml, "    </{}>", element).unwrap();
+        };
+
+        render_unknowns(&mut xml, "Outputs", self.outputs().collect(), true);
+        render_unknowns(&mut xml, "Derivatives", self.derivatives().collect(), true);
+        render_unknowns(
+            &mut xml,
+            "InitialUnknowns",
+            self.initial_unknowns(&states, FmiVersion::Fmi2).collect(),
+            false
+        );
+
+        xml.push_str("  </ModelStructure>");
+
+        Ok(xml)
+    }
+
+    fn render_fmi3(&self) -> DescribeResult<String> {
+        let to_list = |references: &[u32]| references.iter()
+            .map(|reference| reference.to_string())
+            .collect::<Vec<_>>()
+            .join(" ");
+        let states = self.states();
+
+        let mut xml = String::from("<ModelVariables>\n");
+
+        for variable in &self.variables {
+            let has_start_elements = matches!(variable.variable_type.as_str(), "String" | "Binary");
+
+            let mut attributes = vec![
+                (String::from("name"), variable.name.clone()),
+                (String::from("valueReference"), variable.value_reference.to_string()),
+                (String::from("causality"), variable.causality.clone())
+            ];
+            if let Some(variability) = &variable.variability {
+                attributes.push((String::from("variability"), variability.clone()));
+            }
+            if let Some(initial) = &variable.initial {
+                attributes.push((String::from("initial"), initial.clone()));
+            }
+            if let (false, Some(start)) = (has_start_elements, &variable.start) {
+                attributes.push((String::from("start"), render_value(start, variable)?));
+            }
+            if let Some(state) = variable.derivative {
+                attributes.push((String::from("derivative"), state.to_string()));
+            }
+            if !variable.clocks.is_empty() {
+                attributes.push((String::from("clocks"), to_list(&variable.clocks)));
+            }
+            for (name, value) in &variable.attributes {
+                attributes.push((name.clone(), render_value(value, variable)?));
+            }
+            if let Some(description) = &variable.description {
+                attributes.push((String::from("description"), description.clone()));
+            }
+
+            let mut children = Vec::new();
+            for dimension in &variable.dimensions {
+                children.push(match dimension {
+                    Dimension::Start(start) => format!("<Dimension start=\"{}\" />", start),
+                    Dimension::Variable { value_reference } => {
+                        format!("<Dimension valueReference=\"{}\" />", value_reference)
+                    }
+                });
+            }
+            if let (true, Some(start)) = (has_start_elements, &variable.start) {
+                let starts = match start {
+                    toml::Value::Array(values) => values.iter().collect(),
+                    value => vec![value]
+                };
+                for start in starts {
+                    children.push(format!(
+                        "<Start value=\"{}\" />",
+                        escape(&render_value(start, variable)?)
+                    ));
+                }
+            }
+
+            let element = format!("    <{}{}", variable.variable_type, render_attributes(&attributes));
+            if children.is_empty() {
+                writeln!(xml, "{} />", element).unwrap();
+            } else {
+                writeln!(xml, "{}>", element).unwrap();
+                for child in children {
+                    writeln!(xml, "      {}", child).unwrap();
+                }
+                writeln!(xml, "    </{}>", variable.variable_type).unwrap();
+            }
+        }
+
+        xml.push_str("  </ModelVariables>\n  <ModelStructure>\n");
+
+        let render_unknowns = |xml: &mut String, element: &str, unknowns: Vec<&Variable>, with_dependencies: bool| {
+            for unknown in unknowns {
+                let mut attributes = vec![
+                    ("valueReference", unknown.value_reference.to_string())
+                ];
+                if let (true, Some(dependencies)) = (with_dependencies, &unknown.dependencies) {
+                    attributes.push(("dependencies", to_list(dependencies)));
+                }
+                writeln!(xml, "    <{}{} />", element, render_attributes(&attributes)).unwrap();
+            }
+        };
+
+        render_unknowns(&mut xml, "Output", self.outputs().collect(), true);
+        render_unknowns(&mut xml, "ContinuousStateDerivative", self.derivatives().collect(), true);
+        render_unknowns(
+            &mut xml,
+            "InitialUnknown",
+            self.initial_unknowns(&states, FmiVersion::Fmi3).collect(),
+            false
+        );
+        render_unknowns(
+            &mut xml,
+            "EventIndicator",
+            self.variables.iter().filter(|variable| variable.event_indicator).collect(),
+            false
+        );
+
+        xml.push_str("  </ModelStructure>");
+
+        Ok(xml)
+    }
+
+    fn outputs(&self) -> impl Iterator<Item = &Variable> {
+        self.variables.iter()
+            .filter(|variable| variable.causality == "output")
+    }
+
+    fn derivatives(&self) -> impl Iterator<Item = &Variable> {
+        self.variables.iter()
+            .filter(|variable| variable.derivative.is_some())
+    }
+
+    /// Value references of the continuous states, i.e. the variables that
+    /// other variables are the derivatives of.
+    fn states(&self) -> HashSet<u32> {
+        self.variables.iter()
+            .filter_map(|variable| variable.derivative)
+            .collect()
+    }
+
+    /// The unknowns that are computed during Initialization Mode: the
+    /// outputs, continuous states and derivatives whose start values aren't
+    /// exact, along with the calculated parameters.
+    fn initial_unknowns<'a>(
+        &'a self,
+        states: &'a HashSet<u32>,
+        version: FmiVersion
+    ) -> impl Iterator<Item = &'a Variable> {
+        self.variables.iter().filter(move |variable| {
+            if variable.causality == "calculatedParameter" {
+                return true;
+            }
+
+            let is_unknown = variable.causality == "output"
+                || variable.derivative.is_some()
+                || states.contains(&variable.value_reference);
+
+            let default_initial = match (version, variable.causality.as_str()) {
+                (_, "output") => "calculated",
+                (FmiVersion::Fmi2, "local") => "calculated",
+                _ => "exact"
+            };
+
+            let initial = variable.initial.as_deref().unwrap_or(default_initial);
+
+            is_unknown
+                && variable.variable_type != "Clock"
+                && matches!(initial, "approx" | "calculated")
+        })
+    }
+}
+
+/// Rewrites the `<ModelVariables>` and `<ModelStructure>` elements of the
+/// `modelDescription.xml` in the given FMU directory from the variable
+/// specification in its `resources` directory.
+///
+/// The remainder of the model description, such as the capability flags and
+/// log categories, is left as it is.
+pub fn describe(fmu_directory: &Path) -> DescribeResult<()> {
+    let model_description_path = fmu_directory.join("modelDescription.xml");
+
+    let model_description = fs::read_to_string(&model_description_path)
+        .map_err(|error| DescribeError::ReadError(model_description_path.clone(), error))?;
+
+    let version = fmi_version(&model_description)?;
+
+    let spec = VariableSpec::from_fmu_directory(fmu_directory)?;
+    spec.validate(version)?;
+
+    let model_description = replace_variables(&model_description, &spec.render(version)?)?;
+
+    fs::write(&model_description_path, model_description)
+        .map_err(|error| DescribeError::WriteError(model_description_path, error))
+}
+
+/// Reads the FMI version from the `fmiVersion` attribute of a model
+/// description.
+pub(crate) fn fmi_version(model_description: &str) -> DescribeResult<FmiVersion> {
+    let attribute = "fmiVersion=\"";
+
+    let version = model_description
+        .find(attribute)
+        .map(|start| &model_description[start + attribute.len()..])
+        .and_then(|rest| rest.split('"').next())
+        .ok_or(DescribeError::MissingElement("fmiVersion"))?;
+
+    if version.starts_with("2.") {
+        Ok(FmiVersion::Fmi2)
+    } else if version.starts_with("3.") {
+        Ok(FmiVersion::Fmi3)
+    } else {
+        Err(DescribeError::UnknownFmiVersion(String::from(version)))
+    }
+}
+
+/// Replaces everything from the start of `<ModelVariables>` to the end of
+/// `</ModelStructure>` in the model description with the given elements.
+fn replace_variables(model_description: &str, elements: &str) -> DescribeResult<String> {
+    let start = model_description
+        .find("<ModelVariables")
+        .ok_or(DescribeError::MissingElement("ModelVariables"))?;
+
+    let end_tag = "</ModelStructure>";
+    let end = model_description
+        .find(end_tag)
+        .map(|end| end + end_tag.len())
+        .ok_or(DescribeError::MissingElement("ModelStructure"))?;
+
+    if end < start {
+        return Err(DescribeError::MissingElement("ModelVariables"));
+    }
+
+    Ok(format!(
+        "{}{}{}",
+        &model_description[..start],
+        elements,
+        &model_description[end..]
+    ))
+}
+
+fn push_optional(
+    attributes: &mut Vec<(&'static str, String)>,
+    name: &'static str,
+    value: &Option<String>
+) {
+    if let Some(value) = value {
+        attributes.push((name, value.clone()));
+    }
+}
+
+fn render_attributes<N: AsRef<str>>(attributes: &[(N, String)]) -> String {
+    attributes.iter()
+        .map(|(name, value)| format!(" {}=\"{}\"", name.as_ref(), escape(value)))
+        .collect()
+}
+
+/// Renders a specification value as the text of an XML attribute. Arrays are
+/// rendered as space separated lists.
+fn render_value(value: &toml::Value, variable: &Variable) -> DescribeResult<String> {
+    match value {
+        toml::Value::String(string) => Ok(string.clone()),
+        toml::Value::Integer(integer) => Ok(integer.to_string()),
+        toml::Value::Float(float) => Ok(format!("{:?}", float)),
+        toml::Value::Boolean(boolean) => Ok(boolean.to_string()),
+        toml::Value::Array(values) => Ok(values.iter()
+            .map(|value| render_value(value, variable))
+            .collect::<DescribeResult<Vec<_>>>()?
+            .join(" ")),
+        _ => Err(invalid(format!(
+            "variable '{}' has a value that can't be written as an attribute",
+            variable.name
+        )))
+    }
+}
+
+pub(crate) fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+pub(crate) fn invalid(message: String) -> DescribeError {
+    DescribeError::InvalidSpec(message)
+}
+
+pub type DescribeResult<T> = Result<T, DescribeError>;
+
+#[derive(Debug)]
+pub enum DescribeError {
+    ReadError(PathBuf, std::io::Error),
+    WriteError(PathBuf, std::io::Error),
+    ParseError(PathBuf, toml::de::Error),
+    InvalidSpec(String),
+    MissingElement(&'static str),
+    UnknownFmiVersion(String),
+    UnknownPlaceholder(String),
+    NoSkeleton(&'static str)
+}
+
+impl Display for DescribeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ReadError(path, error) => {
+                write!(f, "couldn't read '{}': {}", path.display(), error)
+            }
+            Self::WriteError(path, error) => {
+                write!(f, "couldn't write '{}': {}", path.display(), error)
+            }
+            Self::ParseError(path, error) => {
+                write!(f, "couldn't parse '{}': {}", path.display(), error)
+            }
+            Self::InvalidSpec(message) => {
+                write!(f, "invalid variable specification; {}", message)
+            }
+            Self::MissingElement(name) => {
+                write!(f, "model description has no {}", name)
+            }
+            Self::UnknownFmiVersion(version) => {
+                write!(f, "model description has unsupported fmiVersion '{}'", version)
+            }
+            Self::UnknownPlaceholder(name) => {
+                write!(f, "model skeleton has unknown placeholder '{}'", name)
+            }
+            Self::NoSkeleton(language) => {
+                write!(f, "there are no model skeletons for {} FMUs", language)
+            }
+        }
+    }
+}
+
+impl Error for DescribeError {}
@@ -0,0 +1,609 @@
+//! Renders the model of a generated FMU from a declarative variable
+//! specification, so that the attributes of the model match the variables of
+//! its `modelDescription.xml` in value reference, type and start value.
+//!
+//! The model skeletons in the assets mark where generated code goes with a
+//! comment line holding the name of a placeholder, e.g. `# {{references}}`
+//! or `// {{references}}`. Each such line is replaced by the rendered lines,
+//! indented like the comment.
+
+use std::{collections::HashSet, fmt::Write};
+
+use crate::{
+    model_description::{
+        invalid, DescribeError, DescribeResult, Dimension, Variable, VariableSpec
+    },
+    Language
+};
+
+/// Keywords of each language and the members the model skeletons declare
+/// themselves, which variables can't be named after.
+static PYTHON_RESERVED: &[&str] = &[
+    "False", "None", "True", "and", "as", "assert", "async", "await", "break",
+    "class", "continue", "def", "del", "elif", "else", "except", "finally",
+    "for", "from", "global", "if", "import", "in", "is", "lambda", "nonlocal",
+    "not", "or", "pass", "raise", "return", "try", "while", "with", "yield",
+    "log", "state", "reference_to_attribute", "structural_parameters",
+    "clock_reference_to_interval", "clock_reference_to_shift",
+    "instance_name", "instantiation_token", "resource_path", "visible",
+    "logging_on", "event_mode_used", "early_return_allowed",
+    "required_intermediate_variables", "_log_callback",
+    "_clock_update_callback", "_lock_preemption_callback",
+    "_unlock_preemption_callback", "_intermediate_update_callback"
+];
+
+static CSHARP_RESERVED: &[&str] = &[
+    "abstract", "base", "bool", "byte", "case", "class", "const", "default",
+    "double", "else", "event", "false", "float", "for", "if", "int", "long",
+    "namespace", "new", "null", "object", "operator", "params", "return",
+    "string", "this", "true", "uint", "ulong", "Model", "FMIState",
+    "Fraction", "Log", "state", "reference_to_attributes",
+    "structural_parameters", "clock_reference_to_interval",
+    "clock_reference_to_shift", "log_callback", "instance_name",
+    "instantiation_token", "resource_path", "visible", "logging_on",
+    "event_mode_used", "early_return_allowed",
+    "required_intermediate_variables"
+];
+
+static JAVA_RESERVED: &[&str] = &[
+    "abstract", "boolean", "byte", "case", "class", "const", "default",
+    "double", "else", "enum", "false", "final", "float", "for", "if", "int",
+    "long", "new", "null", "package", "private", "public", "return", "static",
+    "super", "this", "true", "void", "Model", "serialVersionUID", "state",
+    "references_to_attributes", "structural_parameters",
+    "clock_reference_to_interval", "clock_reference_to_shift",
+    "instance_name", "instantiation_token", "resource_path", "visible",
+    "logging_on", "event_mode_used", "early_return_allowed",
+    "required_intermediate_variables"
+];
+
+/// The element type of a variable, which decides the type of the attribute
+/// holding its value in each language.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Kind {
+    Float32,
+    Float64,
+    Int8,
+    UInt8,
+    Int16,
+    UInt16,
+    Int32,
+    UInt32,
+    Int64,
+    UInt64,
+    Boolean,
+    String,
+    Binary
+}
+
+impl Kind {
+    fn of(variable: &Variable) -> DescribeResult<Self> {
+        match variable.variable_type.as_str() {
+            "Float32" => Ok(Self::Float32),
+            "Real" | "Float64" => Ok(Self::Float64),
+            "Int8" => Ok(Self::Int8),
+            "UInt8" => Ok(Self::UInt8),
+            "Int16" => Ok(Self::Int16),
+            "UInt16" => Ok(Self::UInt16),
+            "Integer" | "Int32" => Ok(Self::Int32),
+            "UInt32" => Ok(Self::UInt32),
+            "Int64" => Ok(Self::Int64),
+            "UInt64" => Ok(Self::UInt64),
+            "Boolean" | "Clock" => Ok(Self::Boolean),
+            "String" => Ok(Self::String),
+            "Binary" => Ok(Self::Binary),
+            other => Err(invalid(format!(
+                "variable '{}' has type '{}', which has no model attribute type",
+                variable.name,
+                other
+            )))
+        }
+    }
+
+    fn is_float(self) -> bool {
+        matches!(self, Self::Float32 | Self::Float64)
+    }
+
+    fn is_integer(self) -> bool {
+        matches!(
+            self,
+            Self::Int8 | Self::UInt8 | Self::Int16 | Self::UInt16
+                | Self::Int32 | Self::UInt32 | Self::Int64 | Self::UInt64
+        )
+    }
+
+    fn csharp_type(self) -> &'static str {
+        match self {
+            Self::Float32 => "float",
+            Self::Float64 => "double",
+            Self::Int8 | Self::Int16 | Self::Int32 => "int",
+            Self::UInt8 | Self::UInt16 | Self::UInt32 => "uint",
+            Self::Int64 => "long",
+            Self::UInt64 => "ulong",
+            Self::Boolean => "bool",
+            Self::String => "string",
+            Self::Binary => "byte[]"
+        }
+    }
+
+    fn java_type(self) -> &'static str {
+        // Java has no unsigned integers, so these are held by the signed
+        // type of the same width as in the other Java templates.
+        match self {
+            Self::Float32 => "Float",
+            Self::Float64 => "Double",
+            Self::Int8 | Self::UInt8 | Self::Int16 | Self::UInt16
+                | Self::Int32 | Self::UInt32 => "Integer",
+            Self::Int64 | Self::UInt64 => "Long",
+            Self::Boolean => "Boolean",
+            Self::String => "String",
+            Self::Binary => "byte[]"
+        }
+    }
+}
+
+/// A single start value, independent of the language it is rendered in.
+#[derive(Debug, Clone)]
+enum Scalar {
+    Float(f64),
+    Integer(i128),
+    Boolean(bool),
+    String(String),
+    Binary(Vec<u8>)
+}
+
+/// The languages that have model skeletons.
+enum SkeletonLanguage {
+    Python,
+    CSharp,
+    Java
+}
+
+/// The attribute of the model holding the value of a variable.
+struct Attribute<'a> {
+    variable: &'a Variable,
+    name: String,
+    kind: Kind,
+    /// The start values, one per element of an FMI3 array.
+    start: Vec<Scalar>,
+    is_array: bool
+}
+
+/// Renders the model skeleton of the given language from the variable
+/// specification, which must have been validated for the FMI version of the
+/// skeleton.
+pub fn render_model(
+    skeleton: &str,
+    spec: &VariableSpec,
+    language: &Language
+) -> DescribeResult<String> {
+    let language = &match language {
+        Language::Python => SkeletonLanguage::Python,
+        Language::CSharp => SkeletonLanguage::CSharp,
+        Language::Java => SkeletonLanguage::Java,
+        Language::Matlab => return Err(DescribeError::NoSkeleton("MATLAB")),
+        Language::Rust => return Err(DescribeError::NoSkeleton("Rust"))
+    };
+
+    let attributes = attributes(spec, language)?;
+
+    let mut model = String::with_capacity(skeleton.len());
+
+    for line in skeleton.split_inclusive('\n') {
+        match placeholder(line) {
+            Some((indentation, name)) => {
+                for rendered in render_placeholder(name, &attributes, language)? {
+                    writeln!(model, "{}{}", indentation, rendered).unwrap();
+                }
+            }
+            None => model.push_str(line)
+        }
+    }
+
+    Ok(model)
+}
+
+/// Returns the indentation and placeholder name of a placeholder line.
+fn placeholder(line: &str) -> Option<(&str, &str)> {
+    let content = line.trim_start();
+    let indentation = &line[..line.len() - content.len()];
+
+    let name = content
+        .trim_end()
+        .strip_prefix('#')
+        .or_else(|| content.trim_end().strip_prefix("//"))?
+        .trim()
+        .strip_prefix("{{")?
+        .strip_suffix("}}")?;
+
+    Some((indentation, name))
+}
+
+fn render_placeholder(
+    name: &str,
+    attributes: &[Attribute],
+    language: &SkeletonLanguage
+) -> DescribeResult<Vec<String>> {
+    let clocks = || attributes.iter()
+        .filter(|attribute| attribute.variable.variable_type == "Clock");
+
+    let lines = match name {
+        // Python attributes are declared by assigning their start values
+        "declarations" => attributes.iter()
+            .filter_map(|attribute| match language {
+                SkeletonLanguage::CSharp => Some(format!(
+                    "public {} {} {{ get; set; }}",
+                    csharp_type(attribute),
+                    attribute.name
+                )),
+                SkeletonLanguage::Java => Some(format!(
+                    "public {} {};",
+                    java_type(attribute),
+                    attribute.name
+                )),
+                SkeletonLanguage::Python => None
+            })
+            .collect(),
+
+        "references" => attributes.iter()
+            .map(|attribute| {
+                let reference = attribute.variable.value_reference;
+                match language {
+                    SkeletonLanguage::Python => format!("{}: \"{}\",", reference, attribute.name),
+                    SkeletonLanguage::CSharp => format!(
+                        "{{ {}, type.GetProperty(\"{}\") }},",
+                        reference,
+                        attribute.name
+                    ),
+                    SkeletonLanguage::Java => format!(
+                        "this.references_to_attributes.put({}, this.getClass().getField(\"{}\"));",
+                        reference,
+                        attribute.name
+                    )
+                }
+            })
+            .collect(),
+
+        "start_values" => attributes.iter()
+            .map(|attribute| {
+                let value = render_start(attribute, language);
+                match language {
+                    SkeletonLanguage::Python => format!("self.{} = {}", attribute.name, value),
+                    SkeletonLanguage::CSharp | SkeletonLanguage::Java => {
+                        format!("this.{} = {};", attribute.name, value)
+                    }
+                }
+            })
+            .collect(),
+
+        "clock_intervals" | "clock_shifts" => {
+            let key = match name {
+                "clock_intervals" => "intervalDecimal",
+                _ => "shiftDecimal"
+            };
+            let map = match name {
+                "clock_intervals" => "clock_reference_to_interval",
+                _ => "clock_reference_to_shift"
+            };
+
+            clocks()
+                .map(|attribute| {
+                    let reference = attribute.variable.value_reference;
+                    let value = clock_decimal(attribute.variable, key)?;
+                    Ok(match language {
+                        SkeletonLanguage::Python => format!("{}: {:?},", reference, value),
+                        SkeletonLanguage::CSharp => format!("{{ {}, {:?} }},", reference, value),
+                        SkeletonLanguage::Java => format!("this.{}.put({}, {:?});", map, reference, value)
+                    })
+                })
+                .collect::<DescribeResult<_>>()?
+        }
+
+        "structural_parameters" => attributes.iter()
+            .filter(|attribute| attribute.variable.causality == "structuralParameter")
+            .map(|attribute| {
+                let reference = attribute.variable.value_reference;
+                match language {
+                    SkeletonLanguage::Python | SkeletonLanguage::CSharp => format!("{},", reference),
+                    SkeletonLanguage::Java => format!("this.structural_parameters.add({});", reference)
+                }
+            })
+            .collect(),
+
+        other => return Err(DescribeError::UnknownPlaceholder(String::from(other)))
+    };
+
+    Ok(lines)
+}
+
+/// Pairs each variable with the attribute of the model holding it.
+fn attributes<'a>(
+    spec: &'a VariableSpec,
+    language: &SkeletonLanguage
+) -> DescribeResult<Vec<Attribute<'a>>> {
+    let reserved: &[&str] = match language {
+        SkeletonLanguage::Python => PYTHON_RESERVED,
+        SkeletonLanguage::CSharp => CSHARP_RESERVED,
+        SkeletonLanguage::Java => JAVA_RESERVED
+    };
+
+    let mut names = HashSet::new();
+
+    spec.variables.iter()
+        .map(|variable| {
+            let mut name = identifier(&variable.name);
+            if reserved.contains(&name.as_str()) {
+                name.push('_');
+            }
+            if !names.insert(name.clone()) {
+                name = format!("{}_{}", name, variable.value_reference);
+                names.insert(name.clone());
+            }
+
+            let kind = Kind::of(variable)?;
+            let is_array = !variable.dimensions.is_empty();
+
+            Ok(Attribute {
+                variable,
+                name,
+                kind,
+                start: start_values(variable, kind, spec)?,
+                is_array
+            })
+        })
+        .collect()
+}
+
+/// Turns a variable name, such as `der(h)` or `pipe.flow[2]`, into an
+/// identifier usable in every language.
+fn identifier(name: &str) -> String {
+    let mut identifier: String = name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect::<String>()
+        .trim_end_matches('_')
+        .to_string();
+
+    if identifier.is_empty() || identifier.starts_with(|c: char| c.is_ascii_digit()) {
+        identifier.insert(0, '_');
+    }
+
+    identifier
+}
+
+/// Reads the start values of a variable, falling back to the default value
+/// of its type.
+fn start_values(
+    variable: &Variable,
+    kind: Kind,
+    spec: &VariableSpec
+) -> DescribeResult<Vec<Scalar>> {
+    if variable.dimensions.is_empty() {
+        return match &variable.start {
+            Some(toml::Value::Array(_)) => Err(invalid(format!(
+                "variable '{}' has several start values but no dimensions",
+                variable.name
+            ))),
+            Some(start) => Ok(vec![parse_scalar(start, kind, variable)?]),
+            None => Ok(vec![default_scalar(kind)])
+        };
+    }
+
+    match &variable.start {
+        Some(toml::Value::Array(values)) => values.iter()
+            .map(|value| parse_scalar(value, kind, variable))
+            .collect(),
+        Some(toml::Value::String(values)) if kind != Kind::String => values
+            .split_whitespace()
+            .map(|value| parse_scalar(&toml::Value::String(String::from(value)), kind, variable))
+            .collect(),
+        Some(start) => Ok(vec![parse_scalar(start, kind, variable)?]),
+        None => {
+            let size = variable.dimensions.iter()
+                .map(|dimension| match dimension {
+                    Dimension::Start(size) => *size,
+                    Dimension::Variable { value_reference } => spec.variables.iter()
+                        .find(|other| other.value_reference == *value_reference)
+                        .and_then(|other| other.start.as_ref())
+                        .and_then(|start| start.as_integer())
+                        .map_or(1, |size| size.max(0) as u64)
+                })
+                .product::<u64>();
+
+            Ok((0..size).map(|_| default_scalar(kind)).collect())
+        }
+    }
+}
+
+fn default_scalar(kind: Kind) -> Scalar {
+    match kind {
+        Kind::Float32 | Kind::Float64 => Scalar::Float(0.0),
+        Kind::Boolean => Scalar::Boolean(false),
+        Kind::String => Scalar::String(String::new()),
+        Kind::Binary => Scalar::Binary(Vec::new()),
+        _ => Scalar::Integer(0)
+    }
+}
+
+fn parse_scalar(value: &toml::Value, kind: Kind, variable: &Variable) -> DescribeResult<Scalar> {
+    let scalar = match (kind, value) {
+        _ if kind.is_float() => parse_float(value).map(Scalar::Float),
+        (_, toml::Value::Integer(integer)) if kind.is_integer() => {
+            Some(Scalar::Integer(*integer as i128))
+        }
+        (_, toml::Value::String(text)) if kind.is_integer() => {
+            text.trim().parse().ok().map(Scalar::Integer)
+        }
+        (Kind::Boolean, toml::Value::Boolean(boolean)) => Some(Scalar::Boolean(*boolean)),
+        (Kind::Boolean, toml::Value::String(text)) => match text.trim() {
+            "true" | "1" => Some(Scalar::Boolean(true)),
+            "false" | "0" => Some(Scalar::Boolean(false)),
+            _ => None
+        },
+        (Kind::String, toml::Value::String(text)) => Some(Scalar::String(text.clone())),
+        (Kind::Binary, toml::Value::String(text)) => parse_hex(text).map(Scalar::Binary),
+        _ => None
+    };
+
+    scalar.ok_or_else(|| invalid(format!(
+        "variable '{}' has start value {}, which isn't a valid {}",
+        variable.name,
+        value,
+        variable.variable_type
+    )))
+}
+
+fn parse_float(value: &toml::Value) -> Option<f64> {
+    match value {
+        toml::Value::Float(float) => Some(*float),
+        toml::Value::Integer(integer) => Some(*integer as f64),
+        toml::Value::String(text) => text.trim().parse().ok(),
+        _ => None
+    }
+}
+
+/// Parses the hexadecimal notation of FMI3 binary start values.
+fn parse_hex(text: &str) -> Option<Vec<u8>> {
+    let digits: Vec<char> = text.trim()
+        .trim_start_matches("0x")
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect();
+
+    if !digits.len().is_multiple_of(2) {
+        return None;
+    }
+
+    digits.chunks(2)
+        .map(|pair| u8::from_str_radix(&pair.iter().collect::<String>(), 16).ok())
+        .collect()
+}
+
+/// Reads the given decimal attribute of a clock, e.g. its `intervalDecimal`.
+fn clock_decimal(variable: &Variable, key: &str) -> DescribeResult<f64> {
+    match variable.attributes.get(key) {
+        None => Ok(0.0),
+        Some(value) => parse_float(value).ok_or_else(|| invalid(format!(
+            "clock '{}' has {} {}, which isn't a decimal number",
+            variable.name,
+            key,
+            value
+        )))
+    }
+}
+
+fn csharp_type(attribute: &Attribute) -> String {
+    match attribute.is_array {
+        true => format!("{}[]", attribute.kind.csharp_type()),
+        false => String::from(attribute.kind.csharp_type())
+    }
+}
+
+fn java_type(attribute: &Attribute) -> String {
+    match attribute.is_array {
+        true => format!("{}[]", attribute.kind.java_type()),
+        false => String::from(attribute.kind.java_type())
+    }
+}
+
+fn render_start(attribute: &Attribute, language: &SkeletonLanguage) -> String {
+    let values: Vec<String> = attribute.start.iter()
+        .map(|scalar| render_scalar(scalar, attribute.kind, language))
+        .collect();
+
+    if !attribute.is_array {
+        return values.into_iter().next().unwrap_or_default();
+    }
+
+    match language {
+        SkeletonLanguage::Python => format!("[{}]", values.join(", ")),
+        SkeletonLanguage::CSharp => format!(
+            "new {}[] {{ {} }}",
+            attribute.kind.csharp_type(),
+            values.join(", ")
+        ),
+        SkeletonLanguage::Java => format!(
+            "new {}[] {{ {} }}",
+            attribute.kind.java_type(),
+            values.join(", ")
+        )
+    }
+}
+
+fn render_scalar(scalar: &Scalar, kind: Kind, language: &SkeletonLanguage) -> String {
+    match (language, scalar) {
+        (SkeletonLanguage::Python, Scalar::Float(float)) if !float.is_finite() => {
+            format!("float(\"{}\")", float)
+        }
+        (SkeletonLanguage::Python, Scalar::Float(float)) => format!("{:?}", float),
+        (SkeletonLanguage::Python, Scalar::Integer(integer)) => integer.to_string(),
+        (SkeletonLanguage::Python, Scalar::Boolean(true)) => String::from("True"),
+        (SkeletonLanguage::Python, Scalar::Boolean(false)) => String::from("False"),
+        (SkeletonLanguage::Python, Scalar::Binary(bytes)) => format!(
+            "bytes([{}])",
+            bytes.iter().map(|byte| byte.to_string()).collect::<Vec<_>>().join(", ")
+        ),
+
+        (SkeletonLanguage::CSharp, Scalar::Float(float)) => {
+            let type_name = kind.csharp_type();
+            match float {
+                float if float.is_nan() => format!("{}.NaN", type_name),
+                float if *float == f64::INFINITY => format!("{}.PositiveInfinity", type_name),
+                float if *float == f64::NEG_INFINITY => format!("{}.NegativeInfinity", type_name),
+                float if kind == Kind::Float32 => format!("{:?}f", float),
+                float => format!("{:?}", float)
+            }
+        }
+        (SkeletonLanguage::CSharp, Scalar::Integer(integer)) => match kind {
+            Kind::Int64 => format!("{}L", integer),
+            Kind::UInt64 => format!("{}UL", integer),
+            _ => integer.to_string()
+        },
+        (SkeletonLanguage::CSharp, Scalar::Binary(bytes)) => format!(
+            "new byte[] {{ {} }}",
+            bytes.iter().map(|byte| format!("0x{:02X}", byte)).collect::<Vec<_>>().join(", ")
+        ),
+
+        (SkeletonLanguage::Java, Scalar::Float(float)) => {
+            let type_name = kind.java_type();
+            match float {
+                float if float.is_nan() => format!("{}.NaN", type_name),
+                float if *float == f64::INFINITY => format!("{}.POSITIVE_INFINITY", type_name),
+                float if *float == f64::NEG_INFINITY => format!("{}.NEGATIVE_INFINITY", type_name),
+                float if kind == Kind::Float32 => format!("{:?}f", float),
+                float => format!("{:?}", float)
+            }
+        }
+        (SkeletonLanguage::Java, Scalar::Integer(integer)) => match kind {
+            Kind::UInt32 if *integer > i32::MAX as i128 => format!("(int) {}L", integer),
+            Kind::UInt64 if *integer > i64::MAX as i128 => {
+                format!("Long.parseUnsignedLong(\"{}\")", integer)
+            }
+            Kind::Int64 | Kind::UInt64 => format!("{}L", integer),
+            _ => integer.to_string()
+        },
+        (SkeletonLanguage::Java, Scalar::Binary(bytes)) => format!(
+            "new byte[] {{ {} }}",
+            bytes.iter().map(|byte| format!("(byte) 0x{:02X}", byte)).collect::<Vec<_>>().join(", ")
+        ),
+
+        (SkeletonLanguage::CSharp | SkeletonLanguage::Java, Scalar::Boolean(boolean)) => boolean.to_string(),
+        (_, Scalar::String(text)) => quote(text)
+    }
+}
+
+/// Quotes a string as a literal, with the escapes shared by all languages.
+fn quote(text: &str) -> String {
+    let mut literal = String::from("\"");
+    for c in text.chars() {
+        match c {
+            '\\' => literal.push_str("\\\\"),
+            '"' => literal.push_str("\\\""),
+            '\n' => literal.push_str("\\n"),
+            '\r' => literal.push_str("\\r"),
+            '\t' => literal.push_str("\\t"),
+            c => literal.push(c)
+        }
+    }
+    literal.push('"');
+    literal
+}
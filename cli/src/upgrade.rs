@@ -0,0 +1,163 @@
+//! Contains the `upgrade` command, which brings an FMU generated by an
+//! earlier version of UniFMU up to date with the API binaries and protobuf
+//! schemas embedded in this version, leaving the model of the FMU and its
+//! `modelDescription.xml` as they are.
+
+use log::{info, warn};
+use roxmltree::Document;
+use std::{
+    error::Error,
+    fmt::Display,
+    fs,
+    path::{Path, PathBuf}
+};
+use tempfile::TempDir;
+
+use crate::archive::{pack_replacing, unpack, ArchiveError};
+use crate::model_description::{fmi_version, DescribeError, FmiVersion};
+use crate::{copy_binaries, recognize_template, Assets, FmiFmuVersion};
+
+/// Upgrades the FMU in the given directory or `.fmu` archive in place.
+///
+/// The binaries of every platform that this version of UniFMU has binaries
+/// for are replaced, and the protobuf schemas of the backend are rewritten.
+/// An archive is extracted, upgraded and packed again, reproducibly if
+/// `reproducible` is set, and replaces the original archive only once it is
+/// complete.
+pub fn upgrade(fmu: &Path, reproducible: bool) -> UpgradeResult<()> {
+    if fmu.is_dir() {
+        return upgrade_directory(fmu);
+    }
+
+    if !fmu.is_file() {
+        return Err(UpgradeError::NotFound(fmu.to_path_buf()));
+    }
+
+    let directory = TempDir::new()
+        .map_err(|error| UpgradeError::IoError(fmu.to_path_buf(), error))?;
+
+    unpack(fmu, Some(directory.path()))?;
+    upgrade_directory(directory.path())?;
+    pack_replacing(directory.path(), fmu, reproducible)?;
+
+    Ok(())
+}
+
+fn upgrade_directory(fmu_directory: &Path) -> UpgradeResult<()> {
+    let model_description_path = fmu_directory.join("modelDescription.xml");
+
+    let model_description = fs::read_to_string(&model_description_path)
+        .map_err(|error| UpgradeError::IoError(model_description_path, error))?;
+
+    let version = fmi_version(&model_description)?;
+
+    let fmu_version = match version {
+        FmiVersion::Fmi2 => FmiFmuVersion::FMI2,
+        FmiVersion::Fmi3 => FmiFmuVersion::FMI3,
+    };
+
+    for model_identifier in model_identifiers(&model_description)? {
+        info!("upgrading the binaries named '{}'", model_identifier);
+        copy_binaries(&fmu_version, fmu_directory, &model_identifier)
+            .map_err(|_| UpgradeError::NoBinaries)?;
+    }
+
+    let resources = fmu_directory.join("resources");
+
+    let Some((_, template)) = recognize_template(&resources, &version) else {
+        warn!("couldn't recognize the backend language of the FMU from its resources, so only its binaries were upgraded");
+        return Ok(());
+    };
+
+    let schemas = template
+        .iter()
+        .filter(|(source, _)| source.starts_with("auto_generated/"));
+
+    for (source, destination) in schemas {
+        let destination = resources.join(destination);
+
+        info!("upgrading schema {:?}", destination);
+
+        fs::create_dir_all(destination.parent().unwrap())
+            .and_then(|_| fs::write(&destination, Assets::get(source).unwrap().data))
+            .map_err(|error| UpgradeError::IoError(destination, error))?;
+    }
+
+    Ok(())
+}
+
+/// Gives the model identifiers of the interfaces that the model description
+/// declares, which name the binaries of the FMU.
+fn model_identifiers(model_description: &str) -> UpgradeResult<Vec<String>> {
+    let document = Document::parse(model_description)?;
+
+    let model_identifiers: Vec<String> = document
+        .root_element()
+        .children()
+        .filter_map(|node| node.attribute("modelIdentifier"))
+        .map(String::from)
+        .collect();
+
+    if model_identifiers.is_empty() {
+        return Err(UpgradeError::DescribeError(DescribeError::MissingElement("modelIdentifier")));
+    }
+
+    Ok(model_identifiers)
+}
+
+pub type UpgradeResult<T> = Result<T, UpgradeError>;
+
+#[derive(Debug)]
+pub enum UpgradeError {
+    NotFound(PathBuf),
+    NoBinaries,
+    IoError(PathBuf, std::io::Error),
+    ParseError(roxmltree::Error),
+    DescribeError(DescribeError),
+    ArchiveError(ArchiveError)
+}
+
+impl Display for UpgradeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotFound(path) => {
+                write!(f, "'{}' is neither an FMU directory nor an FMU archive", path.display())
+            }
+            Self::NoBinaries => {
+                write!(f, "this version of UniFMU has no binaries to upgrade the FMU with")
+            }
+            Self::IoError(path, error) => {
+                write!(f, "couldn't access '{}': {}", path.display(), error)
+            }
+            Self::ParseError(error) => {
+                write!(f, "couldn't parse modelDescription.xml: {}", error)
+            }
+            Self::DescribeError(error) => {
+                write!(f, "{}", error)
+            }
+            Self::ArchiveError(error) => {
+                write!(f, "{}", error)
+            }
+        }
+    }
+}
+
+impl Error for UpgradeError {}
+
+impl From<roxmltree::Error> for UpgradeError {
+    fn from(value: roxmltree::Error) -> Self {
+        Self::ParseError(value)
+    }
+}
+
+impl From<DescribeError> for UpgradeError {
+    fn from(value: DescribeError) -> Self {
+        Self::DescribeError(value)
+    }
+}
+
+impl From<ArchiveError> for UpgradeError {
+    fn from(value: ArchiveError) -> Self {
+        Self::ArchiveError(value)
+    }
+}
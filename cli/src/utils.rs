@@ -1,5 +1,6 @@
-use log::info;
-use std::fs::File;
+use chrono::{DateTime, Datelike, TimeZone, Timelike, Utc};
+use log::{info, warn};
+use std::fs::{File, Metadata};
 use std::io::prelude::*;
 use std::io::{Seek, Write};
 use std::iter::Iterator;
@@ -7,45 +8,129 @@ use std::path::Path;
 use walkdir::DirEntry;
 use zip::write::FileOptions;
 
+/// Compresses the given directory entries into a zip archive, naming each by
+/// its path relative to the given prefix.
+///
+/// If `reproducible` is set, the archive only depends on the names, contents
+/// and executability of the files: entries are added in order of their names,
+/// directories get entries of their own, and every entry has normalised
+/// permissions and the time given by [`reproducible_time`].
 pub fn zip_dir<T>(
     it: &mut dyn Iterator<Item = DirEntry>,
     prefix: &str,
     writer: T,
     method: zip::CompressionMethod,
+    reproducible: bool,
 ) -> zip::result::ZipResult<()>
 where
     T: Write + Seek,
 {
     let mut zip = zip::ZipWriter::new(writer);
-    let options: FileOptions<()> = FileOptions::default()
-        .compression_method(method)
-        .unix_permissions(0o755);
+    let mut options: FileOptions<()> = FileOptions::default()
+        .compression_method(method);
+
+    let mut entries: Vec<(String, DirEntry)> = it
+        .map(|entry| {
+            let name = archive_name(entry.path().strip_prefix(Path::new(prefix)).unwrap());
+            (name, entry)
+        })
+        .collect();
+
+    if reproducible {
+        entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        let time = reproducible_time();
+        options = options.last_modified_time(zip::DateTime::from_date_and_time(
+            time.year() as u16,
+            time.month() as u8,
+            time.day() as u8,
+            time.hour() as u8,
+            time.minute() as u8,
+            time.second() as u8,
+        )?);
+    }
 
     let mut buffer = Vec::new();
-    for entry in it {
+    for (name, entry) in entries {
         let path = entry.path();
-        let name = path.strip_prefix(Path::new(prefix)).unwrap();
 
         // Write file or directory explicitly
         // Some unzip tools unzip files with directory paths correctly, some do not!
         if path.is_file() {
             info!("adding file {:?} as {:?} ...", path, name);
-            #[allow(deprecated)]
-            zip.start_file_from_path(name, options)?;
             let mut f = File::open(path)?;
+            let metadata = f.metadata()?;
+            let permissions = match reproducible {
+                true => normalised_permissions(&metadata),
+                false => unix_permissions(&metadata),
+            };
+            zip.start_file(name, options.unix_permissions(permissions))?;
 
             f.read_to_end(&mut buffer)?;
             zip.write_all(&buffer)?;
             buffer.clear();
-        }  /*else if name.as_os_str().is_empty() {
+        } else if reproducible && path.is_dir() && !name.is_empty() {
             // Only if not root! Avoids path spec / warning
             // and mapname conversion failed error on unzip
             info!("adding dir {:?} as {:?} ...", path, name);
-            #[allow(deprecated)]
-            zip.add_directory_from_path(name, options)?;
-        } */
+            zip.add_directory(name, options.unix_permissions(0o755))?;
+        }
     }
     zip.finish()?;
     Result::Ok(())
 }
 
+/// The time at which reproducible FMUs are generated and archived: the time
+/// given by the `SOURCE_DATE_EPOCH` environment variable if it is set, and
+/// otherwise the earliest time that a zip archive can hold.
+///
+/// The time is limited to the range of times that a zip archive can hold.
+pub fn reproducible_time() -> DateTime<Utc> {
+    let earliest = Utc.with_ymd_and_hms(1980, 1, 1, 0, 0, 0).unwrap();
+    let latest = Utc.with_ymd_and_hms(2107, 12, 31, 23, 59, 58).unwrap();
+
+    let Ok(source_date_epoch) = std::env::var("SOURCE_DATE_EPOCH") else {
+        return earliest;
+    };
+
+    match source_date_epoch.trim().parse().ok().and_then(|seconds| DateTime::from_timestamp(seconds, 0)) {
+        Some(time) => time.clamp(earliest, latest),
+        None => {
+            warn!("ignoring SOURCE_DATE_EPOCH '{}', which isn't a number of seconds", source_date_epoch);
+            earliest
+        }
+    }
+}
+
+/// The name of the archive entry for the given relative path, which uses
+/// forward slashes on every platform.
+fn archive_name(relative_path: &Path) -> String {
+    relative_path
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// The permissions stored for a file in a reproducible archive, which only
+/// tell whether the file is executable.
+fn normalised_permissions(metadata: &Metadata) -> u32 {
+    match unix_permissions(metadata) & 0o100 {
+        0 => 0o644,
+        _ => 0o755,
+    }
+}
+
+/// The permissions stored for a file in the archive, which are those of the
+/// file itself where the platform has them.
+#[cfg(unix)]
+fn unix_permissions(metadata: &Metadata) -> u32 {
+    use std::os::unix::fs::PermissionsExt;
+
+    metadata.permissions().mode() & 0o777
+}
+
+#[cfg(not(unix))]
+fn unix_permissions(_metadata: &Metadata) -> u32 {
+    0o755
+}
@@ -0,0 +1,616 @@
+//! Contains the `validate` command, which checks an FMU generated by UniFMU,
+//! and possibly edited since, for the mistakes that would otherwise only show
+//! up once the FMU is imported: an unparsable `launch.toml`, missing binaries,
+//! an inconsistent `modelDescription.xml` or missing backend resources.
+
+use roxmltree::{Document, Node};
+use std::{
+    collections::{HashMap, HashSet},
+    error::Error,
+    fmt::Display,
+    fs::{self, File},
+    path::{Path, PathBuf}
+};
+use tempfile::TempDir;
+use unifmu_launch_config::{BackendLocation, BackendTransport, ConfigError, LaunchConfig};
+use zip::{result::ZipError, ZipArchive};
+
+use crate::model_description::{fmi_version, FmiVersion};
+use crate::{is_required, recognize_template, Language, ASSETSPROXY};
+
+static FMI2_TYPES: [&str; 5] = ["Real", "Integer", "Boolean", "String", "Enumeration"];
+
+/// Checks the FMU in the given directory or `.fmu` archive, and returns a
+/// description of every problem found. An FMU without problems gives an empty
+/// list.
+pub fn validate(fmu: &Path) -> ValidateResult<Vec<String>> {
+    let extracted;
+
+    let fmu_directory = if fmu.is_dir() {
+        fmu.to_path_buf()
+    } else if fmu.is_file() {
+        extracted = extract(fmu)?;
+        extracted.path().to_path_buf()
+    } else {
+        return Err(ValidateError::NotFound(fmu.to_path_buf()));
+    };
+
+    let mut problems = Vec::new();
+
+    let model_description = check_model_description(&fmu_directory, &mut problems);
+    let config = check_launch_config(&fmu_directory, &mut problems);
+
+    if let Some(model_description) = &model_description {
+        check_binaries(&fmu_directory, model_description, config.as_ref(), &mut problems);
+        check_resources(&fmu_directory, model_description, config.as_ref(), &mut problems);
+    }
+
+    Ok(problems)
+}
+
+/// The parts of the model description that the other checks depend upon.
+struct ModelDescriptionSummary {
+    version: FmiVersion,
+    model_identifiers: Vec<String>
+}
+
+fn extract(archive_path: &Path) -> ValidateResult<TempDir> {
+    let file = File::open(archive_path)
+        .map_err(|error| ValidateError::ReadError(archive_path.to_path_buf(), error))?;
+
+    let mut archive = ZipArchive::new(file)?;
+
+    let directory = TempDir::new()
+        .map_err(|error| ValidateError::ReadError(archive_path.to_path_buf(), error))?;
+
+    archive.extract(directory.path())?;
+
+    Ok(directory)
+}
+
+fn check_model_description(
+    fmu_directory: &Path,
+    problems: &mut Vec<String>
+) -> Option<ModelDescriptionSummary> {
+    let model_description_path = fmu_directory.join("modelDescription.xml");
+
+    let model_description = match fs::read_to_string(&model_description_path) {
+        Ok(model_description) => model_description,
+        Err(error) => {
+            problems.push(format!("couldn't read modelDescription.xml: {}", error));
+            return None;
+        }
+    };
+
+    let document = match Document::parse(&model_description) {
+        Ok(document) => document,
+        Err(error) => {
+            problems.push(format!("modelDescription.xml isn't well-formed: {}", error));
+            return None;
+        }
+    };
+
+    let root = document.root_element();
+
+    if !root.has_tag_name("fmiModelDescription") {
+        problems.push(format!(
+            "modelDescription.xml has root element <{}>, should have been <fmiModelDescription>",
+            root.tag_name().name()
+        ));
+        return None;
+    }
+
+    let version = match fmi_version(&model_description) {
+        Ok(version) => version,
+        Err(error) => {
+            problems.push(format!("{}", error));
+            return None;
+        }
+    };
+
+    let interfaces = match version {
+        FmiVersion::Fmi2 => &["CoSimulation", "ModelExchange"][..],
+        FmiVersion::Fmi3 => &["CoSimulation", "ModelExchange", "ScheduledExecution"][..],
+    };
+
+    let model_identifiers: Vec<String> = root
+        .children()
+        .filter(|node| interfaces.iter().any(|interface| node.has_tag_name(*interface)))
+        .filter_map(|node| match node.attribute("modelIdentifier") {
+            Some(identifier) => Some(String::from(identifier)),
+            None => {
+                problems.push(format!(
+                    "<{}> of modelDescription.xml has no modelIdentifier",
+                    node.tag_name().name()
+                ));
+                None
+            }
+        })
+        .collect();
+
+    if model_identifiers.is_empty() {
+        problems.push(format!(
+            "modelDescription.xml declares none of the interfaces {}",
+            interfaces.join(", ")
+        ));
+    }
+
+    let model_variables = root.children().find(|node| node.has_tag_name("ModelVariables"));
+    let model_structure = root.children().find(|node| node.has_tag_name("ModelStructure"));
+
+    match (model_variables, model_structure) {
+        (Some(model_variables), Some(model_structure)) => match version {
+            FmiVersion::Fmi2 => check_fmi2_variables(model_variables, model_structure, problems),
+            FmiVersion::Fmi3 => check_fmi3_variables(model_variables, model_structure, problems),
+        },
+        (None, _) => problems.push(String::from("modelDescription.xml has no <ModelVariables>")),
+        (_, None) => problems.push(String::from("modelDescription.xml has no <ModelStructure>")),
+    }
+
+    Some(ModelDescriptionSummary { version, model_identifiers })
+}
+
+/// Checks the variables of an FMI2 model description, which the model
+/// structure refers to by their (one-based) index. Value references only
+/// need to be unique per type, as variables of the same type sharing a value
+/// reference are aliases.
+fn check_fmi2_variables(model_variables: Node, model_structure: Node, problems: &mut Vec<String>) {
+    let variables: Vec<Node> = model_variables
+        .children()
+        .filter(|node| node.has_tag_name("ScalarVariable"))
+        .collect();
+
+    check_names(&variables, problems);
+
+    for variable in &variables {
+        let name = variable.attribute("name").unwrap_or_default();
+
+        parse_value_reference(variable, name, problems);
+
+        let type_element = variable.children().find(|node| node.is_element());
+        match type_element.map(|node| node.tag_name().name()) {
+            Some(type_name) if FMI2_TYPES.contains(&type_name) => (),
+            Some(type_name) => problems.push(format!(
+                "variable '{}' has unknown type <{}>", name, type_name
+            )),
+            None => problems.push(format!("variable '{}' has no type element", name)),
+        }
+
+        if let Some(derivative) = type_element.and_then(|node| node.attribute("derivative")) {
+            match parse_index(derivative, variables.len()) {
+                Some(index) if is_fmi2_real(&variables[index - 1]) => (),
+                Some(_) => problems.push(format!(
+                    "variable '{}' is the derivative of variable {}, which isn't a Real",
+                    name, derivative
+                )),
+                None => problems.push(format!(
+                    "variable '{}' is the derivative of variable {}, which doesn't exist",
+                    name, derivative
+                )),
+            }
+        }
+    }
+
+    let mut outputs = HashSet::new();
+
+    for list in model_structure.children().filter(|node| node.is_element()) {
+        let list_name = list.tag_name().name();
+
+        for unknown in list.children().filter(|node| node.has_tag_name("Unknown")) {
+            let Some(index) = unknown.attribute("index")
+                .and_then(|index| parse_index(index, variables.len()))
+            else {
+                problems.push(format!(
+                    "<{}> of the model structure lists variable {}, which doesn't exist",
+                    list_name,
+                    unknown.attribute("index").unwrap_or_default()
+                ));
+                continue;
+            };
+
+            let variable = &variables[index - 1];
+            let name = variable.attribute("name").unwrap_or_default();
+
+            match list_name {
+                "Outputs" => {
+                    outputs.insert(index);
+                    if variable.attribute("causality") != Some("output") {
+                        problems.push(format!(
+                            "<Outputs> of the model structure lists variable '{}', which isn't an output",
+                            name
+                        ));
+                    }
+                }
+                "Derivatives" => {
+                    let is_derivative = variable
+                        .children()
+                        .any(|node| node.attribute("derivative").is_some());
+                    if !is_derivative {
+                        problems.push(format!(
+                            "<Derivatives> of the model structure lists variable '{}', which isn't a derivative",
+                            name
+                        ));
+                    }
+                }
+                _ => (),
+            }
+
+            for dependency in unknown.attribute("dependencies").unwrap_or_default().split_whitespace() {
+                if parse_index(dependency, variables.len()).is_none() {
+                    problems.push(format!(
+                        "variable '{}' of the model structure depends on variable {}, which doesn't exist",
+                        name, dependency
+                    ));
+                }
+            }
+        }
+    }
+
+    for (index, variable) in variables.iter().enumerate() {
+        if variable.attribute("causality") == Some("output") && !outputs.contains(&(index + 1)) {
+            problems.push(format!(
+                "output '{}' isn't listed in <Outputs> of the model structure",
+                variable.attribute("name").unwrap_or_default()
+            ));
+        }
+    }
+}
+
+/// Checks the variables of an FMI3 model description, which the model
+/// structure, dimensions and clocks refer to by their value references. These
+/// have to be unique across all variables.
+fn check_fmi3_variables(model_variables: Node, model_structure: Node, problems: &mut Vec<String>) {
+    let variables: Vec<Node> = model_variables
+        .children()
+        .filter(|node| node.is_element())
+        .collect();
+
+    check_names(&variables, problems);
+
+    let mut value_references: HashMap<u32, Node> = HashMap::new();
+
+    for variable in &variables {
+        let name = variable.attribute("name").unwrap_or_default();
+
+        let Some(value_reference) = parse_value_reference(variable, name, problems) else {
+            continue;
+        };
+
+        if let Some(other) = value_references.insert(value_reference, *variable) {
+            problems.push(format!(
+                "value reference {} is used by both '{}' and '{}'",
+                value_reference,
+                other.attribute("name").unwrap_or_default(),
+                name
+            ));
+        }
+    }
+
+    let exists = |value_reference: &str| value_reference
+        .parse::<u32>()
+        .ok()
+        .and_then(|value_reference| value_references.get(&value_reference));
+
+    for variable in &variables {
+        let name = variable.attribute("name").unwrap_or_default();
+
+        if let Some(derivative) = variable.attribute("derivative") {
+            if exists(derivative).is_none() {
+                problems.push(format!(
+                    "variable '{}' is the derivative of value reference {}, which doesn't exist",
+                    name, derivative
+                ));
+            }
+        }
+
+        for clock in variable.attribute("clocks").unwrap_or_default().split_whitespace() {
+            match exists(clock) {
+                Some(node) if node.has_tag_name("Clock") => (),
+                Some(_) => problems.push(format!(
+                    "variable '{}' is clocked by value reference {}, which isn't a Clock",
+                    name, clock
+                )),
+                None => problems.push(format!(
+                    "variable '{}' is clocked by value reference {}, which doesn't exist",
+                    name, clock
+                )),
+            }
+        }
+
+        for dimension in variable.children().filter(|node| node.has_tag_name("Dimension")) {
+            if let Some(value_reference) = dimension.attribute("valueReference") {
+                if exists(value_reference).is_none() {
+                    problems.push(format!(
+                        "variable '{}' has a dimension of value reference {}, which doesn't exist",
+                        name, value_reference
+                    ));
+                }
+            }
+        }
+    }
+
+    let mut outputs = HashSet::new();
+
+    for unknown in model_structure.children().filter(|node| node.is_element()) {
+        let element_name = unknown.tag_name().name();
+        let value_reference = unknown.attribute("valueReference").unwrap_or_default();
+
+        let Some(variable) = exists(value_reference) else {
+            problems.push(format!(
+                "<{}> of the model structure refers to value reference {}, which doesn't exist",
+                element_name, value_reference
+            ));
+            continue;
+        };
+
+        let name = variable.attribute("name").unwrap_or_default();
+
+        if element_name == "Output" {
+            outputs.insert(name);
+            if variable.attribute("causality") != Some("output") {
+                problems.push(format!(
+                    "<Output> of the model structure refers to variable '{}', which isn't an output",
+                    name
+                ));
+            }
+        }
+
+        for dependency in unknown.attribute("dependencies").unwrap_or_default().split_whitespace() {
+            if exists(dependency).is_none() {
+                problems.push(format!(
+                    "variable '{}' of the model structure depends on value reference {}, which doesn't exist",
+                    name, dependency
+                ));
+            }
+        }
+    }
+
+    for variable in &variables {
+        let name = variable.attribute("name").unwrap_or_default();
+        if variable.attribute("causality") == Some("output") && !outputs.contains(name) {
+            problems.push(format!(
+                "output '{}' isn't listed as an <Output> of the model structure",
+                name
+            ));
+        }
+    }
+}
+
+fn check_names(variables: &[Node], problems: &mut Vec<String>) {
+    let mut names = HashSet::new();
+
+    for variable in variables {
+        match variable.attribute("name") {
+            Some(name) if !names.insert(name) => {
+                problems.push(format!("variable name '{}' is declared more than once", name));
+            }
+            Some(_) => (),
+            None => problems.push(format!(
+                "a <{}> variable has no name",
+                variable.tag_name().name()
+            )),
+        }
+    }
+}
+
+fn parse_value_reference(variable: &Node, name: &str, problems: &mut Vec<String>) -> Option<u32> {
+    match variable.attribute("valueReference").map(str::parse::<u32>) {
+        Some(Ok(value_reference)) => Some(value_reference),
+        Some(Err(_)) => {
+            problems.push(format!(
+                "variable '{}' has invalid value reference '{}'",
+                name,
+                variable.attribute("valueReference").unwrap_or_default()
+            ));
+            None
+        }
+        None => {
+            problems.push(format!("variable '{}' has no value reference", name));
+            None
+        }
+    }
+}
+
+/// Parses a one-based index into the FMI2 model variables.
+fn parse_index(index: &str, number_of_variables: usize) -> Option<usize> {
+    index
+        .parse::<usize>()
+        .ok()
+        .filter(|index| (1..=number_of_variables).contains(index))
+}
+
+fn is_fmi2_real(variable: &Node) -> bool {
+    variable.children().any(|node| node.has_tag_name("Real"))
+}
+
+fn check_launch_config(fmu_directory: &Path, problems: &mut Vec<String>) -> Option<LaunchConfig> {
+    let config_path = fmu_directory.join("resources").join("launch.toml");
+
+    let config = if !config_path.exists() {
+        Err(ConfigError::NotFound(config_path))
+    } else {
+        fs::read_to_string(&config_path)
+            .map_err(|error| ConfigError::Unreadable(config_path, error))
+            .and_then(|config| toml::from_str(&config).map_err(ConfigError::Invalid))
+    };
+
+    match config {
+        Ok(config) => Some(config),
+        Err(error) => {
+            problems.push(format!("launch.toml: {}", error));
+            None
+        }
+    }
+}
+
+/// Checks that every platform folder in `binaries` holds a library for each
+/// model identifier, and that the launch configuration of a local backend
+/// has a command for the operating system of each of these platforms.
+fn check_binaries(
+    fmu_directory: &Path,
+    model_description: &ModelDescriptionSummary,
+    config: Option<&LaunchConfig>,
+    problems: &mut Vec<String>
+) {
+    let platforms: Vec<PathBuf> = match fs::read_dir(fmu_directory.join("binaries")) {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| path.is_dir())
+            .collect(),
+        Err(_) => Vec::new(),
+    };
+
+    if platforms.is_empty() {
+        problems.push(String::from("the FMU has no binaries for any platform"));
+        return;
+    }
+
+    for platform_path in platforms {
+        let platform = platform_path
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .into_owned();
+
+        let Some(os) = operating_system(&platform) else {
+            problems.push(format!("binaries for unknown platform '{}'", platform));
+            continue;
+        };
+
+        let extension = match os {
+            "windows" => "dll",
+            "macos" => "dylib",
+            _ => "so",
+        };
+
+        for identifier in &model_description.model_identifiers {
+            let library = platform_path.join(format!("{}.{}", identifier, extension));
+            if !library.is_file() {
+                problems.push(format!(
+                    "binaries for platform '{}' have no {}.{}",
+                    platform, identifier, extension
+                ));
+            }
+        }
+
+        if let Some(config) = config {
+            let command = match os {
+                "windows" => &config.windows,
+                "macos" => &config.macos,
+                _ => &config.linux,
+            };
+
+            let has_command = command.as_ref().is_some_and(|command| !command.is_empty());
+
+            if matches!(config.location, BackendLocation::Local) && !has_command {
+                problems.push(format!(
+                    "launch.toml has no command for {}, which the FMU has binaries for",
+                    os
+                ));
+            }
+        }
+    }
+}
+
+/// Gives the name of the operating system of an FMI2 (e.g. `win64`) or FMI3
+/// (e.g. `x86_64-windows`) platform folder, as named in the launch
+/// configuration.
+fn operating_system(platform: &str) -> Option<&'static str> {
+    if platform.starts_with("win") || platform.ends_with("-windows") {
+        Some("windows")
+    } else if platform.starts_with("darwin") || platform.ends_with("-darwin") {
+        Some("macos")
+    } else if platform.starts_with("linux") || platform.ends_with("-linux") {
+        Some("linux")
+    } else {
+        None
+    }
+}
+
+/// Checks that the resources the backend of the FMU needs are present. A
+/// backend that runs remotely only needs those of the proxy.
+/// 
+/// Also checks that the C# and Java backends, which can't connect through the
+/// `Ipc` transport, fall back to TCP if the launch configuration uses it.
+fn check_resources(
+    fmu_directory: &Path,
+    model_description: &ModelDescriptionSummary,
+    config: Option<&LaunchConfig>,
+    problems: &mut Vec<String>
+) {
+    let resources = fmu_directory.join("resources");
+
+    let required: Vec<&str> = if config.is_some_and(|config| matches!(config.location, BackendLocation::Remote)) {
+        ASSETSPROXY.iter().map(|(_, destination)| *destination).collect()
+    } else {
+        match recognize_template(&resources, &model_description.version) {
+            Some((language, template)) => {
+                let uses_ipc = config.is_some_and(|config| {
+                    matches!(config.transport, BackendTransport::Ipc) && !config.tcp_only
+                });
+                let tcp_only = match language {
+                    Language::Python | Language::Matlab | Language::Rust => None,
+                    Language::CSharp => Some("C#"),
+                    Language::Java => Some("Java"),
+                };
+                if let (true, Some(language)) = (uses_ipc, tcp_only) {
+                    problems.push(format!(
+                        "launch.toml: the {} backend doesn't support the 'Ipc' transport, set 'tcp_only = true' to fall back to TCP",
+                        language
+                    ));
+                }
+
+                template.iter()
+                    .map(|(_, destination)| *destination)
+                    .filter(|destination| is_required(destination))
+                    .collect()
+            },
+            None => {
+                problems.push(String::from(
+                    "couldn't recognize the backend language of the FMU from its resources"
+                ));
+                return;
+            }
+        }
+    };
+
+    for destination in required {
+        if !resources.join(destination).is_file() {
+            problems.push(format!("resources have no {}", destination));
+        }
+    }
+}
+
+pub type ValidateResult<T> = Result<T, ValidateError>;
+
+#[derive(Debug)]
+pub enum ValidateError {
+    NotFound(PathBuf),
+    ReadError(PathBuf, std::io::Error),
+    ZipError(ZipError)
+}
+
+impl Display for ValidateError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotFound(path) => {
+                write!(f, "'{}' is neither an FMU directory nor an FMU archive", path.display())
+            }
+            Self::ReadError(path, error) => {
+                write!(f, "couldn't read '{}': {}", path.display(), error)
+            }
+            Self::ZipError(error) => {
+                write!(f, "couldn't extract the FMU archive: {}", error)
+            }
+        }
+    }
+}
+
+impl Error for ValidateError {}
+
+impl From<ZipError> for ValidateError {
+    fn from(value: ZipError) -> Self {
+        Self::ZipError(value)
+    }
+}
@@ -14,11 +14,25 @@ use unifmu_macros::for_each_fmu;
 
 mod common;
 use common::{
+    break_binaries,
+    convert,
+    describe,
     distributed_fmu_python_test,
+    edit_launch_toml,
     fmu_python_test,
+    instantiation_token,
+    pack,
+    spec_fmu,
+    unpack,
+    upgrade,
+    validate,
+    validate_failure,
     vdm_check,
     BasicFmu,
-    BreakableFmu
+    BreakableFmu,
+    FmiVersion,
+    FmuBackendImplementationLanguage,
+    LocalFmu
 };
 
 #[for_each_fmu]
@@ -29,6 +43,254 @@ fn test_vdm_check() {
     vdm_check(fmu);
 }
 
+#[for_each_fmu(include: local, bare_directory)]
+#[test]
+fn test_describe() {
+    let fmu = WildFmu{};
+
+    describe(&fmu);
+
+    vdm_check(fmu);
+}
+
+#[for_each_fmu]
+#[test]
+fn test_validate() {
+    let fmu = WildFmu{};
+
+    validate(&fmu);
+}
+
+#[for_each_fmu(include: fmi3, local, bare_directory)]
+#[test]
+fn test_validate_duplicate_value_reference() {
+    let fmu = WildFmu{};
+
+    let model_description_path = fmu.importable_path().join("modelDescription.xml");
+    let model_description = std::fs::read_to_string(&model_description_path)
+        .expect("Should be able to read the model description.");
+    std::fs::write(
+        &model_description_path,
+        model_description.replacen("valueReference=\"1\"", "valueReference=\"0\"", 1)
+    ).expect("Should be able to write the model description.");
+
+    validate_failure(&fmu, "value reference 0 is used by both");
+}
+
+#[for_each_fmu(include: local, bare_directory)]
+#[test]
+fn test_validate_missing_launch_config() {
+    let fmu = WildFmu{};
+
+    std::fs::remove_file(fmu.importable_path().join("resources").join("launch.toml"))
+        .expect("Should be able to remove the launch configuration.");
+
+    validate_failure(&fmu, "launch.toml: the config file was not found");
+}
+
+#[for_each_fmu(include: python, matlab, rust, local, bare_directory)]
+#[test]
+fn test_validate_ipc_transport() {
+    let fmu = WildFmu{};
+
+    edit_launch_toml(&fmu, |config| {
+        config.insert("transport".to_string(), "Ipc".into());
+    });
+
+    validate(&fmu);
+}
+
+#[for_each_fmu(include: csharp, java, local, bare_directory)]
+#[test]
+fn test_validate_ipc_transport_fallback() {
+    let fmu = WildFmu{};
+
+    edit_launch_toml(&fmu, |config| {
+        config.insert("transport".to_string(), "Ipc".into());
+    });
+
+    validate(&fmu);
+}
+
+#[for_each_fmu(include: csharp, java, local, bare_directory)]
+#[test]
+fn test_validate_ipc_transport_unsupported() {
+    let fmu = WildFmu{};
+
+    edit_launch_toml(&fmu, |config| {
+        config.insert("transport".to_string(), "Ipc".into());
+        config.remove("tcp_only");
+    });
+
+    validate_failure(&fmu, "doesn't support the 'Ipc' transport");
+}
+
+#[for_each_fmu(include: local, bare_directory)]
+#[test]
+fn test_pack_unpack() {
+    let fmu = WildFmu{};
+
+    let directory = tempfile::TempDir::new()
+        .expect("Should be able to create a temporary directory.");
+    let archive = directory.path().join("packed.fmu");
+    let unpacked = directory.path().join("unpacked");
+
+    pack(&fmu, &archive, false);
+    unpack(&archive, &unpacked);
+
+    let relative_files = |root: &std::path::Path| -> Vec<std::path::PathBuf> {
+        walkdir::WalkDir::new(root)
+            .sort_by_file_name()
+            .into_iter()
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_type().is_file())
+            .map(|entry| entry.path().strip_prefix(root).unwrap().to_path_buf())
+            .collect()
+    };
+
+    let original_files = relative_files(&fmu.importable_path());
+    assert_eq!(original_files, relative_files(&unpacked));
+
+    for file in original_files {
+        assert_eq!(
+            std::fs::read(fmu.importable_path().join(&file)).unwrap(),
+            std::fs::read(unpacked.join(&file)).unwrap(),
+            "{:?} should be unpacked as it was packed",
+            file
+        );
+    }
+}
+
+#[for_each_fmu(include: fmi3, local, bare_directory)]
+#[test]
+fn test_pack_reproducible() {
+    let fmu = WildFmu{};
+
+    let directory = tempfile::TempDir::new()
+        .expect("Should be able to create a temporary directory.");
+    let first_archive = directory.path().join("first.fmu");
+    let second_archive = directory.path().join("second.fmu");
+
+    pack(&fmu, &first_archive, true);
+
+    // Rewriting a file changes its modification time but not its contents.
+    let model_description_path = fmu.importable_path().join("modelDescription.xml");
+    let model_description = std::fs::read(&model_description_path)
+        .expect("Should be able to read the model description.");
+    std::thread::sleep(std::time::Duration::from_secs(2));
+    std::fs::write(&model_description_path, model_description)
+        .expect("Should be able to write the model description.");
+
+    pack(&fmu, &second_archive, true);
+
+    assert!(
+        std::fs::read(&first_archive).unwrap() == std::fs::read(&second_archive).unwrap(),
+        "Packing the same FMU reproducibly should give the same archive."
+    );
+}
+
+#[for_each_fmu(include: fmi2, local, bare_directory)]
+#[test]
+fn test_upgrade() {
+    let fmu = WildFmu{};
+
+    let model_file_path = fmu.model_file_path();
+    let model_description_path = fmu.importable_path().join("modelDescription.xml");
+
+    let model = std::fs::read_to_string(&model_file_path)
+        .expect("Should be able to read the model file.");
+    let model_description = std::fs::read(&model_description_path)
+        .expect("Should be able to read the model description.");
+
+    break_binaries(&fmu);
+    upgrade(&fmu, false);
+
+    assert_eq!(
+        std::fs::read_to_string(&model_file_path).unwrap(),
+        model,
+        "The upgrade should leave the model untouched."
+    );
+    assert!(
+        std::fs::read(&model_description_path).unwrap() == model_description,
+        "The upgrade should leave the model description untouched."
+    );
+
+    fmu_python_test(fmu, "fmi2_instantiate");
+}
+
+#[for_each_fmu(include: fmi3, local, bare_directory)]
+#[test]
+fn test_upgrade() {
+    let fmu = WildFmu{};
+
+    let model_file_path = fmu.model_file_path();
+    let model_description_path = fmu.importable_path().join("modelDescription.xml");
+
+    let model = std::fs::read_to_string(&model_file_path)
+        .expect("Should be able to read the model file.");
+    let model_description = std::fs::read(&model_description_path)
+        .expect("Should be able to read the model description.");
+
+    break_binaries(&fmu);
+    upgrade(&fmu, false);
+
+    assert_eq!(
+        std::fs::read_to_string(&model_file_path).unwrap(),
+        model,
+        "The upgrade should leave the model untouched."
+    );
+    assert!(
+        std::fs::read(&model_description_path).unwrap() == model_description,
+        "The upgrade should leave the model description untouched."
+    );
+
+    fmu_python_test(fmu, "fmi3_instantiate");
+}
+
+#[for_each_fmu(include: fmi3, python, local, zipped)]
+#[test]
+fn test_upgrade_reproducible() {
+    let fmu = WildFmu{};
+
+    upgrade(&fmu, true);
+    let first_archive = std::fs::read(fmu.importable_path())
+        .expect("Should be able to read the upgraded archive.");
+
+    // The upgrade rewrites the binaries and schemas, changing their
+    // modification times but not their contents.
+    std::thread::sleep(std::time::Duration::from_secs(2));
+
+    upgrade(&fmu, true);
+    let second_archive = std::fs::read(fmu.importable_path())
+        .expect("Should be able to read the upgraded archive.");
+
+    assert!(
+        first_archive == second_archive,
+        "Upgrading the same archive reproducibly should give the same archive."
+    );
+}
+
+#[for_each_fmu(include: fmi2, local, bare_directory)]
+#[test]
+fn test_convert() {
+    let fmu = WildFmu{};
+
+    let model_file_path = fmu.model_file_path();
+    let model = std::fs::read_to_string(&model_file_path)
+        .expect("Should be able to read the model file.");
+
+    convert(&fmu);
+
+    assert_eq!(
+        std::fs::read_to_string(&model_file_path).unwrap(),
+        model,
+        "The conversion should leave the model untouched."
+    );
+
+    validate(&fmu);
+    fmu_python_test(fmu, "fmi3_instantiate");
+}
+
 #[for_each_fmu(include: fmi2)]
 #[test]
 fn test_version() {
@@ -85,6 +347,60 @@ fn test_instantiate() {
     distributed_fmu_python_test(fmu, "fmi3_instantiate");
 }
 
+#[for_each_fmu(include: fmi2, local)]
+#[test]
+fn test_instantiate_wrong_guid() {
+    let fmu = WildFmu{};
+
+    fmu_python_test(fmu, "fmi2_instantiate_wrong_guid");
+}
+
+#[for_each_fmu(include: fmi3, local)]
+#[test]
+fn test_instantiate_wrong_instantiation_token() {
+    let fmu = WildFmu{};
+
+    fmu_python_test(fmu, "fmi3_instantiate_wrong_instantiation_token");
+}
+
+#[for_each_fmu(include: fmi2, local)]
+#[test]
+fn test_instantiate_missing_model_description() {
+    let fmu = WildFmu{};
+
+    fmu_python_test(fmu, "fmi2_instantiate_missing_model_description");
+}
+
+#[for_each_fmu(include: fmi3, local)]
+#[test]
+fn test_instantiate_missing_model_description() {
+    let fmu = WildFmu{};
+
+    fmu_python_test(fmu, "fmi3_instantiate_missing_model_description");
+}
+
+#[test]
+fn test_fresh_instantiation_token() {
+    for version in [FmiVersion::Fmi2, FmiVersion::Fmi3] {
+        let first = LocalFmu::new(
+            String::from("first_fmu"),
+            version.clone(),
+            FmuBackendImplementationLanguage::Python
+        );
+        let second = LocalFmu::new(
+            String::from("second_fmu"),
+            version,
+            FmuBackendImplementationLanguage::Python
+        );
+
+        assert_ne!(
+            instantiation_token(&first),
+            instantiation_token(&second),
+            "Two generated FMUs shared the same instantiation token."
+        );
+    }
+}
+
 #[for_each_fmu(include: fmi2, local)]
 #[test]
 fn test_instantiate_multiple() {
@@ -109,6 +425,30 @@ fn test_simulate() {
     fmu_python_test(fmu, "fmi2_simulate");
 }
 
+#[for_each_fmu(include: fmi2, local)]
+#[test]
+fn test_input_output_derivatives() {
+    let fmu = WildFmu{};
+
+    fmu_python_test(fmu, "fmi2_input_output_derivatives");
+}
+
+#[for_each_fmu(include: fmi2, local)]
+#[test]
+fn test_asynchronous_step() {
+    let fmu = WildFmu{};
+
+    fmu_python_test(fmu, "fmi2_asynchronous_step");
+}
+
+#[for_each_fmu(include: fmi2, local)]
+#[test]
+fn test_asynchronous_step_finished() {
+    let fmu = WildFmu{};
+
+    fmu_python_test(fmu, "fmi2_asynchronous_step_finished");
+}
+
 #[for_each_fmu(include: fmi3, local)]
 #[test]
 fn test_simulate() {
@@ -149,6 +489,102 @@ fn test_matrix_operations() {
     distributed_fmu_python_test(fmu, "fmi3_matrix_operations");
 }
 
+#[for_each_fmu(include: fmi3, local)]
+#[test]
+fn test_derivatives() {
+    let fmu = WildFmu{};
+
+    fmu_python_test(fmu, "fmi3_derivatives");
+}
+
+#[for_each_fmu(include: fmi3, local)]
+#[test]
+fn test_output_derivatives() {
+    let fmu = WildFmu{};
+
+    fmu_python_test(fmu, "fmi3_output_derivatives");
+}
+
+#[for_each_fmu(include: fmi3, local)]
+#[test]
+fn test_variable_dependencies() {
+    let fmu = WildFmu{};
+
+    fmu_python_test(fmu, "fmi3_variable_dependencies");
+}
+
+#[for_each_fmu(include: fmi3, local)]
+#[test]
+fn test_event_iteration() {
+    let fmu = WildFmu{};
+
+    fmu_python_test(fmu, "fmi3_event_iteration");
+}
+
+#[for_each_fmu(include: fmi3, python, local)]
+#[test]
+fn test_intermediate_update_early_return() {
+    let fmu = WildFmu{};
+
+    fmu_python_test(fmu, "fmi3_intermediate_update_early_return");
+}
+
+#[for_each_fmu(include: fmi3, local)]
+#[test]
+fn test_scheduled_execution() {
+    let fmu = WildFmu{};
+
+    fmu_python_test(fmu, "fmi3_scheduled_execution");
+}
+
+#[for_each_fmu(include: matlab, rust)]
+#[test]
+fn test_validate() {
+    let fmu = WildFmu{};
+
+    validate(&fmu);
+}
+
+#[for_each_fmu(include: fmi2, matlab, rust)]
+#[test]
+fn test_instantiate() {
+    let fmu = WildFmu{};
+
+    fmu_python_test(fmu, "fmi2_instantiate");
+}
+
+#[for_each_fmu(include: fmi3, matlab, rust)]
+#[test]
+fn test_instantiate() {
+    let fmu = WildFmu{};
+
+    fmu_python_test(fmu, "fmi3_instantiate");
+}
+
+#[for_each_fmu(include: fmi2, matlab, rust)]
+#[test]
+fn test_simulate() {
+    let fmu = WildFmu{};
+
+    fmu_python_test(fmu, "fmi2_simulate");
+}
+
+#[for_each_fmu(include: fmi3, matlab, rust)]
+#[test]
+fn test_simulate() {
+    let fmu = WildFmu{};
+
+    fmu_python_test(fmu, "fmi3_scalar_simulate");
+}
+
+#[for_each_fmu(include: fmi3, matlab, rust)]
+#[test]
+fn test_output_derivatives() {
+    let fmu = WildFmu{};
+
+    fmu_python_test(fmu, "fmi3_output_derivatives");
+}
+
 #[for_each_fmu(include: fmi2, local, bare_directory)]
 #[test]
 #[should_panic(expected = "PYTHON TEST FAILED - fmi2_instantiate - instantiation: Failed to instantiate model")]
@@ -191,4 +627,229 @@ fn test_unexpected_exit_during_command() {
     fmu.inject_fault_into_backend_do_step_function();
 
     fmu_python_test(fmu, "fmi3_simulate");
-}
\ No newline at end of file
+}
+
+#[for_each_fmu(include: fmi2, local, bare_directory)]
+#[test]
+#[should_panic(expected = "PYTHON TEST FAILED - fmi2_simulate: fmi2DoStep failed with status 3 (error).")]
+fn test_timeout_during_do_step() {
+    let fmu = WildFmu{};
+
+    fmu.inject_hang_into_backend_do_step_function();
+    edit_launch_toml(&fmu, |config| {
+        config.insert("timeouts".to_string(), toml::toml! { do_step_ms = 2000 }.into());
+    });
+
+    fmu_python_test(fmu, "fmi2_simulate");
+}
+
+#[for_each_fmu(include: fmi3, local, bare_directory)]
+#[test]
+#[should_panic(expected = "PYTHON TEST FAILED - fmi3_simulate: fmi3DoStep failed with status 3 (error).")]
+fn test_timeout_during_do_step() {
+    let fmu = WildFmu{};
+
+    fmu.inject_hang_into_backend_do_step_function();
+    edit_launch_toml(&fmu, |config| {
+        config.insert("timeouts".to_string(), toml::toml! { do_step_ms = 2000 }.into());
+    });
+
+    fmu_python_test(fmu, "fmi3_simulate");
+}
+
+#[for_each_fmu(include: fmi2, python, local, bare_directory)]
+#[test]
+fn test_ipc_transport() {
+    let fmu = WildFmu{};
+
+    edit_launch_toml(&fmu, |config| {
+        config.insert("transport".to_string(), "Ipc".into());
+    });
+
+    fmu_python_test(fmu, "fmi2_simulate");
+}
+
+#[for_each_fmu(include: fmi3, python, local, bare_directory)]
+#[test]
+fn test_ipc_transport() {
+    let fmu = WildFmu{};
+
+    edit_launch_toml(&fmu, |config| {
+        config.insert("transport".to_string(), "Ipc".into());
+    });
+
+    fmu_python_test(fmu, "fmi3_simulate");
+}
+
+#[for_each_fmu(include: fmi2, csharp, java, local, bare_directory)]
+#[test]
+fn test_ipc_transport_fallback() {
+    let fmu = WildFmu{};
+
+    edit_launch_toml(&fmu, |config| {
+        config.insert("transport".to_string(), "Ipc".into());
+    });
+
+    fmu_python_test(fmu, "fmi2_simulate");
+}
+
+#[for_each_fmu(include: fmi3, csharp, java, local, bare_directory)]
+#[test]
+fn test_ipc_transport_fallback() {
+    let fmu = WildFmu{};
+
+    edit_launch_toml(&fmu, |config| {
+        config.insert("transport".to_string(), "Ipc".into());
+    });
+
+    fmu_python_test(fmu, "fmi3_simulate");
+}
+
+#[for_each_fmu(include: fmi3, python, local, bare_directory)]
+#[test]
+fn test_shared_memory() {
+    let fmu = WildFmu{};
+
+    edit_launch_toml(&fmu, |config| {
+        config.insert("shared_memory".to_string(), true.into());
+    });
+
+    let output = fmu_python_test(fmu, "fmi3_simulate");
+
+    assert!(
+        output.iter().any(|line| line.contains("Exchanging bulk values through shared memory.")),
+        "The backend should have accepted the shared memory region."
+    );
+}
+
+// The benchmarks print their timings, compare them by running with
+// `cargo test float64_bulk_benchmark -- --ignored --nocapture --test-threads 1`.
+#[for_each_fmu(include: fmi3, python, local, bare_directory)]
+#[test]
+#[ignore = "benchmark"]
+fn test_float64_bulk_benchmark() {
+    let fmu = WildFmu{};
+
+    fmu_python_test(fmu, "fmi3_float64_bulk_benchmark");
+}
+
+#[for_each_fmu(include: fmi3, python, local, bare_directory)]
+#[test]
+#[ignore = "benchmark"]
+fn test_float64_bulk_benchmark_shared_memory() {
+    let fmu = WildFmu{};
+
+    edit_launch_toml(&fmu, |config| {
+        config.insert("shared_memory".to_string(), true.into());
+    });
+
+    fmu_python_test(fmu, "fmi3_float64_bulk_benchmark");
+}
+
+#[test]
+fn test_fmi2_model_exchange_simulate() {
+    let fmu = LocalFmu::new_with_options(
+        String::from("fmi2_model_exchange_fmu"),
+        FmiVersion::Fmi2,
+        FmuBackendImplementationLanguage::Python,
+        ["--model-exchange"]
+    );
+
+    fmu_python_test(fmu, "fmi2_model_exchange_simulate");
+}
+
+#[test]
+fn test_fmi2_csharp_model_exchange_simulate() {
+    let fmu = LocalFmu::new_with_options(
+        String::from("fmi2_csharp_model_exchange_fmu"),
+        FmiVersion::Fmi2,
+        FmuBackendImplementationLanguage::CSharp,
+        ["--model-exchange"]
+    );
+
+    fmu_python_test(fmu, "fmi2_model_exchange_simulate");
+}
+
+#[test]
+fn test_fmi2_java_model_exchange_simulate() {
+    let fmu = LocalFmu::new_with_options(
+        String::from("fmi2_java_model_exchange_fmu"),
+        FmiVersion::Fmi2,
+        FmuBackendImplementationLanguage::Java,
+        ["--model-exchange"]
+    );
+
+    fmu_python_test(fmu, "fmi2_model_exchange_simulate");
+}
+
+#[test]
+fn test_fmi3_model_exchange_simulate() {
+    let fmu = LocalFmu::new_with_options(
+        String::from("fmi3_model_exchange_fmu"),
+        FmiVersion::Fmi3,
+        FmuBackendImplementationLanguage::Python,
+        ["--model-exchange"]
+    );
+
+    fmu_python_test(fmu, "fmi3_model_exchange_simulate");
+}
+
+#[test]
+fn test_fmi2_python_spec_simulate() {
+    let fmu = spec_fmu(
+        FmuBackendImplementationLanguage::Python,
+        FmiVersion::Fmi2
+    );
+
+    fmu_python_test(fmu, "fmi2_spec_simulate");
+}
+
+#[test]
+fn test_fmi2_csharp_spec_simulate() {
+    let fmu = spec_fmu(
+        FmuBackendImplementationLanguage::CSharp,
+        FmiVersion::Fmi2
+    );
+
+    fmu_python_test(fmu, "fmi2_spec_simulate");
+}
+
+#[test]
+fn test_fmi2_java_spec_simulate() {
+    let fmu = spec_fmu(
+        FmuBackendImplementationLanguage::Java,
+        FmiVersion::Fmi2
+    );
+
+    fmu_python_test(fmu, "fmi2_spec_simulate");
+}
+
+#[test]
+fn test_fmi3_python_spec_simulate() {
+    let fmu = spec_fmu(
+        FmuBackendImplementationLanguage::Python,
+        FmiVersion::Fmi3
+    );
+
+    fmu_python_test(fmu, "fmi3_spec_simulate");
+}
+
+#[test]
+fn test_fmi3_csharp_spec_simulate() {
+    let fmu = spec_fmu(
+        FmuBackendImplementationLanguage::CSharp,
+        FmiVersion::Fmi3
+    );
+
+    fmu_python_test(fmu, "fmi3_spec_simulate");
+}
+
+#[test]
+fn test_fmi3_java_spec_simulate() {
+    let fmu = spec_fmu(
+        FmuBackendImplementationLanguage::Java,
+        FmiVersion::Fmi3
+    );
+
+    fmu_python_test(fmu, "fmi3_spec_simulate");
+}
@@ -6,7 +6,7 @@
 
 use std::{
     collections::HashMap,
-    ffi::OsString,
+    ffi::{OsStr, OsString},
     fs::{copy, create_dir, create_dir_all, read_dir, remove_dir_all, File},
     io::{self, BufRead, BufReader, Write},
     path::{Path, PathBuf},
@@ -40,14 +40,20 @@ static PERSITENTLY_CREATED_UNIFMUS_DIRECTORY: &str = "../generated_fmus/cli_test
 /// Should NOT be called with a Distributed FMU and a python function that
 /// instantiates that FMU as part of the test.
 /// Distributed FMUs that aren't instantiated during testing are ok.
+/// 
+/// Returns the lines of output of the subprocess, which include the output
+/// of the FMU itself.
 pub fn fmu_python_test(
     fmu: impl BasicFmu,
     python_test_function_name: &str
-) {
+) -> Vec<String> {
+    let mut output = Vec::new();
+
     let python_test_process = start_python_test_process(
         python_test_function_name,
         fmu.importable_path(),
-        fmu.is_zipped()
+        fmu.is_zipped(),
+        fmu.language()
     );
 
     let python_test_output_reader = BufReader::new(&python_test_process);
@@ -63,6 +69,7 @@ pub fn fmu_python_test(
                 
                 } else {
                     println!("{line}");
+                    output.push(line);
                 }
             },
             Err(e) => {
@@ -73,6 +80,30 @@ pub fn fmu_python_test(
             }
         }
     }
+
+    output
+}
+
+/// Generates a local, unzipped FMU of the given language and version from the
+/// variable specification `tests/variable_specs/<version>.toml`.
+pub fn spec_fmu(
+    language: FmuBackendImplementationLanguage,
+    version: FmiVersion
+) -> LocalFmu {
+    let name = format!("{}_{}_spec_fmu", version.as_str(), language.cmd_str());
+
+    let spec = std::env::current_dir()
+        .expect("Should be able to get current directory")
+        .join("tests")
+        .join("variable_specs")
+        .join(format!("{}.toml", version.as_str()));
+
+    LocalFmu::new_with_options(
+        name,
+        version,
+        language,
+        [OsStr::new("--spec"), spec.as_os_str()]
+    )
 }
 
 /// When distributed FMUs are instantiated they'll output a message containing
@@ -100,7 +131,8 @@ pub fn distributed_fmu_python_test(
     let python_test_process = start_python_test_process(
         python_test_function_name,
         fmu.importable_path(),
-        fmu.is_zipped()
+        fmu.is_zipped(),
+        fmu.language()
     );
 
     let python_test_output_reader = BufReader::new(&python_test_process);
@@ -190,11 +222,15 @@ pub fn distributed_fmu_python_test(
 
 /// Starts the python test subprocess, returning a duct::ReaderHandle to it.
 /// 
+/// MATLAB based FMUs are run against the stand-in for the MATLAB engine in
+/// the `matlab_mock` directory, which is put first on the python path.
+/// 
 /// Panics if the test script isn't available or cannot be executed.
 fn start_python_test_process(
     python_test_function_name: &str,
     fmu_path: impl Into<OsString>,
-    is_zipped: bool
+    is_zipped: bool,
+    language: &FmuBackendImplementationLanguage
 ) -> duct::ReaderHandle {
     let test_directory = std::env::current_dir()
         .expect("Should be able to get current directory")
@@ -217,13 +253,22 @@ fn start_python_test_process(
         _other => "python3"
     };
 
-    duct::cmd!(
+    let mut python_test_command = duct::cmd!(
         python_interpreter_binary_name,
         python_test_script_name,
         python_test_function_name,
         fmu_path,
         is_zipped.to_string()
-    )
+    );
+
+    if *language == FmuBackendImplementationLanguage::Matlab {
+        python_test_command = python_test_command.env(
+            "PYTHONPATH",
+            test_directory.join("matlab_mock")
+        );
+    }
+
+    python_test_command
         .dir(test_directory)
         .stderr_to_stdout()
         .reader()
@@ -298,12 +343,195 @@ pub fn vdm_check(fmu: impl BasicFmu) {
         .stdout(contains("No errors found"));
 }
 
+/// Rewrites the model description of the given unzipped FMU from its
+/// variable specification with the `describe` command of the UniFMU CLI.
+///
+/// Panics if the command fails.
+pub fn describe(fmu: &impl BasicFmu) {
+    Command::cargo_bin("unifmu")
+        .expect("The unifmu binary should be present in this crate.")
+        .arg("describe")
+        .arg(fmu.importable_path())
+        .assert()
+        .success()
+        .stderr(contains("written successfully"));
+}
+
+/// Validates the given FMU with the `validate` command of the UniFMU CLI.
+///
+/// Panics if the command finds any problem with the FMU.
+pub fn validate(fmu: &impl BasicFmu) {
+    Command::cargo_bin("unifmu")
+        .expect("The unifmu binary should be present in this crate.")
+        .arg("validate")
+        .arg(fmu.importable_path())
+        .assert()
+        .success()
+        .stderr(contains("the FMU is valid"));
+}
+
+/// Validates the given FMU with the `validate` command of the UniFMU CLI.
+///
+/// Panics unless the command fails and reports the given problem.
+pub fn validate_failure(fmu: &impl BasicFmu, problem: &str) {
+    Command::cargo_bin("unifmu")
+        .expect("The unifmu binary should be present in this crate.")
+        .arg("validate")
+        .arg(fmu.importable_path())
+        .assert()
+        .failure()
+        .stderr(contains(problem));
+}
+
+/// Packs the given unzipped FMU into the given archive with the `pack`
+/// command of the UniFMU CLI, making the archive reproducible if asked to.
+///
+/// Panics if the command fails.
+pub fn pack(fmu: &impl BasicFmu, archive: &Path, reproducible: bool) {
+    let mut command = Command::cargo_bin("unifmu")
+        .expect("The unifmu binary should be present in this crate.");
+
+    command
+        .arg("pack")
+        .arg(fmu.importable_path())
+        .arg(archive);
+
+    if reproducible {
+        command.arg("--reproducible");
+    }
+
+    command
+        .assert()
+        .success()
+        .stderr(contains("the FMU was packed"));
+}
+
+/// Unpacks the given FMU archive into the given directory with the `unpack`
+/// command of the UniFMU CLI.
+///
+/// Panics if the command fails.
+pub fn unpack(archive: &Path, directory: &Path) {
+    Command::cargo_bin("unifmu")
+        .expect("The unifmu binary should be present in this crate.")
+        .arg("unpack")
+        .arg(archive)
+        .arg(directory)
+        .assert()
+        .success()
+        .stderr(contains("the FMU was unpacked"));
+}
+
+/// Upgrades the given FMU in place with the `upgrade` command of the UniFMU
+/// CLI, packing an archive reproducibly if `reproducible` is set.
+///
+/// Panics if the command fails.
+pub fn upgrade(fmu: &impl BasicFmu, reproducible: bool) {
+    let mut command = Command::cargo_bin("unifmu")
+        .expect("The unifmu binary should be present in this crate.");
+
+    command
+        .arg("upgrade")
+        .arg(fmu.importable_path());
+
+    if reproducible {
+        command.arg("--reproducible");
+    }
+
+    command
+        .assert()
+        .success()
+        .stderr(contains("the FMU was upgraded successfully"));
+}
+
+/// Converts the given FMI2 FMU to FMI3 in place with the `convert` command.
+///
+/// Panics if the conversion fails.
+pub fn convert(fmu: &impl BasicFmu) {
+    Command::cargo_bin("unifmu")
+        .expect("The unifmu binary should be present in this crate.")
+        .arg("convert")
+        .arg("--to")
+        .arg("fmi3")
+        .arg(fmu.importable_path())
+        .assert()
+        .success()
+        .stderr(contains("the FMU was converted successfully"));
+}
+
+/// Replaces every binary of the given unzipped FMU with one that can't be
+/// loaded, as if the FMU was generated with some other version of UniFMU.
+///
+/// Panics if a binary can't be replaced.
+pub fn break_binaries(fmu: &impl BasicFmu) {
+    let binaries = WalkDir::new(fmu.importable_path().join("binaries"))
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file());
+
+    for binary in binaries {
+        std::fs::write(binary.path(), "not a library")
+            .expect("Should be able to overwrite the binary.");
+    }
+}
+
+/// Edits the launch configuration of the given unzipped FMU through the given
+/// function, which is handed the parsed `launch.toml`.
+///
+/// Panics if the launch configuration can't be read, parsed or written.
+pub fn edit_launch_toml(fmu: &impl BasicFmu, edit: impl FnOnce(&mut toml::Table)) {
+    edit_toml_file(
+        &fmu.importable_path().join("resources").join("launch.toml"),
+        edit
+    );
+}
+
+/// Edits the TOML file at the given path through the given function, which is
+/// handed the parsed file.
+///
+/// Panics if the file can't be read, parsed or written.
+fn edit_toml_file(path: &Path, edit: impl FnOnce(&mut toml::Table)) {
+    let mut table = std::fs::read_to_string(path)
+        .expect("Should be able to read the TOML file.")
+        .parse::<toml::Table>()
+        .expect("Should be able to parse the TOML file.");
+
+    edit(&mut table);
+
+    std::fs::write(
+        path,
+        toml::to_string(&table).expect("Should be able to serialize the TOML file.")
+    ).expect("Should be able to write the TOML file.");
+}
+
+/// Reads the GUID (FMI2) or instantiation token (FMI3) from the model
+/// description of the given unzipped FMU.
+///
+/// Panics if the model description can't be read or doesn't declare one.
+pub fn instantiation_token(fmu: &impl BasicFmu) -> String {
+    let model_description = std::fs::read_to_string(
+        fmu.importable_path().join("modelDescription.xml")
+    ).expect("Should be able to read the model description of the FMU.");
+
+    let opening = match fmu.version() {
+        FmiVersion::Fmi2 => " guid=\"",
+        FmiVersion::Fmi3 => " instantiationToken=\""
+    };
+
+    model_description
+        .split_once(opening)
+        .and_then(|(_, rest)| rest.split_once('"'))
+        .map(|(token, _)| String::from(token))
+        .expect("The model description should declare an instantiation token.")
+}
+
 /// The UNIFMU backend languages supported by default by the project.
 #[derive(Clone, PartialEq)]
 pub enum FmuBackendImplementationLanguage {
     CSharp,
     Java,
-    Python
+    Python,
+    Matlab,
+    Rust
 }
 
 impl FmuBackendImplementationLanguage {
@@ -313,7 +541,9 @@ impl FmuBackendImplementationLanguage {
         match self {
             FmuBackendImplementationLanguage::CSharp => "c-sharp",
             FmuBackendImplementationLanguage::Java => "java",
-            FmuBackendImplementationLanguage::Python => "python"
+            FmuBackendImplementationLanguage::Python => "python",
+            FmuBackendImplementationLanguage::Matlab => "matlab",
+            FmuBackendImplementationLanguage::Rust => "rust"
         }
     }
 
@@ -323,7 +553,21 @@ impl FmuBackendImplementationLanguage {
         match self {
             FmuBackendImplementationLanguage::CSharp => "throw new Exception();",
             FmuBackendImplementationLanguage::Java => "int doesNotCompute = 1/0;",
-            FmuBackendImplementationLanguage::Python => "raise Exception()"
+            FmuBackendImplementationLanguage::Python => "raise Exception()",
+            FmuBackendImplementationLanguage::Matlab => "error('fault');",
+            FmuBackendImplementationLanguage::Rust => "panic!(\"fault\");"
+        }
+    }
+
+    /// Language dependent codesnippet that causes the backend to stop
+    /// responding for an hour
+    pub fn hang_str(&self) -> &str {
+        match self {
+            FmuBackendImplementationLanguage::CSharp => "System.Threading.Thread.Sleep(3600000);",
+            FmuBackendImplementationLanguage::Java => "try { Thread.sleep(3600000); } catch (InterruptedException e) {}",
+            FmuBackendImplementationLanguage::Python => "import time; time.sleep(3600)",
+            FmuBackendImplementationLanguage::Matlab => "pause(3600);",
+            FmuBackendImplementationLanguage::Rust => "std::thread::sleep(std::time::Duration::from_secs(3600));"
         }
     }
 
@@ -332,7 +576,9 @@ impl FmuBackendImplementationLanguage {
         match self {
             FmuBackendImplementationLanguage::CSharp => "model.cs",
             FmuBackendImplementationLanguage::Java => "src/main/java/Model.java",
-            FmuBackendImplementationLanguage::Python => "model.py"
+            FmuBackendImplementationLanguage::Python => "model.py",
+            FmuBackendImplementationLanguage::Matlab => "matlabcode/Model.m",
+            FmuBackendImplementationLanguage::Rust => "src/model.rs"
         }
     }
 
@@ -341,7 +587,9 @@ impl FmuBackendImplementationLanguage {
         match self {
             FmuBackendImplementationLanguage::CSharp => "C#",
             FmuBackendImplementationLanguage::Java => "Java",
-            FmuBackendImplementationLanguage::Python => "Python"
+            FmuBackendImplementationLanguage::Python => "Python",
+            FmuBackendImplementationLanguage::Matlab => "MATLAB",
+            FmuBackendImplementationLanguage::Rust => "Rust"
         }
     }
 }
@@ -403,7 +651,7 @@ impl FmuDirectory {
     pub fn persist(self) -> FmuDirectory {
         match self {
             FmuDirectory::Persistent(_) => self,
-            FmuDirectory::Temporary(temp_dir) => FmuDirectory::Persistent(temp_dir.into_path())
+            FmuDirectory::Temporary(temp_dir) => FmuDirectory::Persistent(temp_dir.keep())
         }
     }
 }
@@ -456,6 +704,42 @@ pub struct BlackboxDistributedFmu {
     black_box_fmu: ZippedLocalFmu,
 }
 
+impl LocalFmu {
+    /// Generate a wholly new FMU using the UNIFMU CLI in a temporary
+    /// directory, passing the given options on to the `generate` command.
+    ///
+    /// For FMUs that differ from the pregenerated ones behind `get_clone()`,
+    /// such as Model Exchange FMUs.
+    pub fn new_with_options(
+        name: String,
+        version: FmiVersion,
+        language: FmuBackendImplementationLanguage,
+        options: impl IntoIterator<Item = impl AsRef<OsStr>>
+    ) -> LocalFmu {
+        let directory = FmuDirectory::Temporary(Self::new_tmp_dir());
+
+        let this = LocalFmu {
+            name,
+            directory,
+            language,
+            version
+        };
+
+        Command::cargo_bin("unifmu")
+            .expect("The unifmu binary should be present in this crate.")
+            .current_dir(this.directory())
+            .args(this.cmd_args())
+            .args(options)
+            .assert()
+            .success()
+            .stderr(contains("generated successfully"));
+
+        this.post_generation_setup();
+
+        this
+    }
+}
+
 impl BasicFmu for LocalFmu {
     fn new(
         name: String,
@@ -529,6 +813,14 @@ impl BasicFmu for LocalFmu {
             FmuBackendImplementationLanguage::Python => match version {
                 FmiVersion::Fmi2 => (*PYTHON_FMI2).clone(),
                 FmiVersion::Fmi3 => (*PYTHON_FMI3).clone()
+            },
+            FmuBackendImplementationLanguage::Matlab => match version {
+                FmiVersion::Fmi2 => (*MATLAB_FMI2).clone(),
+                FmiVersion::Fmi3 => (*MATLAB_FMI3).clone()
+            },
+            FmuBackendImplementationLanguage::Rust => match version {
+                FmiVersion::Fmi2 => (*RUST_FMI2).clone(),
+                FmiVersion::Fmi3 => (*RUST_FMI3).clone()
             }
         }
     }
@@ -606,7 +898,8 @@ impl BasicFmu for LocalFmu {
             &mut iterable_old_fmu_directory,
             old_prefix,
             zip_file,
-            CompressionMethod::Deflated
+            CompressionMethod::Deflated,
+            false
         ).expect("Should be able to zip old directory into new file.");
 
         ZippedLocalFmu {
@@ -636,20 +929,32 @@ impl BreakableFmu for LocalFmu {
         match self.language() {
             FmuBackendImplementationLanguage::CSharp => {
                 match self.version() {
-                    FmiVersion::Fmi2 => 55,
-                    FmiVersion::Fmi3 => 395
+                    FmiVersion::Fmi2 => 64,
+                    FmiVersion::Fmi3 => 417
                 }
             },
             FmuBackendImplementationLanguage::Java => {
                 match self.version() {
-                    FmiVersion::Fmi2 => 94,
-                    FmiVersion::Fmi3 => 436
+                    FmiVersion::Fmi2 => 105,
+                    FmiVersion::Fmi3 => 475
                 }
             },
             FmuBackendImplementationLanguage::Python => {
                 match self.version() {
-                    FmiVersion::Fmi2 => 38,
-                    FmiVersion::Fmi3 => 209
+                    FmiVersion::Fmi2 => 46,
+                    FmiVersion::Fmi3 => 230
+                }
+            },
+            FmuBackendImplementationLanguage::Matlab => {
+                match self.version() {
+                    FmiVersion::Fmi2 => 43,
+                    FmiVersion::Fmi3 => 84
+                }
+            },
+            FmuBackendImplementationLanguage::Rust => {
+                match self.version() {
+                    FmiVersion::Fmi2 => 112,
+                    FmiVersion::Fmi3 => 142
                 }
             },
         }
@@ -658,6 +963,8 @@ impl BreakableFmu for LocalFmu {
     fn do_step_function_injection_prefix(&self) -> &str {
         match self.language() {
             FmuBackendImplementationLanguage::Python => "        ",
+            FmuBackendImplementationLanguage::Matlab => "            ",
+            FmuBackendImplementationLanguage::Rust => "        ",
             _ => ""
         }
     }
@@ -795,7 +1102,15 @@ impl BasicFmu for ZippedLocalFmu {
             FmuBackendImplementationLanguage::Python => match version {
                 FmiVersion::Fmi2 => (*ZIPPED_PYTHON_FMI2).clone(),
                 FmiVersion::Fmi3 => (*ZIPPED_PYTHON_FMI3).clone()
-            }
+            },
+            FmuBackendImplementationLanguage::Matlab => match version {
+                FmiVersion::Fmi2 => (*ZIPPED_MATLAB_FMI2).clone(),
+                FmiVersion::Fmi3 => (*ZIPPED_MATLAB_FMI3).clone()
+            },
+            FmuBackendImplementationLanguage::Rust => panic!(
+                "Zipped FMUs based on {} would compile their backend in every test; there is no need to write tests for this case.",
+                language.pretty_str()
+            )
         }
     }
 
@@ -941,7 +1256,12 @@ impl BasicFmu for DistributedFmu {
             FmuBackendImplementationLanguage::Python => match version {
                 FmiVersion::Fmi2 => (*DISTRIBUTED_PYTHON_FMI2).clone(),
                 FmiVersion::Fmi3 => (*DISTRIBUTED_PYTHON_FMI3).clone()
-            }
+            },
+            FmuBackendImplementationLanguage::Matlab
+            | FmuBackendImplementationLanguage::Rust => panic!(
+                "UniFMU cannot generate distributed backends based on {}; there is no need to write tests for this case.",
+                language.pretty_str()
+            )
         }
     }
 
@@ -1018,7 +1338,8 @@ impl BasicFmu for DistributedFmu {
             &mut iterable_old_fmu_directory,
             old_prefix,
             zip_file,
-            CompressionMethod::Deflated
+            CompressionMethod::Deflated,
+            false
         ).expect("Should be able to zip old proxy directory into new file.");
 
         copy_directory_recursive(
@@ -1153,7 +1474,12 @@ impl BasicFmu for ZippedDistributedFmu {
             FmuBackendImplementationLanguage::Python => match version {
                 FmiVersion::Fmi2 => (*ZIPPED_DISTRIBUTED_PYTHON_FMI2).clone(),
                 FmiVersion::Fmi3 => (*ZIPPED_DISTRIBUTED_PYTHON_FMI3).clone()
-            }
+            },
+            FmuBackendImplementationLanguage::Matlab
+            | FmuBackendImplementationLanguage::Rust => panic!(
+                "UniFMU cannot generate distributed backends based on {}; there is no need to write tests for this case.",
+                language.pretty_str()
+            )
         }
     }
 
@@ -1592,8 +1918,10 @@ pub trait PostGenerationSetup: BasicFmu {
     /// Code to run after the fmu files have been generated, but before it
     /// is considered a valid, testable fmu.
     fn post_generation_setup(&self) {
-        if self.language() == &FmuBackendImplementationLanguage::Java {
-            self.java_setup()
+        match self.language() {
+            FmuBackendImplementationLanguage::Java => self.java_setup(),
+            FmuBackendImplementationLanguage::Rust => self.rust_setup(),
+            _ => ()
         }
     }
 
@@ -1613,6 +1941,40 @@ pub trait PostGenerationSetup: BasicFmu {
         gradle_build_command.run()
             .expect("Should be able to prebuild Javabased FMU");
     }
+
+    /// Compiles the Rust backend with cargo and launches the built binary
+    /// instead of `cargo run`, so that the copies of the FMU made for each
+    /// test don't compile the backend again.
+    ///
+    /// The build directory is left out of the FMU to keep the copies small.
+    fn rust_setup(&self) {
+        let target_directory = TempDir::new()
+            .expect("Couldn't create temporary cargo target directory.");
+
+        duct::cmd!(
+            "cargo", "build", "--release", "--quiet", "--target-dir", target_directory.path()
+        )
+            .dir(self.backend_path())
+            .run()
+            .expect("Should be able to prebuild Rustbased FMU");
+
+        let binary = format!("backend{}", std::env::consts::EXE_SUFFIX);
+        let binary_directory = self.backend_path().join("target").join("release");
+
+        create_dir_all(&binary_directory)
+            .expect("Should be able to create directory for the Rust backend binary");
+        copy(
+            target_directory.path().join("release").join(&binary),
+            binary_directory.join(&binary)
+        ).expect("Should be able to copy the Rust backend binary into the FMU");
+
+        let launch_command = toml::Value::from(vec![format!("./target/release/{}", binary)]);
+        edit_toml_file(&self.backend_path().join("launch.toml"), |config| {
+            for os in ["linux", "macos", "windows"] {
+                config.insert(os.to_string(), launch_command.clone());
+            }
+        });
+    }
 }
 
 /// Behaviour for FMUs that can be generated by the UNIFMU CLI.
@@ -1648,6 +2010,22 @@ pub trait BreakableFmu: BasicFmu {
             self.do_step_function_line_number()
         ).expect("Should be able to inject fault into model.");
     }
+
+    /// Makes the do_step function in the model stop responding by adding a
+    /// long sleep after the function definition.
+    fn inject_hang_into_backend_do_step_function(&self) {
+        let injection = format!(
+            "{}{}",
+            self.do_step_function_injection_prefix(),
+            self.language().hang_str()
+        );
+
+        inject_line(
+            &self.model_file_path(),
+            &injection,
+            self.do_step_function_line_number()
+        ).expect("Should be able to inject hang into model.");
+    }
 }
 
 /// Behaviour for zipped FMUs.
@@ -1719,7 +2097,12 @@ pub trait RemoteBackend: DistributedFileStructure {
                 duct::cmd!(
                     python_interpreter_binary_name, "main.py", port
                 )
-            }
+            },
+            FmuBackendImplementationLanguage::Matlab
+            | FmuBackendImplementationLanguage::Rust => panic!(
+                "UniFMU cannot generate distributed backends based on {}.",
+                self.language().pretty_str()
+            )
         };
 
         backend_process_cmd.dir(self.backend_directory_path())
@@ -1777,17 +2160,14 @@ fn inject_line(
 
     let lines = BufReader::new(&file).lines();
 
-    let mut current_line_number: u64 = 1;
-
     let mut buffer = Vec::<u8>::new();
 
-    for line in lines {
+    for (current_line_number, line) in (1..).zip(lines) {
         let line = line?;
         if line_number == current_line_number {
             writeln!(buffer, "{}", injection)?;
         }
         writeln!(buffer, "{}", &line)?;
-        current_line_number += 1;
     }
 
     let mut file = File::create(file_path)?;
@@ -1856,6 +2236,42 @@ static PYTHON_FMI3: LazyLock<LocalFmu> = LazyLock::new(|| {
     )
 });
 
+static MATLAB_FMI2: LazyLock<LocalFmu> = LazyLock::new(|| {
+    LocalFmu::new_persistent(
+        String::from("matlab_fmi2"),
+        FmiVersion::Fmi2,
+        FmuBackendImplementationLanguage::Matlab,
+        "PROMETHEAN_matlab_fmi2",
+    )
+});
+
+static MATLAB_FMI3: LazyLock<LocalFmu> = LazyLock::new(|| {
+    LocalFmu::new_persistent(
+        String::from("matlab_fmi3"),
+        FmiVersion::Fmi3,
+        FmuBackendImplementationLanguage::Matlab,
+        "PROMETHEAN_matlab_fmi3",
+    )
+});
+
+static RUST_FMI2: LazyLock<LocalFmu> = LazyLock::new(|| {
+    LocalFmu::new_persistent(
+        String::from("rust_fmi2"),
+        FmiVersion::Fmi2,
+        FmuBackendImplementationLanguage::Rust,
+        "PROMETHEAN_rust_fmi2",
+    )
+});
+
+static RUST_FMI3: LazyLock<LocalFmu> = LazyLock::new(|| {
+    LocalFmu::new_persistent(
+        String::from("rust_fmi3"),
+        FmiVersion::Fmi3,
+        FmuBackendImplementationLanguage::Rust,
+        "PROMETHEAN_rust_fmi3",
+    )
+});
+
 static ZIPPED_CSHARP_FMI2: LazyLock<ZippedLocalFmu> = LazyLock::new(|| {
     ZippedLocalFmu::new_persistent(
         String::from("zipped_csharp_fmi2"),
@@ -1910,6 +2326,24 @@ static ZIPPED_PYTHON_FMI3: LazyLock<ZippedLocalFmu> = LazyLock::new(|| {
     )
 });
 
+static ZIPPED_MATLAB_FMI2: LazyLock<ZippedLocalFmu> = LazyLock::new(|| {
+    ZippedLocalFmu::new_persistent(
+        String::from("zipped_matlab_fmi2"),
+        FmiVersion::Fmi2,
+        FmuBackendImplementationLanguage::Matlab,
+        "PROMETHEAN_zipped_matlab_fmi2",
+    )
+});
+
+static ZIPPED_MATLAB_FMI3: LazyLock<ZippedLocalFmu> = LazyLock::new(|| {
+    ZippedLocalFmu::new_persistent(
+        String::from("zipped_matlab_fmi3"),
+        FmiVersion::Fmi3,
+        FmuBackendImplementationLanguage::Matlab,
+        "PROMETHEAN_zipped_matlab_fmi3",
+    )
+});
+
 static DISTRIBUTED_CSHARP_FMI2: LazyLock<DistributedFmu> = LazyLock::new(|| {
     DistributedFmu::new_persistent(
         String::from("distributed_csharp_fmi2"),
@@ -32,7 +32,7 @@ inner_function : Callable[[FMU2Slave | FMU3Slave]]
 fmu_filename : str
     Full filename of the file containing the FMU. Currently the tests assume an
     unzipped FMU, so this should be the full name of the unzipped FMU directory.
-fmu_class : FMU2Slave | FMU3Slave
+fmu_class : FMU2Slave | FMU2Model | FMU3Slave | FMU3Model
     Class name of the fmpy FMU object to create from the given fmu_filename.
 """
 def uninstantiating_test(
@@ -55,7 +55,7 @@ def uninstantiating_test(
         fmu = fmu_class(
             guid = model_description.guid,
             unzipDirectory = fmu_filename,
-            modelIdentifier = model_description.coSimulation.modelIdentifier,
+            modelIdentifier = _model_identifier(model_description),
             instanceName='test_instance'
         )
 
@@ -80,15 +80,19 @@ inner_function :  Callable[[FMU2Slave | FMU3Slave, ModelDescription]]
 fmu_filename : str
     Full filename of the file containing the FMU. Currently the tests assume an
     unzipped FMU, so this should be the full name of the unzipped FMU directory.
-fmu_class : FMU2Slave | FMU3Slave
+fmu_class : FMU2Slave | FMU2Model | FMU3Slave | FMU3Model
     Class name of the fmpy FMU object to create from the given fmu_filename.
+callbacks : fmi2CallbackFunctions | None
+    Callback functions to instantiate an FMI2 FMU with, instead of the default
+    callbacks of fmpy.
 """
 def instantiating_test(
     caller,
     inner_function,
     fmu_filename,
     fmu_class,
-    is_zipped = False
+    is_zipped = False,
+    callbacks = None
 ):
     if is_zipped:
         try:
@@ -103,11 +107,14 @@ def instantiating_test(
         fmu = fmu_class(
             guid = model_description.guid,
             unzipDirectory = fmu_filename,
-            modelIdentifier = model_description.coSimulation.modelIdentifier,
+            modelIdentifier = _model_identifier(model_description),
             instanceName='test_instance'
         )
 
-        fmu.instantiate(loggingOn=True)
+        if callbacks is None:
+            fmu.instantiate(loggingOn=True)
+        else:
+            fmu.instantiate(callbacks=callbacks, loggingOn=True)
 
     except Exception as e:
         fail_print(caller, e, "instantiation")
@@ -147,6 +154,10 @@ def instantiating_test(
     if is_zipped:
         rmtree(fmu_filename, ignore_errors=True)
 
+def _model_identifier(model_description):
+    interface = model_description.coSimulation or model_description.modelExchange
+    return interface.modelIdentifier
+
 def fail_print(caller, exception, test_context=""):
     test_context_string = f" - {test_context}" if test_context else ""
     exception_context_string = f" - {exception.__context__}" if exception.__context__ else ""
@@ -1,11 +1,15 @@
-from common import barren_test, uninstantiating_test, instantiating_test
+from common import barren_test, uninstantiating_test, instantiating_test, fail_print
 import ctypes
 from ctypes import c_uint8, c_ubyte
 from fmpy import read_model_description, extract
-from fmpy.fmi2 import FMU2Slave
-from fmpy.fmi3 import FMU3Slave, fmi3ValueReference, fmi3UInt64, fmi3Float64, fmi3IntervalQualifier
+from fmpy.fmi2 import FMU2Slave, FMU2Model, fmi2Real
+from fmpy.fmi3 import FMU3Slave, FMU3Model, fmi3ValueReference, fmi3Int32, fmi3UInt64, fmi3Float64, fmi3IntervalQualifier
 from shutil import rmtree
-from fmpy.fmi2 import fmi2OK
+from fmpy.fmi2 import fmi2OK, fmi2Pending, fmi2Status, fmi2StatusKind, fmi2DoStepStatus, fmi2LastSuccessfulTime, fmi2Component, fmi2Boolean
+from fmpy.fmi2 import fmi2CallbackFunctions, fmi2CallbackLoggerTYPE, fmi2StepFinishedTYPE, printLogMessage
+import threading
+import os
+import time
 
 ## Overwrites the clock related functions - a convenience as fmpy as of writing
 ## doesn't have simple convenient functions for clock related stuff.
@@ -67,6 +71,146 @@ def setShiftFraction(fmu,valueReferences, counters, resolutions):
     resolutions = (fmi3UInt64 * nValueReferences)(*resolutions)
     fmu.fmi3SetShiftFraction(fmu.component, valueReferences, nValueReferences, counters, resolutions)
 
+def getDirectionalDerivative(fmu, unknowns, knowns, seed):
+    nUnknowns = len(unknowns)
+    nKnowns = len(knowns)
+    nSeed = len(seed)
+    unknowns = (fmi3ValueReference * nUnknowns)(*unknowns)
+    knowns = (fmi3ValueReference * nKnowns)(*knowns)
+    seed = (fmi3Float64 * nSeed)(*seed)
+    sensitivity = (fmi3Float64 * nUnknowns)()
+    fmu.fmi3GetDirectionalDerivative(fmu.component, unknowns, nUnknowns, knowns, nKnowns, seed, nSeed, sensitivity, nUnknowns)
+    return list(sensitivity)
+
+def getAdjointDerivative(fmu, unknowns, knowns, seed):
+    nUnknowns = len(unknowns)
+    nKnowns = len(knowns)
+    nSeed = len(seed)
+    unknowns = (fmi3ValueReference * nUnknowns)(*unknowns)
+    knowns = (fmi3ValueReference * nKnowns)(*knowns)
+    seed = (fmi3Float64 * nSeed)(*seed)
+    sensitivity = (fmi3Float64 * nKnowns)()
+    fmu.fmi3GetAdjointDerivative(fmu.component, unknowns, nUnknowns, knowns, nKnowns, seed, nSeed, sensitivity, nKnowns)
+    return list(sensitivity)
+
+def getOutputDerivatives(fmu, valueReferences, orders):
+    nValueReferences = len(valueReferences)
+    valueReferences = (fmi3ValueReference * nValueReferences)(*valueReferences)
+    orders = (fmi3Int32 * nValueReferences)(*orders)
+    values = (fmi3Float64 * nValueReferences)()
+    fmu.fmi3GetOutputDerivatives(fmu.component, valueReferences, nValueReferences, orders, values, nValueReferences)
+    return list(values)
+
+def getNumberOfVariableDependencies(fmu, valueReference):
+    nDependencies = ctypes.c_size_t()
+    fmu.fmi3GetNumberOfVariableDependencies(fmu.component, valueReference, ctypes.byref(nDependencies))
+    return nDependencies.value
+
+def getVariableDependencies(fmu, dependent, nDependencies):
+    elementIndicesOfDependent = (ctypes.c_size_t * nDependencies)()
+    independents = (fmi3ValueReference * nDependencies)()
+    elementIndicesOfIndependents = (ctypes.c_size_t * nDependencies)()
+    dependencyKinds = (ctypes.c_int * nDependencies)()
+    fmu.fmi3GetVariableDependencies(fmu.component, dependent, elementIndicesOfDependent, independents, elementIndicesOfIndependents, dependencyKinds, nDependencies)
+    return list(independents), list(dependencyKinds)
+
+## Calls the asynchronous stepping functions of the FMI2 shared library
+## directly, as fmpy raises an exception when a function returns fmi2Pending.
+def doStepAsynchronously(fmu, currentCommunicationPoint, communicationStepSize):
+    fmu.dll.fmi2DoStep.argtypes = [fmi2Component, fmi2Real, fmi2Real, fmi2Boolean]
+    fmu.dll.fmi2DoStep.restype = fmi2Status
+    return fmu.dll.fmi2DoStep(fmu.component, currentCommunicationPoint, communicationStepSize, 1)
+
+def getDoStepStatus(fmu):
+    value = fmi2Status()
+    fmu.dll.fmi2GetStatus.argtypes = [fmi2Component, fmi2StatusKind, ctypes.POINTER(fmi2Status)]
+    fmu.dll.fmi2GetStatus.restype = fmi2Status
+    status = fmu.dll.fmi2GetStatus(fmu.component, fmi2DoStepStatus, ctypes.byref(value))
+    return status, value.value
+
+def cancelStep(fmu):
+    fmu.dll.fmi2CancelStep.argtypes = [fmi2Component]
+    fmu.dll.fmi2CancelStep.restype = fmi2Status
+    return fmu.dll.fmi2CancelStep(fmu.component)
+
+## Callback types of FMI3 Scheduled Execution, declared here as fmpy doesn't
+## support instantiating for Scheduled Execution.
+fmi3LogMessageCallback = ctypes.CFUNCTYPE(None, ctypes.c_void_p, ctypes.c_int, ctypes.c_char_p, ctypes.c_char_p)
+fmi3ClockUpdateCallback = ctypes.CFUNCTYPE(None, ctypes.c_void_p)
+fmi3LockPreemptionCallback = ctypes.CFUNCTYPE(None)
+fmi3UnlockPreemptionCallback = ctypes.CFUNCTYPE(None)
+
+def instantiateScheduledExecution(fmu, model_description, logMessage, clockUpdate, lockPreemption, unlockPreemption):
+    fmu.dll.fmi3InstantiateScheduledExecution.argtypes = [
+        ctypes.c_char_p, ctypes.c_char_p, ctypes.c_char_p, ctypes.c_bool, ctypes.c_bool, ctypes.c_void_p,
+        fmi3LogMessageCallback, fmi3ClockUpdateCallback, fmi3LockPreemptionCallback, fmi3UnlockPreemptionCallback
+    ]
+    fmu.dll.fmi3InstantiateScheduledExecution.restype = ctypes.c_void_p
+    resource_path = os.path.join(fmu.unzipDirectory, "resources") + os.path.sep
+    return fmu.dll.fmi3InstantiateScheduledExecution(
+        b"test_instance",
+        model_description.guid.encode("utf-8"),
+        resource_path.encode("utf-8"),
+        False,
+        True,
+        None,
+        logMessage,
+        clockUpdate,
+        lockPreemption,
+        unlockPreemption
+    )
+
+## The intermediate update callback type of FMI3 Co-Simulation, declared here
+## to instantiate with a callback that requests an early return.
+fmi3IntermediateUpdateCallback = ctypes.CFUNCTYPE(
+    None, ctypes.c_void_p, fmi3Float64, ctypes.c_bool, ctypes.c_bool, ctypes.c_bool, ctypes.c_bool,
+    ctypes.POINTER(ctypes.c_bool), ctypes.POINTER(fmi3Float64)
+)
+
+def instantiateCoSimulationWithEarlyReturn(fmu, model_description, logMessage, intermediateUpdate):
+    fmu.dll.fmi3InstantiateCoSimulation.argtypes = [
+        ctypes.c_char_p, ctypes.c_char_p, ctypes.c_char_p, ctypes.c_bool, ctypes.c_bool, ctypes.c_bool, ctypes.c_bool,
+        ctypes.POINTER(fmi3ValueReference), ctypes.c_size_t, ctypes.c_void_p, fmi3LogMessageCallback, fmi3IntermediateUpdateCallback
+    ]
+    fmu.dll.fmi3InstantiateCoSimulation.restype = ctypes.c_void_p
+    resource_path = os.path.join(fmu.unzipDirectory, "resources") + os.path.sep
+    return fmu.dll.fmi3InstantiateCoSimulation(
+        b"test_instance",
+        model_description.guid.encode("utf-8"),
+        resource_path.encode("utf-8"),
+        False,
+        True,
+        False,
+        True,
+        (fmi3ValueReference * 1)(),
+        0,
+        None,
+        logMessage,
+        intermediateUpdate
+    )
+
+def activateModelPartition(fmu, instance, clockReference, activationTime):
+    fmu.dll.fmi3ActivateModelPartition.argtypes = [ctypes.c_void_p, fmi3ValueReference, fmi3Float64]
+    fmu.dll.fmi3ActivateModelPartition.restype = ctypes.c_int
+    return fmu.dll.fmi3ActivateModelPartition(instance, clockReference, activationTime)
+
+def freeInstance(fmu, instance):
+    fmu.dll.fmi3FreeInstance.argtypes = [ctypes.c_void_p]
+    fmu.dll.fmi3FreeInstance.restype = None
+    fmu.dll.fmi3FreeInstance(instance)
+
+## Declares canRunAsynchronuously in the modelDescription.xml of an unzipped
+## FMI2 Co-Simulation FMU.
+def declareCanRunAsynchronously(unzipped_dir):
+    model_description_path = f"{unzipped_dir}/modelDescription.xml"
+    with open(model_description_path, "r") as file:
+        model_description = file.read()
+    model_description = model_description.replace(
+        "<CoSimulation ", "<CoSimulation canRunAsynchronuously=\"true\" ", 1
+    )
+    with open(model_description_path, "w") as file:
+        file.write(model_description)
+
 
 """Tries to extract (AKA unzip) the fmu using the fmpy extract function
 
@@ -167,6 +311,77 @@ def fmi2_instantiate(fmu_filename, is_zipped):
         fmu_class = FMU2Slave,
         is_zipped = is_zipped
     )
+
+"""Tries to instantiate the FMU with a GUID that differs from the one in its
+model description, which should fail.
+
+The FMU should conform to FMI2.
+
+Parameters
+----------
+fmu_filename : str
+    Full filename of the FMU.
+is_zipped : bool
+    If true, fmu_filename points to a zip directory. If false, fmu_filename
+    points to a normal directory.
+"""
+def fmi2_instantiate_wrong_guid(fmu_filename, is_zipped):
+    def inner(fmu):
+        fmu.guid = "00000000-0000-0000-0000-000000000000"
+
+        try:
+            fmu.instantiate()
+        except Exception:
+            return
+
+        fmu.freeInstance()
+        raise AssertionError("Instantiation with a GUID that doesn't match the model description succeeded.")
+
+    uninstantiating_test(
+        caller = "fmi2_instantiate_wrong_guid",
+        inner_function = inner,
+        fmu_filename = fmu_filename,
+        fmu_class = FMU2Slave,
+        is_zipped = is_zipped
+    )
+
+"""Tries to instantiate the FMU while its model description is missing, which
+should fail as the GUID can't be verified.
+
+The FMU should conform to FMI2.
+
+Parameters
+----------
+fmu_filename : str
+    Full filename of the FMU.
+is_zipped : bool
+    If true, fmu_filename points to a zip directory. If false, fmu_filename
+    points to a normal directory.
+"""
+def fmi2_instantiate_missing_model_description(fmu_filename, is_zipped):
+    def inner(fmu):
+        model_description_path = os.path.join(fmu.unzipDirectory, "modelDescription.xml")
+        moved_path = model_description_path + ".moved"
+        os.rename(model_description_path, moved_path)
+
+        try:
+            try:
+                fmu.instantiate()
+            except Exception:
+                return
+
+            fmu.freeInstance()
+            raise AssertionError("Instantiation without a model description succeeded.")
+        finally:
+            os.rename(moved_path, model_description_path)
+
+    uninstantiating_test(
+        caller = "fmi2_instantiate_missing_model_description",
+        inner_function = inner,
+        fmu_filename = fmu_filename,
+        fmu_class = FMU2Slave,
+        is_zipped = is_zipped
+    )
     
 """Tries to do a full simulation using the FMU.
 Will do a simple simulation where the FMU is set up, evolved, rerolled and reset.
@@ -300,6 +515,189 @@ def fmi2_simulate(fmu_filename, is_zipped):
         is_zipped = is_zipped
     )
 
+"""Interpolates the inputs of the FMU over a step using input derivatives
+and checks the resulting outputs and output derivatives.
+
+The FMU should conform to FMI2 and be a Co-Simulation FMU.
+
+Parameters
+----------
+fmu_filename : str
+    Full filename of the FMU.
+is_zipped : bool
+    If true, fmu_filename points to a zip directory. If false, fmu_filename
+    points to a normal directory.
+"""
+def fmi2_input_output_derivatives(fmu_filename, is_zipped):
+    def inner(fmu, model_description):
+        vrs = {}
+        for variable in model_description.modelVariables:
+            vrs[variable.name] = variable.valueReference
+
+        fmu.setupExperiment(startTime=0.0)
+        fmu.enterInitializationMode()
+        fmu.exitInitializationMode()
+
+        fmu.setReal([vrs["real_a"], vrs["real_b"]], [1.0, 2.0])
+        fmu.setRealInputDerivatives([vrs["real_a"], vrs["real_b"]], [1, 1], [1.0, 0.5])
+        fmu.doStep(currentCommunicationPoint=0.0, communicationStepSize=2.0)
+
+        reals = fmu.getReal([vrs["real_a"], vrs["real_b"], vrs["real_c"]])
+        assert reals == [3.0, 3.0, 6.0], f"Values after interpolated step were {reals}, should have been [3.0, 3.0, 6.0]."
+
+        derivatives = fmu.getRealOutputDerivatives([vrs["real_c"]], [1])
+        assert derivatives == [1.5], f"Output derivatives were {derivatives}, should have been [1.5]."
+
+    instantiating_test(
+        caller = "fmi2_input_output_derivatives",
+        inner_function = inner,
+        fmu_filename = fmu_filename,
+        fmu_class = FMU2Slave,
+        is_zipped = is_zipped
+    )
+
+"""Steps an FMU that declares canRunAsynchronuously, awaiting the completion
+of a pending step through fmi2GetStatus and cancelling another.
+
+The FMU should conform to FMI2 and be a Co-Simulation FMU. The
+modelDescription.xml of the FMU is altered to declare the capability.
+
+Parameters
+----------
+fmu_filename : str
+    Full filename of the FMU.
+is_zipped : bool
+    If true, fmu_filename points to a zip directory. If false, fmu_filename
+    points to a normal directory.
+"""
+def fmi2_asynchronous_step(fmu_filename, is_zipped):
+    def inner(fmu, model_description):
+        vrs = {}
+        for variable in model_description.modelVariables:
+            vrs[variable.name] = variable.valueReference
+
+        fmu.setupExperiment(startTime=0.0)
+        fmu.enterInitializationMode()
+        fmu.exitInitializationMode()
+
+        fmu.setReal([vrs["real_a"], vrs["real_b"]], [1.0, 2.0])
+
+        status = doStepAsynchronously(fmu, 0.0, 1.0)
+        assert status == fmi2Pending, f"fmi2DoStep returned {status}, should have returned fmi2Pending."
+
+        deadline = time.time() + 10.0
+        status, dostep_status = getDoStepStatus(fmu)
+        while status == fmi2OK and dostep_status == fmi2Pending and time.time() < deadline:
+            time.sleep(0.01)
+            status, dostep_status = getDoStepStatus(fmu)
+
+        assert status == fmi2OK, f"fmi2GetStatus returned {status}, should have returned fmi2OK."
+        assert dostep_status == fmi2OK, f"The asynchronous step finished with status {dostep_status}, should have been fmi2OK."
+
+        last_successful_time = fmu.getRealStatus(fmi2LastSuccessfulTime)
+        assert last_successful_time == 1.0, f"Last successful time was {last_successful_time}, should have been 1.0."
+
+        real_c = fmu.getReal([vrs["real_c"]])
+        assert real_c == [3.0], f"real_c was {real_c} after the asynchronous step, should have been [3.0]."
+
+        status = doStepAsynchronously(fmu, 1.0, 1.0)
+        assert status == fmi2Pending, f"fmi2DoStep returned {status}, should have returned fmi2Pending."
+
+        status = cancelStep(fmu)
+        assert status == fmi2OK, f"fmi2CancelStep returned {status}, should have returned fmi2OK."
+
+    if is_zipped:
+        try:
+            fmu_filename = extract(fmu_filename)
+        except Exception as e:
+            fail_print("fmi2_asynchronous_step", e, "zip extraction")
+            return
+
+    declareCanRunAsynchronously(fmu_filename)
+
+    instantiating_test(
+        caller = "fmi2_asynchronous_step",
+        inner_function = inner,
+        fmu_filename = fmu_filename,
+        fmu_class = FMU2Slave,
+        is_zipped = False
+    )
+
+    if is_zipped:
+        rmtree(fmu_filename, ignore_errors=True)
+
+"""Steps an FMU that declares canRunAsynchronuously with the stepFinished
+callback, starting each step as soon as the previous one is announced as
+finished without asking for its status through fmi2GetStatus.
+
+The FMU should conform to FMI2 and be a Co-Simulation FMU. The
+modelDescription.xml of the FMU is altered to declare the capability.
+
+Parameters
+----------
+fmu_filename : str
+    Full filename of the FMU.
+is_zipped : bool
+    If true, fmu_filename points to a zip directory. If false, fmu_filename
+    points to a normal directory.
+"""
+def fmi2_asynchronous_step_finished(fmu_filename, is_zipped):
+    finished_statuses = []
+    step_finished = threading.Event()
+
+    def on_step_finished(component_environment, status):
+        finished_statuses.append(status)
+        step_finished.set()
+
+    callbacks = fmi2CallbackFunctions()
+    callbacks.logger = fmi2CallbackLoggerTYPE(printLogMessage)
+    callbacks.stepFinished = fmi2StepFinishedTYPE(on_step_finished)
+
+    def inner(fmu, model_description):
+        vrs = {}
+        for variable in model_description.modelVariables:
+            vrs[variable.name] = variable.valueReference
+
+        fmu.setupExperiment(startTime=0.0)
+        fmu.enterInitializationMode()
+        fmu.exitInitializationMode()
+
+        fmu.setReal([vrs["real_a"], vrs["real_b"]], [1.0, 2.0])
+
+        for step in range(3):
+            step_finished.clear()
+
+            status = doStepAsynchronously(fmu, float(step), 1.0)
+            assert status == fmi2Pending, f"fmi2DoStep of step {step} returned {status}, should have returned fmi2Pending."
+
+            assert step_finished.wait(10.0), f"stepFinished wasn't called for step {step}."
+
+        assert finished_statuses == [fmi2OK] * 3, f"stepFinished was called with {finished_statuses}, should have been called with fmi2OK for each step."
+
+        last_successful_time = fmu.getRealStatus(fmi2LastSuccessfulTime)
+        assert last_successful_time == 3.0, f"Last successful time was {last_successful_time}, should have been 3.0."
+
+    if is_zipped:
+        try:
+            fmu_filename = extract(fmu_filename)
+        except Exception as e:
+            fail_print("fmi2_asynchronous_step_finished", e, "zip extraction")
+            return
+
+    declareCanRunAsynchronously(fmu_filename)
+
+    instantiating_test(
+        caller = "fmi2_asynchronous_step_finished",
+        inner_function = inner,
+        fmu_filename = fmu_filename,
+        fmu_class = FMU2Slave,
+        is_zipped = False,
+        callbacks = callbacks
+    )
+
+    if is_zipped:
+        rmtree(fmu_filename, ignore_errors=True)
+
 """Tries to instantiate multiple FMUs at once
 
 The FMU should conform to FMI2.
@@ -418,6 +816,77 @@ def fmi3_instantiate(fmu_filename, is_zipped):
         is_zipped = is_zipped
     )
 
+"""Tries to instantiate the FMU with an instantiation token that differs from
+the one in its model description, which should fail.
+
+The FMU should conform to FMI3.
+
+Parameters
+----------
+fmu_filename : str
+    Full filename of the FMU.
+is_zipped : bool
+    If true, fmu_filename points to a zip directory. If false, fmu_filename
+    points to a normal directory.
+"""
+def fmi3_instantiate_wrong_instantiation_token(fmu_filename, is_zipped):
+    def inner(fmu):
+        fmu.guid = "00000000-0000-0000-0000-000000000000"
+
+        try:
+            fmu.instantiate()
+        except Exception:
+            return
+
+        fmu.freeInstance()
+        raise AssertionError("Instantiation with an instantiation token that doesn't match the model description succeeded.")
+
+    uninstantiating_test(
+        caller = "fmi3_instantiate_wrong_instantiation_token",
+        inner_function = inner,
+        fmu_filename = fmu_filename,
+        fmu_class = FMU3Slave,
+        is_zipped = is_zipped
+    )
+
+"""Tries to instantiate the FMU while its model description is missing, which
+should fail as the instantiation token can't be verified.
+
+The FMU should conform to FMI3.
+
+Parameters
+----------
+fmu_filename : str
+    Full filename of the FMU.
+is_zipped : bool
+    If true, fmu_filename points to a zip directory. If false, fmu_filename
+    points to a normal directory.
+"""
+def fmi3_instantiate_missing_model_description(fmu_filename, is_zipped):
+    def inner(fmu):
+        model_description_path = os.path.join(fmu.unzipDirectory, "modelDescription.xml")
+        moved_path = model_description_path + ".moved"
+        os.rename(model_description_path, moved_path)
+
+        try:
+            try:
+                fmu.instantiate()
+            except Exception:
+                return
+
+            fmu.freeInstance()
+            raise AssertionError("Instantiation without a model description succeeded.")
+        finally:
+            os.rename(moved_path, model_description_path)
+
+    uninstantiating_test(
+        caller = "fmi3_instantiate_missing_model_description",
+        inner_function = inner,
+        fmu_filename = fmu_filename,
+        fmu_class = FMU3Slave,
+        is_zipped = is_zipped
+    )
+
 """Tries to do a full simulation using the FMU.
 
 The FMU should conform to FMI3.
@@ -976,6 +1445,775 @@ def fmi3_matrix_operations(fmu_filename, is_zipped):
         is_zipped = is_zipped
     )
 
+"""Gets directional and adjoint derivatives of the continuous outputs of the
+FMU with respect to its continuous inputs.
+
+The FMU should conform to FMI3, where `float64_c = float64_a + float64_b` and
+`float32_c = float32_a + float32_b`.
+
+Parameters
+----------
+fmu_filename : str
+    Full filename of the FMU.
+is_zipped : bool
+    If true, fmu_filename points to a zip directory. If false, fmu_filename
+    points to a normal directory.
+"""
+def fmi3_derivatives(fmu_filename, is_zipped):
+    def inner(fmu, model_description):
+        vrs = {}
+        for variable in model_description.modelVariables:
+            vrs[variable.name] = variable.valueReference
+
+        fmu.enterInitializationMode()
+        fmu.exitInitializationMode()
+
+        unknowns = [vrs["float32_c"], vrs["float64_c"]]
+        knowns = [vrs["float32_a"], vrs["float64_a"], vrs["float64_b"]]
+
+        sensitivity = getDirectionalDerivative(fmu, unknowns, knowns, [1.0, 2.0, 3.0])
+        assert sensitivity == [1.0, 5.0], f"Directional derivative was {sensitivity}, should have been [1.0, 5.0]."
+
+        sensitivity = getAdjointDerivative(fmu, unknowns, knowns, [1.0, 2.0])
+        assert sensitivity == [1.0, 2.0, 2.0], f"Adjoint derivative was {sensitivity}, should have been [1.0, 2.0, 2.0]."
+
+    instantiating_test(
+        caller = "fmi3_derivatives",
+        inner_function = inner,
+        fmu_filename = fmu_filename,
+        fmu_class = FMU3Slave,
+        is_zipped = is_zipped
+    )
+
+"""Gets the output derivatives of the continuous outputs of the FMU after a
+step.
+
+The FMU should conform to FMI3, where the continuous outputs only depend on
+the inputs, making their derivatives zero.
+
+Parameters
+----------
+fmu_filename : str
+    Full filename of the FMU.
+is_zipped : bool
+    If true, fmu_filename points to a zip directory. If false, fmu_filename
+    points to a normal directory.
+"""
+def fmi3_output_derivatives(fmu_filename, is_zipped):
+    def inner(fmu, model_description):
+        vrs = {}
+        for variable in model_description.modelVariables:
+            vrs[variable.name] = variable.valueReference
+
+        fmu.enterInitializationMode()
+        fmu.exitInitializationMode()
+
+        fmu.setFloat64([vrs["float64_a"], vrs["float64_b"]], [1.0, 2.0])
+        fmu.doStep(0.0, 1e-2)
+
+        values = getOutputDerivatives(
+            fmu,
+            [vrs["float32_c"], vrs["float64_c"]],
+            [1, 2]
+        )
+        assert values == [0.0, 0.0], f"Output derivatives were {values}, should have been [0.0, 0.0]."
+
+    instantiating_test(
+        caller = "fmi3_output_derivatives",
+        inner_function = inner,
+        fmu_filename = fmu_filename,
+        fmu_class = FMU3Slave,
+        is_zipped = is_zipped
+    )
+
+"""Drives an event iteration at a communication point, checking that
+fmi3EvaluateDiscreteStates doesn't advance the discrete states and that
+fmi3UpdateDiscreteStates reports when the iteration has converged.
+
+The FMU should conform to FMI3 and be a Co-Simulation FMU.
+
+Parameters
+----------
+fmu_filename : str
+    Full filename of the FMU.
+is_zipped : bool
+    If true, fmu_filename points to a zip directory. If false, fmu_filename
+    points to a normal directory.
+"""
+def fmi3_event_iteration(fmu_filename, is_zipped):
+    def inner(fmu, model_description):
+        vrs = {}
+        for variable in model_description.modelVariables:
+            vrs[variable.name] = variable.valueReference
+
+        fmu.enterInitializationMode()
+        fmu.exitInitializationMode()
+        fmu.doStep(0.0, 1e-2)
+
+        fmu.enterEventMode()
+        fmu.setInt32([vrs["clocked_variable_a"], vrs["clocked_variable_b"]], [1, 2])
+
+        for _ in range(2):
+            fmu.fmi3EvaluateDiscreteStates(fmu.component)
+            clocked_variable_c = fmu.getInt32([vrs["clocked_variable_c"]])[0]
+            assert clocked_variable_c == 3, f"clocked_variable_c was {clocked_variable_c} after evaluating discrete states, should have been 3."
+
+        fmu.setInt32([vrs["clocked_variable_a"]], [2])
+
+        discrete_states_need_update, *_ = fmu.updateDiscreteStates()
+        assert discrete_states_need_update, "Discrete states didn't need an update after the clocked variable changed."
+
+        discrete_states_need_update, *_ = fmu.updateDiscreteStates()
+        assert not discrete_states_need_update, "Discrete states still needed an update after an iteration left them unchanged."
+
+        clocked_variable_c = fmu.getInt32([vrs["clocked_variable_c"]])[0]
+        assert clocked_variable_c == 4, f"clocked_variable_c was {clocked_variable_c} after the event iteration, should have been 4."
+
+        fmu.enterStepMode()
+        fmu.doStep(1e-2, 1e-2)
+
+    instantiating_test(
+        caller = "fmi3_event_iteration",
+        inner_function = inner,
+        fmu_filename = fmu_filename,
+        fmu_class = FMU3Slave,
+        is_zipped = is_zipped
+    )
+
+"""Checks that the variable dependencies reported by the FMU match the
+ModelStructure of the FMU's modelDescription.xml.
+
+The FMU should conform to FMI3 and be a Co-Simulation FMU.
+
+Parameters
+----------
+fmu_filename : str
+    Full filename of the FMU.
+is_zipped : bool
+    If true, fmu_filename points to a zip directory. If false, fmu_filename
+    points to a normal directory.
+"""
+def fmi3_variable_dependencies(fmu_filename, is_zipped):
+    def inner(fmu, model_description):
+        vrs = {}
+        for variable in model_description.modelVariables:
+            vrs[variable.name] = variable.valueReference
+
+        nDependencies = getNumberOfVariableDependencies(fmu, vrs["float64_c"])
+        assert nDependencies == 2, f"float64_c had {nDependencies} dependencies, should have had 2."
+
+        independents, kinds = getVariableDependencies(fmu, vrs["float64_c"], nDependencies)
+        expected = [vrs["float64_a"], vrs["float64_b"]]
+        assert independents == expected, f"float64_c depended on {independents}, should have depended on {expected}."
+        # 5 is fmi3Dependent, the default when no dependenciesKind is given.
+        assert kinds == [5, 5], f"Dependency kinds were {kinds}, should have been [5, 5]."
+
+    instantiating_test(
+        caller = "fmi3_variable_dependencies",
+        inner_function = inner,
+        fmu_filename = fmu_filename,
+        fmu_class = FMU3Slave,
+        is_zipped = is_zipped
+    )
+
+"""Instantiates the FMU with an intermediate update callback that requests an
+early return, checking that fmi3DoStep returns early at the time relayed by
+the callback.
+
+The FMU should conform to FMI3.
+
+Parameters
+----------
+fmu_filename : str
+    Full filename of the FMU.
+is_zipped : bool
+    If true, fmu_filename points to a zip directory. If false, fmu_filename
+    points to a normal directory.
+"""
+def fmi3_intermediate_update_early_return(fmu_filename, is_zipped):
+    def inner(fmu):
+        model_description = read_model_description(fmu.unzipDirectory)
+
+        updates = []
+
+        def intermediate_update(
+            env,
+            intermediate_update_time,
+            intermediate_variable_set_requested,
+            intermediate_variable_get_allowed,
+            intermediate_step_finished,
+            can_return_early,
+            early_return_requested,
+            early_return_time
+        ):
+            updates.append((intermediate_update_time, can_return_early))
+            early_return_requested[0] = True
+            early_return_time[0] = intermediate_update_time
+
+        # The callbacks must be kept alive for as long as the instance is.
+        log_message = fmi3LogMessageCallback(lambda env, status, category, message: None)
+        intermediate_update = fmi3IntermediateUpdateCallback(intermediate_update)
+
+        instance = instantiateCoSimulationWithEarlyReturn(
+            fmu, model_description, log_message, intermediate_update
+        )
+        assert instance is not None, "Instantiation with an intermediate update callback failed."
+        fmu.component = instance
+
+        try:
+            fmu.enterInitializationMode()
+            fmu.exitInitializationMode()
+
+            _, _, early_return, last_successful_time = fmu.doStep(0.0, 1.0)
+
+            assert len(updates) == 1, f"The intermediate update callback was called {len(updates)} times during the step, should have been called once."
+            intermediate_update_time, can_return_early = updates[0]
+            assert can_return_early, "The intermediate update didn't allow an early return."
+            assert early_return, "fmi3DoStep didn't report an early return after the importer requested one."
+            assert last_successful_time == intermediate_update_time, f"fmi3DoStep returned early at {last_successful_time}, should have returned at the relayed time {intermediate_update_time}."
+        finally:
+            fmu.freeInstance()
+
+    uninstantiating_test(
+        caller = "fmi3_intermediate_update_early_return",
+        inner_function = inner,
+        fmu_filename = fmu_filename,
+        fmu_class = FMU3Slave,
+        is_zipped = is_zipped
+    )
+
+"""Instantiates the FMU for Scheduled Execution and activates its model
+partitions, checking that the clock update and preemption callbacks reach the
+importer in the order the model calls them.
+
+The FMU should conform to FMI3.
+
+Parameters
+----------
+fmu_filename : str
+    Full filename of the FMU.
+is_zipped : bool
+    If true, fmu_filename points to a zip directory. If false, fmu_filename
+    points to a normal directory.
+"""
+def fmi3_scheduled_execution(fmu_filename, is_zipped):
+    def inner(fmu):
+        model_description = read_model_description(fmu.unzipDirectory)
+        vrs = {}
+        for variable in model_description.modelVariables:
+            vrs[variable.name] = variable.valueReference
+
+        events = []
+
+        # The callbacks must be kept alive for as long as the instance is.
+        log_message = fmi3LogMessageCallback(lambda env, status, category, message: None)
+        clock_update = fmi3ClockUpdateCallback(lambda env: events.append("clock_update"))
+        lock_preemption = fmi3LockPreemptionCallback(lambda: events.append("lock_preemption"))
+        unlock_preemption = fmi3UnlockPreemptionCallback(lambda: events.append("unlock_preemption"))
+
+        instance = instantiateScheduledExecution(
+            fmu, model_description, log_message, fmi3ClockUpdateCallback(), lock_preemption, unlock_preemption
+        )
+        if instance is not None:
+            freeInstance(fmu, instance)
+            raise AssertionError("Instantiation for Scheduled Execution succeeded without a clockUpdate callback.")
+
+        instance = instantiateScheduledExecution(
+            fmu, model_description, log_message, clock_update, lock_preemption, unlock_preemption
+        )
+        assert instance is not None, "Instantiation for Scheduled Execution failed."
+
+        try:
+            status = activateModelPartition(fmu, instance, vrs["clock_a"], 0.0)
+            assert status == 0, f"Activating the partition of clock_a returned status {status}, should have returned 0 (fmi3OK)."
+            assert events == [], f"Activating only the partition of clock_a triggered the callbacks {events}."
+
+            status = activateModelPartition(fmu, instance, vrs["clock_b"], 0.0)
+            assert status == 0, f"Activating the partition of clock_b returned status {status}, should have returned 0 (fmi3OK)."
+            expected = ["lock_preemption", "unlock_preemption", "clock_update"]
+            assert events == expected, f"The callbacks were called in the order {events}, should have been {expected}."
+
+            status = activateModelPartition(fmu, instance, vrs["clock_c"], 0.0)
+            assert status == 3, f"Activating the partition of the output clock clock_c returned status {status}, should have returned 3 (fmi3Error)."
+        finally:
+            freeInstance(fmu, instance)
+
+    uninstantiating_test(
+        caller = "fmi3_scheduled_execution",
+        inner_function = inner,
+        fmu_filename = fmu_filename,
+        fmu_class = FMU3Slave,
+        is_zipped = is_zipped
+    )
+
+"""Simulates the bouncing ball of the Model Exchange template with explicit
+euler, checking that the ball bounces when the event indicator crosses zero.
+
+The FMU should conform to FMI3 and be a Model Exchange FMU.
+
+Parameters
+----------
+fmu_filename : str
+    Full filename of the FMU.
+is_zipped : bool
+    If true, fmu_filename points to a zip directory. If false, fmu_filename
+    points to a normal directory.
+"""
+def fmi3_model_exchange_simulate(fmu_filename, is_zipped):
+    def inner(fmu, model_description):
+        vrs = {}
+        for variable in model_description.modelVariables:
+            vrs[variable.name] = variable.valueReference
+
+        start_time = 0.0
+        stop_time = 2.0
+        step_size = 1e-3
+
+        fmu.enterInitializationMode(startTime=start_time)
+        fmu.exitInitializationMode()
+
+        discrete_states_need_update = True
+        while discrete_states_need_update:
+            (
+                discrete_states_need_update,
+                terminate_simulation,
+                _,
+                _,
+                _,
+                _
+            ) = fmu.updateDiscreteStates()
+
+        fmu.enterContinuousTimeMode()
+
+        n_states = fmu.getNumberOfContinuousStates()
+        n_event_indicators = fmu.getNumberOfEventIndicators()
+
+        assert n_states == 2, f"Number of continuous states was {n_states}, should have been 2."
+        assert n_event_indicators == 1, f"Number of event indicators was {n_event_indicators}, should have been 1."
+
+        states = (fmi3Float64 * n_states)()
+        derivatives = (fmi3Float64 * n_states)()
+        nominals = (fmi3Float64 * n_states)()
+        event_indicators = (fmi3Float64 * n_event_indicators)()
+
+        fmu.getContinuousStates(states, n_states)
+        fmu.getNominalsOfContinuousStates(nominals, n_states)
+
+        assert list(states) == [1.0, 0.0], f"Initial continuous states were {list(states)}, should have been [1.0, 0.0]."
+        assert list(nominals) == [1.0, 1.0], f"Nominals of continuous states were {list(nominals)}, should have been [1.0, 1.0]."
+
+        bounces = 0
+        time = start_time
+
+        while time < stop_time:
+            fmu.getContinuousStateDerivatives(derivatives, n_states)
+
+            time += step_size
+            fmu.setTime(time)
+
+            for i in range(n_states):
+                states[i] += step_size * derivatives[i]
+            fmu.setContinuousStates(states, n_states)
+
+            fmu.getEventIndicators(event_indicators, n_event_indicators)
+
+            enter_event_mode, terminate_simulation = fmu.completedIntegratorStep()
+
+            if event_indicators[0] <= 0.0 or enter_event_mode:
+                fmu.enterEventMode()
+
+                (
+                    _,
+                    _,
+                    _,
+                    values_continuous_states_changed,
+                    _,
+                    _
+                ) = fmu.updateDiscreteStates()
+
+                assert values_continuous_states_changed, "The ball didn't bounce when hitting the ground."
+                bounces += 1
+
+                fmu.enterContinuousTimeMode()
+                fmu.getContinuousStates(states, n_states)
+
+        h, v = fmu.getFloat64([vrs["h"], vrs["v"]])
+
+        assert bounces > 0, "The ball never hit the ground."
+        assert h >= -1e-2, f"The ball fell through the ground, height was {h}."
+
+    instantiating_test(
+        caller = "fmi3_model_exchange_simulate",
+        inner_function = inner,
+        fmu_filename = fmu_filename,
+        fmu_class = FMU3Model,
+        is_zipped = is_zipped
+    )
+
+"""Simulates the bouncing ball of the FMI2 Model Exchange template with
+explicit euler, checking that the ball bounces when the event indicator
+crosses zero.
+
+The FMU should conform to FMI2 and be a Model Exchange FMU.
+
+Parameters
+----------
+fmu_filename : str
+    Full filename of the FMU.
+is_zipped : bool
+    If true, fmu_filename points to a zip directory. If false, fmu_filename
+    points to a normal directory.
+"""
+def fmi2_model_exchange_simulate(fmu_filename, is_zipped):
+    def inner(fmu, model_description):
+        vrs = {}
+        for variable in model_description.modelVariables:
+            vrs[variable.name] = variable.valueReference
+
+        start_time = 0.0
+        stop_time = 2.0
+        step_size = 1e-3
+
+        fmu.setupExperiment(startTime=start_time)
+        fmu.enterInitializationMode()
+        fmu.exitInitializationMode()
+
+        event_info = fmu.newDiscreteStates()
+        while event_info.newDiscreteStatesNeeded:
+            event_info = fmu.newDiscreteStates()
+
+        fmu.enterContinuousTimeMode()
+
+        n_states = model_description.numberOfContinuousStates
+        n_event_indicators = model_description.numberOfEventIndicators
+
+        assert n_states == 2, f"Number of continuous states was {n_states}, should have been 2."
+        assert n_event_indicators == 1, f"Number of event indicators was {n_event_indicators}, should have been 1."
+
+        states = (fmi2Real * n_states)()
+        derivatives = (fmi2Real * n_states)()
+        nominals = (fmi2Real * n_states)()
+        event_indicators = (fmi2Real * n_event_indicators)()
+
+        fmu.getContinuousStates(states, n_states)
+        fmu.getNominalsOfContinuousStates(nominals, n_states)
+
+        assert list(states) == [1.0, 0.0], f"Initial continuous states were {list(states)}, should have been [1.0, 0.0]."
+        assert list(nominals) == [1.0, 1.0], f"Nominals of continuous states were {list(nominals)}, should have been [1.0, 1.0]."
+
+        bounces = 0
+        time = start_time
+
+        while time < stop_time:
+            fmu.getDerivatives(derivatives, n_states)
+
+            time += step_size
+            fmu.setTime(time)
+
+            for i in range(n_states):
+                states[i] += step_size * derivatives[i]
+            fmu.setContinuousStates(states, n_states)
+
+            fmu.getEventIndicators(event_indicators, n_event_indicators)
+
+            enter_event_mode, terminate_simulation = fmu.completedIntegratorStep()
+
+            if event_indicators[0] <= 0.0 or enter_event_mode:
+                fmu.enterEventMode()
+
+                event_info = fmu.newDiscreteStates()
+
+                assert event_info.valuesOfContinuousStatesChanged, "The ball didn't bounce when hitting the ground."
+                bounces += 1
+
+                fmu.enterContinuousTimeMode()
+                fmu.getContinuousStates(states, n_states)
+
+        h, v = fmu.getReal([vrs["h"], vrs["v"]])
+
+        assert bounces > 0, "The ball never hit the ground."
+        assert h >= -1e-2, f"The ball fell through the ground, height was {h}."
+
+    instantiating_test(
+        caller = "fmi2_model_exchange_simulate",
+        inner_function = inner,
+        fmu_filename = fmu_filename,
+        fmu_class = FMU2Model,
+        is_zipped = is_zipped
+    )
+
+"""Checks the start values of an FMU generated from the variable
+specification `cli/tests/variable_specs/fmi2.toml`, and that its variables
+can be set, stepped and restored from a saved state.
+
+The FMU should conform to FMI2.
+
+Parameters
+----------
+fmu_filename : str
+    Full filename of the FMU.
+is_zipped : bool
+    If true, fmu_filename points to a zip directory. If false, fmu_filename
+    points to a normal directory.
+"""
+def fmi2_spec_simulate(fmu_filename, is_zipped):
+    def inner(fmu, model_description):
+        vrs = {}
+        for variable in model_description.modelVariables:
+            vrs[variable.name] = variable.valueReference
+
+        assert fmu.setupExperiment(startTime=0.0) == fmi2OK, f"setupExperiment returned with error, should have been return value: {fmi2OK}"
+        assert fmu.enterInitializationMode() == fmi2OK, f"enterInitializationMode returned with error, should have been return value: {fmi2OK}"
+        assert fmu.exitInitializationMode() == fmi2OK, f"exitInitializationMode returned with error, should have been return value: {fmi2OK}"
+
+        reals = fmu.getReal([vrs["speed"], vrs["distance"]])
+        integers = fmu.getInteger([vrs["count"]])
+        bools = fmu.getBoolean([vrs["enabled"]])
+        strings = [string.decode("utf-8") for string in fmu.getString([vrs["label"]])]
+
+        assert reals == [1.5, 0.0], f"Initially fetched reals were {reals}, should have been [1.5, 0.0]"
+        assert integers == [3], f"Initially fetched integers were {integers}, should have been [3]"
+        assert bools == [True], f"Initially fetched booleans were {bools}, should have been [True]"
+        assert strings == ["hello"], f"Initially fetched strings were {strings}, should have been ['hello']"
+
+        initial_state = fmu.getFMUstate()
+
+        fmu.setReal([vrs["speed"]], [2.5])
+        fmu.setBoolean([vrs["enabled"]], [False])
+        fmu.setString([vrs["label"]], ["world"])
+
+        assert fmu.doStep(0.0, 1e-2) == fmi2OK, f"doStep returned with error, should have been return value: {fmi2OK}"
+
+        reals = fmu.getReal([vrs["speed"]])
+        bools = fmu.getBoolean([vrs["enabled"]])
+        strings = [string.decode("utf-8") for string in fmu.getString([vrs["label"]])]
+
+        assert reals == [2.5], f"Fetched reals after setting were {reals}, should have been [2.5]"
+        assert bools == [False], f"Fetched booleans after setting were {bools}, should have been [False]"
+        assert strings == ["world"], f"Fetched strings after setting were {strings}, should have been ['world']"
+
+        fmu.setFMUstate(initial_state)
+        fmu.freeFMUstate(initial_state)
+
+        reals = fmu.getReal([vrs["speed"]])
+        assert reals == [1.5], f"Fetched reals after restoring state were {reals}, should have been [1.5]"
+
+    instantiating_test(
+        caller = "fmi2_spec_simulate",
+        inner_function = inner,
+        fmu_filename = fmu_filename,
+        fmu_class = FMU2Slave,
+        is_zipped = is_zipped
+    )
+
+"""Checks the start values of an FMU generated from the variable
+specification `cli/tests/variable_specs/fmi3.toml`, and that its variables,
+arrays included, can be set, stepped and restored from a saved state.
+
+The FMU should conform to FMI3.
+
+Parameters
+----------
+fmu_filename : str
+    Full filename of the FMU.
+is_zipped : bool
+    If true, fmu_filename points to a zip directory. If false, fmu_filename
+    points to a normal directory.
+"""
+def fmi3_spec_simulate(fmu_filename, is_zipped):
+    def inner(fmu, model_description):
+        vrs = {}
+        for variable in model_description.modelVariables:
+            vrs[variable.name] = variable.valueReference
+
+        fmu.enterInitializationMode()
+        fmu.exitInitializationMode()
+
+        gain = fmu.getFloat32([vrs["gain"]])
+        offset = fmu.getInt8([vrs["offset"]])
+        counter = fmu.getUInt64([vrs["counter"]])
+        enabled = fmu.getBoolean([vrs["enabled"]])
+        label = fmu.getString([vrs["label"]])
+        payload = fmu.getBinary([vrs["payload"]])
+        matrix = fmu.getFloat64([vrs["matrix"]], 4)
+        size = fmu.getUInt64([vrs["size"]])
+        vector = fmu.getFloat32([vrs["vector"]], 3)
+        interval = getIntervalDecimal(fmu, [vrs["tick"]])
+
+        assert gain == [2.5], f"Initially fetched gain was {gain}, should have been [2.5]."
+        assert offset == [-3], f"Initially fetched offset was {offset}, should have been [-3]."
+        assert counter == [4000000000], f"Initially fetched counter was {counter}, should have been [4000000000]."
+        assert enabled == [True], f"Initially fetched enabled was {enabled}, should have been [True]."
+        assert label == ["hello"], f"Initially fetched label was {label}, should have been [\"hello\"]."
+        assert payload == [bytes([0x0a, 0xff])], f"Initially fetched payload was {payload}, should have been [b'\\x0a\\xff']."
+        assert matrix == [1.0, 2.0, 3.0, 4.0], f"Initially fetched matrix was {matrix}, should have been [1.0, 2.0, 3.0, 4.0]."
+        assert size == [3], f"Initially fetched size was {size}, should have been [3]."
+        assert vector == [0.5, 1.0, 1.5], f"Initially fetched vector was {vector}, should have been [0.5, 1.0, 1.5]."
+        assert interval[0] == [0.5], f"Initially fetched interval of tick was {interval[0]}, should have been [0.5]."
+
+        initial_state = fmu.getFMUState()
+
+        fmu.setFloat64([vrs["matrix"]], [4.0, 3.0, 2.0, 1.0])
+        fmu.setInt8([vrs["offset"]], [7])
+
+        fmu.doStep(0.0, 1e-2)
+
+        matrix = fmu.getFloat64([vrs["matrix"]], 4)
+        offset = fmu.getInt8([vrs["offset"]])
+
+        assert matrix == [4.0, 3.0, 2.0, 1.0], f"Fetched matrix after setting was {matrix}, should have been [4.0, 3.0, 2.0, 1.0]."
+        assert offset == [7], f"Fetched offset after setting was {offset}, should have been [7]."
+
+        fmu.setFMUState(initial_state)
+        fmu.freeFMUState(initial_state)
+
+        matrix = fmu.getFloat64([vrs["matrix"]], 4)
+        assert matrix == [1.0, 2.0, 3.0, 4.0], f"Fetched matrix after restoring state was {matrix}, should have been [1.0, 2.0, 3.0, 4.0]."
+
+    instantiating_test(
+        caller = "fmi3_spec_simulate",
+        inner_function = inner,
+        fmu_filename = fmu_filename,
+        fmu_class = FMU3Slave,
+        is_zipped = is_zipped
+    )
+
+"""Simulates the FMI3 MATLAB or Rust template, which have the scalar
+variables of the common FMI3 template but no binaries, arrays or clocks.
+
+The FMU should conform to FMI3. MATLAB FMUs should be run against the MATLAB
+engine stand-in in `cli/tests/python_tests/matlab_mock`.
+
+Parameters
+----------
+fmu_filename : str
+    Full filename of the FMU.
+is_zipped : bool
+    If true, fmu_filename points to a zip directory. If false, fmu_filename
+    points to a normal directory.
+"""
+def fmi3_scalar_simulate(fmu_filename, is_zipped):
+    def inner(fmu, model_description):
+        vrs = {}
+        for variable in model_description.modelVariables:
+            vrs[variable.name] = variable.valueReference
+
+        fmu.enterInitializationMode()
+        fmu.exitInitializationMode()
+
+        float32 = fmu.getFloat32([vrs["float32_a"], vrs["float32_b"], vrs["float32_c"]])
+        int8 = fmu.getInt8([vrs["int8_a"], vrs["int8_b"], vrs["int8_c"]])
+        uint64 = fmu.getUInt64([vrs["uint64_a"], vrs["uint64_b"], vrs["uint64_c"]])
+        boolean = fmu.getBoolean([vrs["boolean_a"], vrs["boolean_b"], vrs["boolean_c"]])
+        string = fmu.getString([vrs["string_a"], vrs["string_b"], vrs["string_c"]])
+
+        assert float32 == [0.0, 0.0, 0.0], f"Initially fetched float32s were {float32}, should have been [0.0, 0.0, 0.0]."
+        assert int8 == [0, 0, 0], f"Initially fetched int8s were {int8}, should have been [0, 0, 0]."
+        assert uint64 == [0, 0, 0], f"Initially fetched uint64s were {uint64}, should have been [0, 0, 0]."
+        assert boolean == [False, False, False], f"Initially fetched booleans were {boolean}, should have been [False, False, False]."
+        assert string == ["", "", ""], f"Initially fetched strings were {string}, should have been [\"\", \"\", \"\"]."
+
+        fmu.setFloat32([vrs["float32_a"], vrs["float32_b"]], [1.5, 2.5])
+        fmu.setInt8([vrs["int8_a"], vrs["int8_b"]], [-3, 1])
+        fmu.setUInt64([vrs["uint64_a"], vrs["uint64_b"]], [4000000000, 1])
+        fmu.setBoolean([vrs["boolean_a"], vrs["boolean_b"]], [True, False])
+        fmu.setString([vrs["string_a"], vrs["string_b"]], ["foo", "bar"])
+
+        initial_state = fmu.getFMUState()
+
+        fmu.doStep(0.0, 1e-2)
+
+        float32 = fmu.getFloat32([vrs["float32_c"]])
+        int8 = fmu.getInt8([vrs["int8_c"]])
+        uint64 = fmu.getUInt64([vrs["uint64_c"]])
+        boolean = fmu.getBoolean([vrs["boolean_c"]])
+        string = fmu.getString([vrs["string_c"]])
+        time = fmu.getFloat64([vrs["time"]])
+
+        assert float32 == [4.0], f"Fetched float32_c after stepping was {float32}, should have been [4.0]."
+        assert int8 == [-2], f"Fetched int8_c after stepping was {int8}, should have been [-2]."
+        assert uint64 == [4000000001], f"Fetched uint64_c after stepping was {uint64}, should have been [4000000001]."
+        assert boolean == [True], f"Fetched boolean_c after stepping was {boolean}, should have been [True]."
+        assert string == ["foobar"], f"Fetched string_c after stepping was {string}, should have been [\"foobar\"]."
+        assert time == [1e-2], f"Fetched time after stepping was {time}, should have been [0.01]."
+
+        fmu.setFMUState(initial_state)
+        fmu.freeFMUState(initial_state)
+
+        float32 = fmu.getFloat32([vrs["float32_a"], vrs["float32_c"]])
+        time = fmu.getFloat64([vrs["time"]])
+
+        assert float32 == [1.5, 0.0], f"Fetched float32s after restoring state were {float32}, should have been [1.5, 0.0]."
+        assert time == [0.0], f"Fetched time after restoring state was {time}, should have been [0.0]."
+
+        fmu.reset()
+
+        float32 = fmu.getFloat32([vrs["float32_a"]])
+        string = fmu.getString([vrs["string_a"]])
+
+        assert float32 == [0.0], f"Fetched float32_a after reset was {float32}, should have been [0.0]."
+        assert string == [""], f"Fetched string_a after reset was {string}, should have been [\"\"]."
+
+    instantiating_test(
+        caller = "fmi3_scalar_simulate",
+        inner_function = inner,
+        fmu_filename = fmu_filename,
+        fmu_class = FMU3Slave,
+        is_zipped = is_zipped
+    )
+
+"""Times bulk exchanges of Float64 values with the FMU, and checks that the
+values got back are those that were set.
+
+Prints the mean time of a fmi3SetFloat64 and fmi3GetFloat64 of many values,
+for comparing the exchange of values through the socket with the exchange
+through shared memory.
+
+The FMU should conform to FMI3.
+
+Parameters
+----------
+fmu_filename : str
+    Full filename of the FMU.
+is_zipped : bool
+    If true, fmu_filename points to a zip directory. If false, fmu_filename
+    points to a normal directory.
+"""
+def fmi3_float64_bulk_benchmark(fmu_filename, is_zipped):
+    def inner(fmu, model_description):
+        vrs = {}
+        for variable in model_description.modelVariables:
+            vrs[variable.name] = variable.valueReference
+
+        fmu.enterInitializationMode()
+        fmu.exitInitializationMode()
+
+        n_values = 10000
+        rounds = 50
+
+        # The FMU has few Float64 inputs, so they are repeated to make up the
+        # bulk of values.
+        references = [vrs["float64_a"], vrs["float64_b"]] * (n_values // 2)
+        values = [1.25, -2.5] * (n_values // 2)
+
+        start = time.perf_counter()
+        for _ in range(rounds):
+            fmu.setFloat64(references, values)
+            float64 = fmu.getFloat64(references)
+        elapsed = time.perf_counter() - start
+
+        assert float64 == values, f"Fetched float64s started with {float64[:2]}, should have started with {values[:2]}."
+
+        print(f"fmi3_float64_bulk_benchmark: {elapsed / rounds * 1000:.2f} ms per set and get of {n_values} values")
+
+    instantiating_test(
+        caller = "fmi3_float64_bulk_benchmark",
+        inner_function = inner,
+        fmu_filename = fmu_filename,
+        fmu_class = FMU3Slave,
+        is_zipped = is_zipped
+    )
+
 if __name__ == "__main__":
     import sys
 
@@ -0,0 +1,92 @@
+"""Stand-in for the `matlab` package of the MATLAB Engine API for Python.
+
+Only the parts used by the MATLAB backend of UniFMU are provided: the typed
+arrays, which hold a single row like the arrays the engine creates from a
+flat list, and the `engine` module.
+"""
+
+import struct
+
+
+class _Array:
+    _convert = None
+
+    def __init__(self, initializer=None, size=None, is_complex=False):
+        self._data = [self._convert(value) for value in (initializer or [])]
+
+    @property
+    def size(self):
+        return (1, len(self._data))
+
+    def __len__(self):
+        return 1
+
+    def __getitem__(self, index):
+        if index != 0:
+            raise IndexError(index)
+        return list(self._data)
+
+    def __iter__(self):
+        return iter([list(self._data)])
+
+    def __repr__(self):
+        return f"matlab.{type(self).__name__}({self._data})"
+
+
+def _integer(bits, signed):
+    low, high = (-2 ** (bits - 1), 2 ** (bits - 1) - 1) if signed else (0, 2 ** bits - 1)
+
+    def convert(value):
+        if isinstance(value, float) or not low <= int(value) <= high:
+            raise ValueError(f"{value} doesn't fit in a {'' if signed else 'u'}int{bits}")
+        return int(value)
+
+    return staticmethod(convert)
+
+
+def _single(value):
+    return struct.unpack("f", struct.pack("f", float(value)))[0]
+
+
+class double(_Array):
+    _convert = staticmethod(float)
+
+
+class single(_Array):
+    _convert = staticmethod(_single)
+
+
+class int8(_Array):
+    _convert = _integer(8, True)
+
+
+class uint8(_Array):
+    _convert = _integer(8, False)
+
+
+class int16(_Array):
+    _convert = _integer(16, True)
+
+
+class uint16(_Array):
+    _convert = _integer(16, False)
+
+
+class int32(_Array):
+    _convert = _integer(32, True)
+
+
+class uint32(_Array):
+    _convert = _integer(32, False)
+
+
+class int64(_Array):
+    _convert = _integer(64, True)
+
+
+class uint64(_Array):
+    _convert = _integer(64, False)
+
+
+class logical(_Array):
+    _convert = staticmethod(bool)
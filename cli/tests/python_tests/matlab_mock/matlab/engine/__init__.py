@@ -0,0 +1,361 @@
+"""Stand-in for the `matlab.engine` module of the MATLAB Engine API for Python.
+
+The engine can't run MATLAB code, so instead of evaluating the `Model.m` of
+the FMU it creates a Python copy of the `Model` class of the MATLAB templates
+of UniFMU, which behaves like the MATLAB code for the calls made by the
+backend. Arguments and results are converted like the real engine does: a 1x1
+array is returned as a Python scalar, a cell array as a list, and any other
+array as a MATLAB array. Calls that MATLAB would reject, like indexing with a
+cell array or calling a method that doesn't exist, raise
+`MatlabExecutionError`.
+"""
+
+import os
+import pickle
+import re
+
+import matlab
+
+
+class MatlabExecutionError(Exception):
+    pass
+
+
+def start_matlab(option="-nodesktop", background=False):
+    return MatlabEngine()
+
+
+class MatlabObject:
+    """Handle of a MATLAB object held by the engine."""
+
+    def __init__(self, model):
+        self._model = model
+
+
+class MatlabEngine:
+    def __init__(self):
+        self._directory = os.getcwd()
+
+    def cd(self, directory, nargout=1):
+        if not os.path.isdir(directory):
+            raise MatlabExecutionError(f"Cannot CD to {directory} (Name is nonexistent or not a directory).")
+
+        self._directory = directory
+
+        return _outputs((), nargout)
+
+    def quit(self):
+        pass
+
+    def Model(self, *args, nargout=1):
+        model_file = os.path.join(self._directory, "Model.m")
+        if not os.path.isfile(model_file):
+            raise MatlabExecutionError("Unrecognized function or variable 'Model'.")
+
+        with open(model_file) as f:
+            is_fmi3 = "fmi3DoStep" in f.read()
+
+        model_class = Fmi3Model if is_fmi3 else Fmi2Model
+        if len(args) != model_class.ARGUMENTS:
+            raise MatlabExecutionError("Too many input arguments." if len(args) > model_class.ARGUMENTS else "Not enough input arguments.")
+
+        return _outputs((MatlabObject(model_class(*args)),), nargout)
+
+    def __getattr__(self, name):
+        def call(model, *args, nargout=1):
+            if not isinstance(model, MatlabObject):
+                raise MatlabExecutionError(f"Undefined function '{name}' for input arguments of type '{type(model).__name__}'.")
+
+            method = getattr(model._model, name, None)
+            if method is None:
+                raise MatlabExecutionError(f"Undefined function '{name}' for input arguments of type 'Model'.")
+
+            return _outputs(method(*args), nargout)
+
+        return call
+
+
+def _outputs(outputs, nargout):
+    if nargout > len(outputs):
+        raise MatlabExecutionError("Too many output arguments.")
+
+    outputs = [_to_python(output) for output in outputs[:nargout]]
+
+    if nargout == 0:
+        return None
+    if nargout == 1:
+        return outputs[0]
+
+    return tuple(outputs)
+
+
+def _to_python(value):
+    if isinstance(value, list):
+        return [_to_python(v) for v in value]
+    if isinstance(value, matlab._Array) and value.size == (1, 1):
+        return value[0][0]
+
+    return value
+
+
+def _status(status=0):
+    return matlab.int32([status])
+
+
+class _Model:
+    """Python copy of the `Model` class of a MATLAB template.
+
+    The values of the variables are kept in the MATLAB array type of the
+    variable, or as a character vector for strings.
+    """
+
+    # Value reference, name and MATLAB type of each variable
+    VARIABLES = []
+    # The MATLAB type of each FMI type
+    TYPES = {}
+
+    def __init__(self):
+        self.types = {name: array_type for _, name, array_type in self.VARIABLES}
+        self.reference_to_attribute = {reference: name for reference, name, _ in self.VARIABLES}
+        self.reset()
+
+    def reset(self):
+        self.values = {
+            name: "" if array_type is str else array_type([0])[0][0]
+            for name, array_type in self.types.items()
+        }
+        self.update_outputs()
+
+    def update_outputs(self):
+        for name, array_type in self.types.items():
+            if name.endswith("_c"):
+                a, b = self.values[name[:-1] + "a"], self.values[name[:-1] + "b"]
+                if array_type is matlab.logical:
+                    self.values[name] = a or b
+                else:
+                    self.values[name] = array_type([a + b])[0][0] if array_type is not str else a + b
+
+    def __getattr__(self, name):
+        match = re.fullmatch(r"fmi[23](Get|Set)(\w+)", name)
+        if match is None or match.group(2) not in self.TYPES:
+            raise AttributeError(name)
+
+        array_type = self.TYPES[match.group(2)]
+        if match.group(1) == "Get":
+            return lambda references: self.get_values(references, array_type)
+
+        return lambda references, values: self.set_values(references, values, array_type)
+
+    def get_values(self, references, array_type):
+        names = [self.name(reference) for reference in _indices(references)]
+
+        if array_type is str:
+            return _status(), [self.values[name] for name in names]
+
+        return _status(), array_type([self.values[name] for name in names])
+
+    def set_values(self, references, values, array_type):
+        if array_type is str:
+            if not isinstance(values, list):
+                raise MatlabExecutionError("Brace indexing is not supported for variables of this type.")
+            values = list(values)
+        else:
+            if type(values) is not array_type:
+                raise MatlabExecutionError(f"Expected values of type '{array_type.__name__}', got {values!r}.")
+            values = values[0]
+
+        for reference, value in zip(_indices(references), values):
+            self.values[self.name(reference)] = value
+
+        return (_status(),)
+
+    def name(self, reference):
+        if reference not in self.reference_to_attribute:
+            raise MatlabExecutionError("Index exceeds the number of array elements.")
+
+        return self.reference_to_attribute[reference]
+
+    def serialize(self, extra=()):
+        return _status(), matlab.uint8(list(pickle.dumps((self.values, *extra))))
+
+    def deserialize(self, state):
+        if not isinstance(state, matlab.uint8):
+            raise MatlabExecutionError("The input must be a uint8 array.")
+
+        return pickle.loads(bytes(state[0]))
+
+
+def _indices(references):
+    # Indexing a cell array with a cell array fails in MATLAB, so the value
+    # references must be passed as a numeric array.
+    if not isinstance(references, matlab.int64):
+        raise MatlabExecutionError("Array indices must be positive integers or logical values.")
+
+    return references[0]
+
+
+class Fmi2Model(_Model):
+    ARGUMENTS = 0
+
+    VARIABLES = [
+        (0, "real_a", matlab.double),
+        (1, "real_b", matlab.double),
+        (2, "real_c", matlab.double),
+        (3, "integer_a", matlab.int32),
+        (4, "integer_b", matlab.int32),
+        (5, "integer_c", matlab.int32),
+        (6, "boolean_a", matlab.logical),
+        (7, "boolean_b", matlab.logical),
+        (8, "boolean_c", matlab.logical),
+        (9, "string_a", str),
+        (10, "string_b", str),
+        (11, "string_c", str),
+    ]
+
+    TYPES = {
+        "Real": matlab.double,
+        "Integer": matlab.int32,
+        "Boolean": matlab.logical,
+        "String": str,
+    }
+
+    def fmi2DoStep(self, current_time, step_size, no_set_fmu_state_prior_to_current_point):
+        self.update_outputs()
+        return (_status(),)
+
+    def fmi2SetDebugLogging(self, categories, logging_on):
+        return (_status(),)
+
+    def fmi2SetupExperiment(self, start_time, stop_time, tolerance):
+        return (_status(),)
+
+    def fmi2EnterInitializationMode(self):
+        return (_status(),)
+
+    def fmi2ExitInitializationMode(self):
+        self.update_outputs()
+        return (_status(),)
+
+    def fmi2Terminate(self):
+        return (_status(),)
+
+    def fmi2Reset(self):
+        self.reset()
+        return (_status(),)
+
+    def fmi2SerializeFmuState(self):
+        return self.serialize()
+
+    def fmi2DeserializeFmuState(self, state):
+        (self.values,) = self.deserialize(state)
+        return (_status(),)
+
+
+class Fmi3Model(_Model):
+    ARGUMENTS = 6
+
+    TIME_REFERENCE = 999
+
+    VARIABLES = [
+        (reference, f"{prefix}_{suffix}", array_type)
+        for index, (prefix, array_type) in enumerate([
+            ("float32", matlab.single),
+            ("float64", matlab.double),
+            ("int8", matlab.int8),
+            ("uint8", matlab.uint8),
+            ("int16", matlab.int16),
+            ("uint16", matlab.uint16),
+            ("int32", matlab.int32),
+            ("uint32", matlab.uint32),
+            ("int64", matlab.int64),
+            ("uint64", matlab.uint64),
+            ("boolean", matlab.logical),
+            ("string", str),
+        ])
+        for reference, suffix in zip(range(3 * index, 3 * index + 3), "abc")
+    ]
+
+    TYPES = {
+        "Float32": matlab.single,
+        "Float64": matlab.double,
+        "Int8": matlab.int8,
+        "UInt8": matlab.uint8,
+        "Int16": matlab.int16,
+        "UInt16": matlab.uint16,
+        "Int32": matlab.int32,
+        "UInt32": matlab.uint32,
+        "Int64": matlab.int64,
+        "UInt64": matlab.uint64,
+        "Boolean": matlab.logical,
+        "String": str,
+    }
+
+    def __init__(self, instance_name, instantiation_token, resource_path, visible, logging_on, event_mode_used):
+        self.time = 0.0
+        super().__init__()
+
+    def reset(self):
+        self.time = 0.0
+        super().reset()
+
+    def name(self, reference):
+        if reference == self.TIME_REFERENCE:
+            return "time"
+
+        return super().name(reference)
+
+    def get_values(self, references, array_type):
+        self.values["time"] = self.time
+        return super().get_values(references, array_type)
+
+    def fmi3DoStep(self, current_communication_point, communication_step_size, no_set_fmu_state_prior_to_current_point):
+        self.update_outputs()
+        self.time = current_communication_point + communication_step_size
+        return (
+            _status(),
+            matlab.logical([False]),
+            matlab.logical([False]),
+            matlab.logical([False]),
+            matlab.double([self.time]),
+        )
+
+    def fmi3EnterInitializationMode(self, tolerance_defined, tolerance, start_time, stop_time_defined, stop_time):
+        self.time = start_time
+        return (_status(),)
+
+    def fmi3ExitInitializationMode(self):
+        self.update_outputs()
+        return (_status(),)
+
+    def fmi3EnterEventMode(self):
+        return (_status(),)
+
+    def fmi3EnterStepMode(self):
+        return (_status(),)
+
+    def fmi3UpdateDiscreteStates(self):
+        self.update_outputs()
+        return (_status(), *[matlab.logical([False])] * 5, matlab.double([0.0]))
+
+    def fmi3SetDebugLogging(self, categories, logging_on):
+        return (_status(),)
+
+    def fmi3Terminate(self):
+        return (_status(),)
+
+    def fmi3Reset(self):
+        self.reset()
+        return (_status(),)
+
+    def fmi3SerializeFmuState(self):
+        return self.serialize((self.time,))
+
+    def fmi3DeserializeFmuState(self, state):
+        self.values, self.time = self.deserialize(state)
+        return (_status(),)
+
+    def fmi3GetOutputDerivatives(self, references, orders):
+        if not all(reference in (2, 5) for reference in _indices(references)):
+            return _status(3), matlab.double([])
+
+        return _status(), matlab.double([0.0] * len(_indices(references)))
@@ -25,6 +25,8 @@ echo "generating protobuf schemas for python, csharp, and java backends"
 mkdir -p assets/auto_generated/fmi2
 mkdir -p assets/auto_generated/fmi3
 protoc -I=schemas --python_out=assets/auto_generated --csharp_out=assets/auto_generated --java_out=assets/auto_generated fmi2_messages.proto fmi3_messages.proto unifmu_handshake.proto
+echo "copying the protobuf messages generated by prost while building fmiapi for the rust backend"
+cp fmiapi/src/fmi2/fmi2_messages.rs fmiapi/src/fmi3/fmi3_messages.rs fmiapi/src/common/unifmu_handshake.rs assets/auto_generated/
 # ------------------------------ cli ------------------------------
 tgt=unifmu
 echo "installing dependencies for testing cli in linux"
@@ -10,12 +10,17 @@ use bytes::Bytes;
 use prost::{DecodeError, Message};
 use zeromq::{Endpoint, RepSocket, Socket, SocketRecv, SocketSend, ZmqError};
 
+use super::private_directory::PrivateDirectory;
+
 /// Represents the communication socket with the backend process.
 /// 
 /// Stores the actual ZeroMQ Socket for concurrency reasons.
 pub struct BackendSocket {
     socket: RepSocket,
-    pub endpoint: Endpoint
+    pub endpoint: Endpoint,
+    /// The directory holding the socket file of an IPC socket, declared
+    /// after the socket so that it is removed after the socket is closed.
+    _ipc_directory: Option<PrivateDirectory>
 }
 
 impl BackendSocket {
@@ -29,7 +34,29 @@ impl BackendSocket {
             }
         };
 
-        Ok(Self {socket, endpoint})
+        Ok(Self {socket, endpoint, _ipc_directory: None})
+    }
+
+    /// Creates a socket bound to a Unix domain socket in a new directory
+    /// that only the current user can access.
+    /// 
+    /// On platforms without Unix domain sockets, the socket is bound to a
+    /// TCP port of the loopback interface instead.
+    pub async fn create_ipc() -> SocketResult<Self> {
+        if cfg!(not(unix)) {
+            return Self::create("tcp://127.0.0.1:0").await;
+        }
+
+        let directory = PrivateDirectory::create()
+            .map_err(SocketError::IpcDirectory)?;
+
+        let endpoint = format!(
+            "ipc://{}", directory.path().join("dispatcher.sock").display()
+        );
+
+        let mut socket = Self::create(&endpoint).await?;
+        socket._ipc_directory = Some(directory);
+        Ok(socket)
     }
 
     /// Sends the contents of a message through the ZeroMQ socket to
@@ -99,6 +126,7 @@ type SocketResult<T> = Result<T, SocketError>;
 #[derive(Debug)]
 pub enum SocketError {
     ZmqBind(ZmqError),
+    IpcDirectory(std::io::Error),
     ZmqSend(String, ZmqError),
     ZmqReceive(ZmqError),
     EmptyBuffer,
@@ -111,6 +139,9 @@ impl Display for SocketError {
             Self::ZmqBind(error) => write!(
                 f, "couldn't bind to backend socket; {}", error
             ),
+            Self::IpcDirectory(error) => write!(
+                f, "couldn't create a private directory for the ipc socket; {}", error
+            ),
             Self::ZmqSend(message, error) => write!(
                 f, "failed to send message {}; {}", message, error
             ),
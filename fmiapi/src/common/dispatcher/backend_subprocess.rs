@@ -2,95 +2,190 @@
 //! process containing the backend.
 
 use std::{
-    error::Error, ffi::OsString, fmt::{Debug, Display}, io::Read, path::Path
+    collections::VecDeque,
+    error::Error,
+    fmt::{Debug, Display},
+    io::{pipe, BufRead, BufReader, PipeReader},
+    path::Path,
+    process::{ExitStatus, Stdio},
+    sync::{Arc, Mutex},
+    time::Duration
 };
 
-use subprocess::{ExitStatus, Popen, PopenConfig, PopenError};
-use tokio::time::{Duration, sleep};
+use tokio::{
+    process::{Child, Command},
+    select,
+    sync::broadcast::{
+        channel,
+        error::{RecvError, TryRecvError},
+        Receiver
+    }
+};
+
+/// The number of the latest lines of backend output kept for crash reports,
+/// and kept for passing on while the FMU isn't waiting on the backend.
+const CRASH_REPORT_LINES: usize = 100;
+
+/// How long the output written by the backend right before exiting is read
+/// for, in case a process started by the backend keeps the output open.
+const OUTPUT_DRAIN_PERIOD: Duration = Duration::from_millis(500);
 
 /// Representes the subprocess containing the backend.
 /// 
 /// Stores the subprocess handle for concurrency reasons.
 pub struct BackendSubprocess {
-    polling_time: Duration,
-    subprocess: Popen
+    subprocess: Child,
+    /// The lines written by the backend, read by a background thread so that
+    /// the backend never blocks on a full pipe. Only about the
+    /// `CRASH_REPORT_LINES` latest lines not yet passed on are kept.
+    output: Receiver<String>,
+    /// The latest lines read by the background thread, for reporting an
+    /// unexpected exit.
+    latest_output: Arc<Mutex<LatestOutput>>
 }
 
 impl BackendSubprocess {
     pub fn create(
         endpoint: String,
+        shared_memory_path: Option<&Path>,
         launch_command: &Vec<String>,
         resource_path: &Path
     ) -> SubprocessResult<Self> {
-        let endpoint_port = match endpoint
-            .split(":")
-            .last() {
-                Some(port) => port,
-                None => {
-                    return Err(SubprocessError::NoPortGiven)
+        // Only TCP endpoints have a port, IPC endpoints are file paths.
+        let endpoint_port = if endpoint.starts_with("tcp://") {
+            match endpoint
+                .split(":")
+                .last() {
+                    Some(port) => Some(port.to_owned()),
+                    None => {
+                        return Err(SubprocessError::NoPortGiven)
+                    }
                 }
-            }
-            .to_owned();
-        
-        let mut env_vars: Vec<(OsString, OsString)> = std::env::vars_os().collect();
-
-        env_vars.push((
-            OsString::from("UNIFMU_DISPATCHER_ENDPOINT"),
-            OsString::from(endpoint),
-        ));
-        env_vars.push((
-            OsString::from("UNIFMU_DISPATCHER_ENDPOINT_PORT"),
-            OsString::from(endpoint_port),
-        ));
-
-        let subprocess = match Popen::create(
-            launch_command,
-            PopenConfig {
-                cwd: Some(resource_path.as_os_str().to_owned()),
-                env: Some(env_vars),
-                stderr: subprocess::Redirection::Merge,
-                stdout: subprocess::Redirection::Pipe,
-                ..Default::default()
-            },
-        ) {
-            Ok(subprocess) => subprocess,
-            Err(error) => {
-                return Err(SubprocessError::UnExecutableCommand(
-                    format!("{:?}", launch_command),
-                    error
-                ))
-            }
+        } else {
+            None
         };
 
+        let unexecutable = |error| SubprocessError::UnExecutableCommand(
+            format!("{:?}", launch_command),
+            error
+        );
+
+        let (program, arguments) = launch_command
+            .split_first()
+            .ok_or_else(|| unexecutable(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "the command is empty"
+            )))?;
+
+        // The backend writes both stdout and stderr to the same pipe, so that
+        // its output can be reported in order if it exits unexpectedly.
+        let (output, output_writer) = pipe().map_err(unexecutable)?;
+        let error_writer = output_writer.try_clone().map_err(unexecutable)?;
+
+        let mut command = Command::new(program);
+        command
+            .args(arguments)
+            .current_dir(resource_path)
+            .env("UNIFMU_DISPATCHER_ENDPOINT", endpoint.as_str())
+            .stdout(Stdio::from(output_writer))
+            .stderr(Stdio::from(error_writer));
+
+        if let Some(port) = endpoint_port {
+            command.env("UNIFMU_DISPATCHER_ENDPOINT_PORT", port);
+        }
+
+        if let Some(path) = shared_memory_path {
+            command.env("UNIFMU_SHARED_MEMORY", path);
+        }
+
+        // Must be called from within the context of the tokio runtime that
+        // the subprocess is later monitored in.
+        let subprocess = command
+            .spawn()
+            .map_err(unexecutable)?;
+
+        let latest_output = Arc::new(Mutex::new(LatestOutput::default()));
+
         Ok(
             Self{
-                // TODO: This is a magic number. Is there a smarter way to define polling_time?
-                polling_time: Duration::from_millis(100),
-                subprocess
+                subprocess,
+                output: read_lines_in_background(output, latest_output.clone()),
+                latest_output
             }
         )
     }
 
-    /// Continously polls the backend subprocess and returns if the subprocess
-    /// returns an exit status.
+    /// Waits for the backend subprocess to exit, and returns when it does.
+    /// 
+    /// Each line written by the backend in the meantime is passed to
+    /// on_output as soon as it is read.
     /// 
     /// Will only ever return an Err.
-    pub async fn monitor_subprocess(&mut self) -> SubprocessResult<()> {
+    pub async fn monitor_subprocess(
+        &mut self,
+        on_output: &mut impl FnMut(&str)
+    ) -> SubprocessResult<()> {
         loop {
-            match self.subprocess.poll() {
-                Some(exit_status) => {
-                    let mut output_dump = String::new();
-                    if let Some(file) = &mut self.subprocess.stdout {
-                        let _ = file.read_to_string(&mut output_dump);
-                    }
-                    return Err(SubprocessError::UnexpectedExit(exit_status, output_dump))
-                },
-                None => {
-                    sleep(self.polling_time).await; // Is magic number.
+            select! {
+                exit_status = self.subprocess.wait() => {
+                    let exit_status = exit_status
+                        .map_err(SubprocessError::Unmonitorable)?;
+
+                    // The output written right before the exit may not have
+                    // been read yet.
+                    let _ = tokio::time::timeout(OUTPUT_DRAIN_PERIOD, async {
+                        while let Some(line) = next_line(&mut self.output).await {
+                            on_output(&line);
+                        }
+                    }).await;
+
+                    let crash_report = self.latest_output
+                        .lock()
+                        .map(|latest_output| latest_output.report())
+                        .unwrap_or_default();
+
+                    return Err(SubprocessError::UnexpectedExit(
+                        exit_status,
+                        crash_report
+                    ));
+                }
+                Some(line) = next_line(&mut self.output) => {
+                    on_output(&line);
                 }
             }
         }
     }
+
+    /// Passes each line that the backend has written, and that has been read
+    /// but not yet passed on, to on_output without waiting for more.
+    pub fn forward_read_output(&mut self, on_output: &mut impl FnMut(&str)) {
+        loop {
+            match self.output.try_recv() {
+                Ok(line) => on_output(&line),
+                Err(TryRecvError::Lagged(omitted)) => on_output(&omitted_lines(omitted)),
+                Err(_) => break
+            }
+        }
+    }
+
+    /// Waits for the backend subprocess to exit, ignoring how it exited.
+    /// 
+    /// If the subprocess hasn't exited within the grace period, it is killed
+    /// and waited for, so that it doesn't outlive the wait.
+    pub async fn wait_or_kill(&mut self, grace_period: Duration) {
+        if tokio::time::timeout(grace_period, self.subprocess.wait()).await.is_err() {
+            self.kill();
+            let _ = self.subprocess.wait().await;
+        }
+    }
+
+    /// Kills the backend subprocess.
+    /// 
+    /// Failure to kill is ignored, as it means that the subprocess has
+    /// already exited.
+    pub fn kill(&mut self) {
+        let _ = self.subprocess.start_kill();
+    }
 }
 
 type SubprocessResult<T> = Result<T, SubprocessError>;
@@ -99,24 +194,22 @@ type SubprocessResult<T> = Result<T, SubprocessError>;
 pub enum SubprocessError {
     UnexpectedExit(ExitStatus, String),
     NoPortGiven,
-    UnExecutableCommand(String, PopenError)
+    UnExecutableCommand(String, std::io::Error),
+    Unmonitorable(std::io::Error)
 }
 
 impl Display for SubprocessError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::UnexpectedExit(exit_status, output_dump) => {
-                let clarification = match exit_status {
-                    ExitStatus::Exited(code) => format!(
+                let clarification = match (exit_status.code(), signal(exit_status)) {
+                    (Some(code), _) => format!(
                         "with exit status {}", code
                     ),
-                    ExitStatus::Signaled(signal) => format!(
+                    (None, Some(signal)) => format!(
                         "because of signal {}", signal
                     ),
-                    ExitStatus::Other(status) => format!(
-                        "with unknown status {}", status
-                    ),
-                    ExitStatus::Undetermined => String::from(
+                    (None, None) => String::from(
                         "for an undeterminable reason"
                     )
                 };
@@ -130,11 +223,110 @@ impl Display for SubprocessError {
             Self::NoPortGiven => {
                 write!(f, "the endpoint given to for the backend to connect to didn't have a portnumber")
             }
-            Self::UnExecutableCommand(command, popen_error) => {
-                write!(f, "unable to start the backend subprocess using the specified command '{}'; {}", command, popen_error)
+            Self::UnExecutableCommand(command, io_error) => {
+                write!(f, "unable to start the backend subprocess using the specified command '{}'; {}", command, io_error)
+            }
+            Self::Unmonitorable(io_error) => {
+                write!(f, "unable to wait for the backend subprocess to exit; {}", io_error)
             }
         }
     }
 }
 
+/// The latest lines of output of the backend, bounded to the
+/// `CRASH_REPORT_LINES` newest lines.
+#[derive(Default)]
+struct LatestOutput {
+    lines: VecDeque<String>,
+    /// Whether any lines have been pushed out of `lines`.
+    truncated: bool
+}
+
+impl LatestOutput {
+    fn push(&mut self, line: String) {
+        if self.lines.len() == CRASH_REPORT_LINES {
+            self.lines.pop_front();
+            self.truncated = true;
+        }
+        self.lines.push_back(line);
+    }
+
+    fn report(&self) -> String {
+        let mut report = Vec::with_capacity(self.lines.len() + 1);
+        if self.truncated {
+            report.push("[earlier output omitted]");
+        }
+        report.extend(self.lines.iter().map(String::as_str));
+        report.join("\n")
+    }
+}
+
+/// Receives the next line of output, or None once the output is closed.
+/// 
+/// Lines that were dropped as newer lines arrived are reported by a line in
+/// their place.
+async fn next_line(output: &mut Receiver<String>) -> Option<String> {
+    match output.recv().await {
+        Ok(line) => Some(line),
+        Err(RecvError::Lagged(omitted)) => Some(omitted_lines(omitted)),
+        Err(RecvError::Closed) => None
+    }
+}
+
+/// The line reporting that the given number of lines of output was dropped.
+fn omitted_lines(omitted: u64) -> String {
+    format!("[{} lines of output omitted]", omitted)
+}
+
+/// Reads the output on a background thread, recording each line in
+/// latest_output and sending it through the returned receiver until the
+/// output is closed.
+/// 
+/// The receiver keeps at least the `CRASH_REPORT_LINES` latest lines that it
+/// hasn't yet received, dropping the oldest.
+fn read_lines_in_background(
+    output: PipeReader,
+    latest_output: Arc<Mutex<LatestOutput>>
+) -> Receiver<String> {
+    let (sender, receiver) = channel(CRASH_REPORT_LINES);
+
+    std::thread::spawn(move || {
+        let mut reader = BufReader::new(output);
+        let mut line = Vec::new();
+
+        loop {
+            line.clear();
+            match reader.read_until(b'\n', &mut line) {
+                Ok(0) | Err(_) => break,
+                Ok(_) => {
+                    let line = String::from_utf8_lossy(&line)
+                        .trim_end_matches(['\r', '\n'])
+                        .to_owned();
+                    if let Ok(mut latest_output) = latest_output.lock() {
+                        latest_output.push(line.clone());
+                    }
+                    if sender.send(line).is_err() {
+                        break;
+                    }
+                }
+            }
+        }
+    });
+
+    receiver
+}
+
+/// The signal that terminated the process, if any.
+#[cfg(unix)]
+fn signal(exit_status: &ExitStatus) -> Option<i32> {
+    use std::os::unix::process::ExitStatusExt;
+    exit_status.signal()
+}
+
+/// The signal that terminated the process, if any.
+#[cfg(not(unix))]
+fn signal(_exit_status: &ExitStatus) -> Option<i32> {
+    None
+}
+
 impl Error for SubprocessError {}
\ No newline at end of file
@@ -2,16 +2,22 @@
 
 mod backend_subprocess;
 mod backend_socket;
+mod private_directory;
+mod shared_memory;
 
 use backend_subprocess::{BackendSubprocess, SubprocessError};
 use backend_socket::{BackendSocket, SocketError};
 
+pub use shared_memory::SharedMemory;
+
 use super::unifmu_handshake::{HandshakeStatus, HandshakeReply};
 
 use std::{
     error::Error,
     fmt::{Debug, Display},
-    path::Path
+    future::Future,
+    path::Path,
+    time::{Duration, Instant}
 };
 
 use prost::{Message, UnknownEnumValue};
@@ -30,15 +36,24 @@ impl Dispatcher {
     /// 
     /// The backend is started as a subprocess with the launch_command using
     /// the resources at resource_path as part of the Dispatchers creation.
+    /// 
+    /// If shared_memory is set, the backend is offered a shared memory region
+    /// for bulk values, which it accepts or declines during the handshake.
     pub fn local(
         resource_path: &Path,
-        launch_command: &Vec<String>
+        launch_command: &Vec<String>,
+        transport: Transport,
+        shared_memory: bool,
+        timeouts: Timeouts
     ) -> DispatcherResult<Self> {
         Ok(
             Self::Local(
                 LocalDispatcher::create(
                     resource_path,
-                    launch_command
+                    launch_command,
+                    transport,
+                    shared_memory,
+                    timeouts
                 )?
             )
         )
@@ -50,42 +65,127 @@ impl Dispatcher {
     /// socket is ready, the function passed in `remote_connection_notifier`
     /// will be called with the socket port number as the only parameter.
     pub fn remote(
-        remote_connection_notifier: impl Fn(&str)
+        remote_connection_notifier: impl Fn(&str),
+        timeouts: Timeouts
     ) -> DispatcherResult<Self> {
         Ok(
             Self::Remote(
-                RemoteDispatcher::create(remote_connection_notifier)?
+                RemoteDispatcher::create(remote_connection_notifier, timeouts)?
             )
         )
     }
+
+    /// The shared memory region that the backend accepted during the
+    /// handshake, if any. Only local backends can share memory.
+    pub fn shared_memory(&mut self) -> Option<&mut SharedMemory> {
+        match self {
+            Dispatcher::Local(d) => d.shared_memory.as_deref_mut(),
+            Dispatcher::Remote(_) => None
+        }
+    }
 }
 
 impl Dispatch for Dispatcher {
-    fn send<S: Message + Debug>(&mut self, msg: &S) -> DispatcherResult<()> {
+    fn await_handshake(
+        &mut self,
+        on_output: impl FnMut(&str)
+    ) -> DispatcherResult<()> {
         match self {
-            Dispatcher::Local(d) => d.send(msg),
-            Dispatcher::Remote(d) => d.send(msg)
+            Dispatcher::Local(d) => d.await_handshake(on_output),
+            Dispatcher::Remote(d) => d.await_handshake(on_output)
         }
     }
 
-    fn recv<R: Message + Default>(&mut self) -> DispatcherResult<R> {
+    fn timeouts(&self) -> &Timeouts {
+        match self {
+            Dispatcher::Local(d) => d.timeouts(),
+            Dispatcher::Remote(d) => d.timeouts()
+        }
+    }
+
+    fn send<S: Message + Debug>(
+        &mut self,
+        msg: &S,
+        deadline: Option<Deadline>,
+        on_output: impl FnMut(&str)
+    ) -> DispatcherResult<()> {
         match self {
-            Dispatcher::Local(d) => d.recv::<R>(),
-            Dispatcher::Remote(d) => d.recv::<R>()
+            Dispatcher::Local(d) => d.send(msg, deadline, on_output),
+            Dispatcher::Remote(d) => d.send(msg, deadline, on_output)
+        }
+    }
+
+    fn recv<R: Message + Default>(
+        &mut self,
+        deadline: Option<Deadline>,
+        on_output: impl FnMut(&str)
+    ) -> DispatcherResult<R> {
+        match self {
+            Dispatcher::Local(d) => d.recv::<R>(deadline, on_output),
+            Dispatcher::Remote(d) => d.recv::<R>(deadline, on_output)
         }
     }
 
     fn send_and_recv<S: Message + Debug, R: Message + Default>(
         &mut self,
         msg: &S,
+        deadline: Option<Deadline>,
+        on_output: impl FnMut(&str)
     ) -> DispatcherResult<R> {
         match self {
-            Dispatcher::Local(d) => d.send_and_recv::<S, R>(msg),
-            Dispatcher::Remote(d) => d.send_and_recv::<S, R>(msg)
+            Dispatcher::Local(d) => d.send_and_recv::<S, R>(msg, deadline, on_output),
+            Dispatcher::Remote(d) => d.send_and_recv::<S, R>(msg, deadline, on_output)
         }
     }
 }
 
+/// The transport of the socket between a Dispatcher and a local backend.
+#[derive(Clone, Copy, Debug, Default)]
+pub enum Transport {
+    /// TCP through a port of the loopback interface.
+    #[default]
+    Tcp,
+    /// A Unix domain socket in a directory private to the current user,
+    /// falling back to TCP on platforms without Unix domain sockets.
+    Ipc
+}
+
+/// How long a Dispatcher waits on the backend before giving up.
+/// 
+/// A timeout of None waits indefinitely.
+#[derive(Clone, Debug, Default)]
+pub struct Timeouts {
+    /// Limit on the wait for the initial handshake from the backend.
+    pub handshake: Option<Duration>,
+    /// Limit on the wait for the reply to any command other than a step.
+    pub call: Option<Duration>,
+    /// Limit on the wait for the reply to a step command.
+    pub do_step: Option<Duration>,
+    /// Whether a local backend is killed when a timeout elapses.
+    pub kill_backend: bool
+}
+
+/// The point in time by which an exchange with the backend must complete.
+/// 
+/// A single deadline spans every message of the exchange, such that replies
+/// to callbacks of the backend don't extend the wait.
+#[derive(Clone, Copy, Debug)]
+pub struct Deadline {
+    timeout: Duration,
+    instant: Instant
+}
+
+impl Deadline {
+    /// The deadline that is the given timeout from now. No timeout gives no
+    /// deadline, which waits indefinitely.
+    pub fn after(timeout: Option<Duration>) -> Option<Self> {
+        timeout.map(|timeout| Self {
+            timeout,
+            instant: Instant::now() + timeout
+        })
+    }
+}
+
 /// Dispatcher for dispatching FMI commands to a locally run backend.
 /// 
 /// Holds the handle for the subprocess as well as the handle for the socket
@@ -94,66 +194,165 @@ pub struct LocalDispatcher {
     socket: BackendSocket,
     subprocess: BackendSubprocess,
     runtime: Runtime,
+    timeouts: Timeouts,
+    shared_memory: Option<Box<SharedMemory>>,
+    poisoned: bool,
 }
 
 impl LocalDispatcher {
     pub fn create(
         resource_path: &Path,
-        launch_command: &Vec<String>
+        launch_command: &Vec<String>,
+        transport: Transport,
+        shared_memory: bool,
+        timeouts: Timeouts
     ) -> DispatcherResult<Self> {
+        let shared_memory = match shared_memory {
+            true => Some(Box::new(
+                SharedMemory::create().map_err(DispatcherError::SharedMemory)?
+            )),
+            false => None
+        };
+
         let runtime = tokio::runtime::Builder::new_current_thread()
             .enable_all()
             .build()?;
 
-        let socket = runtime.block_on(
-            BackendSocket::create("tcp://127.0.0.1:0")
-        )?;
-
-        let subprocess = BackendSubprocess::create(
-            socket.endpoint.to_string(),
-            launch_command,
-            resource_path
-        )?;
+        let socket = match transport {
+            Transport::Tcp => runtime.block_on(
+                BackendSocket::create("tcp://127.0.0.1:0")
+            )?,
+            Transport::Ipc => runtime.block_on(
+                BackendSocket::create_ipc()
+            )?
+        };
+
+        let subprocess = {
+            let _runtime_context = runtime.enter();
+            BackendSubprocess::create(
+                socket.endpoint.to_string(),
+                shared_memory.as_ref().map(|region| region.path.as_path()),
+                launch_command,
+                resource_path
+            )?
+        };
 
         Ok(
             Self {
                 socket,
                 subprocess,
-                runtime
+                runtime,
+                timeouts,
+                shared_memory,
+                poisoned: false
             }
         )
     }
+
+    /// Poisons the dispatcher if the result is a timeout, and kills the
+    /// backend if the timeouts say so.
+    /// 
+    /// A backend that missed its deadline may never answer, and the
+    /// subprocess handle waits for the backend to exit when dropped.
+    fn handle_timeout<T>(
+        &mut self,
+        result: DispatcherResult<T>
+    ) -> DispatcherResult<T> {
+        if matches!(result, Err(DispatcherError::Timeout(_))) {
+            self.poisoned = true;
+            if self.timeouts.kill_backend {
+                self.subprocess.kill();
+            }
+        }
+        result
+    }
+}
+
+/// How long a dropped LocalDispatcher waits for the backend to exit if no
+/// call timeout is given, before killing it.
+const BACKEND_EXIT_GRACE_PERIOD: Duration = Duration::from_secs(30);
+
+/// Waits for the backend to exit when the dispatcher is dropped, so that the
+/// backend isn't left running after the FMU instance is freed.
+/// 
+/// A backend that doesn't exit within the call timeout, or the
+/// `BACKEND_EXIT_GRACE_PERIOD` without one, is killed, as it may never have
+/// received the command to free the instance.
+impl Drop for LocalDispatcher {
+    fn drop(&mut self) {
+        let grace_period = self.timeouts.call.unwrap_or(BACKEND_EXIT_GRACE_PERIOD);
+        self.runtime.block_on(self.subprocess.wait_or_kill(grace_period));
+    }
 }
 
 impl Dispatch for LocalDispatcher {
-    fn send<S: Message + Debug>(&mut self, msg: &S) -> DispatcherResult<()> {
-        self.runtime.block_on(async {
+    /// Awaits the handshake like the default implementation, and drops the
+    /// shared memory region if the backend declined it.
+    fn await_handshake(
+        &mut self,
+        on_output: impl FnMut(&str)
+    ) -> DispatcherResult<()> {
+        let deadline = Deadline::after(self.timeouts().handshake);
+        let response = self.recv::<HandshakeReply>(deadline, on_output)?;
+        check_handshake(&response)?;
+        if !response.shared_memory {
+            self.shared_memory = None;
+        }
+        Ok(())
+    }
+
+    fn timeouts(&self) -> &Timeouts {
+        &self.timeouts
+    }
+
+    fn send<S: Message + Debug>(
+        &mut self,
+        msg: &S,
+        deadline: Option<Deadline>,
+        mut on_output: impl FnMut(&str)
+    ) -> DispatcherResult<()> {
+        check_poisoned(self.poisoned)?;
+        let result = self.runtime.block_on(within(deadline, async {
             select! {
-                Err(e) = self.subprocess.monitor_subprocess() => Ok(Err(e)?),
+                Err(e) = self.subprocess.monitor_subprocess(&mut on_output) => Ok(Err(e)?),
                 result = self.socket.send::<S>(msg) => Ok(result?),
             }
-        })
+        }));
+        self.subprocess.forward_read_output(&mut on_output);
+        self.handle_timeout(result)
     }
 
-    fn recv<R: Message + Default>(&mut self) -> DispatcherResult<R> {
-        self.runtime.block_on(async {
+    fn recv<R: Message + Default>(
+        &mut self,
+        deadline: Option<Deadline>,
+        mut on_output: impl FnMut(&str)
+    ) -> DispatcherResult<R> {
+        check_poisoned(self.poisoned)?;
+        let result = self.runtime.block_on(within(deadline, async {
             select! {
-                Err(e) = self.subprocess.monitor_subprocess() => Ok(Err(e)?),
+                Err(e) = self.subprocess.monitor_subprocess(&mut on_output) => Ok(Err(e)?),
                 result = self.socket.recv::<R>() => Ok(result?),
             }
-        })
+        }));
+        self.subprocess.forward_read_output(&mut on_output);
+        self.handle_timeout(result)
     }
 
     fn send_and_recv<S: Message + Debug, R: Message + Default>(
         &mut self,
         msg: &S,
+        deadline: Option<Deadline>,
+        mut on_output: impl FnMut(&str)
     ) -> DispatcherResult<R> {
-        self.runtime.block_on(async {
+        check_poisoned(self.poisoned)?;
+        let result = self.runtime.block_on(within(deadline, async {
             select! {
                 result = self.socket.send_and_recv::<S, R>(msg) => Ok(result?),
-                Err(e) = self.subprocess.monitor_subprocess() => Ok(Err(e)?),
+                Err(e) = self.subprocess.monitor_subprocess(&mut on_output) => Ok(Err(e)?),
             }
-        })
+        }));
+        self.subprocess.forward_read_output(&mut on_output);
+        self.handle_timeout(result)
     }
 }
 
@@ -163,10 +362,15 @@ impl Dispatch for LocalDispatcher {
 pub struct RemoteDispatcher {
     socket: BackendSocket,
     runtime: Runtime,
+    timeouts: Timeouts,
+    poisoned: bool,
 }
 
 impl RemoteDispatcher {
-    pub fn create(remote_connection_notifier: impl Fn(&str)) -> DispatcherResult<Self> {
+    pub fn create(
+        remote_connection_notifier: impl Fn(&str),
+        timeouts: Timeouts
+    ) -> DispatcherResult<Self> {
         let runtime = tokio::runtime::Builder::new_current_thread()
             .enable_all()
             .build()?;
@@ -184,25 +388,107 @@ impl RemoteDispatcher {
             Self {
                 socket,
                 runtime,
+                timeouts,
+                poisoned: false,
             }
         )
     }
+
+    /// Poisons the dispatcher if the result is a timeout.
+    fn handle_timeout<T>(
+        &mut self,
+        result: DispatcherResult<T>
+    ) -> DispatcherResult<T> {
+        if matches!(result, Err(DispatcherError::Timeout(_))) {
+            self.poisoned = true;
+        }
+        result
+    }
 }
 
 impl Dispatch for RemoteDispatcher {
-    fn send<S: Message + Debug>(&mut self, msg: &S) -> DispatcherResult<()> {
-        Ok(self.runtime.block_on(self.socket.send::<S>(msg))?)
+    fn timeouts(&self) -> &Timeouts {
+        &self.timeouts
+    }
+
+    fn send<S: Message + Debug>(
+        &mut self,
+        msg: &S,
+        deadline: Option<Deadline>,
+        _on_output: impl FnMut(&str)
+    ) -> DispatcherResult<()> {
+        check_poisoned(self.poisoned)?;
+        let result = self.runtime.block_on(within(deadline, async {
+            Ok(self.socket.send::<S>(msg).await?)
+        }));
+        self.handle_timeout(result)
     }
 
-    fn recv<R: Message + Default>(&mut self) -> DispatcherResult<R> {
-        Ok(self.runtime.block_on(self.socket.recv::<R>())?)
+    fn recv<R: Message + Default>(
+        &mut self,
+        deadline: Option<Deadline>,
+        _on_output: impl FnMut(&str)
+    ) -> DispatcherResult<R> {
+        check_poisoned(self.poisoned)?;
+        let result = self.runtime.block_on(within(deadline, async {
+            Ok(self.socket.recv::<R>().await?)
+        }));
+        self.handle_timeout(result)
     }
 
     fn send_and_recv<S: Message + Debug, R: Message + Default>(
         &mut self,
         msg: &S,
+        deadline: Option<Deadline>,
+        _on_output: impl FnMut(&str)
     ) -> DispatcherResult<R> {
-        Ok(self.runtime.block_on(self.socket.send_and_recv::<S, R>(msg))?)
+        check_poisoned(self.poisoned)?;
+        let result = self.runtime.block_on(within(deadline, async {
+            Ok(self.socket.send_and_recv::<S, R>(msg).await?)
+        }));
+        self.handle_timeout(result)
+    }
+}
+
+/// Awaits the future, failing with a `DispatcherError::Timeout` if it doesn't
+/// complete by the deadline.
+async fn within<T>(
+    deadline: Option<Deadline>,
+    future: impl Future<Output = DispatcherResult<T>>
+) -> DispatcherResult<T> {
+    match deadline {
+        Some(deadline) => tokio::time::timeout_at(deadline.instant.into(), future)
+            .await
+            .map_err(|_| DispatcherError::Timeout(deadline.timeout))?,
+        None => future.await
+    }
+}
+
+/// Fails with a `DispatcherError::Poisoned` if an earlier exchange with the
+/// backend timed out.
+/// 
+/// The socket expects the reply that never arrived before anything else, so
+/// the exchanges that would follow could only fail or pair up a command
+/// with the reply to an earlier one.
+fn check_poisoned(poisoned: bool) -> DispatcherResult<()> {
+    match poisoned {
+        true => Err(DispatcherError::Poisoned),
+        false => Ok(())
+    }
+}
+
+/// Checks that the backend reported no error as part of the handshake.
+fn check_handshake(response: &HandshakeReply) -> DispatcherResult<()> {
+    match HandshakeStatus::try_from(response.status) {
+        Ok(HandshakeStatus::Ok) => {
+            Ok(())
+        },
+        Ok(_) => {
+            Err(DispatcherError::DeniedHandshake)
+        },
+        Err(error) => {
+            Err(DispatcherError::MalformedHandshake(error))
+        }
     }
 }
 
@@ -215,26 +501,39 @@ pub trait Dispatch {
     /// This method includes a call to Self::recv() and thus can be followed by
     /// a call to Self::send() or Self::send_and_recv() without error (if the
     /// await_handshake() method itself returns without error).
-    fn await_handshake(&mut self) -> DispatcherResult<()> {
-        let response = self.recv::<HandshakeReply>()?;
-        match HandshakeStatus::try_from(response.status) {
-            Ok(HandshakeStatus::Ok) => {
-                Ok(())
-            },
-            Ok(_) => {
-                Err(DispatcherError::DeniedHandshake)
-            },
-            Err(error) => {
-                Err(DispatcherError::MalformedHandshake(error))
-            }
-        }
+    /// 
+    /// Waits no longer than the handshake timeout of the Dispatcher, passing
+    /// any output of the backend in the meantime to on_output.
+    fn await_handshake(
+        &mut self,
+        on_output: impl FnMut(&str)
+    ) -> DispatcherResult<()> {
+        let deadline = Deadline::after(self.timeouts().handshake);
+        let response = self.recv::<HandshakeReply>(deadline, on_output)?;
+        check_handshake(&response)
     }
 
+    /// The timeouts that the Dispatcher was created with.
+    fn timeouts(&self) -> &Timeouts;
+
     /// Send a message to the backend.
     /// 
     /// A successfull call to Self::recv() or Self::send_and_recv() must have
     /// been made previous to a call to Self::send(). If not, send() will fail.
-    fn send<S: Message + Debug>(&mut self, msg: &S) -> DispatcherResult<()>; 
+    /// 
+    /// Fails with a `DispatcherError::Timeout` if the message isn't sent
+    /// by the deadline, if any. Every following call then fails with a
+    /// `DispatcherError::Poisoned`, as is the case for the following methods.
+    /// 
+    /// Each line that a local backend writes to stdout or stderr while
+    /// waiting is passed to on_output, as is the case for the following
+    /// methods.
+    fn send<S: Message + Debug>(
+        &mut self,
+        msg: &S,
+        deadline: Option<Deadline>,
+        on_output: impl FnMut(&str)
+    ) -> DispatcherResult<()>;
 
     /// Receive a response from the backend.
     /// 
@@ -242,16 +541,28 @@ pub trait Dispatch {
     /// this function.
     /// Any subsequent calls to this function must be preceded by a call to
     /// Self::send(). Otherwise the recv() call will fail.
-    fn recv<R: Message + Default>(&mut self) -> DispatcherResult<R>;
+    /// 
+    /// Fails with a `DispatcherError::Timeout` if no response is received
+    /// by the deadline, if any.
+    fn recv<R: Message + Default>(
+        &mut self,
+        deadline: Option<Deadline>,
+        on_output: impl FnMut(&str)
+    ) -> DispatcherResult<R>;
 
     /// Sends a message to - and receives a response from - the backend.
     /// 
     /// A successfull call to Self::recv() or Self::send_and_recv() must have
     /// been made previous to a call to Self::send_and_recv(). If not,
     /// send_and_recv() will fail.
+    /// 
+    /// Fails with a `DispatcherError::Timeout` if the exchange doesn't
+    /// complete by the deadline, if any.
     fn send_and_recv<S: Message + Debug, R: Message + Default>(
         &mut self,
         msg: &S,
+        deadline: Option<Deadline>,
+        on_output: impl FnMut(&str)
     ) -> DispatcherResult<R>;
 }
 
@@ -263,7 +574,10 @@ pub enum DispatcherError {
     DeniedHandshake,
     Socket(SocketError),
     Subprocess(SubprocessError),
-    RuntimeSetup(std::io::Error)
+    RuntimeSetup(std::io::Error),
+    SharedMemory(std::io::Error),
+    Timeout(Duration),
+    Poisoned
 }
 
 impl Display for DispatcherError {
@@ -283,6 +597,15 @@ impl Display for DispatcherError {
             ),
             Self::RuntimeSetup(io_error) => write!(
                 f, "couldn't setup concurrency runtime; {}", io_error
+            ),
+            Self::SharedMemory(io_error) => write!(
+                f, "couldn't create shared memory region; {}", io_error
+            ),
+            Self::Timeout(duration) => write!(
+                f, "backend didn't respond within {} ms", duration.as_millis()
+            ),
+            Self::Poisoned => write!(
+                f, "backend can't be reached after an earlier call timed out"
             )
         }
     }
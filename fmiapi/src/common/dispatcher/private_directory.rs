@@ -0,0 +1,56 @@
+//! Contains the PrivateDirectory, a temporary directory for the files that a
+//! dispatcher shares with its local backend.
+
+use std::path::{Path, PathBuf};
+
+/// A new directory in the temporary directory of the system, accessible only
+/// to the current user, and removed along with its contents when dropped.
+pub struct PrivateDirectory {
+    path: PathBuf
+}
+
+impl PrivateDirectory {
+    pub fn create() -> std::io::Result<Self> {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        static CREATED: AtomicUsize = AtomicUsize::new(0);
+
+        loop {
+            let path = std::env::temp_dir().join(format!(
+                "unifmu-{}-{}",
+                std::process::id(),
+                CREATED.fetch_add(1, Ordering::Relaxed)
+            ));
+
+            // Creation fails rather than reusing any existing file, so a
+            // directory left by an earlier process with the same id is
+            // skipped.
+            match Self::create_directory(&path) {
+                Ok(()) => return Ok(Self {path}),
+                Err(error) if error.kind() == std::io::ErrorKind::AlreadyExists => continue,
+                Err(error) => return Err(error)
+            }
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    #[cfg(unix)]
+    fn create_directory(path: &Path) -> std::io::Result<()> {
+        use std::os::unix::fs::DirBuilderExt;
+        std::fs::DirBuilder::new().mode(0o700).create(path)
+    }
+
+    #[cfg(not(unix))]
+    fn create_directory(path: &Path) -> std::io::Result<()> {
+        std::fs::create_dir(path)
+    }
+}
+
+impl Drop for PrivateDirectory {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.path);
+    }
+}
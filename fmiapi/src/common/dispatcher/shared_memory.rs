@@ -0,0 +1,110 @@
+//! Contains the SharedMemory, a memory mapped region through which bulk
+//! values are exchanged with a local backend instead of through the socket.
+
+use std::{
+    fs::OpenOptions,
+    path::PathBuf
+};
+
+use memmap2::MmapMut;
+
+use super::private_directory::PrivateDirectory;
+
+/// Size of the region in bytes. Exchanges that don't fit go through the
+/// socket instead.
+const REGION_SIZE: usize = 4 * 1024 * 1024;
+
+/// A file in a private directory, mapped into the memory of both the
+/// dispatcher and the backend.
+///
+/// An exchange places the value references as `u32` from the start of the
+/// region, followed by the values from the next multiple of 8 bytes, all in
+/// the native byte order. The dispatcher and the backend take turns accessing
+/// the region, as each only does so while the other waits on the socket.
+pub struct SharedMemory {
+    map: MmapMut,
+    pub path: PathBuf,
+    // Declared last so that the directory is removed after the file is
+    // unmapped.
+    _directory: PrivateDirectory
+}
+
+impl SharedMemory {
+    pub fn create() -> std::io::Result<Self> {
+        let directory = PrivateDirectory::create()?;
+        let path = directory.path().join("values");
+
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create_new(true)
+            .open(&path)?;
+        file.set_len(REGION_SIZE as u64)?;
+
+        // The file is only mapped by the dispatcher and the backend it
+        // launches, and is never truncated while mapped.
+        let map = unsafe { MmapMut::map_mut(&file)? };
+
+        Ok(Self {map, path, _directory: directory})
+    }
+
+    /// Whether an exchange of the given numbers of value references and
+    /// `f64` values fits in the region.
+    pub fn fits_float64(&self, n_value_references: usize, n_values: usize) -> bool {
+        n_value_references.checked_mul(size_of::<u32>())
+            .and_then(|size| size.checked_next_multiple_of(size_of::<u64>()))
+            .zip(n_values.checked_mul(size_of::<f64>()))
+            .and_then(|(offset, size)| offset.checked_add(size))
+            .is_some_and(|end| end <= self.map.len())
+    }
+
+    /// Places the value references at the start of the region.
+    ///
+    /// The exchange must fit in the region.
+    pub fn write_value_references(&mut self, value_references: &[u32]) {
+        let region = &mut self.map[..size_of_val(value_references)];
+        for (slot, value_reference) in region
+            .chunks_exact_mut(size_of::<u32>())
+            .zip(value_references)
+        {
+            slot.copy_from_slice(&value_reference.to_ne_bytes());
+        }
+    }
+
+    /// Places the values after the given number of value references.
+    ///
+    /// The exchange must fit in the region.
+    pub fn write_float64(&mut self, n_value_references: usize, values: &[f64]) {
+        let offset = values_offset(n_value_references);
+        let region = &mut self.map[offset..offset + size_of_val(values)];
+        for (slot, value) in region
+            .chunks_exact_mut(size_of::<f64>())
+            .zip(values)
+        {
+            slot.copy_from_slice(&value.to_ne_bytes());
+        }
+    }
+
+    /// Reads the values after the given number of value references into
+    /// `values_out`.
+    ///
+    /// The exchange must fit in the region.
+    pub fn read_float64(&self, n_value_references: usize, values_out: &mut [f64]) {
+        let offset = values_offset(n_value_references);
+        let region = &self.map[offset..offset + size_of_val(values_out)];
+        for (value, slot) in values_out
+            .iter_mut()
+            .zip(region.chunks_exact(size_of::<f64>()))
+        {
+            *value = f64::from_ne_bytes(
+                slot.try_into().expect("chunks should be the size of an f64")
+            );
+        }
+    }
+}
+
+/// The offset of the values following the given number of value references,
+/// aligned for any value type.
+fn values_offset(n_value_references: usize) -> usize {
+    (n_value_references * size_of::<u32>()).next_multiple_of(size_of::<u64>())
+}
@@ -0,0 +1,75 @@
+//! This module checks the GUID (FMI2) or instantiation token (FMI3) that the
+//! importer passes on instantiation against the one declared in the FMU's
+//! `modelDescription.xml`, so that an importer can't instantiate an FMU with
+//! the model description of another.
+
+use std::{
+    error::Error,
+    fmt::Display
+};
+
+/// Checks that the given token equals the value of the given attribute on the
+/// root element of the given model description.
+pub fn verify_instantiation_token(
+    model_description: &str,
+    attribute: &str,
+    token: &str
+) -> TokenResult<()> {
+    let document = roxmltree::Document::parse(model_description)?;
+
+    let expected = document
+        .root_element()
+        .attribute(attribute)
+        .ok_or_else(|| TokenError::MissingAttribute(String::from(attribute)))?;
+
+    if expected != token {
+        return Err(TokenError::Mismatch {
+            attribute: String::from(attribute),
+            expected: String::from(expected),
+            given: String::from(token)
+        });
+    }
+
+    Ok(())
+}
+
+pub type TokenResult<T> = Result<T, TokenError>;
+
+#[derive(Debug)]
+pub enum TokenError {
+    ParseError(roxmltree::Error),
+    MissingAttribute(String),
+    Mismatch {
+        attribute: String,
+        expected: String,
+        given: String
+    }
+}
+
+impl Display for TokenError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ParseError(error) => {
+                write!(f, "couldn't parse model description: {}", error)
+            }
+            Self::MissingAttribute(attribute) => {
+                write!(f, "model description doesn't declare a {}", attribute)
+            }
+            Self::Mismatch { attribute, expected, given } => {
+                write!(
+                    f,
+                    "given {} '{}' doesn't match '{}' of the model description",
+                    attribute, given, expected
+                )
+            }
+        }
+    }
+}
+
+impl Error for TokenError {}
+
+impl From<roxmltree::Error> for TokenError {
+    fn from(value: roxmltree::Error) -> Self {
+        Self::ParseError(value)
+    }
+}
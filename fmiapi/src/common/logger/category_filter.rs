@@ -13,6 +13,7 @@ use std::collections::HashSet;
 /// 
 /// If the filter is the Whitelist variant, any category in the list is
 /// allowed.
+#[derive(Clone)]
 pub enum CategoryFilter<T> 
 where
     T: LogCategory
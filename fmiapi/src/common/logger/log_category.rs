@@ -10,4 +10,5 @@ pub trait LogCategory: for <'a> From<&'a str> + Default + Debug + Display + Eq +
     fn fatal() -> Self;
 
     fn unifmu_message() -> Self;
+    fn backend_output() -> Self;
 }
\ No newline at end of file
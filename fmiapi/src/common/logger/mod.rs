@@ -67,6 +67,15 @@ where
         );
     }
 
+    /// Emit a line of the output that the backend wrote to stdout or stderr.
+    fn backend_output(&self, line: &str) {
+        self.log(
+            Self::Status::ok(),
+            Self::Category::backend_output(),
+            line
+        );
+    }
+
     /// Enable the given categories so that any log event with any of those
     /// categories are emitted to the importer.
     fn enable_categories(
@@ -1,7 +1,9 @@
 //! Modules common to both FMI versions
 
 pub mod dispatcher;
+pub mod instantiation_token;
 pub mod logger;
+pub mod model_description;
 pub mod protobuf_extensions;
 pub mod spawn;
 pub mod string_conversion;
@@ -0,0 +1,49 @@
+//! This module reads the FMU's `modelDescription.xml`, from which the API
+//! takes the static information it acts upon without involving the backend,
+//! such as the instantiation token, the Co-Simulation capabilities and the
+//! model structure.
+
+use std::{
+    error::Error,
+    fmt::Display,
+    fs,
+    path::{Path, PathBuf}
+};
+
+/// Reads the `modelDescription.xml` that sits next to the given resources
+/// directory in the extracted FMU.
+pub fn read_model_description(resources_dir: &Path) -> ModelDescriptionResult<String> {
+    let model_description_path = resources_dir
+        .parent()
+        .ok_or(ModelDescriptionError::NoModelDescription)?
+        .join("modelDescription.xml");
+
+    fs::read_to_string(&model_description_path)
+        .map_err(|error| ModelDescriptionError::ReadError(
+            model_description_path,
+            error
+        ))
+}
+
+pub type ModelDescriptionResult<T> = Result<T, ModelDescriptionError>;
+
+#[derive(Debug)]
+pub enum ModelDescriptionError {
+    NoModelDescription,
+    ReadError(PathBuf, std::io::Error)
+}
+
+impl Display for ModelDescriptionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NoModelDescription => {
+                write!(f, "resources directory has no parent directory containing a model description")
+            }
+            Self::ReadError(path, error) => {
+                write!(f, "couldn't read '{}': {}", path.display(), error)
+            }
+        }
+    }
+}
+
+impl Error for ModelDescriptionError {}
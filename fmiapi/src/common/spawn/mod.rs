@@ -3,16 +3,17 @@
 //! 
 //! Also contains other types related to spawning.
 
-mod launch_config;
-
-use launch_config::{BackendLocation, ConfigError, LaunchConfig};
+use unifmu_launch_config::{
+    BackendLocation, BackendTransport, ConfigError, LaunchConfig, TimeoutsConfig
+};
 
-use super::dispatcher::{Dispatch, Dispatcher, DispatcherError};
+use super::dispatcher::{Dispatch, Dispatcher, DispatcherError, Timeouts, Transport};
 
 use std::{
     error::Error,
     fmt::{Debug, Display},
     path::Path,
+    time::Duration,
 };
 
 /// Initiates the UniFMU backend based on the contents of the `launch.toml`
@@ -21,18 +22,28 @@ use std::{
 /// User action may be required in the case that the backend is remote.
 /// In this case the `remote_connection_notifier` will be called with the
 /// port number that the Rust layer will be listening on.
+/// 
+/// Each line that a local backend writes to stdout or stderr before the
+/// handshake completes is passed to `on_output`.
 pub fn spawn_slave(
     resource_path: &Path,
-    remote_connection_notifier: impl Fn(&str)
+    remote_connection_notifier: impl Fn(&str),
+    on_output: impl FnMut(&str)
 ) -> SpawnResult<Dispatcher> {
     let config = LaunchConfig::create(resource_path)?;
 
     let dispatcher_result = match config.location {
         BackendLocation::Local => Dispatcher::local(
             resource_path,
-            &config.get_launch_command()?
+            &config.get_launch_command()?,
+            transport(&config),
+            config.shared_memory,
+            timeouts(&config.timeouts)
         ),
-        BackendLocation::Remote => Dispatcher::remote(remote_connection_notifier)
+        BackendLocation::Remote => Dispatcher::remote(
+            remote_connection_notifier,
+            timeouts(&config.timeouts)
+        )
     };
 
     let mut dispatcher = match dispatcher_result {
@@ -43,9 +54,12 @@ pub fn spawn_slave(
     };
 
     println!("Awaiting handshake.");
-    match dispatcher.await_handshake() {
+    match dispatcher.await_handshake(on_output) {
         Ok(_) => {
             println!("Connection established!");
+            if dispatcher.shared_memory().is_some() {
+                println!("Exchanging bulk values through shared memory.");
+            }
             Ok(dispatcher)
         },
         Err(error) => {
@@ -54,6 +68,33 @@ pub fn spawn_slave(
     } 
 }
 
+/// Converts the transport of the `launch.toml` config file to the transport
+/// that the dispatcher binds its socket with.
+/// 
+/// Falls back to TCP for a backend that can only connect through TCP.
+fn transport(config: &LaunchConfig) -> Transport {
+    match (&config.transport, config.tcp_only) {
+        (BackendTransport::Tcp, _) => Transport::Tcp,
+        (BackendTransport::Ipc, true) => {
+            println!("The backend only supports TCP, falling back from the Ipc transport.");
+            Transport::Tcp
+        },
+        (BackendTransport::Ipc, false) => Transport::Ipc,
+    }
+}
+
+/// Converts the timeouts of the `launch.toml` config file to the timeouts
+/// that the dispatcher waits on the backend with.
+fn timeouts(config: &TimeoutsConfig) -> Timeouts {
+    let call = config.call_ms.map(Duration::from_millis);
+    Timeouts {
+        handshake: config.handshake_ms.map(Duration::from_millis),
+        call,
+        do_step: config.do_step_ms.map(Duration::from_millis).or(call),
+        kill_backend: config.kill_backend,
+    }
+}
+
 pub type SpawnResult<T> = Result<T, SpawnError>;
 
 #[derive(Debug)]
@@ -0,0 +1,78 @@
+//! This module contains the `CoSimulationCapabilities` struct, which holds
+//! the capability flags declared in the `<CoSimulation>` element of the FMU's
+//! `modelDescription.xml` (see section 4.3.1 of the FMI2 specification) that
+//! change how the API talks to the backend.
+
+use std::{
+    error::Error,
+    fmt::Display
+};
+
+/// The capability flags of a Co-Simulation FMU that the API acts upon.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct CoSimulationCapabilities {
+    /// Whether `fmi2DoStep` may return `fmi2Pending` and finish the step
+    /// asynchronously.
+    pub can_run_asynchronously: bool
+}
+
+impl CoSimulationCapabilities {
+    /// Parses the `<CoSimulation>` element of a model description.
+    ///
+    /// A model description without a `<CoSimulation>` element has none of
+    /// the capabilities.
+    pub fn parse(model_description: &str) -> CapabilitiesResult<Self> {
+        let document = roxmltree::Document::parse(model_description)?;
+
+        let Some(co_simulation_element) = document
+            .root_element()
+            .children()
+            .find(|node| node.has_tag_name("CoSimulation"))
+        else {
+            return Ok(Self::default());
+        };
+
+        // The misspelling is that of the FMI2 standard.
+        let can_run_asynchronously = match co_simulation_element
+            .attribute("canRunAsynchronuously")
+        {
+            None | Some("false") => false,
+            Some("true") => true,
+            Some(value) => return Err(CapabilitiesError::InvalidAttribute(
+                String::from("canRunAsynchronuously"),
+                String::from(value)
+            ))
+        };
+
+        Ok(Self { can_run_asynchronously })
+    }
+}
+
+pub type CapabilitiesResult<T> = Result<T, CapabilitiesError>;
+
+#[derive(Debug)]
+pub enum CapabilitiesError {
+    ParseError(roxmltree::Error),
+    InvalidAttribute(String, String)
+}
+
+impl Display for CapabilitiesError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ParseError(error) => {
+                write!(f, "couldn't parse model description: {}", error)
+            }
+            Self::InvalidAttribute(attribute, value) => {
+                write!(f, "CoSimulation attribute {} has invalid value '{}'", attribute, value)
+            }
+        }
+    }
+}
+
+impl Error for CapabilitiesError {}
+
+impl From<roxmltree::Error> for CapabilitiesError {
+    fn from(value: roxmltree::Error) -> Self {
+        Self::ParseError(value)
+    }
+}
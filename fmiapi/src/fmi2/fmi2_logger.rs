@@ -21,6 +21,7 @@ use std::ffi::CStr;
 /// Primarily implements the `common::logger::Logger` trait for FMI2 types,
 /// sending log events to the implementer through the contained
 /// `Fmi2CallbackLogger` function pointer.
+#[derive(Clone)]
 pub struct Fmi2Logger {
     callback: Fmi2CallbackLogger,
     environment: *const ComponentEnvironment,
@@ -10,12 +10,13 @@ use super::{
         fmi2_return,
         fmi2_return::ReturnMessage
     },
-    fmi2_types::Fmi2Status,
+    fmi2_capabilities::CoSimulationCapabilities,
+    fmi2_types::{ComponentEnvironment, Fmi2Status, Fmi2StepFinished},
     fmi2_logger::Fmi2Logger
 };
 
 use crate::common::{
-    dispatcher::{Dispatch, Dispatcher, DispatcherError},
+    dispatcher::{Deadline, Dispatch, Dispatcher, DispatcherError},
     logger::Logger,
     protobuf_extensions::ExpectableReturn
 };
@@ -23,7 +24,16 @@ use crate::common::{
 use std::{
     error::Error,
     ffi::CString,
-    fmt::{Debug, Display}
+    fmt::{Debug, Display},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+        Mutex,
+        MutexGuard,
+        PoisonError
+    },
+    thread::{self, JoinHandle},
+    time::Duration
 };
 
 use prost::Message;
@@ -46,13 +56,30 @@ pub struct Fmi2Slave {
     /// The reason for this recommendation is that a FMU is allowed to free or overwrite the memory as soon as another call is made to the FMI interface.
     pub string_buffer: Vec<CString>,
 
-    /// Object performing remote procedure calls on the slave
-    pub dispatcher: Dispatcher,
+    /// Object performing remote procedure calls on the slave, shared with the
+    /// `StepWatcher` of a pending asynchronous step.
+    pub dispatcher: Arc<Mutex<Dispatcher>>,
 
     pub logger: Fmi2Logger,
+
+    /// The capabilities declared in the model description, if it is a
+    /// Co-Simulation FMU.
+    pub capabilities: CoSimulationCapabilities,
+
     pub last_successful_time: Option<f64>,
     pub pending_message: Option<String>,
-    pub dostep_status: Option<Fmi2Status>
+    pub dostep_status: Option<Fmi2Status>,
+
+    /// The time that an asynchronous `fmi2DoStep` that returned
+    /// `fmi2Pending` will have reached when it finishes.
+    pub pending_step_end_time: Option<f64>,
+
+    /// Only set if the importer provided the `stepFinished` callback.
+    pub step_finished: Option<StepFinishedCallback>,
+
+    /// Watches the pending asynchronous `fmi2DoStep`, if any, when the
+    /// importer provided the `stepFinished` callback.
+    pub step_watcher: Option<StepWatcher>
 }
 //  + Send + UnwindSafe + RefUnwindSafe
 // impl RefUnwindSafe for Slave {}
@@ -65,12 +92,37 @@ impl Fmi2Slave {
         logger: Fmi2Logger
     ) -> Self {
         Self {
-            dispatcher,
+            dispatcher: Arc::new(Mutex::new(dispatcher)),
             logger,
             string_buffer: Vec::new(),
             last_successful_time: None,
             pending_message: None,
             dostep_status: None,
+            capabilities: CoSimulationCapabilities::default(),
+            pending_step_end_time: None,
+            step_finished: None,
+            step_watcher: None,
+        }
+    }
+
+    /// Starts watching the asynchronous `fmi2DoStep` that just returned
+    /// `fmi2Pending`, if the importer provided the `stepFinished` callback.
+    pub fn watch_pending_step(&mut self) {
+        self.stop_watching_step();
+
+        if let Some(step_finished) = self.step_finished {
+            self.step_watcher = Some(StepWatcher::start(
+                self.dispatcher.clone(),
+                self.logger.clone(),
+                step_finished
+            ));
+        }
+    }
+
+    /// Stops watching the pending asynchronous `fmi2DoStep`, if watched.
+    pub fn stop_watching_step(&mut self) {
+        if let Some(step_watcher) = self.step_watcher.take() {
+            step_watcher.stop();
         }
     }
 
@@ -85,6 +137,9 @@ impl Fmi2Slave {
     /// Currently only the 'logger' callback is handled (accepting and
     /// emitting log events from the backend) (see page 21 fo the Fmi 2.0.5
     /// specification, and the `common::logger` module for further details).
+    /// 
+    /// Waits on the backend no longer than the call timeout of the
+    /// dispatcher, which spans the replies to any callbacks.
     pub fn dispatch<R>(
         &mut self,
         command: &(impl Message + Debug)
@@ -92,41 +147,245 @@ impl Fmi2Slave {
     where
         R: Message + ExpectableReturn<ReturnMessage>
     {
-        let mut return_message = self.dispatcher
-            .send_and_recv::<_, Fmi2Return>(command)?
+        let mut dispatcher = lock_dispatcher(&self.dispatcher);
+        let deadline = Deadline::after(dispatcher.timeouts().call);
+        dispatch_within(&mut dispatcher, &self.logger, command, deadline)
+    }
+
+    /// Dispatches a step command to the backend like `Self::dispatch()`, but
+    /// waits no longer than the do step timeout of the dispatcher.
+    pub fn dispatch_step<R>(
+        &mut self,
+        command: &(impl Message + Debug)
+    ) -> Fmi2SlaveResult<R>
+    where
+        R: Message + ExpectableReturn<ReturnMessage>
+    {
+        let mut dispatcher = lock_dispatcher(&self.dispatcher);
+        let deadline = Deadline::after(dispatcher.timeouts().do_step);
+        dispatch_within(&mut dispatcher, &self.logger, command, deadline)
+    }
+}
+
+/// Locks the dispatcher shared between the slave and its `StepWatcher`.
+/// 
+/// The dispatcher is used regardless of a panic in another holder of the
+/// lock, as a failed correspondance with the backend surfaces as an error on
+/// the next dispatch anyway.
+fn lock_dispatcher(dispatcher: &Mutex<Dispatcher>) -> MutexGuard<'_, Dispatcher> {
+    dispatcher.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Dispatches the command through the given dispatcher as described for
+/// `Fmi2Slave::dispatch()`, emitting log events from the backend through the
+/// given logger.
+fn dispatch_within<R>(
+    dispatcher: &mut Dispatcher,
+    logger: &Fmi2Logger,
+    command: &(impl Message + Debug),
+    deadline: Option<Deadline>
+) -> Fmi2SlaveResult<R>
+where
+    R: Message + ExpectableReturn<ReturnMessage>
+{
+    let mut return_message = dispatcher
+        .send_and_recv::<_, Fmi2Return>(
+            command,
+            deadline,
+            |line| logger.backend_output(line)
+        )?
+        .return_message
+        .ok_or(Fmi2SlaveError::ReturnError)?;
+
+    while let fmi2_return::ReturnMessage::Log(log_return) = return_message {
+        handle_log_return(logger, log_return);
+
+        let continue_command = Fmi2Command {
+            command: Some(Command::Fmi2CallbackContinue(
+                fmi2_messages::Fmi2CallbackContinue {}
+            )),
+        };
+
+        return_message = dispatcher
+            .send_and_recv::<_, Fmi2Return>(
+                &continue_command,
+                deadline,
+                |line| logger.backend_output(line)
+            )?
             .return_message
             .ok_or(Fmi2SlaveError::ReturnError)?;
+    }
 
-        while let fmi2_return::ReturnMessage::Log(log_return) = return_message {
-            self.handle_log_return(log_return);
+    R::extract_from(return_message)
+        .ok_or(Fmi2SlaveError::ReturnError)
+}
 
-            let continue_command = Fmi2Command {
-                command: Some(Command::Fmi2CallbackContinue(
-                    fmi2_messages::Fmi2CallbackContinue {}
-                )),
-            };
+/// Logs the logging event contained in the Fmi2LogReturn message using the
+/// given logger.
+fn handle_log_return(
+    logger: &Fmi2Logger,
+    log_return: fmi2_messages::Fmi2LogReturn
+) {
+    logger.log(
+        log_return.status().into(),
+        log_return.category.into(),
+        &log_return.log_message
+    );
+}
+
+/// The `stepFinished` callback given by the importer when instantiating the
+/// FMU.
+#[derive(Clone, Copy)]
+pub struct StepFinishedCallback {
+    pub component_environment: *const ComponentEnvironment,
+    pub step_finished: Fmi2StepFinished
+}
+
+/// How long a `StepWatcher` waits before first asking the backend whether the
+/// pending step has finished.
+const STEP_POLL_INTERVAL_MIN: Duration = Duration::from_millis(10);
+
+/// The longest that a `StepWatcher` waits between asking the backend whether
+/// the pending step has finished.
+const STEP_POLL_INTERVAL_MAX: Duration = Duration::from_millis(500);
 
-            return_message = self.dispatcher
-                .send_and_recv::<_, Fmi2Return>(&continue_command)?
-                .return_message
-                .ok_or(Fmi2SlaveError::ReturnError)?;
+/// Polls the backend for the completion of a pending asynchronous
+/// `fmi2DoStep` on a separate thread, and calls the importer's `stepFinished`
+/// callback with the status of the step once it has finished.
+/// 
+/// The backend only answers commands, so it can't announce the end of a step
+/// by itself. Each poll is a round trip to the backend during which other FMI
+/// calls wait for the dispatcher, so the interval between polls doubles from
+/// `STEP_POLL_INTERVAL_MIN` up to `STEP_POLL_INTERVAL_MAX`. `stepFinished` is
+/// thereby called at most one interval after the step has finished.
+/// 
+/// The importer may keep polling the step through `fmi2GetStatus` in the
+/// meantime, as the watcher only holds the dispatcher while polling.
+pub struct StepWatcher {
+    stop: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>
+}
+
+/// Everything that the thread of a `StepWatcher` needs.
+struct StepWatch {
+    dispatcher: Arc<Mutex<Dispatcher>>,
+    logger: Fmi2Logger,
+    callback: StepFinishedCallback,
+    stop: Arc<AtomicBool>
+}
+
+// The pointers in the logger and callback are given by the importer, which
+// keeps them valid for the lifetime of the instance. The watcher is stopped
+// before the instance is freed.
+unsafe impl Send for StepWatch {}
+
+impl StepWatcher {
+    fn start(
+        dispatcher: Arc<Mutex<Dispatcher>>,
+        logger: Fmi2Logger,
+        callback: StepFinishedCallback
+    ) -> Self {
+        let stop = Arc::new(AtomicBool::new(false));
+
+        let watch = StepWatch {
+            dispatcher,
+            logger,
+            callback,
+            stop: stop.clone()
+        };
+
+        Self {
+            stop,
+            thread: Some(thread::spawn(move || watch.run()))
         }
+    }
 
-        R::extract_from(return_message)
-            .ok_or(Fmi2SlaveError::ReturnError)
+    /// Stops the watcher, waiting for an ongoing poll of the backend to end.
+    pub fn stop(mut self) {
+        self.stop_and_join();
     }
 
-    /// Logs the logging event contained in the Fmi2LogReturn message using the
-    /// Fmi2Slaves logger.
-    fn handle_log_return(
-        &mut self,
-        log_return: fmi2_messages::Fmi2LogReturn
-    ) {
-        self.logger.log(
-            log_return.status().into(),
-            log_return.category.into(),
-            &log_return.log_message
-        );
+    fn stop_and_join(&mut self) {
+        self.stop.store(true, Ordering::Release);
+
+        if let Some(thread) = self.thread.take() {
+            // The importer may stop the watcher from within the
+            // `stepFinished` callback, which runs on the watcher's thread.
+            if thread.thread().id() != thread::current().id() {
+                let _ = thread.join();
+            }
+        }
+    }
+}
+
+impl Drop for StepWatcher {
+    fn drop(&mut self) {
+        self.stop_and_join();
+    }
+}
+
+impl StepWatch {
+    fn run(self) {
+        let cmd = Fmi2Command {
+            command: Some(Command::Fmi2GetDoStepStatus(
+                fmi2_messages::Fmi2GetDoStepStatus {},
+            )),
+        };
+
+        let mut interval = STEP_POLL_INTERVAL_MIN;
+
+        loop {
+            thread::sleep(interval);
+
+            let reply = {
+                let mut dispatcher = lock_dispatcher(&self.dispatcher);
+
+                if self.stop.load(Ordering::Acquire) {
+                    return;
+                }
+
+                let deadline = Deadline::after(dispatcher.timeouts().call);
+                dispatch_within::<fmi2_messages::Fmi2GetDoStepStatusReturn>(
+                    &mut dispatcher,
+                    &self.logger,
+                    &cmd,
+                    deadline
+                )
+            };
+
+            let status = match reply {
+                Ok(reply) => match Fmi2Status::try_from(reply.status) {
+                    Ok(Fmi2Status::Pending) => {
+                        interval = (interval * 2).min(STEP_POLL_INTERVAL_MAX);
+                        continue;
+                    }
+                    Ok(status) => status,
+                    Err(_) => {
+                        self.logger.fatal(&format!(
+                            "Unknown status [{}] returned from backend.",
+                            reply.status
+                        ));
+                        Fmi2Status::Fatal
+                    }
+                },
+                Err(error) => {
+                    self.logger.error(&format!(
+                        "Polling the asynchronous fmi2DoStep failed with error: {}.",
+                        error
+                    ));
+                    Fmi2Status::Error
+                }
+            };
+
+            unsafe {
+                (self.callback.step_finished)(
+                    self.callback.component_environment,
+                    status
+                );
+            }
+
+            return;
+        }
     }
 }
 
@@ -139,7 +398,15 @@ impl Drop for Fmi2Slave {
             )),
         };
 
-        match self.dispatcher.send(&cmd) {
+        self.stop_watching_step();
+
+        let mut dispatcher = lock_dispatcher(&self.dispatcher);
+        let deadline = Deadline::after(dispatcher.timeouts().call);
+        match dispatcher.send(
+            &cmd,
+            deadline,
+            |line| self.logger.backend_output(line)
+        ) {
             Ok(_) => self.logger.ok("Send free instance message to shut down backend."),
             Err(error) => self.logger.error(&format!(
                 "Freeing instance failed with error: {}.", error
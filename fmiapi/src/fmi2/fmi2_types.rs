@@ -179,6 +179,7 @@ pub enum Fmi2LogCategory {
     LogStatusPending,
     #[default] LogAll,
     LogUnifmuMessages,
+    LogBackendOutput,
     LogUserDefined(String)
 }
 
@@ -196,6 +197,7 @@ impl LogCategory for Fmi2LogCategory {
             Self::LogStatusPending => "logStatusPending",
             Self::LogAll => "logAll",
             Self::LogUnifmuMessages => "logUnifmuMessages",
+            Self::LogBackendOutput => "logBackendOutput",
             Self::LogUserDefined(name) => name,
         }
     }
@@ -219,6 +221,10 @@ impl LogCategory for Fmi2LogCategory {
     fn unifmu_message() -> Self {
         Self::LogUnifmuMessages
     }
+
+    fn backend_output() -> Self {
+        Self::LogBackendOutput
+    }
 }
 
 impl Display for Fmi2LogCategory {
@@ -241,6 +247,7 @@ impl From<&str> for Fmi2LogCategory {
             "logStatusPending" => Self::LogStatusPending,
             "logAll" => Self::LogAll,
             "logUnifmuMessages" => Self::LogUnifmuMessages,
+            "logBackendOutput" => Self::LogBackendOutput,
             _ => Self::LogUserDefined(String::from(value))
         }
     }
@@ -305,6 +312,29 @@ impl From<fmi2_messages::Fmi2Type> for Fmi2Type {
     }
 }
 
+impl From<Fmi2Type> for fmi2_messages::Fmi2Type {
+    fn from(src: Fmi2Type) -> Self {
+        match src {
+            Fmi2Type::Fmi2ModelExchange => Self::Fmi2ModelExchange,
+            Fmi2Type::Fmi2CoSimulation => Self::Fmi2CoSimulation,
+        }
+    }
+}
+
+/// Information about the next event, filled out by the FMU during
+/// `fmi2NewDiscreteStates`.
+/// 
+/// FMI2 Spec name: fmi2EventInfo
+#[repr(C)]
+pub struct Fmi2EventInfo {
+    pub new_discrete_states_needed: Fmi2Boolean,
+    pub terminate_simulation: Fmi2Boolean,
+    pub nominals_of_continuous_states_changed: Fmi2Boolean,
+    pub values_of_continuous_states_changed: Fmi2Boolean,
+    pub next_event_time_defined: Fmi2Boolean,
+    pub next_event_time: Fmi2Real,
+}
+
 // ----------------------- Protocol Buffer Trait decorations ---------------------------
 // The trait ExpectableReturn extends the Return message with an extract
 // function that let's us pattern match and unwrap the inner type of a
@@ -318,4 +348,11 @@ implement_expectable_return!(fmi2_messages::Fmi2GetBooleanReturn, ReturnMessage,
 implement_expectable_return!(fmi2_messages::Fmi2GetStringReturn, ReturnMessage, GetString);
 implement_expectable_return!(fmi2_messages::Fmi2GetRealOutputDerivativesReturn, ReturnMessage, GetRealOutputDerivatives);
 implement_expectable_return!(fmi2_messages::Fmi2GetDirectionalDerivativesReturn, ReturnMessage, GetDirectionalDerivatives);
-implement_expectable_return!(fmi2_messages::Fmi2SerializeFmuStateReturn, ReturnMessage, SerializeFmuState);
\ No newline at end of file
+implement_expectable_return!(fmi2_messages::Fmi2SerializeFmuStateReturn, ReturnMessage, SerializeFmuState);
+implement_expectable_return!(fmi2_messages::Fmi2NewDiscreteStatesReturn, ReturnMessage, NewDiscreteStates);
+implement_expectable_return!(fmi2_messages::Fmi2CompletedIntegratorStepReturn, ReturnMessage, CompletedIntegratorStep);
+implement_expectable_return!(fmi2_messages::Fmi2GetDerivativesReturn, ReturnMessage, GetDerivatives);
+implement_expectable_return!(fmi2_messages::Fmi2GetEventIndicatorsReturn, ReturnMessage, GetEventIndicators);
+implement_expectable_return!(fmi2_messages::Fmi2GetContinuousStatesReturn, ReturnMessage, GetContinuousStates);
+implement_expectable_return!(fmi2_messages::Fmi2GetNominalsOfContinuousStatesReturn, ReturnMessage, GetNominalsOfContinuousStates);
+implement_expectable_return!(fmi2_messages::Fmi2GetDoStepStatusReturn, ReturnMessage, GetDoStepStatus);
@@ -5,11 +5,13 @@
 #![allow(unused_variables)]
 #![deny(unsafe_op_in_unsafe_fn)]
 
+mod fmi2_capabilities;
 mod fmi2_logger;
 mod fmi2_messages;
 mod fmi2_slave;
 mod fmi2_types;
 
+use fmi2_capabilities::CoSimulationCapabilities;
 use fmi2_logger::Fmi2Logger;
 use fmi2_messages::{
     Fmi2Command,
@@ -17,7 +19,8 @@ use fmi2_messages::{
 };
 use fmi2_slave::{
     Fmi2Slave,
-    SlaveState
+    SlaveState,
+    StepFinishedCallback
 };
 use fmi2_types::{
     Fmi2Real,
@@ -26,13 +29,16 @@ use fmi2_types::{
     Fmi2String,
     Fmi2Status,
     Fmi2CallbackFunctions,
+    Fmi2EventInfo,
     Fmi2LogCategory,
     Fmi2StatusKind,
     Fmi2Type
 };
 
 use crate::common::{
+    instantiation_token::verify_instantiation_token,
     logger::Logger,
+    model_description::read_model_description,
     spawn::spawn_slave,
     string_conversion::{c2s, c2non_empty_s}
 };
@@ -117,13 +123,6 @@ pub unsafe extern "C" fn fmi2Instantiate(
         logging_on
     );
 
-    // Erroring out in case the importer tries to instantiate the FMU for
-    // Model Exchange as that is not yet implemented.
-    if let Fmi2Type::Fmi2ModelExchange = fmu_type {
-        logger.error("Model Exchange is not implemented for UNIFMU.");
-        return None
-    }
-
     let instance_name = match c2non_empty_s(instance_name) {
         Err(error) => {
             logger.error(&format!(
@@ -175,9 +174,26 @@ pub unsafe extern "C" fn fmi2Instantiate(
         Ok(resources_dir) => resources_dir
     };
 
+    let model_description = match read_model_description(&resources_dir) {
+        Ok(model_description) => model_description,
+        Err(error) => {
+            logger.error(&format!(
+                "Couldn't read model description to verify fmu_guid; {}.",
+                error
+            ));
+            return None;
+        }
+    };
+
+    if let Err(error) = verify_instantiation_token(&model_description, "guid", &fmu_guid) {
+        logger.error(&format!("Couldn't verify fmu_guid; {}.", error));
+        return None;
+    }
+
     let dispatcher = match spawn_slave(
         Path::new(&resources_dir),
-        |port| logger.communicate_port_connection_action(port)
+        |port| logger.communicate_port_connection_action(port),
+        |line| logger.backend_output(line)
     ) {
         Err(error) => {
             logger.error(&format!("Spawning fmi2 slave failed; {}", error));
@@ -186,13 +202,38 @@ pub unsafe extern "C" fn fmi2Instantiate(
         Ok(dispatcher) => dispatcher
     };
 
+    let capabilities = match fmu_type {
+        Fmi2Type::Fmi2CoSimulation => {
+            match CoSimulationCapabilities::parse(&model_description) {
+                Ok(capabilities) => capabilities,
+                Err(error) => {
+                    logger.warning(&format!(
+                        "Couldn't read Co-Simulation capabilities, fmi2DoStep will run synchronously; {}.",
+                        error
+                    ));
+                    CoSimulationCapabilities::default()
+                }
+            }
+        }
+        _ => CoSimulationCapabilities::default()
+    };
+
     let mut slave = Fmi2Slave::new(dispatcher, logger);
 
+    slave.capabilities = capabilities;
+
+    slave.step_finished = functions.step_finished.map(
+        |step_finished| StepFinishedCallback {
+            component_environment: &(functions.component_environment),
+            step_finished
+        }
+    );
+
     let cmd = Fmi2Command {
         command: Some(Command::Fmi2Instantiate(
             fmi2_messages::Fmi2Instantiate {
                 instance_name,
-                fmu_type: 0,
+                fmu_type: fmi2_messages::Fmi2Type::from(fmu_type).into(),
                 fmu_guid,
                 fmu_resource_location,
                 visible: false,
@@ -384,25 +425,50 @@ pub extern "C" fn fmi2DoStep(
     step_size: Fmi2Real,
     no_step_prior: Fmi2Boolean,
 ) -> Fmi2Status {
+    // The previous step may have finished without the importer asking for
+    // its status, as when it was announced through `stepFinished`.
+    if update_pending_step(slave, "fmi2DoStep") != Fmi2Status::Ok {
+        return Fmi2Status::Error;
+    }
+
+    if let Some(Fmi2Status::Pending) = slave.dostep_status {
+        slave.logger.error(
+            "'fmi2DoStep' called while the previous asynchronous step is still pending."
+        );
+        return Fmi2Status::Error;
+    }
+
     let cmd = Fmi2Command {
         command: Some(Command::Fmi2DoStep(
             fmi2_messages::Fmi2DoStep {
                 current_time,
                 step_size,
                 no_set_fmu_state_prior_to_current_point: no_step_prior != 0,
+                run_asynchronously: slave.capabilities.can_run_asynchronously,
             }
         )),
     };
 
-    match slave.dispatch::<fmi2_messages::Fmi2StatusReturn>(&cmd)
+    match slave.dispatch_step::<fmi2_messages::Fmi2StatusReturn>(&cmd)
         .map(|status| status.into())
     {
         Ok(status) => match status {
             Fmi2Status::Ok | Fmi2Status::Warning => {
                 slave.last_successful_time = Some(current_time + step_size);
+                slave.dostep_status = None;
+                status
+            }
+            Fmi2Status::Pending => {
+                slave.dostep_status = Some(Fmi2Status::Pending);
+                slave.pending_message = None;
+                slave.pending_step_end_time = Some(current_time + step_size);
+                slave.watch_pending_step();
+                status
+            }
+            status => {
+                slave.dostep_status = None;
                 status
             }
-            status => status,
         },
         Err(error) => {
             slave.logger.error(&format!(
@@ -415,13 +481,479 @@ pub extern "C" fn fmi2DoStep(
 
 #[no_mangle]
 pub extern "C" fn fmi2CancelStep(slave: &mut Fmi2Slave) -> Fmi2Status {
+    if update_pending_step(slave, "fmi2CancelStep") != Fmi2Status::Ok {
+        return Fmi2Status::Error;
+    }
+
+    if !matches!(slave.dostep_status, Some(Fmi2Status::Pending)) {
+        slave.logger.error(
+            "'fmi2CancelStep' called while no asynchronous 'fmi2DoStep' is pending."
+        );
+        return Fmi2Status::Error;
+    }
+
+    slave.stop_watching_step();
+
     let cmd = Fmi2Command {
         command: Some(Command::Fmi2CancelStep(
             fmi2_messages::Fmi2CancelStep {},
         )),
     };
 
-    send_cmd_recv_status(slave, cmd, "fmi2CancelStep")
+    let status = send_cmd_recv_status(slave, cmd, "fmi2CancelStep");
+
+    slave.dostep_status = None;
+    slave.pending_message = None;
+    slave.pending_step_end_time = None;
+
+    status
+}
+
+// ------------------------------------- FMI FUNCTIONS (Model Exchange) --------------------------------
+#[no_mangle]
+pub extern "C" fn fmi2EnterEventMode(slave: &mut Fmi2Slave) -> Fmi2Status {
+    let cmd = Fmi2Command {
+        command: Some(Command::Fmi2EnterEventMode(
+            fmi2_messages::Fmi2EnterEventMode {},
+        )),
+    };
+
+    send_cmd_recv_status(slave, cmd, "fmi2EnterEventMode")
+}
+
+/// # Safety
+/// Behavior is undefined if `event_info` points outside of address space and
+/// if it is dereferenced after function call.
+#[no_mangle]
+pub unsafe extern "C" fn fmi2NewDiscreteStates(
+    slave: &mut Fmi2Slave,
+    event_info: *mut Fmi2EventInfo,
+) -> Fmi2Status {
+    let event_info = match unsafe { event_info.as_mut() } {
+        Some(event_info) => event_info,
+        None => {
+            slave.logger.error(
+                "Pointer to event_info was null."
+            );
+            return Fmi2Status::Error;
+        }
+    };
+
+    let cmd = Fmi2Command {
+        command: Some(Command::Fmi2NewDiscreteStates(
+            fmi2_messages::Fmi2NewDiscreteStates {},
+        )),
+    };
+
+    match slave.dispatch::<fmi2_messages::Fmi2NewDiscreteStatesReturn>(&cmd) {
+        Ok(reply) => {
+            let status = parse_status(reply.status, &slave.logger);
+
+            if status.output_is_defined() {
+                event_info.new_discrete_states_needed = reply
+                    .new_discrete_states_needed.into();
+                event_info.terminate_simulation = reply
+                    .terminate_simulation.into();
+                event_info.nominals_of_continuous_states_changed = reply
+                    .nominals_of_continuous_states_changed.into();
+                event_info.values_of_continuous_states_changed = reply
+                    .values_of_continuous_states_changed.into();
+                event_info.next_event_time_defined = reply
+                    .next_event_time_defined.into();
+                event_info.next_event_time = reply.next_event_time;
+            }
+
+            status
+        }
+        Err(error) => {
+            slave.logger.error(&format!(
+                "fmi2NewDiscreteStates failed with error: {}.", error
+            ));
+            Fmi2Status::Error
+        }
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn fmi2EnterContinuousTimeMode(slave: &mut Fmi2Slave) -> Fmi2Status {
+    let cmd = Fmi2Command {
+        command: Some(Command::Fmi2EnterContinuousTimeMode(
+            fmi2_messages::Fmi2EnterContinuousTimeMode {},
+        )),
+    };
+
+    send_cmd_recv_status(slave, cmd, "fmi2EnterContinuousTimeMode")
+}
+
+/// # Safety
+/// Behavior is undefined if any of `enter_event_mode` and
+/// `terminate_simulation` points outside of address space and if they are
+/// dereferenced after function call.
+#[no_mangle]
+pub unsafe extern "C" fn fmi2CompletedIntegratorStep(
+    slave: &mut Fmi2Slave,
+    no_set_fmu_state_prior_to_current_point: Fmi2Boolean,
+    enter_event_mode: *mut Fmi2Boolean,
+    terminate_simulation: *mut Fmi2Boolean,
+) -> Fmi2Status {
+    let cmd = Fmi2Command {
+        command: Some(Command::Fmi2CompletedIntegratorStep(
+            fmi2_messages::Fmi2CompletedIntegratorStep {
+                no_set_fmu_state_prior_to_current_point:
+                    no_set_fmu_state_prior_to_current_point != 0,
+            }
+        )),
+    };
+
+    match slave.dispatch::<fmi2_messages::Fmi2CompletedIntegratorStepReturn>(&cmd) {
+        Ok(reply) => {
+            let mut status = parse_status(reply.status, &slave.logger);
+
+            if status.output_is_defined() {
+                match unsafe { enter_event_mode.as_mut() } {
+                    Some(enter_event_mode) => {
+                        *enter_event_mode = reply.enter_event_mode.into();
+                    }
+                    None => {
+                        slave.logger.warning(
+                            "The parameter enter_event_mode was a null pointer and consequently wasn't set as part of the integrator step."
+                        );
+                        status = status.escalate_status(Fmi2Status::Warning);
+                    }
+                }
+
+                match unsafe { terminate_simulation.as_mut() } {
+                    Some(terminate_simulation) => {
+                        *terminate_simulation = reply.terminate_simulation.into();
+                    }
+                    None => {
+                        slave.logger.warning(
+                            "The parameter terminate_simulation was a null pointer and consequently wasn't set as part of the integrator step."
+                        );
+                        status = status.escalate_status(Fmi2Status::Warning);
+                    }
+                }
+            }
+
+            status
+        }
+        Err(error) => {
+            slave.logger.error(&format!(
+                "fmi2CompletedIntegratorStep failed with error: {}.", error
+            ));
+            Fmi2Status::Error
+        }
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn fmi2SetTime(slave: &mut Fmi2Slave, time: Fmi2Real) -> Fmi2Status {
+    let cmd = Fmi2Command {
+        command: Some(Command::Fmi2SetTime(
+            fmi2_messages::Fmi2SetTime { time },
+        )),
+    };
+
+    send_cmd_recv_status(slave, cmd, "fmi2SetTime")
+}
+
+/// # Safety
+/// Behavior is undefined if any of the following conditions are violated:
+/// * `continuous_states` must be \[valid\] for reads for
+///   `nx * mem::size_of::<c_double>()` many bytes, and it must be properly
+///   aligned. This means in particular:
+///     * The entire memory range of this slice must be contained within a
+///       single allocated object! Slices can never span across multiple
+///       allocated objects.
+///     * `continuous_states` may only be null if `nx` is 0, as a null
+///       pointer is otherwise rejected with `Fmi2Status::Error`.
+/// * `continuous_states` must point to `nx` consecutive properly initialized
+///   values of type `c_double`.
+/// * The total size `nx * mem::size_of::<c_double>()` of the slice must be no
+///   larger than `isize::MAX`, and adding that size to `continuous_states`
+///   must not "wrap around" the address space. See the safety documentation
+///   of [`pointer::offset`].
+#[no_mangle]
+pub unsafe extern "C" fn fmi2SetContinuousStates(
+    slave: &mut Fmi2Slave,
+    continuous_states: *const Fmi2Real,
+    nx: size_t,
+) -> Fmi2Status {
+    if continuous_states.is_null() && nx > 0 {
+        slave.logger.error(
+            "fmi2SetContinuousStates was called with a null pointer."
+        );
+        return Fmi2Status::Error;
+    }
+
+    let continuous_states = match nx {
+        0 => Vec::new(),
+        _ => unsafe { from_raw_parts(continuous_states, nx) }.to_owned()
+    };
+
+    let cmd = Fmi2Command {
+        command: Some(Command::Fmi2SetContinuousStates(
+            fmi2_messages::Fmi2SetContinuousStates { continuous_states },
+        )),
+    };
+
+    send_cmd_recv_status(slave, cmd, "fmi2SetContinuousStates")
+}
+
+/// # Safety
+/// Behavior is undefined if any of the following conditions are violated:
+/// * `derivatives` must be \[valid\] for writes for
+///   `nx * mem::size_of::<c_double>()` many bytes, and it must be properly
+///   aligned. This means in particular:
+///     * The entire memory range of this slice must be contained within a
+///       single allocated object! Slices can never span across multiple
+///       allocated objects.
+///     * `derivatives` may only be null if `nx` is 0, as a null pointer is
+///       otherwise rejected with `Fmi2Status::Error`.
+/// * The total size `nx * mem::size_of::<c_double>()` of the slice must be no
+///   larger than `isize::MAX`, and adding that size to `derivatives` must not
+///   "wrap around" the address space. See the safety documentation of
+///   [`pointer::offset`].
+#[no_mangle]
+pub unsafe extern "C" fn fmi2GetDerivatives(
+    slave: &mut Fmi2Slave,
+    derivatives: *mut Fmi2Real,
+    nx: size_t,
+) -> Fmi2Status {
+    if derivatives.is_null() && nx > 0 {
+        slave.logger.error(
+            "fmi2GetDerivatives was called with a null pointer."
+        );
+        return Fmi2Status::Error;
+    }
+
+    let cmd = Fmi2Command {
+        command: Some(Command::Fmi2GetDerivatives(
+            fmi2_messages::Fmi2GetDerivatives {
+                n_continuous_states: nx as u64,
+            },
+        )),
+    };
+
+    match slave.dispatch::<fmi2_messages::Fmi2GetDerivativesReturn>(&cmd) {
+        Ok(reply) => {
+            let status = parse_status(reply.status, &slave.logger);
+
+            if status.output_is_defined() {
+                let derivatives_out: &mut [_] = match nx {
+                    0 => &mut [],
+                    _ => unsafe { from_raw_parts_mut(derivatives, nx) }
+                };
+                copy_reply_values(
+                    derivatives_out,
+                    &reply.derivatives,
+                    status,
+                    &slave.logger,
+                    "fmi2GetDerivatives"
+                )
+            } else {
+                status
+            }
+        }
+        Err(error) => {
+            slave.logger.error(&format!(
+                "fmi2GetDerivatives failed with error: {}.", error
+            ));
+            Fmi2Status::Error
+        }
+    }
+}
+
+/// # Safety
+/// Behavior is undefined if any of the following conditions are violated:
+/// * `event_indicators` must be \[valid\] for writes for
+///   `ni * mem::size_of::<c_double>()` many bytes, and it must be properly
+///   aligned. This means in particular:
+///     * The entire memory range of this slice must be contained within a
+///       single allocated object! Slices can never span across multiple
+///       allocated objects.
+///     * `event_indicators` may only be null if `ni` is 0, as a null
+///       pointer is otherwise rejected with `Fmi2Status::Error`.
+/// * The total size `ni * mem::size_of::<c_double>()` of the slice must be no
+///   larger than `isize::MAX`, and adding that size to `event_indicators`
+///   must not "wrap around" the address space. See the safety documentation
+///   of [`pointer::offset`].
+#[no_mangle]
+pub unsafe extern "C" fn fmi2GetEventIndicators(
+    slave: &mut Fmi2Slave,
+    event_indicators: *mut Fmi2Real,
+    ni: size_t,
+) -> Fmi2Status {
+    if event_indicators.is_null() && ni > 0 {
+        slave.logger.error(
+            "fmi2GetEventIndicators was called with a null pointer."
+        );
+        return Fmi2Status::Error;
+    }
+
+    let cmd = Fmi2Command {
+        command: Some(Command::Fmi2GetEventIndicators(
+            fmi2_messages::Fmi2GetEventIndicators {
+                n_event_indicators: ni as u64,
+            },
+        )),
+    };
+
+    match slave.dispatch::<fmi2_messages::Fmi2GetEventIndicatorsReturn>(&cmd) {
+        Ok(reply) => {
+            let status = parse_status(reply.status, &slave.logger);
+
+            if status.output_is_defined() {
+                let event_indicators_out: &mut [_] = match ni {
+                    0 => &mut [],
+                    _ => unsafe { from_raw_parts_mut(event_indicators, ni) }
+                };
+                copy_reply_values(
+                    event_indicators_out,
+                    &reply.event_indicators,
+                    status,
+                    &slave.logger,
+                    "fmi2GetEventIndicators"
+                )
+            } else {
+                status
+            }
+        }
+        Err(error) => {
+            slave.logger.error(&format!(
+                "fmi2GetEventIndicators failed with error: {}.", error
+            ));
+            Fmi2Status::Error
+        }
+    }
+}
+
+/// # Safety
+/// Behavior is undefined if any of the following conditions are violated:
+/// * `continuous_states` must be \[valid\] for writes for
+///   `nx * mem::size_of::<c_double>()` many bytes, and it must be properly
+///   aligned. This means in particular:
+///     * The entire memory range of this slice must be contained within a
+///       single allocated object! Slices can never span across multiple
+///       allocated objects.
+///     * `continuous_states` may only be null if `nx` is 0, as a null
+///       pointer is otherwise rejected with `Fmi2Status::Error`.
+/// * The total size `nx * mem::size_of::<c_double>()` of the slice must be no
+///   larger than `isize::MAX`, and adding that size to `continuous_states`
+///   must not "wrap around" the address space. See the safety documentation
+///   of [`pointer::offset`].
+#[no_mangle]
+pub unsafe extern "C" fn fmi2GetContinuousStates(
+    slave: &mut Fmi2Slave,
+    continuous_states: *mut Fmi2Real,
+    nx: size_t,
+) -> Fmi2Status {
+    if continuous_states.is_null() && nx > 0 {
+        slave.logger.error(
+            "fmi2GetContinuousStates was called with a null pointer."
+        );
+        return Fmi2Status::Error;
+    }
+
+    let cmd = Fmi2Command {
+        command: Some(Command::Fmi2GetContinuousStates(
+            fmi2_messages::Fmi2GetContinuousStates {
+                n_continuous_states: nx as u64,
+            },
+        )),
+    };
+
+    match slave.dispatch::<fmi2_messages::Fmi2GetContinuousStatesReturn>(&cmd) {
+        Ok(reply) => {
+            let status = parse_status(reply.status, &slave.logger);
+
+            if status.output_is_defined() {
+                let continuous_states_out: &mut [_] = match nx {
+                    0 => &mut [],
+                    _ => unsafe { from_raw_parts_mut(continuous_states, nx) }
+                };
+                copy_reply_values(
+                    continuous_states_out,
+                    &reply.continuous_states,
+                    status,
+                    &slave.logger,
+                    "fmi2GetContinuousStates"
+                )
+            } else {
+                status
+            }
+        }
+        Err(error) => {
+            slave.logger.error(&format!(
+                "fmi2GetContinuousStates failed with error: {}.", error
+            ));
+            Fmi2Status::Error
+        }
+    }
+}
+
+/// # Safety
+/// Behavior is undefined if any of the following conditions are violated:
+/// * `nominals` must be \[valid\] for writes for
+///   `nx * mem::size_of::<c_double>()` many bytes, and it must be properly
+///   aligned. This means in particular:
+///     * The entire memory range of this slice must be contained within a
+///       single allocated object! Slices can never span across multiple
+///       allocated objects.
+///     * `nominals` may only be null if `nx` is 0, as a null pointer is
+///       otherwise rejected with `Fmi2Status::Error`.
+/// * The total size `nx * mem::size_of::<c_double>()` of the slice must be no
+///   larger than `isize::MAX`, and adding that size to `nominals` must not
+///   "wrap around" the address space. See the safety documentation of
+///   [`pointer::offset`].
+#[no_mangle]
+pub unsafe extern "C" fn fmi2GetNominalsOfContinuousStates(
+    slave: &mut Fmi2Slave,
+    nominals: *mut Fmi2Real,
+    nx: size_t,
+) -> Fmi2Status {
+    if nominals.is_null() && nx > 0 {
+        slave.logger.error(
+            "fmi2GetNominalsOfContinuousStates was called with a null pointer."
+        );
+        return Fmi2Status::Error;
+    }
+
+    let cmd = Fmi2Command {
+        command: Some(Command::Fmi2GetNominalsOfContinuousStates(
+            fmi2_messages::Fmi2GetNominalsOfContinuousStates {
+                n_continuous_states: nx as u64,
+            },
+        )),
+    };
+
+    match slave.dispatch::<fmi2_messages::Fmi2GetNominalsOfContinuousStatesReturn>(&cmd) {
+        Ok(reply) => {
+            let status = parse_status(reply.status, &slave.logger);
+
+            if status.output_is_defined() {
+                let nominals_out: &mut [_] = match nx {
+                    0 => &mut [],
+                    _ => unsafe { from_raw_parts_mut(nominals, nx) }
+                };
+                copy_reply_values(
+                    nominals_out,
+                    &reply.nominals,
+                    status,
+                    &slave.logger,
+                    "fmi2GetNominalsOfContinuousStates"
+                )
+            } else {
+                status
+            }
+        }
+        Err(error) => {
+            slave.logger.error(&format!(
+                "fmi2GetNominalsOfContinuousStates failed with error: {}.", error
+            ));
+            Fmi2Status::Error
+        }
+    }
 }
 
 // ------------------------------------- FMI FUNCTIONS (Getters) --------------------------------
@@ -1123,21 +1655,20 @@ pub unsafe extern "C" fn fmi2GetRealOutputDerivatives(
         &cmd
     ) {
         Ok(reply) => {
-            let mut status = parse_status(reply.status, &slave.logger);
+            let status = parse_status(reply.status, &slave.logger);
 
             if status.output_is_defined() {
-                if !reply.values.is_empty() {
-                    let values_out = unsafe { from_raw_parts_mut(values, nvr) };
-                    values_out.copy_from_slice(&reply.values)
-                } else {
-                    slave.logger.warning(
-                        "fmi2GetRealOutputDerivatives returned no values."
-                    );
-                    status = status.escalate_status(Fmi2Status::Warning);
-                }
+                let values_out = unsafe { from_raw_parts_mut(values, nvr) };
+                copy_reply_values(
+                    values_out,
+                    &reply.values,
+                    status,
+                    &slave.logger,
+                    "fmi2GetRealOutputDerivatives"
+                )
+            } else {
+                status
             }
-
-            status
         }
         Err(error) => {
             slave.logger.error(&format!(
@@ -1443,21 +1974,38 @@ pub extern "C" fn fmi2SerializedFMUstateSize(
 }
 
 // ------------------------------------- FMI FUNCTIONS (Status) --------------------------------
+/// # Safety
+/// Behavior is undefined if `value` points outside of address space and if it
+/// is dereferenced after function call.
 #[no_mangle]
-pub extern "C" fn fmi2GetStatus(
+pub unsafe extern "C" fn fmi2GetStatus(
     slave: &mut Fmi2Slave,
     status_kind: Fmi2StatusKind,
     value: *mut Fmi2Status,
 ) -> Fmi2Status {
+    if value.is_null() {
+        slave.logger.error("'fmi2GetStatus' called with a null value pointer.");
+        return Fmi2Status::Error;
+    }
+
     match status_kind {
-        Fmi2StatusKind::Fmi2DoStepStatus => match slave.dostep_status {
-            Some(status) => status,
-            None => {
+        Fmi2StatusKind::Fmi2DoStepStatus => {
+            if slave.dostep_status.is_none() {
                 slave.logger.error(
                     "'fmi2GetStatus' called with fmi2StatusKind 'Fmi2DoStepStatus' before 'fmi2DoStep' has returned pending."
                 );
-                Fmi2Status::Error
+                return Fmi2Status::Error;
+            }
+
+            let status = update_pending_step(slave, "fmi2GetStatus");
+
+            if let (Fmi2Status::Ok, Some(dostep_status)) = (status, slave.dostep_status) {
+                unsafe {
+                    *value = dostep_status;
+                }
             }
+
+            status
         },
         _ => {
             slave.logger.error(&format!(
@@ -1478,6 +2026,10 @@ pub unsafe extern "C" fn fmi2GetRealStatus(
     status_kind: Fmi2StatusKind,
     value: *mut Fmi2Real,
 ) -> Fmi2Status {
+    if update_pending_step(slave, "fmi2GetRealStatus") != Fmi2Status::Ok {
+        return Fmi2Status::Error;
+    }
+
     match status_kind {
         Fmi2StatusKind::Fmi2LastSuccessfulTime => match slave.last_successful_time {
             Some(last_time) => {
@@ -1527,16 +2079,100 @@ pub extern "C" fn fmi2GetBooleanStatus(
     Fmi2Status::Error
 }
 
+/// # Safety
+/// Behavior is undefined if `value` points outside of address space and if it
+/// is dereferenced after function call.
 #[no_mangle]
-pub extern "C" fn fmi2GetStringStatus(
+pub unsafe extern "C" fn fmi2GetStringStatus(
     slave: &mut Fmi2Slave,
     status_kind: Fmi2StatusKind,
     value: *mut Fmi2String,
 ) -> Fmi2Status {
-    slave.logger.error(
-        "fmi2GetStringStatus is not implemented by UniFMU."
-    );
-    Fmi2Status::Error
+    match status_kind {
+        Fmi2StatusKind::Fmi2PendingStatus => {
+            if update_pending_step(slave, "fmi2GetStringStatus") != Fmi2Status::Ok {
+                return Fmi2Status::Error;
+            }
+
+            let Some(pending_message) = slave.pending_message.take() else {
+                slave.logger.error(
+                    "'fmi2GetStringStatus' called with fmi2StatusKind 'Fmi2PendingStatus' while no asynchronous 'fmi2DoStep' is pending."
+                );
+                return Fmi2Status::Error;
+            };
+
+            match CString::new(pending_message) {
+                Ok(pending_message) => {
+                    slave.string_buffer = vec![pending_message];
+                    unsafe {
+                        *value = slave.string_buffer[0].as_ptr();
+                    }
+                    Fmi2Status::Ok
+                }
+                Err(error) => {
+                    slave.logger.error(&format!(
+                        "Couldn't convert pending message to a C string; {}.", error
+                    ));
+                    Fmi2Status::Error
+                }
+            }
+        }
+        _ => {
+            slave.logger.error(&format!(
+                "'fmi2GetStringStatus' only accepts the status kind '{:?}'",
+                Fmi2StatusKind::Fmi2PendingStatus
+            ));
+            Fmi2Status::Error
+        }
+    }
+}
+
+/// Asks the backend whether the asynchronous `fmi2DoStep` that returned
+/// `fmi2Pending` has finished, updating the step status, pending message and
+/// last successful time of the slave with the answer.
+/// 
+/// Does nothing if no asynchronous step is pending. If the correspondance
+/// with the backend fails, this returns a Fmi2Status::Error and emits an
+/// error message through the given slave's logger.
+fn update_pending_step(slave: &mut Fmi2Slave, function_name: &str) -> Fmi2Status {
+    if !matches!(slave.dostep_status, Some(Fmi2Status::Pending)) {
+        return Fmi2Status::Ok;
+    }
+
+    let cmd = Fmi2Command {
+        command: Some(Command::Fmi2GetDoStepStatus(
+            fmi2_messages::Fmi2GetDoStepStatus {},
+        )),
+    };
+
+    match slave.dispatch::<fmi2_messages::Fmi2GetDoStepStatusReturn>(&cmd) {
+        Ok(reply) => {
+            let dostep_status = parse_status(reply.status, &slave.logger);
+
+            match dostep_status {
+                Fmi2Status::Pending => {
+                    slave.pending_message = Some(reply.pending_message);
+                }
+                Fmi2Status::Ok | Fmi2Status::Warning => {
+                    slave.last_successful_time = slave.pending_step_end_time.take();
+                    slave.pending_message = None;
+                }
+                _ => {
+                    slave.pending_step_end_time = None;
+                    slave.pending_message = None;
+                }
+            }
+
+            slave.dostep_status = Some(dostep_status);
+            Fmi2Status::Ok
+        }
+        Err(error) => {
+            slave.logger.error(&format!(
+                "{function_name} failed with error: {error}."
+            ));
+            Fmi2Status::Error
+        }
+    }
 }
 
 /// Send a Fmi2Command to the backend and parse and return the status that it
@@ -1576,4 +2212,34 @@ fn parse_status(status_int: i32, logger: &Fmi2Logger) -> Fmi2Status {
             ));
             Fmi2Status::Fatal
     })
-}
\ No newline at end of file
+}
+
+/// Copies the values returned by the backend into the given output slice,
+/// escalating and returning the given status if the values can't be copied.
+/// 
+/// If the backend returned a different number of values than expected, an
+/// error message is emitted through the given logger and the status is
+/// escalated to Fmi2Status::Error. If the backend returned no values where
+/// some were expected, a warning is emitted instead.
+fn copy_reply_values(
+    values_out: &mut [Fmi2Real],
+    reply_values: &[Fmi2Real],
+    status: Fmi2Status,
+    logger: &Fmi2Logger,
+    function_name: &str
+) -> Fmi2Status {
+    if values_out.len() == reply_values.len() {
+        values_out.copy_from_slice(reply_values);
+        status
+    } else if reply_values.is_empty() {
+        logger.warning(&format!("{function_name} returned no values."));
+        status.escalate_status(Fmi2Status::Warning)
+    } else {
+        logger.error(&format!(
+            "{function_name} returned {} values, but {} was expected",
+            reply_values.len(),
+            values_out.len()
+        ));
+        status.escalate_status(Fmi2Status::Error)
+    }
+}
@@ -0,0 +1,187 @@
+//! This module contains the `ModelStructure` struct, which holds the
+//! dependency information declared in the `<ModelStructure>` element of the
+//! FMU's `modelDescription.xml` (see section 2.4.8 of the FMI3
+//! specification).
+//!
+//! This information is static for a given FMU, so the API can answer the
+//! variable dependency functions of an instance without involving the
+//! backend.
+
+use super::fmi3_types::{Fmi3DependencyKind, Fmi3ValueReference};
+
+use std::{
+    collections::HashMap,
+    error::Error,
+    fmt::Display
+};
+
+/// A single dependency of an unknown on a known variable.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Dependency {
+    pub independent: Fmi3ValueReference,
+    pub kind: Fmi3DependencyKind
+}
+
+/// The dependencies of every unknown listed in the `<ModelStructure>` element.
+///
+/// An unknown mapped to `None` is listed without a `dependencies` attribute,
+/// meaning that it may depend on every known variable.
+#[derive(Debug, Default)]
+pub struct ModelStructure {
+    unknowns: HashMap<Fmi3ValueReference, Option<Vec<Dependency>>>
+}
+
+impl ModelStructure {
+    /// Parses the `<ModelStructure>` element of a model description.
+    ///
+    /// An unknown that is listed more than once (for instance both as an
+    /// `<Output>` and an `<InitialUnknown>`) keeps the dependencies of the
+    /// element that isn't an `<InitialUnknown>`, as those are the ones that
+    /// hold outside of Initialization Mode.
+    pub fn parse(model_description: &str) -> ModelStructureResult<Self> {
+        let document = roxmltree::Document::parse(model_description)?;
+
+        let model_structure_element = document
+            .root_element()
+            .children()
+            .find(|node| node.has_tag_name("ModelStructure"))
+            .ok_or(ModelStructureError::NoModelStructure)?;
+
+        let mut unknowns = HashMap::new();
+
+        for element in model_structure_element
+            .children()
+            .filter(|node| node.is_element())
+        {
+            let value_reference = parse_value_reference(
+                element.attribute("valueReference"),
+                &element
+            )?;
+
+            let dependencies = parse_dependencies(&element)?;
+
+            if element.has_tag_name("InitialUnknown") {
+                unknowns.entry(value_reference).or_insert(dependencies);
+            } else {
+                unknowns.insert(value_reference, dependencies);
+            }
+        }
+
+        Ok(Self { unknowns })
+    }
+
+    /// Returns the dependencies of the unknown with the given value reference.
+    pub fn dependencies(
+        &self,
+        unknown: Fmi3ValueReference
+    ) -> ModelStructureResult<&[Dependency]> {
+        match self.unknowns.get(&unknown) {
+            Some(Some(dependencies)) => Ok(dependencies),
+            Some(None) => Err(ModelStructureError::UndeclaredDependencies(unknown)),
+            None => Err(ModelStructureError::NotAnUnknown(unknown))
+        }
+    }
+}
+
+fn parse_value_reference(
+    value_reference: Option<&str>,
+    element: &roxmltree::Node
+) -> ModelStructureResult<Fmi3ValueReference> {
+    value_reference
+        .and_then(|value_reference| value_reference.parse().ok())
+        .ok_or_else(|| ModelStructureError::InvalidElement(
+            element.tag_name().name().to_string()
+        ))
+}
+
+fn parse_dependencies(
+    element: &roxmltree::Node
+) -> ModelStructureResult<Option<Vec<Dependency>>> {
+    let Some(dependencies) = element.attribute("dependencies") else {
+        return Ok(None);
+    };
+
+    let independents = dependencies
+        .split_whitespace()
+        .map(|independent| parse_value_reference(Some(independent), element))
+        .collect::<ModelStructureResult<Vec<_>>>()?;
+
+    let kinds = match element.attribute("dependenciesKind") {
+        Some(kinds) => kinds
+            .split_whitespace()
+            .map(|kind| parse_dependency_kind(kind, element))
+            .collect::<ModelStructureResult<Vec<_>>>()?,
+        None => vec![Fmi3DependencyKind::Fmi3Dependent; independents.len()]
+    };
+
+    if kinds.len() != independents.len() {
+        return Err(ModelStructureError::InvalidElement(
+            element.tag_name().name().to_string()
+        ));
+    }
+
+    Ok(Some(
+        independents.into_iter()
+            .zip(kinds)
+            .map(|(independent, kind)| Dependency { independent, kind })
+            .collect()
+    ))
+}
+
+fn parse_dependency_kind(
+    kind: &str,
+    element: &roxmltree::Node
+) -> ModelStructureResult<Fmi3DependencyKind> {
+    match kind {
+        "independent" => Ok(Fmi3DependencyKind::Fmi3Independent),
+        "constant" => Ok(Fmi3DependencyKind::Fmi3Constant),
+        "fixed" => Ok(Fmi3DependencyKind::Fmi3Fixed),
+        "tunable" => Ok(Fmi3DependencyKind::Fmi3Tunable),
+        "discrete" => Ok(Fmi3DependencyKind::Fmi3Discrete),
+        "dependent" => Ok(Fmi3DependencyKind::Fmi3Dependent),
+        _ => Err(ModelStructureError::InvalidElement(
+            element.tag_name().name().to_string()
+        ))
+    }
+}
+
+pub type ModelStructureResult<T> = Result<T, ModelStructureError>;
+
+#[derive(Debug)]
+pub enum ModelStructureError {
+    ParseError(roxmltree::Error),
+    NoModelStructure,
+    InvalidElement(String),
+    NotAnUnknown(Fmi3ValueReference),
+    UndeclaredDependencies(Fmi3ValueReference)
+}
+
+impl Display for ModelStructureError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ParseError(error) => {
+                write!(f, "couldn't parse model description: {}", error)
+            }
+            Self::NoModelStructure => {
+                write!(f, "model description has no ModelStructure element")
+            }
+            Self::InvalidElement(tag_name) => {
+                write!(f, "ModelStructure contains an invalid {} element", tag_name)
+            }
+            Self::NotAnUnknown(value_reference) => {
+                write!(f, "variable with value reference {} is not listed in the ModelStructure", value_reference)
+            }
+            Self::UndeclaredDependencies(value_reference) => {
+                write!(f, "the ModelStructure doesn't declare the dependencies of variable with value reference {}", value_reference)
+            }
+        }
+    }
+}
+
+impl Error for ModelStructureError {}
+
+impl From<roxmltree::Error> for ModelStructureError {
+    fn from(value: roxmltree::Error) -> Self {
+        Self::ParseError(value)
+    }
+}
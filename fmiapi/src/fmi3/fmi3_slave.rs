@@ -3,6 +3,7 @@
 
 use super::{
     fmi3_logger::Fmi3Logger,
+    fmi3_model_structure::ModelStructure,
     fmi3_messages::{
         self,
         Fmi3Command,
@@ -11,11 +12,20 @@ use super::{
         fmi3_return,
         fmi3_return::ReturnMessage
     },
-    fmi3_types::Fmi3Byte
+    fmi3_types::{
+        Fmi3Byte,
+        Fmi3Boolean,
+        Fmi3ClockUpdateCallback,
+        Fmi3Float64,
+        Fmi3InstanceEnvironment,
+        Fmi3IntermediateUpdateCallback,
+        Fmi3LockPreemptionCallback,
+        Fmi3UnlockPreemptionCallback
+    }
 };
 
 use crate::common::{
-    dispatcher::{Dispatch, Dispatcher, DispatcherError},
+    dispatcher::{Deadline, Dispatch, Dispatcher, DispatcherError, SharedMemory},
     logger::Logger,
     protobuf_extensions::ExpectableReturn
 };
@@ -44,6 +54,14 @@ pub struct Fmi3Slave {
     dispatcher: Dispatcher,
     pub logger: Fmi3Logger,
     pub last_successful_time: Option<f64>,
+    /// Only set if the FMU's modelDescription.xml could be read and contains
+    /// a valid `<ModelStructure>` element.
+    pub model_structure: Option<ModelStructure>,
+    /// Only set if the instance was instantiated for Co-Simulation and the
+    /// importer provided an `fmi3IntermediateUpdateCallback`.
+    pub intermediate_update_callback: Option<IntermediateUpdateCallback>,
+    /// Only set if the instance was instantiated for Scheduled Execution.
+    pub scheduled_execution_callbacks: Option<ScheduledExecutionCallbacks>,
     pub string_buffer: Vec<CString>
 }
 
@@ -54,6 +72,9 @@ impl Fmi3Slave {
             dispatcher,
             logger,
             last_successful_time: None,
+            model_structure: None,
+            intermediate_update_callback: None,
+            scheduled_execution_callbacks: None,
             string_buffer: Vec::new()
         }
     }
@@ -66,36 +87,116 @@ impl Fmi3Slave {
     /// return message from the backend, or if communication with the backend
     /// is disrupted.
     /// 
-    /// Currently only the 'fmi3LogMessageCallback' callback is handled
-    /// (accepting and emitting log events from the backend) (see section
-    /// 2.3.1 of the FMI3 specification, and the `common::logger` module for
-    /// further details).
-    pub fn dispatch<R>(&mut self, command: &(impl Message + Debug)) -> Fmi3SlaveResult<R>
+    /// The 'fmi3LogMessageCallback' callback is handled by accepting and
+    /// emitting log events from the backend (see section 2.3.1 of the FMI3
+    /// specification, and the `common::logger` module for further details).
+    /// 
+    /// For instances instantiated for Scheduled Execution the
+    /// 'fmi3ClockUpdateCallback', 'fmi3LockPreemptionCallback' and
+    /// 'fmi3UnlockPreemptionCallback' callbacks are handled by calling the
+    /// corresponding callback given by the importer (see section 5.2.1 of the
+    /// FMI3 specification).
+    /// 
+    /// The 'fmi3IntermediateUpdateCallback' callback is handled by calling
+    /// the callback given by the importer, if any, and relaying the importers
+    /// early return request back to the backend (see section 4.2.2 of the
+    /// FMI3 specification).
+    /// 
+    /// Waits on the backend no longer than the call timeout of the
+    /// dispatcher, which spans the replies to any callbacks.
+    pub fn dispatch<R>(
+        &mut self,
+        command: &(impl Message + Debug)
+    ) -> Fmi3SlaveResult<R>
+    where
+        R: Message + ExpectableReturn<ReturnMessage>
+    {
+        let deadline = Deadline::after(self.dispatcher.timeouts().call);
+        self.dispatch_within(command, deadline)
+    }
+
+    /// Dispatches a step command to the backend like `Self::dispatch()`, but
+    /// waits no longer than the do step timeout of the dispatcher.
+    pub fn dispatch_step<R>(
+        &mut self,
+        command: &(impl Message + Debug)
+    ) -> Fmi3SlaveResult<R>
+    where
+        R: Message + ExpectableReturn<ReturnMessage>
+    {
+        let deadline = Deadline::after(self.dispatcher.timeouts().do_step);
+        self.dispatch_within(command, deadline)
+    }
+
+    /// The shared memory region for bulk values that the backend accepted
+    /// during the handshake, if any.
+    pub fn shared_memory(&mut self) -> Option<&mut SharedMemory> {
+        self.dispatcher.shared_memory()
+    }
+
+    fn dispatch_within<R>(
+        &mut self,
+        command: &(impl Message + Debug),
+        deadline: Option<Deadline>
+    ) -> Fmi3SlaveResult<R>
     where
         R: Message + ExpectableReturn<ReturnMessage>
     {
         let mut return_message = self.dispatcher
-            .send_and_recv::<_, Fmi3Return>(command)?
+            .send_and_recv::<_, Fmi3Return>(
+                command,
+                deadline,
+                |line| self.logger.backend_output(line)
+            )?
             .return_message
             .ok_or(Fmi3SlaveError::ReturnError)?;
 
-        while let fmi3_return::ReturnMessage::Log(log_return) = return_message {
-            self.handle_log_return(log_return);
-
-            let continue_command = Fmi3Command {
-                command: Some(Command::Fmi3CallbackContinue(
-                    fmi3_messages::Fmi3CallbackContinue {}
-                )),
+        loop {
+            let continue_command = match return_message {
+                fmi3_return::ReturnMessage::Log(log_return) => {
+                    self.handle_log_return(log_return);
+                    Self::callback_continue_command()
+                }
+                fmi3_return::ReturnMessage::ClockUpdate(_) => {
+                    self.handle_clock_update_return();
+                    Self::callback_continue_command()
+                }
+                fmi3_return::ReturnMessage::LockPreemption(_) => {
+                    self.handle_lock_preemption_return();
+                    Self::callback_continue_command()
+                }
+                fmi3_return::ReturnMessage::UnlockPreemption(_) => {
+                    self.handle_unlock_preemption_return();
+                    Self::callback_continue_command()
+                }
+                fmi3_return::ReturnMessage::IntermediateUpdate(update_return) => {
+                    self.handle_intermediate_update_return(update_return)
+                }
+                return_message => {
+                    return R::extract_from(return_message)
+                        .ok_or(Fmi3SlaveError::ReturnError);
+                }
             };
 
             return_message = self.dispatcher
-                .send_and_recv::<_, Fmi3Return>(&continue_command)?
+                .send_and_recv::<_, Fmi3Return>(
+                    &continue_command,
+                    deadline,
+                    |line| self.logger.backend_output(line)
+                )?
                 .return_message
                 .ok_or(Fmi3SlaveError::ReturnError)?;
         }
+    }
 
-        R::extract_from(return_message)
-            .ok_or(Fmi3SlaveError::ReturnError)
+    /// The command telling the backend to continue after a callback that
+    /// carries no result back to the backend.
+    fn callback_continue_command() -> Fmi3Command {
+        Fmi3Command {
+            command: Some(Command::Fmi3CallbackContinue(
+                fmi3_messages::Fmi3CallbackContinue {}
+            )),
+        }
     }
 
     /// Logs the logging event contained in the Fmi3LogReturn message using the
@@ -110,6 +211,95 @@ impl Fmi3Slave {
             &log_return.log_message
         );
     }
+
+    /// Informs the importer of a clock update requested by the backend
+    /// through the importers `fmi3ClockUpdateCallback`.
+    fn handle_clock_update_return(&mut self) {
+        match &self.scheduled_execution_callbacks {
+            Some(callbacks) => unsafe {
+                (callbacks.clock_update)(callbacks.instance_environment)
+            },
+            None => self.logger.error(
+                "Backend requested a clock update, but the instance wasn't instantiated for Scheduled Execution."
+            )
+        }
+    }
+
+    /// Locks preemption for the backend through the importers
+    /// `fmi3LockPreemptionCallback`.
+    fn handle_lock_preemption_return(&mut self) {
+        match &self.scheduled_execution_callbacks {
+            Some(callbacks) => unsafe { (callbacks.lock_preemption)() },
+            None => self.logger.error(
+                "Backend requested a preemption lock, but the instance wasn't instantiated for Scheduled Execution."
+            )
+        }
+    }
+
+    /// Unlocks preemption for the backend through the importers
+    /// `fmi3UnlockPreemptionCallback`.
+    fn handle_unlock_preemption_return(&mut self) {
+        match &self.scheduled_execution_callbacks {
+            Some(callbacks) => unsafe { (callbacks.unlock_preemption)() },
+            None => self.logger.error(
+                "Backend requested a preemption unlock, but the instance wasn't instantiated for Scheduled Execution."
+            )
+        }
+    }
+
+    /// Informs the importer of an intermediate update from the backend through
+    /// the importers `fmi3IntermediateUpdateCallback`, and returns the
+    /// command relaying the importers early return request to the backend.
+    /// 
+    /// If the importer didn't provide the callback, no early return is
+    /// requested.
+    fn handle_intermediate_update_return(
+        &mut self,
+        update_return: fmi3_messages::Fmi3IntermediateUpdateReturn
+    ) -> Fmi3Command {
+        let mut early_return_requested: Fmi3Boolean = false;
+        let mut early_return_time: Fmi3Float64 = 0.0;
+
+        if let Some(callback) = &self.intermediate_update_callback {
+            unsafe {
+                (callback.intermediate_update)(
+                    callback.instance_environment,
+                    update_return.intermediate_update_time,
+                    update_return.intermediate_variable_set_requested,
+                    update_return.intermediate_variable_get_allowed,
+                    update_return.intermediate_step_finished,
+                    update_return.can_return_early,
+                    &mut early_return_requested,
+                    &mut early_return_time
+                )
+            }
+        }
+
+        Fmi3Command {
+            command: Some(Command::Fmi3IntermediateUpdateContinue(
+                fmi3_messages::Fmi3IntermediateUpdateContinue {
+                    early_return_requested,
+                    early_return_time
+                }
+            )),
+        }
+    }
+}
+
+/// The intermediate update callback given by the importer when instantiating
+/// an FMU for Co-Simulation.
+pub struct IntermediateUpdateCallback {
+    pub instance_environment: *const Fmi3InstanceEnvironment,
+    pub intermediate_update: Fmi3IntermediateUpdateCallback
+}
+
+/// The callbacks given by the importer when instantiating an FMU for
+/// Scheduled Execution.
+pub struct ScheduledExecutionCallbacks {
+    pub instance_environment: *const Fmi3InstanceEnvironment,
+    pub clock_update: Fmi3ClockUpdateCallback,
+    pub lock_preemption: Fmi3LockPreemptionCallback,
+    pub unlock_preemption: Fmi3UnlockPreemptionCallback
 }
 
 /// Sends the fmi3FreeInstance message to the backend when the slave is dropped.
@@ -121,7 +311,12 @@ impl Drop for Fmi3Slave {
             )),
         };
 
-        match self.dispatcher.send(&cmd) {
+        let deadline = Deadline::after(self.dispatcher.timeouts().call);
+        match self.dispatcher.send(
+            &cmd,
+            deadline,
+            |line| self.logger.backend_output(line)
+        ) {
             Ok(_) => self.logger.ok("Send free instance message to shut down backend."),
             Err(error) => self.logger.error(&format!(
                 "Freeing instance failed with error: {}.", error
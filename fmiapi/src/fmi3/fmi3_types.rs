@@ -208,6 +208,7 @@ pub enum Fmi3LogCategory {
     LogStatusError,
     LogStatusFatal,
     LogUnifmuMessages,
+    LogBackendOutput,
     LogUserDefined(String)
 }
 
@@ -223,6 +224,7 @@ impl LogCategory for Fmi3LogCategory {
             Self::LogStatusError => "logStatusError",
             Self::LogStatusFatal => "logStatusFatal",
             Self::LogUnifmuMessages => "logUnifmuMessages",
+            Self::LogBackendOutput => "logBackendOutput",
             Self::LogUserDefined(name) => name,
         }
     }
@@ -246,6 +248,10 @@ impl LogCategory for Fmi3LogCategory {
     fn unifmu_message() -> Self {
         Self::LogUnifmuMessages
     }
+
+    fn backend_output() -> Self {
+        Self::LogBackendOutput
+    }
 }
 
 impl Display for Fmi3LogCategory {
@@ -266,6 +272,7 @@ impl From<&str> for Fmi3LogCategory {
             "logStatusError" => Self::LogStatusError,
             "logStatusFatal" => Self::LogStatusFatal,
             "logUnifmuMessages" => Self::LogUnifmuMessages,
+            "logBackendOutput" => Self::LogBackendOutput,
             _ => Self::LogUserDefined(String::from(value))
         }
     }
@@ -277,8 +284,10 @@ impl From<String> for Fmi3LogCategory {
     }
 }
 
-/// NOT CURRENTLY FULLY SUPPORTED!
-/// ~ "This is not my final form" ~
+/// Called by the FMU during `fmi3DoStep` to inform the environment that
+/// intermediate variables can be accessed, or to ask whether the step should
+/// return early. The results are written to `early_return_requested` and
+/// `early_return_time`.
 /// 
 /// FMI3 Spec name: fmi3IntermediateUpdateCallback
 pub type Fmi3IntermediateUpdateCallback = unsafe extern "C" fn(
@@ -288,13 +297,29 @@ pub type Fmi3IntermediateUpdateCallback = unsafe extern "C" fn(
     intermediate_variable_get_allowed: Fmi3Boolean,
     intermediate_step_finished: Fmi3Boolean,
     can_return_early: Fmi3Boolean,
-    early_return_requested: *const Fmi3Boolean, //might be *mut instead depending on how its used
-    early_return_time: *const Fmi3Float64 //might be *mut instead depending on how its used
+    early_return_requested: *mut Fmi3Boolean,
+    early_return_time: *mut Fmi3Float64
+);
+
+/// Called by the FMU to inform the environment that a clock has ticked, or that
+/// a countdown clock has been set, during Scheduled Execution.
+/// 
+/// FMI3 Spec name: fmi3ClockUpdateCallback
+pub type Fmi3ClockUpdateCallback = unsafe extern "C" fn(
+    instance_environment: *const Fmi3InstanceEnvironment
 );
 
-/// Callbacks of this type are part of the FMI3 API that is not supported by
-/// UniFMU.
-pub type UnsupportedCallback = unsafe extern "C" fn(...);
+/// Called by the FMU before entering a critical section that must not be
+/// preempted by other model partitions during Scheduled Execution.
+/// 
+/// FMI3 Spec name: fmi3LockPreemptionCallback
+pub type Fmi3LockPreemptionCallback = unsafe extern "C" fn();
+
+/// Called by the FMU when leaving a critical section entered with
+/// `Fmi3LockPreemptionCallback`.
+/// 
+/// FMI3 Spec name: fmi3UnlockPreemptionCallback
+pub type Fmi3UnlockPreemptionCallback = unsafe extern "C" fn();
 
 // ----------------------- Protocol Buffer Trait decorations ---------------------------
 // The trait ExpectableReturn extends the Return message with an extract
@@ -306,6 +331,7 @@ implement_expectable_return!(fmi3_messages::Fmi3DoStepReturn, ReturnMessage, DoS
 implement_expectable_return!(fmi3_messages::Fmi3FreeInstanceReturn, ReturnMessage, FreeInstance);
 implement_expectable_return!(fmi3_messages::Fmi3GetFloat32Return, ReturnMessage, GetFloat32);
 implement_expectable_return!(fmi3_messages::Fmi3GetFloat64Return, ReturnMessage, GetFloat64);
+implement_expectable_return!(fmi3_messages::Fmi3GetFloat64SharedReturn, ReturnMessage, GetFloat64Shared);
 implement_expectable_return!(fmi3_messages::Fmi3GetInt8Return, ReturnMessage, GetInt8);
 implement_expectable_return!(fmi3_messages::Fmi3GetUInt8Return, ReturnMessage, GetUInt8);
 implement_expectable_return!(fmi3_messages::Fmi3GetInt16Return, ReturnMessage, GetInt16);
@@ -326,4 +352,11 @@ implement_expectable_return!(fmi3_messages::Fmi3UpdateDiscreteStatesReturn, Retu
 implement_expectable_return!(fmi3_messages::Fmi3GetIntervalDecimalReturn, ReturnMessage, GetIntervalDecimal);
 implement_expectable_return!(fmi3_messages::Fmi3GetIntervalFractionReturn, ReturnMessage, GetIntervalFraction);
 implement_expectable_return!(fmi3_messages::Fmi3GetShiftDecimalReturn, ReturnMessage, GetShiftDecimal);
-implement_expectable_return!(fmi3_messages::Fmi3GetShiftFractionReturn, ReturnMessage, GetShiftFraction);
\ No newline at end of file
+implement_expectable_return!(fmi3_messages::Fmi3GetShiftFractionReturn, ReturnMessage, GetShiftFraction);
+implement_expectable_return!(fmi3_messages::Fmi3CompletedIntegratorStepReturn, ReturnMessage, CompletedIntegratorStep);
+implement_expectable_return!(fmi3_messages::Fmi3GetContinuousStateDerivativesReturn, ReturnMessage, GetContinuousStateDerivatives);
+implement_expectable_return!(fmi3_messages::Fmi3GetEventIndicatorsReturn, ReturnMessage, GetEventIndicators);
+implement_expectable_return!(fmi3_messages::Fmi3GetContinuousStatesReturn, ReturnMessage, GetContinuousStates);
+implement_expectable_return!(fmi3_messages::Fmi3GetNominalsOfContinuousStatesReturn, ReturnMessage, GetNominalsOfContinuousStates);
+implement_expectable_return!(fmi3_messages::Fmi3GetNumberOfEventIndicatorsReturn, ReturnMessage, GetNumberOfEventIndicators);
+implement_expectable_return!(fmi3_messages::Fmi3GetNumberOfContinuousStatesReturn, ReturnMessage, GetNumberOfContinuousStates);
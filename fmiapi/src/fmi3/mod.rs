@@ -3,6 +3,7 @@
 
 mod fmi3_logger;
 mod fmi3_messages;
+mod fmi3_model_structure;
 mod fmi3_slave;
 mod fmi3_types;
 
@@ -11,9 +12,12 @@ use fmi3_messages::{
     fmi3_command::Command,
     Fmi3Command
 };
+use fmi3_model_structure::ModelStructure;
 use fmi3_slave::{
     Fmi3Slave,
     Fmi3SlaveType,
+    IntermediateUpdateCallback,
+    ScheduledExecutionCallbacks,
     SlaveState
 };
 use fmi3_types::{
@@ -40,11 +44,15 @@ use fmi3_types::{
     Fmi3LogCategory,
     Fmi3LogMessageCallback,
     Fmi3IntermediateUpdateCallback,
-    UnsupportedCallback,
+    Fmi3ClockUpdateCallback,
+    Fmi3LockPreemptionCallback,
+    Fmi3UnlockPreemptionCallback,
 };
 
 use crate::common::{
+    instantiation_token::verify_instantiation_token,
     logger::Logger,
+    model_description::read_model_description,
     spawn::spawn_slave,
     string_conversion::{c2s, c2non_empty_s}
 };
@@ -145,8 +153,82 @@ pub extern "C" fn fmi3InstantiateModelExchange(
         instance_environment,
         logging_on
     );
-    logger.error("fmi3InstantiateModelExchange is not implemented by UNIFMU.");
-    None // Currently, we only support CoSimulation, return null pointer as per the FMI standard
+
+    let instance_name = match c2non_empty_s(instance_name) {
+        Ok(name) => name,
+        Err(error) => {
+            logger.error(&format!(
+                "Could not parse instance_name; {}", error
+            ));
+            return None
+        }
+    };
+
+    let instantiation_token = match c2s(instantiation_token) {
+        Ok(string) => string,
+        Err(error) => {
+            logger.error(&format!(
+                "Could not convert instantiation_token to String; {}", error
+            ));
+            return None
+        }
+    };
+
+    let resources_dir = parse_resource_path(resource_path, &logger)?;
+
+    let model_structure = load_model_description(
+        &resources_dir,
+        &instantiation_token,
+        &logger
+    ).ok()?;
+
+    let dispatcher = match spawn_slave(
+        Path::new(&resources_dir),
+        |port| logger.communicate_port_connection_action(port),
+        |line| logger.backend_output(line)
+    ) {
+        Ok(dispatcher) => dispatcher,
+        Err(error) => {
+            logger.error(&format!("Spawning fmi3 slave failed; {}.", error));
+            return None;
+        }
+    };
+
+    let resource_path = match resources_dir.into_os_string().into_string() {
+        Ok(string_path) => string_path,
+        Err(error) => {
+            logger.error(&format!("Couldn't convert resource directory path into String; {:?}", error));
+            return None;
+        }
+    };
+
+    let mut slave = Fmi3Slave::new(dispatcher, logger);
+
+    slave.model_structure = model_structure;
+
+    let cmd = Fmi3Command {
+        command: Some(Command::Fmi3InstantiateModelExchange(
+            fmi3_messages::Fmi3InstantiateModelExchange {
+                instance_name: instance_name.clone(),
+                instantiation_token,
+                resource_path,
+                visible,
+                logging_on,
+            },
+        )),
+    };
+
+    match slave.dispatch::<fmi3_messages::Fmi3EmptyReturn>(&cmd) {
+        Err(error) => {
+            slave.logger.error(&format!(
+                "Instantiation of fmi3 slave '{}' failed with error [{}].",
+                instance_name,
+                error
+            ));
+            None
+        },
+        Ok(_) => Some(Box::new(slave))
+    }
 }
 
 /// # Safety
@@ -173,6 +255,8 @@ pub extern "C" fn fmi3InstantiateModelExchange(
 ///   must be no larger than `isize::MAX`, and adding that size to
 ///   `required_intermediate_variables` must not "wrap around" the address
 ///   space. See the safety documentation of [`pointer::offset`].
+/// * `intermediate_update` must be either null or a function that is safe to
+///   call with `instance_environment` for the lifetime of the instance.
 #[no_mangle]
 pub unsafe extern "C" fn fmi3InstantiateCoSimulation(
     instance_name: Fmi3String,
@@ -186,7 +270,7 @@ pub unsafe extern "C" fn fmi3InstantiateCoSimulation(
     n_required_intermediate_variables: size_t,
     instance_environment: *const Fmi3InstanceEnvironment,
     log_message: Fmi3LogMessageCallback,
-    intermediate_update: Fmi3IntermediateUpdateCallback,
+    intermediate_update: Option<Fmi3IntermediateUpdateCallback>,
 ) -> Option<Fmi3SlaveType> {
     let logger = Fmi3Logger::new(
         log_message,
@@ -222,64 +306,18 @@ pub unsafe extern "C" fn fmi3InstantiateCoSimulation(
     }
     .to_owned();
 
-    let resource_path_str = match c2non_empty_s(resource_path) {
-        Ok(path_string) => path_string,
-        Err(error) => {
-            logger.error(&format!(
-                "could not parse resource_path; {}", error
-            ));
-            return None;
-        }
-    };
-
-    // NOTE: In version 3 of the FMI standard, resourcePath should be a path, e.g., "C:\...". At
-    // least one tool seems to still follow version 2 in that it passes a URI, e.g., starting with
-    // "file:///C://..." instead. The current implementation maintains this "backwards
-    // compatibility" with this incorrect implementation of version 3 of the standard.
-
-    // Check for supported URI schemes or treat as a direct file path
-    let resources_dir = if resource_path_str.starts_with("file:")
-        || resource_path_str.starts_with("http:")
-        || resource_path_str.starts_with("https:")
-        || resource_path_str.starts_with("ftp:")
-        || resource_path_str.starts_with("fmi2:")
-    {
-        // Parse as a URI
-        let resource_uri = match Url::parse(&resource_path_str) {
-            Ok(uri) => uri,
-            Err(error) => {
-                logger.error(&format!(
-                    "Unable to parse uri: {}", error
-                ));
-                return None;
-            }
-        };
+    let resources_dir = parse_resource_path(resource_path, &logger)?;
 
-        if resource_uri.scheme() == "file" {
-            match resource_uri.to_file_path() {
-                Ok(path) => path,
-                Err(_) => {
-                    logger.error(&format!(
-                        "URI was parsed but could not be converted into a file path, got: '{:?}'.",
-                        resource_uri
-                    ));
-                    return None;
-                }
-            }
-        } else {
-            logger.error(&format!(
-                "Unsupported URI scheme: '{}'", resource_uri.scheme()
-            ));
-            return None;
-        }
-    } else {
-        // Treat it as a direct file path
-        PathBuf::from(resource_path_str)
-    };
+    let model_structure = load_model_description(
+        &resources_dir,
+        &instantiation_token,
+        &logger
+    ).ok()?;
 
     let dispatcher = match spawn_slave(
         Path::new(&resources_dir),
-        |port| logger.communicate_port_connection_action(port)
+        |port| logger.communicate_port_connection_action(port),
+        |line| logger.backend_output(line)
     ) {
         Ok(dispatcher) => dispatcher,
         Err(error) => {
@@ -298,6 +336,15 @@ pub unsafe extern "C" fn fmi3InstantiateCoSimulation(
 
     let mut slave = Fmi3Slave::new(dispatcher, logger);
 
+    slave.model_structure = model_structure;
+
+    slave.intermediate_update_callback = intermediate_update.map(
+        |intermediate_update| IntermediateUpdateCallback {
+            instance_environment,
+            intermediate_update
+        }
+    );
+
     let cmd = Fmi3Command {
         command: Some(Command::Fmi3InstantiateCoSimulation(
             fmi3_messages::Fmi3InstantiateCoSimulation {
@@ -335,9 +382,9 @@ pub extern "C" fn fmi3InstantiateScheduledExecution(
     logging_on: Fmi3Boolean,
     instance_environment: *const Fmi3InstanceEnvironment,
     log_message: Fmi3LogMessageCallback,
-    clock_update: UnsupportedCallback,
-    lock_preemption: UnsupportedCallback,
-    unlock_preemption: UnsupportedCallback,
+    clock_update: Option<Fmi3ClockUpdateCallback>,
+    lock_preemption: Option<Fmi3LockPreemptionCallback>,
+    unlock_preemption: Option<Fmi3UnlockPreemptionCallback>,
 ) -> Option<Fmi3SlaveType> {
     let logger = Fmi3Logger::new(
         log_message,
@@ -345,8 +392,99 @@ pub extern "C" fn fmi3InstantiateScheduledExecution(
         logging_on
     );
 
-    logger.error("fmi3InstantiateScheduledExecution is not implemented by UNIFMU.");
-    None // Currently, we only support CoSimulation, return null pointer as per the FMI standard
+    let (
+        Some(clock_update),
+        Some(lock_preemption),
+        Some(unlock_preemption)
+    ) = (clock_update, lock_preemption, unlock_preemption) else {
+        logger.error(
+            "Scheduled Execution requires the clockUpdate, lockPreemption and unlockPreemption callbacks, but at least one of them was NULL."
+        );
+        return None;
+    };
+
+    let instance_name = match c2non_empty_s(instance_name) {
+        Ok(name) => name,
+        Err(error) => {
+            logger.error(&format!(
+                "Could not parse instance_name; {}", error
+            ));
+            return None
+        }
+    };
+
+    let instantiation_token = match c2s(instantiation_token) {
+        Ok(string) => string,
+        Err(error) => {
+            logger.error(&format!(
+                "Could not convert instantiation_token to String; {}", error
+            ));
+            return None
+        }
+    };
+
+    let resources_dir = parse_resource_path(resource_path, &logger)?;
+
+    let model_structure = load_model_description(
+        &resources_dir,
+        &instantiation_token,
+        &logger
+    ).ok()?;
+
+    let dispatcher = match spawn_slave(
+        Path::new(&resources_dir),
+        |port| logger.communicate_port_connection_action(port),
+        |line| logger.backend_output(line)
+    ) {
+        Ok(dispatcher) => dispatcher,
+        Err(error) => {
+            logger.error(&format!("Spawning fmi3 slave failed; {}.", error));
+            return None;
+        }
+    };
+
+    let resource_path = match resources_dir.into_os_string().into_string() {
+        Ok(string_path) => string_path,
+        Err(error) => {
+            logger.error(&format!("Couldn't convert resource directory path into String; {:?}", error));
+            return None;
+        }
+    };
+
+    let mut slave = Fmi3Slave::new(dispatcher, logger);
+
+    slave.model_structure = model_structure;
+
+    slave.scheduled_execution_callbacks = Some(ScheduledExecutionCallbacks {
+        instance_environment,
+        clock_update,
+        lock_preemption,
+        unlock_preemption
+    });
+
+    let cmd = Fmi3Command {
+        command: Some(Command::Fmi3InstantiateScheduledExecution(
+            fmi3_messages::Fmi3InstantiateScheduledExecution {
+                instance_name: instance_name.clone(),
+                instantiation_token,
+                resource_path,
+                visible,
+                logging_on,
+            },
+        )),
+    };
+
+    match slave.dispatch::<fmi3_messages::Fmi3EmptyReturn>(&cmd) {
+        Err(error) => {
+            slave.logger.error(&format!(
+                "Instantiation of fmi3 slave '{}' failed with error [{}].",
+                instance_name,
+                error
+            ));
+            None
+        },
+        Ok(_) => Some(Box::new(slave))
+    }
 }
 
 /// # Safety
@@ -374,7 +512,7 @@ pub unsafe extern "C" fn fmi3DoStep(
         )),
     };
 
-    match instance.dispatch::<fmi3_messages::Fmi3DoStepReturn>(&cmd) {
+    match instance.dispatch_step::<fmi3_messages::Fmi3DoStepReturn>(&cmd) {
         Ok(result) => {
             let mut status = parse_status(result.status, &instance.logger);
 
@@ -620,11 +758,24 @@ pub unsafe extern "C" fn fmi3GetFloat64(
 ) -> Fmi3Status {
     let value_references = unsafe {
         from_raw_parts(value_references, n_value_references)
-    }.to_owned();
+    };
+
+    if let Some(region) = instance.shared_memory() {
+        if region.fits_float64(n_value_references, n_values) {
+            region.write_value_references(value_references);
+            let values_out = unsafe {
+                from_raw_parts_mut(values, n_values)
+            };
+            return get_float64_shared(instance, n_value_references, values_out);
+        }
+    }
 
     let cmd = Fmi3Command {
         command: Some(Command::Fmi3GetFloat64(
-            fmi3_messages::Fmi3GetFloat64 { value_references, n_values: n_values as u64 }
+            fmi3_messages::Fmi3GetFloat64 {
+                value_references: value_references.to_owned(),
+                n_values: n_values as u64
+            }
         )),
     };
 
@@ -2088,8 +2239,13 @@ pub extern "C" fn fmi3SetShiftFraction(
 pub extern "C" fn fmi3EvaluateDiscreteStates(
     instance: &mut Fmi3Slave,
 ) -> Fmi3Status {
-    instance.logger.error("fmi3EvaluateDiscreteStates is not implemented by UNIFMU.");
-    Fmi3Status::Fmi3Error
+    let cmd = Fmi3Command {
+        command: Some(Command::Fmi3EvaluateDiscreteStates(
+            fmi3_messages::Fmi3EvaluateDiscreteStates {}
+        )),
+    };
+
+    send_cmd_recv_status(instance, cmd, "fmi3EvaluateDiscreteStates")
 }
 
 /// # Safety
@@ -2170,19 +2326,71 @@ pub unsafe extern "C" fn fmi3UpdateDiscreteStates(
 pub extern "C" fn fmi3EnterContinuousTimeMode(
     instance: &mut Fmi3Slave,
 ) -> Fmi3Status {
-    instance.logger.error("fmi3EnterContinuousTimeMode is not implemented by UNIFMU.");
-    Fmi3Status::Fmi3Error
+    let cmd = Fmi3Command {
+        command: Some(Command::Fmi3EnterContinuousTimeMode(
+            fmi3_messages::Fmi3EnterContinuousTimeMode {}
+        )),
+    };
+
+    send_cmd_recv_status(instance, cmd, "fmi3EnterContinuousTimeMode")
 }
 
+/// # Safety
+/// Behavior is undefined if any of `enter_event_mode` and
+/// `terminate_simulation` points outside of address space and if they are
+/// dereferenced after function call.
 #[no_mangle]
-pub extern "C" fn fmi3CompletedIntegratorStep(
+pub unsafe extern "C" fn fmi3CompletedIntegratorStep(
     instance: &mut Fmi3Slave,
 	no_set_fmu_state_prior_to_current_point: Fmi3Boolean,
-	enter_event_mode: *const Fmi3Boolean,
-	terminate_simulation: *const Fmi3Boolean,
+	enter_event_mode: *mut Fmi3Boolean,
+	terminate_simulation: *mut Fmi3Boolean,
 ) -> Fmi3Status {
-    instance.logger.error("fmi3CompletedIntegratorStep is not implemented by UNIFMU.");
-    Fmi3Status::Fmi3Error
+    let cmd = Fmi3Command {
+        command: Some(Command::Fmi3CompletedIntegratorStep(
+            fmi3_messages::Fmi3CompletedIntegratorStep {
+                no_set_fmu_state_prior_to_current_point
+            }
+        )),
+    };
+
+    match instance.dispatch::<fmi3_messages::Fmi3CompletedIntegratorStepReturn>(&cmd) {
+        Ok(result) => {
+            let mut status = parse_status(result.status, &instance.logger);
+
+            if status.output_is_defined() {
+                if !enter_event_mode.is_null() {
+                    unsafe {
+                        *enter_event_mode = result.enter_event_mode;
+                    }
+                } else {
+                    instance.logger.warning(
+                        "The parameter enter_event_mode was a null pointer and consequently wasn't set as part of the integrator step."
+                    );
+                    status = status.escalate_status(Fmi3Status::Fmi3Warning);
+                }
+
+                if !terminate_simulation.is_null() {
+                    unsafe {
+                        *terminate_simulation = result.terminate_simulation;
+                    }
+                } else {
+                    instance.logger.warning(
+                        "The parameter terminate_simulation was a null pointer and consequently wasn't set as part of the integrator step."
+                    );
+                    status = status.escalate_status(Fmi3Status::Fmi3Warning);
+                }
+            }
+
+            status
+        }
+        Err(error) => {
+            instance.logger.error(&format!(
+                "fmi3CompletedIntegratorStep failed with error: {}.", error
+            ));
+            Fmi3Status::Fmi3Error
+        }
+    }
 }
 
 #[no_mangle]
@@ -2190,80 +2398,419 @@ pub extern "C" fn fmi3SetTime(
     instance: &mut Fmi3Slave,
 	time: Fmi3Float64,
 ) -> Fmi3Status {
-    instance.logger.error("fmi3SetTime is not implemented by UNIFMU.");
-    Fmi3Status::Fmi3Error
+    let cmd = Fmi3Command {
+        command: Some(Command::Fmi3SetTime(
+            fmi3_messages::Fmi3SetTime { time }
+        )),
+    };
+
+    send_cmd_recv_status(instance, cmd, "fmi3SetTime")
 }
 
+/// # Safety
+/// Behavior is undefined if any of the following conditions are violated:
+/// * `continuous_states` must be \[valid\] for reads for
+///   `n_continuous_states * mem::size_of::<f64>()` many bytes, and it must be
+///   properly aligned. This means in particular:
+///     * The entire memory range of this slice must be contained within a
+///       single allocated object! Slices can never span across multiple
+///       allocated objects.
+///     * `continuous_states` may only be null if `n_continuous_states` is
+///       0, as a null pointer is otherwise rejected with
+///       `Fmi3Status::Fmi3Error`.
+/// * `continuous_states` must point to `n_continuous_states` consecutive
+///   properly initialized values of type `f64`.
+/// * The total size `n_continuous_states * mem::size_of::<f64>()` of the
+///   slice must be no larger than `isize::MAX`, and adding that size to
+///   `continuous_states` must not "wrap around" the address space. See the
+///   safety documentation of [`pointer::offset`].
 #[no_mangle]
-pub extern "C" fn fmi3SetContinuousStates(
+pub unsafe extern "C" fn fmi3SetContinuousStates(
     instance: &mut Fmi3Slave,
 	continuous_states: *const Fmi3Float64,
 	n_continuous_states: size_t,
 ) -> Fmi3Status {
-    instance.logger.error("fmi3SetContinuousStates is not implemented by UNIFMU.");
-    Fmi3Status::Fmi3Error
-}
+    if continuous_states.is_null() && n_continuous_states > 0 {
+        instance.logger.error(
+            "fmi3SetContinuousStates was called with a null pointer."
+        );
+        return Fmi3Status::Fmi3Error;
+    }
 
-#[no_mangle]
-pub extern "C" fn fmi3GetContinuousStateDerivatives(
-    instance: &mut Fmi3Slave,
+    let continuous_states = match n_continuous_states {
+        0 => Vec::new(),
+        _ => unsafe { from_raw_parts(continuous_states, n_continuous_states) }.to_owned()
+    };
+
+    let cmd = Fmi3Command {
+        command: Some(Command::Fmi3SetContinuousStates(
+            fmi3_messages::Fmi3SetContinuousStates { continuous_states }
+        )),
+    };
+
+    send_cmd_recv_status(instance, cmd, "fmi3SetContinuousStates")
+}
+
+/// # Safety
+/// Behavior is undefined if any of the following conditions are violated:
+/// * `derivatives` must be \[valid\] for writes for
+///   `n_continuous_states * mem::size_of::<f64>()` many bytes, and it must be
+///   properly aligned. This means in particular:
+///     * The entire memory range of this slice must be contained within a
+///       single allocated object! Slices can never span across multiple
+///       allocated objects.
+///     * `derivatives` may only be null if `n_continuous_states` is 0, as
+///       a null pointer is otherwise rejected with
+///       `Fmi3Status::Fmi3Error`.
+/// * The total size `n_continuous_states * mem::size_of::<f64>()` of the
+///   slice must be no larger than `isize::MAX`, and adding that size to
+///   `derivatives` must not "wrap around" the address space. See the safety
+///   documentation of [`pointer::offset`].
+#[no_mangle]
+pub unsafe extern "C" fn fmi3GetContinuousStateDerivatives(
+    instance: &mut Fmi3Slave,
 	derivatives: *mut Fmi3Float64,
 	n_continuous_states: size_t,
 ) -> Fmi3Status {
-    instance.logger.error("fmi3GetContinuousStateDerivatives is not implemented by UNIFMU.");
-    Fmi3Status::Fmi3Error
+    if derivatives.is_null() && n_continuous_states > 0 {
+        instance.logger.error(
+            "fmi3GetContinuousStateDerivatives was called with a null pointer."
+        );
+        return Fmi3Status::Fmi3Error;
+    }
+
+    let cmd = Fmi3Command {
+        command: Some(Command::Fmi3GetContinuousStateDerivatives(
+            fmi3_messages::Fmi3GetContinuousStateDerivatives {
+                n_continuous_states: n_continuous_states as u64
+            }
+        )),
+    };
+
+    match instance.dispatch::<fmi3_messages::Fmi3GetContinuousStateDerivativesReturn>(&cmd) {
+        Ok(reply) => {
+            let status = parse_status(reply.status, &instance.logger);
+
+            if status.output_is_defined() {
+                let derivatives_out: &mut [_] = match n_continuous_states {
+                    0 => &mut [],
+                    _ => unsafe { from_raw_parts_mut(derivatives, n_continuous_states) }
+                };
+                copy_reply_values(
+                    derivatives_out,
+                    &reply.derivatives,
+                    status,
+                    &instance.logger,
+                    "fmi3GetContinuousStateDerivatives"
+                )
+            } else {
+                status
+            }
+        }
+        Err(error) => {
+            instance.logger.error(&format!(
+                "fmi3GetContinuousStateDerivatives failed with error: {}.", error
+            ));
+            Fmi3Status::Fmi3Error
+        }
+    }
 }
 
+/// # Safety
+/// Behavior is undefined if any of the following conditions are violated:
+/// * `event_indicators` must be \[valid\] for writes for
+///   `n_event_indicators * mem::size_of::<f64>()` many bytes, and it must be
+///   properly aligned. This means in particular:
+///     * The entire memory range of this slice must be contained within a
+///       single allocated object! Slices can never span across multiple
+///       allocated objects.
+///     * `event_indicators` may only be null if `n_event_indicators` is 0,
+///       as a null pointer is otherwise rejected with
+///       `Fmi3Status::Fmi3Error`.
+/// * The total size `n_event_indicators * mem::size_of::<f64>()` of the slice
+///   must be no larger than `isize::MAX`, and adding that size to
+///   `event_indicators` must not "wrap around" the address space. See the
+///   safety documentation of [`pointer::offset`].
 #[no_mangle]
-pub extern "C" fn fmi3GetEventIndicators(
+pub unsafe extern "C" fn fmi3GetEventIndicators(
     instance: &mut Fmi3Slave,
 	event_indicators: *mut Fmi3Float64,
 	n_event_indicators: size_t,
 ) -> Fmi3Status {
-    instance.logger.error("fmi3GetEventIndicators is not implemented by UNIFMU.");
-    Fmi3Status::Fmi3Error
+    if event_indicators.is_null() && n_event_indicators > 0 {
+        instance.logger.error(
+            "fmi3GetEventIndicators was called with a null pointer."
+        );
+        return Fmi3Status::Fmi3Error;
+    }
+
+    let cmd = Fmi3Command {
+        command: Some(Command::Fmi3GetEventIndicators(
+            fmi3_messages::Fmi3GetEventIndicators {
+                n_event_indicators: n_event_indicators as u64
+            }
+        )),
+    };
+
+    match instance.dispatch::<fmi3_messages::Fmi3GetEventIndicatorsReturn>(&cmd) {
+        Ok(reply) => {
+            let status = parse_status(reply.status, &instance.logger);
+
+            if status.output_is_defined() {
+                let event_indicators_out: &mut [_] = match n_event_indicators {
+                    0 => &mut [],
+                    _ => unsafe { from_raw_parts_mut(event_indicators, n_event_indicators) }
+                };
+                copy_reply_values(
+                    event_indicators_out,
+                    &reply.event_indicators,
+                    status,
+                    &instance.logger,
+                    "fmi3GetEventIndicators"
+                )
+            } else {
+                status
+            }
+        }
+        Err(error) => {
+            instance.logger.error(&format!(
+                "fmi3GetEventIndicators failed with error: {}.", error
+            ));
+            Fmi3Status::Fmi3Error
+        }
+    }
 }
 
+/// # Safety
+/// Behavior is undefined if any of the following conditions are violated:
+/// * `continuous_states` must be \[valid\] for writes for
+///   `n_continuous_states * mem::size_of::<f64>()` many bytes, and it must be
+///   properly aligned. This means in particular:
+///     * The entire memory range of this slice must be contained within a
+///       single allocated object! Slices can never span across multiple
+///       allocated objects.
+///     * `continuous_states` may only be null if `n_continuous_states` is
+///       0, as a null pointer is otherwise rejected with
+///       `Fmi3Status::Fmi3Error`.
+/// * The total size `n_continuous_states * mem::size_of::<f64>()` of the
+///   slice must be no larger than `isize::MAX`, and adding that size to
+///   `continuous_states` must not "wrap around" the address space. See the
+///   safety documentation of [`pointer::offset`].
 #[no_mangle]
-pub extern "C" fn fmi3GetContinuousStates(
+pub unsafe extern "C" fn fmi3GetContinuousStates(
     instance: &mut Fmi3Slave,
 	continuous_states: *mut Fmi3Float64,
 	n_continuous_states: size_t,
 ) -> Fmi3Status {
-    instance.logger.error("fmi3GetContinuousStates is not implemented by UNIFMU.");
-    Fmi3Status::Fmi3Error
+    if continuous_states.is_null() && n_continuous_states > 0 {
+        instance.logger.error(
+            "fmi3GetContinuousStates was called with a null pointer."
+        );
+        return Fmi3Status::Fmi3Error;
+    }
+
+    let cmd = Fmi3Command {
+        command: Some(Command::Fmi3GetContinuousStates(
+            fmi3_messages::Fmi3GetContinuousStates {
+                n_continuous_states: n_continuous_states as u64
+            }
+        )),
+    };
+
+    match instance.dispatch::<fmi3_messages::Fmi3GetContinuousStatesReturn>(&cmd) {
+        Ok(reply) => {
+            let status = parse_status(reply.status, &instance.logger);
+
+            if status.output_is_defined() {
+                let continuous_states_out: &mut [_] = match n_continuous_states {
+                    0 => &mut [],
+                    _ => unsafe { from_raw_parts_mut(continuous_states, n_continuous_states) }
+                };
+                copy_reply_values(
+                    continuous_states_out,
+                    &reply.continuous_states,
+                    status,
+                    &instance.logger,
+                    "fmi3GetContinuousStates"
+                )
+            } else {
+                status
+            }
+        }
+        Err(error) => {
+            instance.logger.error(&format!(
+                "fmi3GetContinuousStates failed with error: {}.", error
+            ));
+            Fmi3Status::Fmi3Error
+        }
+    }
 }
 
+/// # Safety
+/// Behavior is undefined if any of the following conditions are violated:
+/// * `nominals` must be \[valid\] for writes for
+///   `n_continuous_states * mem::size_of::<f64>()` many bytes, and it must be
+///   properly aligned. This means in particular:
+///     * The entire memory range of this slice must be contained within a
+///       single allocated object! Slices can never span across multiple
+///       allocated objects.
+///     * `nominals` may only be null if `n_continuous_states` is 0, as a
+///       null pointer is otherwise rejected with `Fmi3Status::Fmi3Error`.
+/// * The total size `n_continuous_states * mem::size_of::<f64>()` of the
+///   slice must be no larger than `isize::MAX`, and adding that size to
+///   `nominals` must not "wrap around" the address space. See the safety
+///   documentation of [`pointer::offset`].
 #[no_mangle]
-pub extern "C" fn fmi3GetNominalsOfContinuousStates(
+pub unsafe extern "C" fn fmi3GetNominalsOfContinuousStates(
     instance: &mut Fmi3Slave,
 	nominals: *mut Fmi3Float64,
 	n_continuous_states: size_t,
 ) -> Fmi3Status {
-    instance.logger.error("fmi3GetNominalsOfContinuousStates is not implemented by UNIFMU.");
-    Fmi3Status::Fmi3Error
+    if nominals.is_null() && n_continuous_states > 0 {
+        instance.logger.error(
+            "fmi3GetNominalsOfContinuousStates was called with a null pointer."
+        );
+        return Fmi3Status::Fmi3Error;
+    }
+
+    let cmd = Fmi3Command {
+        command: Some(Command::Fmi3GetNominalsOfContinuousStates(
+            fmi3_messages::Fmi3GetNominalsOfContinuousStates {
+                n_continuous_states: n_continuous_states as u64
+            }
+        )),
+    };
+
+    match instance.dispatch::<fmi3_messages::Fmi3GetNominalsOfContinuousStatesReturn>(&cmd) {
+        Ok(reply) => {
+            let status = parse_status(reply.status, &instance.logger);
+
+            if status.output_is_defined() {
+                let nominals_out: &mut [_] = match n_continuous_states {
+                    0 => &mut [],
+                    _ => unsafe { from_raw_parts_mut(nominals, n_continuous_states) }
+                };
+                copy_reply_values(
+                    nominals_out,
+                    &reply.nominals,
+                    status,
+                    &instance.logger,
+                    "fmi3GetNominalsOfContinuousStates"
+                )
+            } else {
+                status
+            }
+        }
+        Err(error) => {
+            instance.logger.error(&format!(
+                "fmi3GetNominalsOfContinuousStates failed with error: {}.", error
+            ));
+            Fmi3Status::Fmi3Error
+        }
+    }
 }
 
+/// # Safety
+/// Behavior is undefined if `n_event_indicators` points outside of address
+/// space and if it is dereferenced after function call.
 #[no_mangle]
-pub extern "C" fn fmi3GetNumberOfEventIndicators(
+pub unsafe extern "C" fn fmi3GetNumberOfEventIndicators(
     instance: &mut Fmi3Slave,
-	n_event_indicators: *const size_t,
+	n_event_indicators: *mut size_t,
 ) -> Fmi3Status {
-    instance.logger.error("fmi3GetNumberOfEventIndicators is not implemented by UNIFMU.");
-    Fmi3Status::Fmi3Error
+    if n_event_indicators.is_null() {
+        instance.logger.error(
+            "The parameter n_event_indicators was a null pointer."
+        );
+        return Fmi3Status::Fmi3Error;
+    }
+
+    let cmd = Fmi3Command {
+        command: Some(Command::Fmi3GetNumberOfEventIndicators(
+            fmi3_messages::Fmi3GetNumberOfEventIndicators {}
+        )),
+    };
+
+    match instance.dispatch::<fmi3_messages::Fmi3GetNumberOfEventIndicatorsReturn>(&cmd) {
+        Ok(reply) => {
+            let status = parse_status(reply.status, &instance.logger);
+
+            if status.output_is_defined() {
+                unsafe {
+                    *n_event_indicators = reply.n_event_indicators as size_t;
+                }
+            }
+
+            status
+        }
+        Err(error) => {
+            instance.logger.error(&format!(
+                "fmi3GetNumberOfEventIndicators failed with error: {}.", error
+            ));
+            Fmi3Status::Fmi3Error
+        }
+    }
 }
 
+/// # Safety
+/// Behavior is undefined if `n_continuous_states` points outside of address
+/// space and if it is dereferenced after function call.
 #[no_mangle]
-pub extern "C" fn fmi3GetNumberOfContinuousStates(
+pub unsafe extern "C" fn fmi3GetNumberOfContinuousStates(
     instance: &mut Fmi3Slave,
-	n_continuous_states: *const size_t,
+	n_continuous_states: *mut size_t,
 ) -> Fmi3Status {
-    instance.logger.error("fmi3GetNumberOfContinuousStates is not implemented by UNIFMU.");
-    Fmi3Status::Fmi3Error
+    if n_continuous_states.is_null() {
+        instance.logger.error(
+            "The parameter n_continuous_states was a null pointer."
+        );
+        return Fmi3Status::Fmi3Error;
+    }
+
+    let cmd = Fmi3Command {
+        command: Some(Command::Fmi3GetNumberOfContinuousStates(
+            fmi3_messages::Fmi3GetNumberOfContinuousStates {}
+        )),
+    };
+
+    match instance.dispatch::<fmi3_messages::Fmi3GetNumberOfContinuousStatesReturn>(&cmd) {
+        Ok(reply) => {
+            let status = parse_status(reply.status, &instance.logger);
+
+            if status.output_is_defined() {
+                unsafe {
+                    *n_continuous_states = reply.n_continuous_states as size_t;
+                }
+            }
+
+            status
+        }
+        Err(error) => {
+            instance.logger.error(&format!(
+                "fmi3GetNumberOfContinuousStates failed with error: {}.", error
+            ));
+            Fmi3Status::Fmi3Error
+        }
+    }
 }
 
+/// # Safety
+/// Behavior is undefined if any of the following conditions are violated:
+/// * `value_references` and `orders` must be \[valid\] for reads for
+///   `n_value_references` elements of their respective types, `values` must
+///   be \[valid\] for writes for `n_values` elements of type `f64`, and they
+///   must be properly aligned. This means in particular:
+///     * For each of the pointers the entire memory range of that slice must
+///       be contained within a single allocated object! Slices can never span
+///       across multiple allocated objects.
+///     * Null pointers are rejected with `Fmi3Status::Fmi3Error`, but the
+///       pointers must be aligned even for zero-length slices.
+/// * The total size of each of the slices must be no larger than
+///   `isize::MAX`, and adding that size to the respective pointer must not
+///   "wrap around" the address space. See the safety documentation of
+///   [`pointer::offset`].
 #[no_mangle]
-pub extern "C" fn fmi3GetOutputDerivatives(
+pub unsafe extern "C" fn fmi3GetOutputDerivatives(
     instance: &mut Fmi3Slave,
 	value_references: *const Fmi3ValueReference,
 	n_value_references: size_t,
@@ -2271,8 +2818,75 @@ pub extern "C" fn fmi3GetOutputDerivatives(
 	values: *mut Fmi3Float64,
 	n_values: size_t,
 ) -> Fmi3Status {
-    instance.logger.error("fmi3GetOutputDerivatives is not implemented by UNIFMU.");
-    Fmi3Status::Fmi3Error
+    if value_references.is_null() || orders.is_null() || values.is_null() {
+        instance.logger.error(
+            "fmi3GetOutputDerivatives was called with a null pointer."
+        );
+        return Fmi3Status::Fmi3Error;
+    }
+
+    // Each output variable has at least one element in the values.
+    if n_values < n_value_references {
+        instance.logger.error(&format!(
+            "fmi3GetOutputDerivatives was called with {} values for {} value references.",
+            n_values,
+            n_value_references
+        ));
+        return Fmi3Status::Fmi3Error;
+    }
+
+    let value_references = unsafe {
+        from_raw_parts(value_references, n_value_references)
+    }.to_owned();
+
+    let orders = unsafe {
+        from_raw_parts(orders, n_value_references)
+    }.to_owned();
+
+    if let Some(order) = orders.iter().find(|order| **order < 1) {
+        instance.logger.error(&format!(
+            "fmi3GetOutputDerivatives was called with order {}, but orders must be at least 1.",
+            order
+        ));
+        return Fmi3Status::Fmi3Error;
+    }
+
+    let cmd = Fmi3Command {
+        command: Some(Command::Fmi3GetOutputDerivatives(
+            fmi3_messages::Fmi3GetOutputDerivatives {
+                value_references,
+                orders,
+                n_values: n_values as u64
+            }
+        )),
+    };
+
+    match instance.dispatch::<fmi3_messages::Fmi3GetOutputDerivativesReturn>(&cmd) {
+        Ok(reply) => {
+            let status = parse_status(reply.status, &instance.logger);
+
+            if status.output_is_defined() {
+                let values_out = unsafe {
+                    from_raw_parts_mut(values, n_values)
+                };
+                copy_reply_values(
+                    values_out,
+                    &reply.values,
+                    status,
+                    &instance.logger,
+                    "fmi3GetOutputDerivatives"
+                )
+            } else {
+                status
+            }
+        }
+        Err(error) => {
+            instance.logger.error(&format!(
+                "fmi3GetOutputDerivatives failed with error: {}.", error
+            ));
+            Fmi3Status::Fmi3Error
+        }
+    }
 }
 
 #[no_mangle]
@@ -2281,8 +2895,23 @@ pub extern "C" fn fmi3ActivateModelPartition(
 	value_reference: Fmi3ValueReference,
 	activation_time: Fmi3Float64,
 ) -> Fmi3Status {
-    instance.logger.error("fmi3ActivateModelPartition is not implemented by UNIFMU.");
-    Fmi3Status::Fmi3Error
+    if instance.scheduled_execution_callbacks.is_none() {
+        instance.logger.error(
+            "fmi3ActivateModelPartition can only be called for instances instantiated for Scheduled Execution."
+        );
+        return Fmi3Status::Fmi3Error;
+    }
+
+    let cmd = Fmi3Command {
+        command: Some(Command::Fmi3ActivateModelPartition(
+            fmi3_messages::Fmi3ActivateModelPartition {
+                clock_reference: value_reference,
+                activation_time
+            }
+        )),
+    };
+
+    send_cmd_recv_status(instance, cmd, "fmi3ActivateModelPartition")
 }
 
 /// # Safety
@@ -2371,19 +3000,35 @@ pub unsafe extern "C" fn fmi3SetFloat64(
 ) -> Fmi3Status {
     let value_references = unsafe {
         from_raw_parts(value_references, n_value_references)
-    }
-        .to_owned();
+    };
 
     let values = unsafe {
         from_raw_parts(values, n_values)
+    };
+
+    if let Some(region) = instance.shared_memory() {
+        if region.fits_float64(n_value_references, n_values) {
+            region.write_value_references(value_references);
+            region.write_float64(n_value_references, values);
+
+            let cmd = Fmi3Command {
+                command: Some(Command::Fmi3SetFloat64Shared(
+                    fmi3_messages::Fmi3SetFloat64Shared {
+                        n_value_references: n_value_references as u64,
+                        n_values: n_values as u64
+                    }
+                )),
+            };
+
+            return send_cmd_recv_status(instance, cmd, "fmi3SetFloat64");
+        }
     }
-        .to_owned();
 
     let cmd = Fmi3Command {
         command: Some(Command::Fmi3SetFloat64(
             fmi3_messages::Fmi3SetFloat64 {
-                value_references,
-                values
+                value_references: value_references.to_owned(),
+                values: values.to_owned()
             }
         )),
     };
@@ -3103,28 +3748,144 @@ pub unsafe extern "C" fn fmi3SetClock(
 	send_cmd_recv_status(instance, cmd, "fmi3SetClock")
 }
 
+/// Answered from the `<ModelStructure>` element of the FMU's
+/// modelDescription.xml without involving the backend.
+///
+/// # Safety
+/// Behavior is undefined if `n_dependencies` is neither null nor \[valid\]
+/// for writes of a single properly aligned `size_t`.
 #[no_mangle]
-pub extern "C" fn fmi3GetNumberOfVariableDependencies(
+pub unsafe extern "C" fn fmi3GetNumberOfVariableDependencies(
     instance: &mut Fmi3Slave,
-    value_reference: *const Fmi3ValueReference,
-    n_dependencies: *const size_t,
+    value_reference: Fmi3ValueReference,
+    n_dependencies: *mut size_t,
 ) -> Fmi3Status {
-    instance.logger.error("fmi3GetNumberOfVariableDependencies is not implemented by UNIFMU.");
-    Fmi3Status::Fmi3Error
+    if n_dependencies.is_null() {
+        instance.logger.error(
+            "fmi3GetNumberOfVariableDependencies was called with a null pointer."
+        );
+        return Fmi3Status::Fmi3Error;
+    }
+
+    let Some(model_structure) = &instance.model_structure else {
+        instance.logger.error(
+            "fmi3GetNumberOfVariableDependencies failed as the model structure of the FMU couldn't be read at instantiation."
+        );
+        return Fmi3Status::Fmi3Error;
+    };
+
+    match model_structure.dependencies(value_reference) {
+        Ok(dependencies) => {
+            unsafe { *n_dependencies = dependencies.len() };
+            Fmi3Status::Fmi3OK
+        }
+        Err(error) => {
+            instance.logger.error(&format!(
+                "fmi3GetNumberOfVariableDependencies failed with error: {}.", error
+            ));
+            Fmi3Status::Fmi3Error
+        }
+    }
 }
 
+/// Answered from the `<ModelStructure>` element of the FMU's
+/// modelDescription.xml without involving the backend.
+///
+/// As the model structure of UniFMU models only describes dependencies
+/// between whole variables, all element indices are set to 0.
+///
+/// Backends can't override the dependencies yet, so they always follow the
+/// model description.
+///
+/// # Safety
+/// Behavior is undefined if any of the following conditions are violated:
+/// * `element_indices_of_dependent`, `independents`,
+///   `element_indices_of_independents` and `dependency_kinds` must be
+///   \[valid\] for writes for `n_dependencies` elements of their respective
+///   types, and they must be properly aligned. This means in particular:
+///     * For each of the pointers the entire memory range of that slice must
+///       be contained within a single allocated object! Slices can never span
+///       across multiple allocated objects.
+///     * The pointers may only be null if `n_dependencies` is 0, as null
+///       pointers are otherwise rejected with `Fmi3Status::Fmi3Error`.
+/// * The total size of each of the slices must be no larger than
+///   `isize::MAX`, and adding that size to the respective pointer must not
+///   "wrap around" the address space. See the safety documentation of
+///   [`pointer::offset`].
 #[no_mangle]
-pub extern "C" fn fmi3GetVariableDependencies(
+pub unsafe extern "C" fn fmi3GetVariableDependencies(
     instance: &mut Fmi3Slave,
-    dependent: *const Fmi3ValueReference,
-    element_indices_of_dependent: *const size_t,
-	independents: *const Fmi3ValueReference,
-	element_indices_of_independents: *const size_t,
+    dependent: Fmi3ValueReference,
+    element_indices_of_dependent: *mut size_t,
+	independents: *mut Fmi3ValueReference,
+	element_indices_of_independents: *mut size_t,
 	dependency_kinds: *mut Fmi3DependencyKind,
 	n_dependencies: size_t,
 ) -> Fmi3Status {
-    instance.logger.error("fmi3GetVariableDependencies is not implemented by UNIFMU.");
-    Fmi3Status::Fmi3Error
+    let any_null = element_indices_of_dependent.is_null()
+        || independents.is_null()
+        || element_indices_of_independents.is_null()
+        || dependency_kinds.is_null();
+
+    if any_null && n_dependencies > 0 {
+        instance.logger.error(
+            "fmi3GetVariableDependencies was called with a null pointer."
+        );
+        return Fmi3Status::Fmi3Error;
+    }
+
+    let Some(model_structure) = &instance.model_structure else {
+        instance.logger.error(
+            "fmi3GetVariableDependencies failed as the model structure of the FMU couldn't be read at instantiation."
+        );
+        return Fmi3Status::Fmi3Error;
+    };
+
+    let dependencies = match model_structure.dependencies(dependent) {
+        Ok(dependencies) => dependencies,
+        Err(error) => {
+            instance.logger.error(&format!(
+                "fmi3GetVariableDependencies failed with error: {}.", error
+            ));
+            return Fmi3Status::Fmi3Error;
+        }
+    };
+
+    if dependencies.len() != n_dependencies {
+        instance.logger.error(&format!(
+            "fmi3GetVariableDependencies was called with n_dependencies {}, but variable with value reference {} has {} dependencies.",
+            n_dependencies,
+            dependent,
+            dependencies.len()
+        ));
+        return Fmi3Status::Fmi3Error;
+    }
+
+    // Nothing to write, and the arrays may be null.
+    if n_dependencies == 0 {
+        return Fmi3Status::Fmi3OK;
+    }
+
+    let (
+        element_indices_of_dependent,
+        independents,
+        element_indices_of_independents,
+        dependency_kinds
+    ) = unsafe {(
+        from_raw_parts_mut(element_indices_of_dependent, n_dependencies),
+        from_raw_parts_mut(independents, n_dependencies),
+        from_raw_parts_mut(element_indices_of_independents, n_dependencies),
+        from_raw_parts_mut(dependency_kinds, n_dependencies)
+    )};
+
+    for (index, dependency) in dependencies.iter().enumerate() {
+        element_indices_of_dependent[index] = 0;
+        independents[index] = dependency.independent;
+        element_indices_of_independents[index] = 0;
+        dependency_kinds[index] = dependency.kind;
+    }
+
+    Fmi3Status::Fmi3OK
 }
 
 #[no_mangle]
@@ -3327,36 +4088,198 @@ pub unsafe extern "C" fn fmi3DeserializeFMUState(
     Fmi3Status::Fmi3OK
 }
 
+/// # Safety
+/// Behavior is undefined if any of the following conditions are violated:
+/// * `unknowns`, `knowns`, `seed` and `sensitivity` must be \[valid\] for
+///   reads (`sensitivity` for writes) for `n_unknowns`, `n_knowns`, `n_seed`
+///   and `n_sensitivity` elements of their respective types, and they must be
+///   properly aligned. This means in particular:
+///     * For each of the pointers the entire memory range of that slice must
+///       be contained within a single allocated object! Slices can never span
+///       across multiple allocated objects.
+///     * Null pointers are rejected with `Fmi3Status::Fmi3Error`, but the
+///       pointers must be aligned even for zero-length slices.
+/// * The total size of each of the slices must be no larger than
+///   `isize::MAX`, and adding that size to the respective pointer must not
+///   "wrap around" the address space. See the safety documentation of
+///   [`pointer::offset`].
 #[no_mangle]
-pub extern "C" fn fmi3GetDirectionalDerivative(
+pub unsafe extern "C" fn fmi3GetDirectionalDerivative(
     instance: &mut Fmi3Slave,
 	unknowns: *const Fmi3ValueReference,
 	n_unknowns: size_t,
 	knowns: *const Fmi3ValueReference,
 	n_knowns: size_t,
-	delta_knowns: *const Fmi3Float64,
-	n_delta_knowns: size_t,
-	delta_unknowns: *const Fmi3Float64,
-	n_delta_unknowns: size_t,
+	seed: *const Fmi3Float64,
+	n_seed: size_t,
+	sensitivity: *mut Fmi3Float64,
+	n_sensitivity: size_t,
 ) -> Fmi3Status {
-    instance.logger.error("fmi3GetDirectionalDerivative is not implemented by UNIFMU.");
-    Fmi3Status::Fmi3Error
+    if unknowns.is_null()
+        || knowns.is_null()
+        || seed.is_null()
+        || sensitivity.is_null()
+    {
+        instance.logger.error(
+            "fmi3GetDirectionalDerivative was called with a null pointer."
+        );
+        return Fmi3Status::Fmi3Error;
+    }
+
+    // Each known has at least one element in the seed, and each
+    // unknown has at least one element in the sensitivity.
+    if n_seed < n_knowns || n_sensitivity < n_unknowns {
+        instance.logger.error(&format!(
+            "fmi3GetDirectionalDerivative was called with {} seed and {} sensitivity elements for {} unknowns and {} knowns.",
+            n_seed,
+            n_sensitivity,
+            n_unknowns,
+            n_knowns
+        ));
+        return Fmi3Status::Fmi3Error;
+    }
+
+    let unknowns = unsafe {
+        from_raw_parts(unknowns, n_unknowns)
+    }.to_owned();
+
+    let knowns = unsafe {
+        from_raw_parts(knowns, n_knowns)
+    }.to_owned();
+
+    let seed = unsafe {
+        from_raw_parts(seed, n_seed)
+    }.to_owned();
+
+    let cmd = Fmi3Command {
+        command: Some(Command::Fmi3GetDirectionalDerivative(
+            fmi3_messages::Fmi3GetDirectionalDerivative { unknowns, knowns, seed }
+        )),
+    };
+
+    match instance.dispatch::<fmi3_messages::Fmi3GetDirectionalDerivativeReturn>(&cmd) {
+        Ok(reply) => {
+            let status = parse_status(reply.status, &instance.logger);
+
+            if status.output_is_defined() {
+                let sensitivity_out = unsafe {
+                    from_raw_parts_mut(sensitivity, n_sensitivity)
+                };
+                copy_reply_values(
+                    sensitivity_out,
+                    &reply.values,
+                    status,
+                    &instance.logger,
+                    "fmi3GetDirectionalDerivative"
+                )
+            } else {
+                status
+            }
+        }
+        Err(error) => {
+            instance.logger.error(&format!(
+                "fmi3GetDirectionalDerivative failed with error: {}.", error
+            ));
+            Fmi3Status::Fmi3Error
+        }
+    }
 }
 
+/// # Safety
+/// Behavior is undefined if any of the following conditions are violated:
+/// * `unknowns`, `knowns`, `seed` and `sensitivity` must be \[valid\] for
+///   reads (`sensitivity` for writes) for `n_unknowns`, `n_knowns`, `n_seed`
+///   and `n_sensitivity` elements of their respective types, and they must be
+///   properly aligned. This means in particular:
+///     * For each of the pointers the entire memory range of that slice must
+///       be contained within a single allocated object! Slices can never span
+///       across multiple allocated objects.
+///     * Null pointers are rejected with `Fmi3Status::Fmi3Error`, but the
+///       pointers must be aligned even for zero-length slices.
+/// * The total size of each of the slices must be no larger than
+///   `isize::MAX`, and adding that size to the respective pointer must not
+///   "wrap around" the address space. See the safety documentation of
+///   [`pointer::offset`].
 #[no_mangle]
-pub extern "C" fn fmi3GetAdjointDerivative(
+pub unsafe extern "C" fn fmi3GetAdjointDerivative(
     instance: &mut Fmi3Slave,
 	unknowns: *const Fmi3ValueReference,
 	n_unknowns: size_t,
 	knowns: *const Fmi3ValueReference,
 	n_knowns: size_t,
-	delta_unknowns: *const Fmi3Float64,
-	n_delta_unknowns: size_t,
-	delta_knowns: *const Fmi3Float64,
-	n_delta_knowns: size_t,
+	seed: *const Fmi3Float64,
+	n_seed: size_t,
+	sensitivity: *mut Fmi3Float64,
+	n_sensitivity: size_t,
 ) -> Fmi3Status {
-    instance.logger.error("fmi3GetAdjointDerivative is not implemented by UNIFMU.");
-    Fmi3Status::Fmi3Error
+    if unknowns.is_null()
+        || knowns.is_null()
+        || seed.is_null()
+        || sensitivity.is_null()
+    {
+        instance.logger.error(
+            "fmi3GetAdjointDerivative was called with a null pointer."
+        );
+        return Fmi3Status::Fmi3Error;
+    }
+
+    // Each unknown has at least one element in the seed, and each
+    // known has at least one element in the sensitivity.
+    if n_seed < n_unknowns || n_sensitivity < n_knowns {
+        instance.logger.error(&format!(
+            "fmi3GetAdjointDerivative was called with {} seed and {} sensitivity elements for {} unknowns and {} knowns.",
+            n_seed,
+            n_sensitivity,
+            n_unknowns,
+            n_knowns
+        ));
+        return Fmi3Status::Fmi3Error;
+    }
+
+    let unknowns = unsafe {
+        from_raw_parts(unknowns, n_unknowns)
+    }.to_owned();
+
+    let knowns = unsafe {
+        from_raw_parts(knowns, n_knowns)
+    }.to_owned();
+
+    let seed = unsafe {
+        from_raw_parts(seed, n_seed)
+    }.to_owned();
+
+    let cmd = Fmi3Command {
+        command: Some(Command::Fmi3GetAdjointDerivative(
+            fmi3_messages::Fmi3GetAdjointDerivative { unknowns, knowns, seed }
+        )),
+    };
+
+    match instance.dispatch::<fmi3_messages::Fmi3GetAdjointDerivativeReturn>(&cmd) {
+        Ok(reply) => {
+            let status = parse_status(reply.status, &instance.logger);
+
+            if status.output_is_defined() {
+                let sensitivity_out = unsafe {
+                    from_raw_parts_mut(sensitivity, n_sensitivity)
+                };
+                copy_reply_values(
+                    sensitivity_out,
+                    &reply.values,
+                    status,
+                    &instance.logger,
+                    "fmi3GetAdjointDerivative"
+                )
+            } else {
+                status
+            }
+        }
+        Err(error) => {
+            instance.logger.error(&format!(
+                "fmi3GetAdjointDerivative failed with error: {}.", error
+            ));
+            Fmi3Status::Fmi3Error
+        }
+    }
 }
 
 #[no_mangle]
@@ -3448,6 +4371,57 @@ fn send_cmd_recv_status(
         })
 }
 
+/// Has the backend get the values of fmi3GetFloat64 for the value references
+/// placed in the shared memory region, and copies them from the region into
+/// the given output slice.
+/// 
+/// Escalates the status like the exchange through the socket if the backend
+/// placed a different number of values in the region than expected.
+fn get_float64_shared(
+    instance: &mut Fmi3Slave,
+    n_value_references: usize,
+    values_out: &mut [Fmi3Float64]
+) -> Fmi3Status {
+    let cmd = Fmi3Command {
+        command: Some(Command::Fmi3GetFloat64Shared(
+            fmi3_messages::Fmi3GetFloat64Shared {
+                n_value_references: n_value_references as u64,
+                n_values: values_out.len() as u64
+            }
+        )),
+    };
+
+    match instance.dispatch::<fmi3_messages::Fmi3GetFloat64SharedReturn>(&cmd) {
+        Ok(reply) => {
+            let mut status = parse_status(reply.status, &instance.logger);
+
+            if status.output_is_defined() {
+                if reply.n_values == 0 && !values_out.is_empty() {
+                    instance.logger.warning("fmi3GetFloat64 returned no values.");
+                    status = status.escalate_status(Fmi3Status::Fmi3Warning);
+                } else if reply.n_values as usize != values_out.len() {
+                    instance.logger.error(&format!(
+                        "fmi3GetFloat64 returned {} values, but {} was expected",
+                        reply.n_values,
+                        values_out.len()
+                    ));
+                    status = status.escalate_status(Fmi3Status::Fmi3Error);
+                } else if let Some(region) = instance.shared_memory() {
+                    region.read_float64(n_value_references, values_out);
+                }
+            }
+
+            status
+        }
+        Err(error) => {
+            instance.logger.error(&format!(
+                "fmi3GetFloat64 failed with error: {}.", error
+            ));
+            Fmi3Status::Fmi3Error
+        }
+    }
+}
+
 /// Parses the given status_int as a Fmi3Status, defaulting to
 /// Fmi3Status::Fmi3Fatal, if no Fmi3Status corresponds to the
 /// given status_int.
@@ -3463,3 +4437,143 @@ fn parse_status(status_int: i32, logger: &Fmi3Logger) -> Fmi3Status {
             Fmi3Status::Fmi3Fatal
     })
 }
+
+/// Copies the values returned by the backend into the given output slice,
+/// escalating and returning the given status if the values can't be copied.
+/// 
+/// If the backend returned a different number of values than expected, an
+/// error message is emitted through the given logger and the status is
+/// escalated to Fmi3Status::Fmi3Error. If the backend returned no values
+/// where some were expected, a warning is emitted instead.
+fn copy_reply_values(
+    values_out: &mut [Fmi3Float64],
+    reply_values: &[Fmi3Float64],
+    status: Fmi3Status,
+    logger: &Fmi3Logger,
+    function_name: &str
+) -> Fmi3Status {
+    if values_out.len() == reply_values.len() {
+        values_out.copy_from_slice(reply_values);
+        status
+    } else if reply_values.is_empty() {
+        logger.warning(&format!("{function_name} returned no values."));
+        status.escalate_status(Fmi3Status::Fmi3Warning)
+    } else {
+        logger.error(&format!(
+            "{function_name} returned {} values, but {} was expected",
+            reply_values.len(),
+            values_out.len()
+        ));
+        status.escalate_status(Fmi3Status::Fmi3Error)
+    }
+}
+
+/// Reads the model description of the FMU with the given resources
+/// directory, verifies the instantiation_token given by the importer at
+/// instantiation against it, and parses its model structure.
+/// 
+/// If the model description can't be read or declares another instantiation
+/// token, an error message is emitted through the given logger and Err is
+/// returned. A model structure that can't be parsed only makes the variable
+/// dependencies unavailable, so a warning is emitted and Ok(None) returned.
+fn load_model_description(
+    resources_dir: &Path,
+    instantiation_token: &str,
+    logger: &Fmi3Logger
+) -> Result<Option<ModelStructure>, ()> {
+    let model_description = match read_model_description(resources_dir) {
+        Ok(model_description) => model_description,
+        Err(error) => {
+            logger.error(&format!(
+                "Couldn't read model description to verify instantiation_token; {}.",
+                error
+            ));
+            return Err(());
+        }
+    };
+
+    if let Err(error) = verify_instantiation_token(
+        &model_description,
+        "instantiationToken",
+        instantiation_token
+    ) {
+        logger.error(&format!("Couldn't verify instantiation_token; {}.", error));
+        return Err(());
+    }
+
+    match ModelStructure::parse(&model_description) {
+        Ok(model_structure) => Ok(Some(model_structure)),
+        Err(error) => {
+            logger.warning(&format!(
+                "Couldn't read model structure, variable dependencies will be unavailable; {}.",
+                error
+            ));
+            Ok(None)
+        }
+    }
+}
+
+/// Parses the resource_path given by the importer at instantiation into the
+/// path of the resources directory of the FMU.
+/// 
+/// If the resource_path can't be parsed, an error message is emitted through
+/// the given logger and None is returned.
+fn parse_resource_path(
+    resource_path: Fmi3String,
+    logger: &Fmi3Logger
+) -> Option<PathBuf> {
+    let resource_path_str = match c2non_empty_s(resource_path) {
+        Ok(path_string) => path_string,
+        Err(error) => {
+            logger.error(&format!(
+                "could not parse resource_path; {}", error
+            ));
+            return None;
+        }
+    };
+
+    // NOTE: In version 3 of the FMI standard, resourcePath should be a path, e.g., "C:\...". At
+    // least one tool seems to still follow version 2 in that it passes a URI, e.g., starting with
+    // "file:///C://..." instead. The current implementation maintains this "backwards
+    // compatibility" with this incorrect implementation of version 3 of the standard.
+
+    // Check for supported URI schemes or treat as a direct file path
+    if resource_path_str.starts_with("file:")
+        || resource_path_str.starts_with("http:")
+        || resource_path_str.starts_with("https:")
+        || resource_path_str.starts_with("ftp:")
+        || resource_path_str.starts_with("fmi2:")
+    {
+        // Parse as a URI
+        let resource_uri = match Url::parse(&resource_path_str) {
+            Ok(uri) => uri,
+            Err(error) => {
+                logger.error(&format!(
+                    "Unable to parse uri: {}", error
+                ));
+                return None;
+            }
+        };
+
+        if resource_uri.scheme() == "file" {
+            match resource_uri.to_file_path() {
+                Ok(path) => Some(path),
+                Err(_) => {
+                    logger.error(&format!(
+                        "URI was parsed but could not be converted into a file path, got: '{:?}'.",
+                        resource_uri
+                    ));
+                    None
+                }
+            }
+        } else {
+            logger.error(&format!(
+                "Unsupported URI scheme: '{}'", resource_uri.scheme()
+            ));
+            None
+        }
+    } else {
+        // Treat it as a direct file path
+        Some(PathBuf::from(resource_path_str))
+    }
+}
\ No newline at end of file
@@ -1,5 +1,6 @@
 protoc -I=./schemas --python_out=./assets/auto_generated --csharp_out=./assets/auto_generated --java_out ./assets/auto_generated fmi2_messages.proto fmi3_messages.proto unifmu_handshake.proto
 cargo build --package fmiapi --target x86_64-unknown-linux-gnu --release
+cp ./fmiapi/src/fmi2/fmi2_messages.rs ./fmiapi/src/fmi3/fmi3_messages.rs ./fmiapi/src/common/unifmu_handshake.rs ./assets/auto_generated/
 cp ./target/x86_64-unknown-linux-gnu/release/libfmiapi.so ./assets/auto_generated/binaries/x86_64-linux/unifmu.so
 cargo test
 cargo run --bin unifmu --release -- generate python myfmu
@@ -1,5 +1,8 @@
 //! Contains the LaunchConfig, a struct representing the `launch.toml` file
 //! present in the FMU, along with related types.
+//! 
+//! Shared between the API, which launches the backend from it when the FMU is
+//! instantiated, and the CLI, which validates it ahead of time.
 
 use std::{
     error::Error,
@@ -17,14 +20,61 @@ pub enum BackendLocation {
     Remote,
 }
 
+/// The transport between the dispatcher and a local backend.
+/// 
+/// `Ipc` uses a Unix domain socket, and falls back to `Tcp` on platforms
+/// without Unix domain sockets or to backends that are `tcp_only`.
+#[derive(Debug, Default, Deserialize)]
+pub enum BackendTransport {
+    #[default]
+    Tcp,
+    Ipc,
+}
+
 /// Represents the parsed form of a `launch.toml` config file.
 #[derive(Debug, Deserialize)]
 pub struct LaunchConfig {
     #[serde(default)]
     pub location: BackendLocation,
+    #[serde(default)]
+    pub transport: BackendTransport,
+    /// Whether a local backend can only connect through TCP, in which case it
+    /// is connected through TCP whatever the transport.
+    #[serde(default)]
+    pub tcp_only: bool,
+    /// Whether a local backend is offered a shared memory region for bulk
+    /// values, instead of exchanging them through the socket.
+    #[serde(default)]
+    pub shared_memory: bool,
     pub windows: Option<Vec<String>>,
     pub linux: Option<Vec<String>>,
     pub macos: Option<Vec<String>>,
+    #[serde(default)]
+    pub timeouts: TimeoutsConfig,
+}
+
+/// Represents the `[timeouts]` table of a `launch.toml` config file.
+/// 
+/// Each timeout is given in milliseconds, and a missing timeout means waiting
+/// indefinitely, except for `do_step_ms` which falls back to `call_ms`.
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct TimeoutsConfig {
+    pub handshake_ms: Option<u64>,
+    pub call_ms: Option<u64>,
+    pub do_step_ms: Option<u64>,
+    pub kill_backend: bool,
+}
+
+impl Default for TimeoutsConfig {
+    fn default() -> Self {
+        Self {
+            handshake_ms: None,
+            call_ms: None,
+            do_step_ms: None,
+            kill_backend: true,
+        }
+    }
 }
 
 impl LaunchConfig {
@@ -33,7 +83,6 @@ impl LaunchConfig {
     /// succesful parse.
     pub fn create(resource_path: &Path) -> ConfigResult<LaunchConfig> {
         let config_path = resource_path.join("launch.toml");
-        println!("Reading configuration file at path '{:?}'",config_path);
 
         // Check if file is there and give error if not.
         if !config_path.exists() {
@@ -66,7 +115,7 @@ impl LaunchConfig {
     }
 }
 
-type ConfigResult<T> = Result<T, ConfigError>;
+pub type ConfigResult<T> = Result<T, ConfigError>;
 
 #[derive(Debug)]
 pub enum ConfigError {
@@ -15,7 +15,7 @@ extern crate proc_macro;
 /// variations with or without those traits respectively.
 /// 
 /// For example decorating a function with the "include:" attribute
-/// ```
+/// ```ignore
 /// #[unifmu_macros::for_each_fmu(include: fmi2, python, bare_directory)]
 /// #[test]
 /// fn some_function() {
@@ -23,7 +23,7 @@ extern crate proc_macro;
 /// }
 /// ```
 /// will expand it to
-/// ```
+/// ```ignore
 /// #[test]
 /// #[serial_test::parallel]
 /// fn some_function_fmi2_python_bare_directory_local() {
@@ -53,7 +53,7 @@ extern crate proc_macro;
 /// ```
 /// 
 /// while decorating the same function with the "exclude:" attribute
-/// ```
+/// ```ignore
 /// #[unifmu_macros::for_each_fmu(exclude: fmi2, python, bare_directory)]
 /// #[test]
 /// fn some_function() {
@@ -61,7 +61,7 @@ extern crate proc_macro;
 /// }
 /// ```
 /// will expand it to
-/// ```
+/// ```ignore
 /// #[test]
 /// #[serial_test::parallel]
 /// fn some_function_fmi3_csharp_zipped_local() {
@@ -108,11 +108,21 @@ extern crate proc_macro;
 ///  - csharp
 ///  - java
 ///  - python
+///  - matlab
+///  - rust
 ///  - bare_directory
 ///  - zipped
 ///  - local
 ///  - distributed
 ///  - blackbox
+/// 
+/// MATLAB based FMUs are only run against a stand-in for the MATLAB engine,
+/// and only as local FMUs, so they are left out unless `matlab` is included
+/// explicitly.
+///
+/// Rust based FMUs compile their backend when generated, so they are left out
+/// unless `rust` is included explicitly, and are only tested as local,
+/// non-zipped FMUs.
 #[proc_macro_attribute]
 pub fn for_each_fmu(
     attr: proc_macro::TokenStream,
@@ -243,17 +253,21 @@ impl FmiVersion {
 enum ProgrammingLanguage {
     CSharp,
     Java,
-    Python
+    Python,
+    Matlab,
+    Rust
 }
 
-const NUM_OF_PROGRAMMING_LANGUAGES: usize = 3;
+const NUM_OF_PROGRAMMING_LANGUAGES: usize = 5;
 
 impl ProgrammingLanguage {
     pub fn function_suffix_part(&self) -> std::string::String {
         match self {
             ProgrammingLanguage::CSharp => std::string::String::from("csharp"),
             ProgrammingLanguage::Java => std::string::String::from("java"),
-            ProgrammingLanguage::Python => std::string::String::from("python")
+            ProgrammingLanguage::Python => std::string::String::from("python"),
+            ProgrammingLanguage::Matlab => std::string::String::from("matlab"),
+            ProgrammingLanguage::Rust => std::string::String::from("rust")
         }
     }
 
@@ -261,7 +275,9 @@ impl ProgrammingLanguage {
         let ident_string = match self {
             ProgrammingLanguage::CSharp => "CSharp",
             ProgrammingLanguage::Java => "Java",
-            ProgrammingLanguage::Python => "Python"
+            ProgrammingLanguage::Python => "Python",
+            ProgrammingLanguage::Matlab => "Matlab",
+            ProgrammingLanguage::Rust => "Rust"
         };
 
         let path_segment = syn::PathSegment {
@@ -398,6 +414,8 @@ struct FmuPossibilities {
     pub csharp: bool,
     pub java: bool,
     pub python: bool,
+    pub matlab: bool,
+    pub rust: bool,
     pub bare_directory: bool,
     pub zipped: bool,
     pub blackbox: bool,
@@ -413,6 +431,8 @@ impl FmuPossibilities {
             csharp: true,
             java: true,
             python: true,
+            matlab: false,
+            rust: false,
             bare_directory: true,
             zipped: true,
             blackbox: true,
@@ -428,6 +448,8 @@ impl FmuPossibilities {
             csharp: false,
             java: false,
             python: false,
+            matlab: false,
+            rust: false,
             bare_directory: false,
             zipped: false,
             blackbox: false,
@@ -451,6 +473,8 @@ impl FmuPossibilities {
             "csharp" => self.csharp = new_value,
             "java" => self.java = new_value,
             "python" => self.python = new_value,
+            "matlab" => self.matlab = new_value,
+            "rust" => self.rust = new_value,
             "bare_directory" => self.bare_directory = new_value,
             "zipped" => self.zipped = new_value,
             "blackbox" => self.blackbox = new_value,
@@ -466,7 +490,7 @@ impl FmuPossibilities {
             self.fmi2 = true;
             self.fmi3 = true;
         }
-        if !self.csharp && !self.java && !self.python {
+        if !self.csharp && !self.java && !self.python && !self.matlab && !self.rust {
             self.csharp = true;
             self.java = true;
             self.python = true;
@@ -491,6 +515,8 @@ impl FmuPossibilities {
         if self.csharp {languages.push(ProgrammingLanguage::CSharp);}
         if self.java {languages.push(ProgrammingLanguage::Java);}
         if self.python {languages.push(ProgrammingLanguage::Python);}
+        if self.matlab {languages.push(ProgrammingLanguage::Matlab);}
+        if self.rust {languages.push(ProgrammingLanguage::Rust);}
 
         let mut packagings: Vec<FmuPackaging> = Vec::with_capacity(NUM_OF_FMU_PACKAGINGS);
         if self.bare_directory {packagings.push(FmuPackaging::BareDirectory);}
@@ -521,6 +547,23 @@ impl FmuPossibilities {
                             // Blackbox FMUs must be python based and not zipped.
                             continue;
                         }
+                        if language == ProgrammingLanguage::Matlab
+                            && backend != FmuBackend::Local
+                        {
+                            // MATLAB FMUs can't be generated as distributed FMUs.
+                            continue;
+                        }
+                        if language == ProgrammingLanguage::Rust
+                            && (
+                                backend != FmuBackend::Local
+                                || packaging != FmuPackaging::BareDirectory
+                            )
+                        {
+                            // Rust FMUs are only tested as local, non-zipped
+                            // FMUs, since a zipped FMU would compile its
+                            // backend in every test.
+                            continue;
+                        }
 
                         variations.push(
                             FmuVariant {version, language, packaging, backend}